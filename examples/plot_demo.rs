@@ -350,7 +350,7 @@ live_design! {
                                     text: "Log & SymLog Scales (NEW!)"
                                     draw_text: { text_style: <THEME_FONT_BOLD> { font_size: 12.0 }, color: #333 }
                                 }
-                                main_log_scale = <LinePlot> { height: Fill, width: Fill }
+                                main_log_scale = <LinePlot> { height: Fill, width: Fill, title: "Exponential (Log Y)", y_scale: Log }
                             }
 
                             time_scale_card = <ChartCard> {
@@ -2753,11 +2753,9 @@ impl App {
 
         // Log Scale preview
         let log_scale = self.ui.line_plot(&[id!(main_log_scale)]);
-        log_scale.set_title("Exponential (Log Y)");
         let x: Vec<f64> = (0..30).map(|i| i as f64 * 0.3).collect();
         let y: Vec<f64> = x.iter().map(|&xi| (xi * 0.5).exp()).collect();
         log_scale.add_series(Series::new("e^(0.5x)").with_data(x, y).with_color(vec4(0.12, 0.47, 0.71, 1.0)));
-        log_scale.set_y_scale(ScaleType::Log);
         log_scale.redraw(cx);
 
         // Time Scale preview
@@ -2808,7 +2806,7 @@ impl App {
         annotations.set_title("Annotations Demo");
         let x: Vec<f64> = (0..30).map(|i| i as f64 * 0.3).collect();
         annotations.add_series(Series::new("Data")
-            .with_data(x.clone(), x.iter().map(|&v| (v * 0.5).sin() * 3.0 + 5.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| (v * 0.5).sin() * 3.0 + 5.0)))
             .with_color(vec4(0.12, 0.47, 0.71, 1.0)));
         // Add sample annotations
         annotations.axvline(3.0, vec4(0.9, 0.2, 0.2, 1.0), 1.5, LineStyle::Dashed);
@@ -3071,11 +3069,11 @@ impl App {
         dashed.set_title("Dashed Lines (Interactive - drag to pan, scroll to zoom)");
         let x: Vec<f64> = (0..30).map(|i| i as f64 * 0.3).collect();
         dashed.add_series(Series::new("Dashed")
-            .with_data(x.clone(), x.iter().map(|&v| (v * 0.5).sin() * 5.0 + 10.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| (v * 0.5).sin() * 5.0 + 10.0)))
             .with_color(vec4(0.12, 0.47, 0.71, 1.0))
             .with_line_style(LineStyle::Dashed));
         dashed.add_series(Series::new("Also Dashed")
-            .with_data(x.clone(), x.iter().map(|&v| (v * 0.3).cos() * 4.0 + 5.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| (v * 0.3).cos() * 4.0 + 5.0)))
             .with_color(vec4(0.84, 0.15, 0.16, 1.0))
             .with_line_style(LineStyle::Dashed));
         dashed.set_legend(LegendPosition::TopRight);
@@ -3088,11 +3086,11 @@ impl App {
         dotted.set_title("Dotted Lines");
         let x: Vec<f64> = (0..30).map(|i| i as f64 * 0.3).collect();
         dotted.add_series(Series::new("Dotted")
-            .with_data(x.clone(), x.iter().map(|&v| (v * 0.4).sin() * 6.0 + 12.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| (v * 0.4).sin() * 6.0 + 12.0)))
             .with_color(vec4(0.17, 0.63, 0.17, 1.0))
             .with_line_style(LineStyle::Dotted));
         dotted.add_series(Series::new("Also Dotted")
-            .with_data(x.clone(), x.iter().map(|&v| (v * 0.6).cos() * 5.0 + 6.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| (v * 0.6).cos() * 5.0 + 6.0)))
             .with_color(vec4(0.58, 0.40, 0.74, 1.0))
             .with_line_style(LineStyle::Dotted));
         dotted.set_legend(LegendPosition::TopRight);
@@ -3104,19 +3102,19 @@ impl App {
         mixed.set_title("All Line Styles");
         let x: Vec<f64> = (0..30).map(|i| i as f64 * 0.3).collect();
         mixed.add_series(Series::new("Solid")
-            .with_data(x.clone(), x.iter().map(|&v| v * 0.8 + 2.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| v * 0.8 + 2.0)))
             .with_color(vec4(0.12, 0.47, 0.71, 1.0))
             .with_line_style(LineStyle::Solid));
         mixed.add_series(Series::new("Dashed")
-            .with_data(x.clone(), x.iter().map(|&v| v * 0.8 + 6.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| v * 0.8 + 6.0)))
             .with_color(vec4(0.84, 0.15, 0.16, 1.0))
             .with_line_style(LineStyle::Dashed));
         mixed.add_series(Series::new("Dotted")
-            .with_data(x.clone(), x.iter().map(|&v| v * 0.8 + 10.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| v * 0.8 + 10.0)))
             .with_color(vec4(0.17, 0.63, 0.17, 1.0))
             .with_line_style(LineStyle::Dotted));
         mixed.add_series(Series::new("DashDot")
-            .with_data(x.clone(), x.iter().map(|&v| v * 0.8 + 14.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| v * 0.8 + 14.0)))
             .with_color(vec4(0.58, 0.40, 0.74, 1.0))
             .with_line_style(LineStyle::DashDot));
         mixed.set_legend(LegendPosition::TopRight);
@@ -3158,17 +3156,17 @@ impl App {
         with_markers.set_title("Lines + Markers");
         let x: Vec<f64> = (0..8).map(|i| i as f64).collect();
         with_markers.add_series(Series::new("Circle")
-            .with_data(x.clone(), x.iter().map(|&v| v * 0.8 + 2.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| v * 0.8 + 2.0)))
             .with_color(vec4(0.12, 0.47, 0.71, 1.0))
             .with_marker(MarkerStyle::Circle)
             .with_marker_size(8.0));
         with_markers.add_series(Series::new("Square")
-            .with_data(x.clone(), x.iter().map(|&v| v * 0.9 + 5.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| v * 0.9 + 5.0)))
             .with_color(vec4(0.84, 0.15, 0.16, 1.0))
             .with_marker(MarkerStyle::Square)
             .with_marker_size(8.0));
         with_markers.add_series(Series::new("Diamond")
-            .with_data(x.clone(), x.iter().map(|&v| v * 0.7 + 8.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| v * 0.7 + 8.0)))
             .with_color(vec4(0.17, 0.63, 0.17, 1.0))
             .with_marker(MarkerStyle::Diamond)
             .with_marker_size(8.0));
@@ -3305,7 +3303,7 @@ impl App {
         reflines.set_title("Reference Lines");
         let x: Vec<f64> = (0..30).map(|i| i as f64 * 0.3).collect();
         reflines.add_series(Series::new("Data")
-            .with_data(x.clone(), x.iter().map(|&v| (v * 0.5).sin() * 3.0 + 5.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| (v * 0.5).sin() * 3.0 + 5.0)))
             .with_color(vec4(0.12, 0.47, 0.71, 1.0)));
         reflines.axvline(3.0, vec4(0.84, 0.15, 0.16, 1.0), 2.0, LineStyle::Dashed);
         reflines.axvline(6.0, vec4(0.84, 0.15, 0.16, 1.0), 2.0, LineStyle::Dashed);
@@ -3319,7 +3317,7 @@ impl App {
         spans.set_title("Shaded Regions");
         let x: Vec<f64> = (0..40).map(|i| i as f64 * 0.25).collect();
         spans.add_series(Series::new("Signal")
-            .with_data(x.clone(), x.iter().map(|&v| (v * 0.4).sin() * 4.0 + 5.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| (v * 0.4).sin() * 4.0 + 5.0)))
             .with_color(vec4(0.12, 0.47, 0.71, 1.0)));
         spans.axvspan(2.0, 4.0, vec4(1.0, 0.5, 0.05, 0.3));
         spans.axvspan(7.0, 8.0, vec4(0.17, 0.63, 0.17, 0.3));
@@ -3659,11 +3657,11 @@ impl App {
         stem_multi.set_title("Multi-Series Stem");
         let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
         stem_multi.add_series(Series::new("Series A")
-            .with_data(x.clone(), x.iter().map(|&v| (v * 0.4).sin() * 4.0 + 5.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| (v * 0.4).sin() * 4.0 + 5.0)))
             .with_color(vec4(0.12, 0.47, 0.71, 1.0))
             .with_marker(MarkerStyle::Circle));
         stem_multi.add_series(Series::new("Series B")
-            .with_data(x.clone(), x.iter().map(|&v| (v * 0.6).cos() * 3.0 + 6.0).collect())
+            .with_data(&x, Values(x.iter().map(|&v| (v * 0.6).cos() * 3.0 + 6.0)))
             .with_color(vec4(0.17, 0.63, 0.17, 1.0))
             .with_marker(MarkerStyle::Square));
         stem_multi.set_legend(LegendPosition::TopRight);
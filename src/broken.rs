@@ -0,0 +1,118 @@
+// Broken axes - a y axis split into a lower and an upper range with a gap between them,
+// for data where a few outliers sit far above the rest
+
+use makepad_widgets::*;
+use crate::plot::{PlotArea, ScaleType};
+
+/// Pixel gap between the two ranges of a broken axis
+const BREAK_GAP: f64 = 12.0;
+
+/// Half width of the diagonal marks drawn across the spines at the break
+const BREAK_MARK: f64 = 5.0;
+
+/// A y axis showing a `lower` and an `upper` value range stacked on top of each other,
+/// like two matplotlib axes sharing x with the spines between them hidden
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AxisBreak {
+    pub lower: (f64, f64),
+    pub upper: (f64, f64),
+    /// Share of the plot height given to the upper range
+    pub upper_share: f64,
+    /// Pixel gap between the ranges
+    pub gap: f64,
+}
+
+impl AxisBreak {
+    /// `None` unless both ranges are finite and ascending with the lower one below the
+    /// upper one: `lower.0 < lower.1 <= upper.0 < upper.1`
+    pub fn new(lower: (f64, f64), upper: (f64, f64)) -> Option<Self> {
+        let ends = [lower.0, lower.1, upper.0, upper.1];
+        let valid = ends.iter().all(|v| v.is_finite()) && lower.0 < lower.1 && lower.1 <= upper.0 && upper.0 < upper.1;
+        valid.then_some(Self { lower, upper, upper_share: 0.5, gap: BREAK_GAP })
+    }
+
+    pub fn with_upper_share(mut self, share: f64) -> Self {
+        self.upper_share = share.clamp(0.05, 0.95);
+        self
+    }
+
+    pub fn with_gap(mut self, gap: f64) -> Self {
+        self.gap = gap.max(0.0);
+        self
+    }
+
+    /// (top, bottom) pixel edges of the lower and the upper range
+    pub fn panels(&self, area: &PlotArea) -> [(f64, f64); 2] {
+        let height = (area.height() - self.gap).max(0.0);
+        let upper_height = height * self.upper_share;
+        let lower_height = height - upper_height;
+        [(area.bottom - lower_height, area.bottom), (area.top, area.top + upper_height)]
+    }
+
+    /// Pixel y of `value`. Values between the two ranges fall in the gap, so a line
+    /// jumping to an outlier still crosses it.
+    pub fn pixel_y(&self, value: f64, scale: ScaleType, area: &PlotArea) -> f64 {
+        let [lower, upper] = self.panels(area);
+        let map = |range: (f64, f64), (top, bottom): (f64, f64)| {
+            let lo = scale.transform(range.0);
+            let hi = scale.transform(range.1);
+            if hi == lo {
+                return (top + bottom) / 2.0;
+            }
+            bottom - (scale.transform(value) - lo) / (hi - lo) * (bottom - top)
+        };
+        if value <= self.lower.1 {
+            map(self.lower, lower)
+        } else if value >= self.upper.0 {
+            map(self.upper, upper)
+        } else {
+            map((self.lower.1, self.upper.0), (upper.1, lower.0))
+        }
+    }
+
+    /// Value at pixel y, the inverse of `pixel_y`
+    pub fn value_at(&self, y: f64, scale: ScaleType, area: &PlotArea) -> f64 {
+        let [lower, upper] = self.panels(area);
+        let unmap = |range: (f64, f64), (top, bottom): (f64, f64)| {
+            let lo = scale.transform(range.0);
+            let hi = scale.transform(range.1);
+            let t = if bottom == top { 0.5 } else { (bottom - y) / (bottom - top) };
+            scale.inverse(lo + t * (hi - lo))
+        };
+        if y >= lower.0 {
+            unmap(self.lower, lower)
+        } else if y <= upper.1 {
+            unmap(self.upper, upper)
+        } else {
+            unmap((self.lower.1, self.upper.0), (upper.1, lower.0))
+        }
+    }
+
+    /// Ticks within each range
+    pub fn ticks(&self, scale: ScaleType) -> Vec<f64> {
+        [self.lower, self.upper].iter()
+            .flat_map(|&(lo, hi)| {
+                scale.generate_ticks(lo, hi, 3).into_iter().filter(move |v| *v >= lo && *v <= hi)
+            })
+            .collect()
+    }
+
+    /// The gap across the plot area, widened by `pad` on the left and right so it also
+    /// cuts through the spines
+    pub fn gap_rect(&self, area: &PlotArea, pad: f64) -> Rect {
+        let [lower, upper] = self.panels(area);
+        Rect {
+            pos: dvec2(area.left - pad, upper.1),
+            size: dvec2(area.width() + 2.0 * pad, lower.0 - upper.1),
+        }
+    }
+
+    /// Diagonal marks across the left and right spines on both edges of the gap
+    pub fn marks(&self, area: &PlotArea) -> Vec<(DVec2, DVec2)> {
+        let [lower, upper] = self.panels(area);
+        let d = dvec2(BREAK_MARK, -BREAK_MARK / 2.0);
+        [area.left, area.right].iter()
+            .flat_map(|&x| [lower.0, upper.1].map(|y| (dvec2(x, y) - d, dvec2(x, y) + d)))
+            .collect()
+    }
+}
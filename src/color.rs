@@ -0,0 +1,387 @@
+// Colour utilities - parsing colour strings, sRGB/HSL/OKLab conversion, palettes and property cycles
+
+use makepad_widgets::*;
+use crate::elements::{LineStyle, MarkerStyle};
+use crate::plot::get_color;
+use crate::style::PlotStyle;
+
+/// Matplotlib's tab10 names, used with a `tab:` prefix
+const TAB_COLORS: &[(&str, u32)] = &[
+    ("blue", 0x1f77b4), ("orange", 0xff7f0e), ("green", 0x2ca02c), ("red", 0xd62728), ("purple", 0x9467bd),
+    ("brown", 0x8c564b), ("pink", 0xe377c2), ("gray", 0x7f7f7f), ("grey", 0x7f7f7f), ("olive", 0xbcbd22),
+    ("cyan", 0x17becf),
+];
+
+/// Matplotlib's single-letter base colours
+const BASE_COLORS: &[(&str, u32)] = &[
+    ("b", 0x0000ff), ("g", 0x008000), ("r", 0xff0000), ("c", 0x00bfbf), ("m", 0xbf00bf), ("y", 0xbfbf00),
+    ("k", 0x000000), ("w", 0xffffff),
+];
+
+/// CSS Color Module Level 4 named colours
+const CSS_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff), ("antiquewhite", 0xfaebd7), ("aqua", 0x00ffff), ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff), ("beige", 0xf5f5dc), ("bisque", 0xffe4c4), ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd), ("blue", 0x0000ff), ("blueviolet", 0x8a2be2), ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887), ("cadetblue", 0x5f9ea0), ("chartreuse", 0x7fff00), ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50), ("cornflowerblue", 0x6495ed), ("cornsilk", 0xfff8dc), ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff), ("darkblue", 0x00008b), ("darkcyan", 0x008b8b), ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9), ("darkgreen", 0x006400), ("darkgrey", 0xa9a9a9), ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b), ("darkolivegreen", 0x556b2f), ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc), ("darkred", 0x8b0000), ("darksalmon", 0xe9967a), ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b), ("darkslategray", 0x2f4f4f), ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1), ("darkviolet", 0x9400d3), ("deeppink", 0xff1493), ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969), ("dimgrey", 0x696969), ("dodgerblue", 0x1e90ff), ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0), ("forestgreen", 0x228b22), ("fuchsia", 0xff00ff), ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff), ("gold", 0xffd700), ("goldenrod", 0xdaa520), ("gray", 0x808080),
+    ("green", 0x008000), ("greenyellow", 0xadff2f), ("grey", 0x808080), ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4), ("indianred", 0xcd5c5c), ("indigo", 0x4b0082), ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c), ("lavender", 0xe6e6fa), ("lavenderblush", 0xfff0f5), ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd), ("lightblue", 0xadd8e6), ("lightcoral", 0xf08080), ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2), ("lightgray", 0xd3d3d3), ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3), ("lightpink", 0xffb6c1), ("lightsalmon", 0xffa07a), ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de), ("lightyellow", 0xffffe0), ("lime", 0x00ff00), ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6), ("magenta", 0xff00ff), ("maroon", 0x800000), ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd), ("mediumorchid", 0xba55d3), ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371), ("mediumslateblue", 0x7b68ee), ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc), ("mediumvioletred", 0xc71585), ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa), ("mistyrose", 0xffe4e1), ("moccasin", 0xffe4b5), ("navajowhite", 0xffdead),
+    ("navy", 0x000080), ("oldlace", 0xfdf5e6), ("olive", 0x808000), ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500), ("orangered", 0xff4500), ("orchid", 0xda70d6), ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98), ("paleturquoise", 0xafeeee), ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5), ("peachpuff", 0xffdab9), ("peru", 0xcd853f), ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd), ("powderblue", 0xb0e0e6), ("purple", 0x800080), ("rebeccapurple", 0x663399),
+    ("red", 0xff0000), ("rosybrown", 0xbc8f8f), ("royalblue", 0x4169e1), ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072), ("sandybrown", 0xf4a460), ("seagreen", 0x2e8b57), ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d), ("silver", 0xc0c0c0), ("skyblue", 0x87ceeb), ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090), ("slategrey", 0x708090), ("snow", 0xfffafa), ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4), ("tan", 0xd2b48c), ("teal", 0x008080), ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347), ("turquoise", 0x40e0d0), ("violet", 0xee82ee), ("wheat", 0xf5deb3),
+    ("white", 0xffffff), ("whitesmoke", 0xf5f5f5), ("yellow", 0xffff00), ("yellowgreen", 0x9acd32),
+];
+
+/// The most common names from the XKCD colour survey, used with an `xkcd:` prefix
+const XKCD_COLORS: &[(&str, u32)] = &[
+    ("purple", 0x7e1e9c), ("green", 0x15b01a), ("blue", 0x0343df), ("pink", 0xff81c0), ("brown", 0x653700),
+    ("red", 0xe50000), ("light blue", 0x95d0fc), ("teal", 0x029386), ("orange", 0xf97306),
+    ("light green", 0x96f97b), ("magenta", 0xc20078), ("yellow", 0xffff14), ("sky blue", 0x75bbfd),
+    ("grey", 0x929591), ("lime green", 0x89fe05), ("light purple", 0xbf77f6), ("violet", 0x9a0eea),
+    ("dark green", 0x033500), ("turquoise", 0x06c2ac), ("lavender", 0xc79fef), ("dark blue", 0x00035b),
+    ("tan", 0xd1b26f), ("cyan", 0x00ffff), ("aqua", 0x13eac9), ("forest green", 0x06470c),
+    ("mauve", 0xae7181), ("dark purple", 0x35063e), ("bright green", 0x01ff07), ("maroon", 0x650021),
+    ("olive", 0x6e750e), ("salmon", 0xff796c), ("beige", 0xe6daa6), ("royal blue", 0x0504aa),
+    ("navy blue", 0x001146), ("lilac", 0xcea2fd), ("black", 0x000000), ("hot pink", 0xff028d),
+    ("light pink", 0xffd1df), ("mustard", 0xceb301), ("white", 0xffffff),
+];
+
+/// Okabe-Ito palette, distinguishable with the common forms of colour blindness
+const OKABE_ITO: &[u32] = &[0x0072b2, 0xe69f00, 0x009e73, 0xcc79a7, 0x56b4e9, 0xd55e00, 0xf0e442, 0x000000];
+
+/// Colour from a 0xRRGGBB value
+pub fn hex_color(hex: u32) -> Vec4 {
+    vec4(
+        ((hex >> 16) & 0xff) as f32 / 255.0,
+        ((hex >> 8) & 0xff) as f32 / 255.0,
+        (hex & 0xff) as f32 / 255.0,
+        1.0,
+    )
+}
+
+/// Parse a colour the way matplotlib accepts it: "#1f77b4", "#fff", "#1f77b480", "rgb(31, 119, 180)",
+/// "rgba(31, 119, 180, 0.5)", "hsl(205, 70%, 41%)", "tab:orange", "xkcd:sky blue", CSS names
+/// such as "steelblue", the single-letter base colours "r", "g", "b", "c", "m", "y", "k", "w",
+/// and "C0", "C1"... for the colours of the global style's cycle
+pub fn parse_color(text: &str) -> Option<Vec4> {
+    let text = text.trim().to_ascii_lowercase();
+    if let Some(hex) = text.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if let Some(index) = text.strip_prefix('c').and_then(|n| n.parse::<usize>().ok()) {
+        return Some(PlotStyle::global().prop_cycle.color(index));
+    }
+    if let Some(args) = function_args(&text, "rgba").or_else(|| function_args(&text, "rgb")) {
+        let values = split_args(args);
+        if values.len() < 3 {
+            return None;
+        }
+        let channel = |v: &str| parse_component(v, 255.0);
+        let alpha = values.get(3).map(|v| parse_component(v, 1.0)).unwrap_or(Some(1.0))?;
+        return Some(vec4(channel(values[0])? as f32, channel(values[1])? as f32, channel(values[2])? as f32, alpha as f32));
+    }
+    if let Some(args) = function_args(&text, "hsla").or_else(|| function_args(&text, "hsl")) {
+        let values = split_args(args);
+        if values.len() < 3 {
+            return None;
+        }
+        let hue = values[0].trim_end_matches("deg").parse::<f64>().ok()?;
+        let alpha = values.get(3).map(|v| parse_component(v, 1.0)).unwrap_or(Some(1.0))?;
+        let hsl = Hsl { h: hue, s: parse_component(values[1], 100.0)?, l: parse_component(values[2], 100.0)? };
+        return Some(hsl.to_rgb(alpha as f32));
+    }
+    if text == "transparent" || text == "none" {
+        return Some(vec4(0.0, 0.0, 0.0, 0.0));
+    }
+    let (table, name) = if let Some(name) = text.strip_prefix("tab:") {
+        (TAB_COLORS, name)
+    } else if let Some(name) = text.strip_prefix("xkcd:") {
+        (XKCD_COLORS, name)
+    } else if text.len() == 1 {
+        (BASE_COLORS, text.as_str())
+    } else {
+        (CSS_COLORS, text.as_str())
+    };
+    table.iter().find(|(n, _)| *n == name).map(|(_, hex)| hex_color(*hex))
+}
+
+fn parse_hex(hex: &str) -> Option<Vec4> {
+    let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok().map(|v| v as f32 * 17.0 / 255.0);
+    let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok().map(|v| v as f32 / 255.0);
+    match hex.len() {
+        3 => Some(vec4(digit(0)?, digit(1)?, digit(2)?, 1.0)),
+        4 => Some(vec4(digit(0)?, digit(1)?, digit(2)?, digit(3)?)),
+        6 => Some(vec4(byte(0)?, byte(2)?, byte(4)?, 1.0)),
+        8 => Some(vec4(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+        _ => None,
+    }
+}
+
+/// The text between the parentheses of `name(...)`
+fn function_args<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    text.strip_prefix(name)?.trim_start().strip_prefix('(')?.strip_suffix(')')
+}
+
+/// Arguments separated by commas, spaces or the CSS `/` before alpha
+fn split_args(args: &str) -> Vec<&str> {
+    args.split(|c: char| c == ',' || c == '/' || c.is_whitespace()).filter(|v| !v.is_empty()).collect()
+}
+
+/// A number on a 0..`scale` range, or a percentage, mapped to [0, 1]
+fn parse_component(value: &str, scale: f64) -> Option<f64> {
+    let v = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f64>().ok()? / 100.0,
+        None => value.parse::<f64>().ok()? / scale,
+    };
+    Some(v.clamp(0.0, 1.0))
+}
+
+/// Hex string for a colour, "#rrggbb" or "#rrggbbaa" when not opaque
+pub fn to_hex(color: Vec4) -> String {
+    let byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    if color.w >= 1.0 {
+        format!("#{:02x}{:02x}{:02x}", byte(color.x), byte(color.y), byte(color.z))
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", byte(color.x), byte(color.y), byte(color.z), byte(color.w))
+    }
+}
+
+/// Linear blend from `a` (t = 0) to `b` (t = 1), alpha included
+pub fn mix(a: Vec4, b: Vec4, t: f64) -> Vec4 {
+    let t = t as f32;
+    vec4(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t, a.z + (b.z - a.z) * t, a.w + (b.w - a.w) * t)
+}
+
+/// Colour seen when `color` is drawn over an opaque `backdrop`
+pub fn composite(color: Vec4, backdrop: Vec4) -> Vec4 {
+    let blended = mix(backdrop, color, color.w as f64);
+    vec4(blended.x, blended.y, blended.z, 1.0)
+}
+
+/// WCAG relative luminance of an sRGB colour
+pub fn relative_luminance(color: Vec4) -> f64 {
+    0.2126 * srgb_to_linear(color.x as f64) + 0.7152 * srgb_to_linear(color.y as f64) + 0.0722 * srgb_to_linear(color.z as f64)
+}
+
+/// WCAG contrast ratio, from 1 (same luminance) to 21 (black on white)
+pub fn contrast_ratio(a: Vec4, b: Vec4) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Black or white, whichever has the higher contrast against `background`
+pub fn contrasting_text(background: Vec4) -> Vec4 {
+    let black = vec4(0.0, 0.0, 0.0, 1.0);
+    let white = vec4(1.0, 1.0, 1.0, 1.0);
+    if contrast_ratio(background, black) >= contrast_ratio(background, white) { black } else { white }
+}
+
+/// Whether light text reads better than dark text on this colour
+pub fn is_dark(color: Vec4) -> bool {
+    contrasting_text(color).x > 0.5
+}
+
+fn srgb_to_linear(c: f64) -> f64 {
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn linear_to_srgb(c: f64) -> f64 {
+    if c <= 0.0031308 { 12.92 * c } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
+}
+
+/// Hue in degrees, saturation and lightness in [0, 1]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+impl Hsl {
+    pub fn from_rgb(color: Vec4) -> Self {
+        let (r, g, b) = (color.x as f64, color.y as f64, color.z as f64);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return Self { h: 0.0, s: 0.0, l };
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / d).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / d + 2.0)
+        } else {
+            60.0 * ((r - g) / d + 4.0)
+        };
+        Self { h, s, l }
+    }
+
+    pub fn to_rgb(self, alpha: f32) -> Vec4 {
+        let c = (1.0 - (2.0 * self.l - 1.0).abs()) * self.s;
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = self.l - c / 2.0;
+        vec4((r + m) as f32, (g + m) as f32, (b + m) as f32, alpha)
+    }
+}
+
+/// OKLab perceptual colour space: lightness `l` in [0, 1], `a` green-red and `b` blue-yellow axes.
+/// Equal steps in OKLab look like equal steps to the eye, unlike sRGB or HSL.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Oklab {
+    pub fn from_rgb(color: Vec4) -> Self {
+        let (r, g, b) = (srgb_to_linear(color.x as f64), srgb_to_linear(color.y as f64), srgb_to_linear(color.z as f64));
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+        Self {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+
+    /// Back to sRGB; colours outside the sRGB gamut are clipped
+    pub fn to_rgb(self, alpha: f32) -> Vec4 {
+        let l = (self.l + 0.3963377774 * self.a + 0.2158037573 * self.b).powi(3);
+        let m = (self.l - 0.1055613458 * self.a - 0.0638541728 * self.b).powi(3);
+        let s = (self.l - 0.0894841775 * self.a - 1.2914855480 * self.b).powi(3);
+        let r = 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s;
+        let g = -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s;
+        let b = -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s;
+        let channel = |c: f64| linear_to_srgb(c.clamp(0.0, 1.0)) as f32;
+        vec4(channel(r), channel(g), channel(b), alpha)
+    }
+
+    /// Point at `hue` degrees and `chroma` distance from grey, at lightness `l`
+    pub fn from_lch(l: f64, chroma: f64, hue: f64) -> Self {
+        let h = hue.to_radians();
+        Self { l, a: chroma * h.cos(), b: chroma * h.sin() }
+    }
+
+    /// Perceptual distance between two colours
+    pub fn distance(self, other: Oklab) -> f64 {
+        ((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2)).sqrt()
+    }
+}
+
+/// `n` colours of equal lightness and chroma with hues spread evenly around the OKLab circle,
+/// so no colour stands out more than the others
+pub fn even_palette(n: usize, lightness: f64, chroma: f64) -> Vec<Vec4> {
+    // Start at a blue-ish hue so the first colour matches the usual first series colour
+    let start = 250.0;
+    (0..n)
+        .map(|i| Oklab::from_lch(lightness, chroma, start + i as f64 * 360.0 / n as f64).to_rgb(1.0))
+        .collect()
+}
+
+/// Okabe-Ito colours, safe for deuteranopia, protanopia and tritanopia
+pub fn colorblind_palette() -> Vec<Vec4> {
+    OKABE_ITO.iter().map(|hex| hex_color(*hex)).collect()
+}
+
+/// Colours, line styles and markers handed out in turn to series that don't set their own,
+/// like matplotlib's `axes.prop_cycle`. Each list cycles independently.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropCycle {
+    pub colors: Vec<Vec4>,
+    pub line_styles: Vec<LineStyle>,
+    pub markers: Vec<MarkerStyle>,
+}
+
+impl Default for PropCycle {
+    fn default() -> Self {
+        Self::new((0..8).map(get_color).collect())
+    }
+}
+
+impl PropCycle {
+    pub fn new(colors: Vec<Vec4>) -> Self {
+        Self { colors, line_styles: Vec::new(), markers: Vec::new() }
+    }
+
+    /// Cycle through colours given as strings; see `parse_color`. Unknown names are skipped.
+    pub fn from_names(names: &[&str]) -> Self {
+        Self::new(names.iter().filter_map(|name| parse_color(name)).collect())
+    }
+
+    /// Colour-blind-safe cycle
+    pub fn colorblind() -> Self {
+        Self::new(colorblind_palette())
+    }
+
+    /// `n` perceptually even colours
+    pub fn even(n: usize) -> Self {
+        Self::new(even_palette(n.max(1), 0.65, 0.13))
+    }
+
+    pub fn with_line_styles(mut self, line_styles: Vec<LineStyle>) -> Self {
+        self.line_styles = line_styles;
+        self
+    }
+
+    pub fn with_markers(mut self, markers: Vec<MarkerStyle>) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    pub fn color(&self, index: usize) -> Vec4 {
+        if self.colors.is_empty() {
+            return get_color(index);
+        }
+        self.colors[index % self.colors.len()]
+    }
+
+    pub fn line_style(&self, index: usize) -> Option<LineStyle> {
+        self.line_styles.get(index % self.line_styles.len().max(1)).copied()
+    }
+
+    pub fn marker(&self, index: usize) -> Option<MarkerStyle> {
+        self.markers.get(index % self.markers.len().max(1)).copied()
+    }
+}
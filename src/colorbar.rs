@@ -0,0 +1,343 @@
+// Colorbar component shared by the colormapped charts - colour scale, ticks, extend arrows and discrete bands
+
+use makepad_widgets::*;
+use crate::elements::*;
+use crate::text::*;
+use crate::plot::PlotArea;
+use crate::colormap::Colormap;
+use crate::norm::{Norm, Normalize};
+use crate::layout::max_text_width;
+use crate::style::PlotStyle;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+
+    use crate::elements::DrawPlotLine;
+    use crate::elements::DrawPlotBar;
+    use crate::elements::DrawTriangle;
+    use crate::text::PlotLabel;
+
+    pub PlotColorbar = {{PlotColorbar}} {
+        label: <PlotLabel> {}
+    }
+}
+
+/// Thickness of the colour strip
+const COLORBAR_WIDTH: f64 = 15.0;
+
+/// Gap between the plot area and the colour strip
+const COLORBAR_GAP: f64 = 10.0;
+
+/// Length of the ticks on the outer edge of the strip
+const COLORBAR_TICK: f64 = 4.0;
+
+/// Gap between a tick and its label
+const COLORBAR_TICK_PAD: f64 = 3.0;
+
+/// Gap between the tick labels and the colorbar label
+const COLORBAR_LABEL_PAD: f64 = 6.0;
+
+/// Number of strips approximating a continuous gradient
+const GRADIENT_STEPS: usize = 64;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ColorbarOrientation {
+    /// To the right of the plot area, low values at the bottom
+    #[default]
+    Vertical,
+    /// Along the bottom of the widget, low values on the left
+    Horizontal,
+}
+
+/// Triangular ends marking values outside the norm range, like matplotlib's `extend`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ColorbarExtend {
+    #[default]
+    Neither,
+    Min,
+    Max,
+    Both,
+}
+
+impl ColorbarExtend {
+    fn has_min(&self) -> bool {
+        matches!(self, ColorbarExtend::Min | ColorbarExtend::Both)
+    }
+
+    fn has_max(&self) -> bool {
+        matches!(self, ColorbarExtend::Max | ColorbarExtend::Both)
+    }
+}
+
+/// Colour scale drawn next to a colormapped chart.
+/// The chart hands over its colormap and norm every frame and maps its values through `color`,
+/// so the cells and the bar always agree. Norms with boundaries are drawn as discrete bands.
+#[derive(Live, LiveHook, LiveRegister)]
+pub struct PlotColorbar {
+    #[live] draw_bar: DrawPlotBar,
+    #[live] draw_line: DrawPlotLine,
+    #[live] draw_triangle: DrawTriangle,
+    #[live] label: PlotLabel,
+    #[rust] pub orientation: ColorbarOrientation,
+    #[rust] pub extend: ColorbarExtend,
+    #[rust] pub title: String,  // Label along the bar
+    #[rust] colormap: Colormap,
+    #[rust] norm: Option<Box<dyn Norm>>,
+    #[rust] style: PlotStyle,
+}
+
+/// Norm used until a chart hands over its own
+const DEFAULT_NORM: Normalize = Normalize { vmin: 0.0, vmax: 1.0, clip: true };
+
+impl PlotColorbar {
+    pub fn set_orientation(&mut self, orientation: ColorbarOrientation) {
+        self.orientation = orientation;
+    }
+
+    pub fn set_extend(&mut self, extend: ColorbarExtend) {
+        self.extend = extend;
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.title = label.into();
+    }
+
+    /// Take the outline and text colours from the chart's style
+    pub fn set_style(&mut self, style: &PlotStyle) {
+        self.style = style.clone();
+    }
+
+    pub fn set_colormap(&mut self, colormap: Colormap) {
+        self.colormap = colormap;
+    }
+
+    pub fn set_norm(&mut self, norm: Box<dyn Norm>) {
+        self.norm = Some(norm);
+    }
+
+    pub fn norm(&self) -> &dyn Norm {
+        self.norm.as_deref().unwrap_or(&DEFAULT_NORM)
+    }
+
+    /// Number of discrete bands, 0 for a continuous norm
+    fn bands(&self) -> usize {
+        self.norm().boundaries().map(|b| b.len().saturating_sub(1)).unwrap_or(0)
+    }
+
+    /// Colour of discrete band `index`
+    fn band_color(&self, index: usize) -> Vec4 {
+        match self.norm().boundaries() {
+            Some(b) if index + 1 < b.len() => self.color((b[index] + b[index + 1]) / 2.0),
+            _ => self.colormap.sample(0.5),
+        }
+    }
+
+    /// Colour for a data value; values outside the norm's range take the colormap's
+    /// under/over colours and NaN its bad colour
+    pub fn color(&self, value: f64) -> Vec4 {
+        let norm = self.norm();
+        let (vmin, vmax) = norm.range();
+        if value.is_nan() {
+            self.colormap.bad_color()
+        } else if value < vmin.min(vmax) {
+            self.colormap.under_color()
+        } else if value > vmin.max(vmax) {
+            self.colormap.over_color()
+        } else {
+            self.colormap.sample(norm.normalize(value))
+        }
+    }
+
+    /// Position of a value along the bar in [0, 1].
+    /// Discrete bands are drawn at equal size whatever their width in data units.
+    fn position(&self, value: f64) -> f64 {
+        let norm = self.norm();
+        match norm.boundaries() {
+            Some(b) if b.len() >= 2 => {
+                let bands = (b.len() - 1) as f64;
+                let i = b.windows(2).position(|w| value <= w[1]).unwrap_or(b.len() - 2);
+                let width = (b[i + 1] - b[i]).max(f64::EPSILON);
+                (i as f64 + (value - b[i]) / width) / bands
+            }
+            _ => norm.normalize(value),
+        }
+    }
+
+    /// Tick positions along the bar in [0, 1] with their labels
+    fn tick_labels(&self) -> Vec<(f64, String)> {
+        self.norm().ticks()
+            .into_iter()
+            .map(|(v, text)| (self.position(v), text))
+            .filter(|(t, _)| (-1e-9..=1.0 + 1e-9).contains(t))
+            .collect()
+    }
+
+    /// Space taken across the bar, from the plot area edge to the end of the label
+    pub fn thickness(&mut self, cx: &mut Cx2d) -> f64 {
+        let labels: Vec<String> = self.tick_labels().into_iter().map(|(_, text)| text).collect();
+        let tick_extent = match self.orientation {
+            ColorbarOrientation::Vertical => max_text_width(cx, &mut self.label, &labels),
+            ColorbarOrientation::Horizontal => self.label.measure(cx, "0").y,
+        };
+        let title_extent = if self.title.is_empty() {
+            0.0
+        } else {
+            // The vertical label is rotated, so its height is the horizontal extent either way
+            COLORBAR_LABEL_PAD + self.label.measure(cx, &self.title).y
+        };
+        COLORBAR_GAP + COLORBAR_WIDTH + COLORBAR_TICK + COLORBAR_TICK_PAD + tick_extent + title_extent
+    }
+
+    /// Extra right and bottom margin the chart should reserve for the colorbar
+    pub fn margins(&mut self, cx: &mut Cx2d) -> (f64, f64) {
+        match self.orientation {
+            ColorbarOrientation::Vertical => (self.thickness(cx), 0.0),
+            ColorbarOrientation::Horizontal => (0.0, self.thickness(cx)),
+        }
+    }
+
+    /// Rect covering the part of the bar between `t0` and `t1`
+    fn segment(&self, bar: Rect, t0: f64, t1: f64) -> Rect {
+        match self.orientation {
+            ColorbarOrientation::Vertical => {
+                let bottom = bar.pos.y + bar.size.y;
+                Rect {
+                    pos: dvec2(bar.pos.x, bottom - t1 * bar.size.y),
+                    size: dvec2(bar.size.x, (t1 - t0) * bar.size.y),
+                }
+            }
+            ColorbarOrientation::Horizontal => Rect {
+                pos: dvec2(bar.pos.x + t0 * bar.size.x, bar.pos.y),
+                size: dvec2((t1 - t0) * bar.size.x, bar.size.y),
+            },
+        }
+    }
+
+    /// Draw the colorbar next to `area`; a horizontal bar sits at the bottom of `bounds`
+    pub fn draw(&mut self, cx: &mut Cx2d, area: &PlotArea, bounds: Rect) {
+        let thickness = self.thickness(cx);
+        let min_arrow = if self.extend.has_min() { COLORBAR_WIDTH } else { 0.0 };
+        let max_arrow = if self.extend.has_max() { COLORBAR_WIDTH } else { 0.0 };
+
+        let bar = match self.orientation {
+            ColorbarOrientation::Vertical => Rect {
+                pos: dvec2(area.right + COLORBAR_GAP, area.top + max_arrow),
+                size: dvec2(COLORBAR_WIDTH, area.height() - min_arrow - max_arrow),
+            },
+            ColorbarOrientation::Horizontal => Rect {
+                pos: dvec2(area.left + min_arrow, bounds.pos.y + bounds.size.y - thickness + COLORBAR_GAP),
+                size: dvec2(area.width() - min_arrow - max_arrow, COLORBAR_WIDTH),
+            },
+        };
+        if bar.size.x <= 0.0 || bar.size.y <= 0.0 {
+            return;
+        }
+
+        // Colour strip: one rect per band, or thin strips for a gradient
+        let bands = self.bands();
+        let (steps, overlap) = if bands > 0 { (bands, 0.0) } else { (GRADIENT_STEPS, 1.0) };
+        for i in 0..steps {
+            let t0 = i as f64 / steps as f64;
+            let t1 = (i + 1) as f64 / steps as f64;
+            self.draw_bar.color = if bands > 0 {
+                self.band_color(i)
+            } else {
+                self.colormap.sample((t0 + t1) / 2.0)
+            };
+            let mut rect = self.segment(bar, t0, t1);
+            // Overlap gradient strips by a pixel so no seams show
+            rect.size = rect.size + dvec2(overlap, overlap);
+            self.draw_bar.draw_bar(cx, rect);
+        }
+
+        // Extend arrows in the colour of the ends they extend
+        let left = bar.pos.x;
+        let right = bar.pos.x + bar.size.x;
+        let top = bar.pos.y;
+        let bottom = bar.pos.y + bar.size.y;
+        let center = bar.pos + bar.size * 0.5;
+        let low_color = self.colormap.under_color();
+        let high_color = self.colormap.over_color();
+        let (min_tri, max_tri) = match self.orientation {
+            ColorbarOrientation::Vertical => (
+                [dvec2(left, bottom), dvec2(right, bottom), dvec2(center.x, bottom + min_arrow)],
+                [dvec2(left, top), dvec2(right, top), dvec2(center.x, top - max_arrow)],
+            ),
+            ColorbarOrientation::Horizontal => (
+                [dvec2(left, top), dvec2(left, bottom), dvec2(left - min_arrow, center.y)],
+                [dvec2(right, top), dvec2(right, bottom), dvec2(right + max_arrow, center.y)],
+            ),
+        };
+        if self.extend.has_min() {
+            self.draw_triangle.color = low_color;
+            self.draw_triangle.draw_triangle(cx, min_tri[0], min_tri[1], min_tri[2]);
+        }
+        if self.extend.has_max() {
+            self.draw_triangle.color = high_color;
+            self.draw_triangle.draw_triangle(cx, max_tri[0], max_tri[1], max_tri[2]);
+        }
+
+        // Outline, following the arrows
+        self.draw_line.color = self.style.axes_color;
+        let outline: Vec<DVec2> = match self.orientation {
+            ColorbarOrientation::Vertical => {
+                let mut points = vec![dvec2(left, top)];
+                if self.extend.has_max() { points.push(max_tri[2]); }
+                points.extend([dvec2(right, top), dvec2(right, bottom)]);
+                if self.extend.has_min() { points.push(min_tri[2]); }
+                points.push(dvec2(left, bottom));
+                points
+            }
+            ColorbarOrientation::Horizontal => {
+                let mut points = vec![dvec2(left, top), dvec2(right, top)];
+                if self.extend.has_max() { points.push(max_tri[2]); }
+                points.extend([dvec2(right, bottom), dvec2(left, bottom)]);
+                if self.extend.has_min() { points.push(min_tri[2]); }
+                points
+            }
+        };
+        for (i, p) in outline.iter().enumerate() {
+            self.draw_line.draw_line(cx, *p, outline[(i + 1) % outline.len()], 1.0);
+        }
+
+        // Ticks and their labels on the outer edge
+        self.label.set_color(self.style.text_color);
+        let ticks = self.tick_labels();
+        let mut tick_extent: f64 = 0.0;
+        for (t, text) in &ticks {
+            match self.orientation {
+                ColorbarOrientation::Vertical => {
+                    let y = bottom - t * bar.size.y;
+                    self.draw_line.draw_line(cx, dvec2(right, y), dvec2(right + COLORBAR_TICK, y), 1.0);
+                    let pos = dvec2(right + COLORBAR_TICK + COLORBAR_TICK_PAD, y);
+                    self.label.draw_at(cx, pos, text, TextAnchor::MiddleLeft);
+                    tick_extent = tick_extent.max(self.label.measure(cx, text).x);
+                }
+                ColorbarOrientation::Horizontal => {
+                    let x = left + t * bar.size.x;
+                    self.draw_line.draw_line(cx, dvec2(x, bottom), dvec2(x, bottom + COLORBAR_TICK), 1.0);
+                    let pos = dvec2(x, bottom + COLORBAR_TICK + COLORBAR_TICK_PAD);
+                    self.label.draw_at(cx, pos, text, TextAnchor::TopCenter);
+                    tick_extent = tick_extent.max(self.label.measure(cx, text).y);
+                }
+            }
+        }
+
+        if !self.title.is_empty() {
+            let title = self.title.clone();
+            let offset = COLORBAR_TICK + COLORBAR_TICK_PAD + tick_extent + COLORBAR_LABEL_PAD;
+            match self.orientation {
+                ColorbarOrientation::Vertical => {
+                    // Reads bottom-to-top with the glyph tops facing the bar
+                    let pos = dvec2(right + offset, (area.top + area.bottom) / 2.0);
+                    self.label.draw_rotated(cx, pos, &title, TextAnchor::TopCenter, 90.0);
+                }
+                ColorbarOrientation::Horizontal => {
+                    let pos = dvec2((area.left + area.right) / 2.0, bottom + offset);
+                    self.label.draw_at(cx, pos, &title, TextAnchor::TopCenter);
+                }
+            }
+        }
+    }
+}
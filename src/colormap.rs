@@ -0,0 +1,538 @@
+// Colormaps - named sequential, diverging and qualitative maps, a name registry and derived maps
+
+use makepad_widgets::*;
+use std::sync::Mutex;
+use crate::color::{hex_color, mix};
+
+/// Colormap for heatmap and other visualizations
+#[derive(Clone, Debug, PartialEq)]
+pub enum Colormap {
+    // Perceptually uniform sequential
+    Viridis,
+    Plasma,
+    Inferno,
+    Magma,
+    Cividis,  // Colorblind-friendly
+    // Diverging
+    Coolwarm,
+    RdBu,     // Red-Blue diverging
+    Spectral,
+    // Sequential
+    Blues,
+    Greens,
+    Oranges,
+    Reds,
+    Greys,
+    // Classic
+    Jet,      // Rainbow (legacy)
+    Hot,      // Black-Red-Yellow-White
+    // Special
+    Turbo,    // Improved rainbow
+    // Qualitative (listed): a fixed set of distinct colours, no interpolation
+    Tab10,
+    Tab20,
+    Set1,
+    Set2,
+    Set3,
+    Paired,
+    Pastel1,
+    Pastel2,
+    Dark2,
+    Accent,
+    Custom(Vec<(f64, Vec4)>),  // User-defined color stops
+    Listed(Vec<Vec4>),         // User-defined discrete colours
+    // Derived maps, built through `reversed`, `with_alpha`, `blend` and `set_over`/`set_under`/`set_bad`
+    Reversed(Box<Colormap>),
+    Blended(Box<Colormap>, Box<Colormap>, f64),
+    WithAlpha(Box<Colormap>, f32),
+    Extremes(Box<Colormap>, ColormapExtremes),
+}
+
+/// Colours for values beyond either end of a colormap and for NaN.
+/// Unset ends fall back to the end colours of the map; unset "bad" is transparent.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColormapExtremes {
+    pub over: Option<Vec4>,
+    pub under: Option<Vec4>,
+    pub bad: Option<Vec4>,
+}
+
+#[derive(Clone, Copy)]
+enum Extreme {
+    Under,
+    Over,
+    Bad,
+}
+
+impl Default for Colormap {
+    fn default() -> Self {
+        Colormap::Viridis
+    }
+}
+
+// Qualitative tables, same colours as matplotlib
+const TAB10: &[u32] = &[
+    0x1f77b4, 0xff7f0e, 0x2ca02c, 0xd62728, 0x9467bd, 0x8c564b, 0xe377c2, 0x7f7f7f, 0xbcbd22, 0x17becf,
+];
+const TAB20: &[u32] = &[
+    0x1f77b4, 0xaec7e8, 0xff7f0e, 0xffbb78, 0x2ca02c, 0x98df8a, 0xd62728, 0xff9896, 0x9467bd, 0xc5b0d5,
+    0x8c564b, 0xc49c94, 0xe377c2, 0xf7b6d2, 0x7f7f7f, 0xc7c7c7, 0xbcbd22, 0xdbdb8d, 0x17becf, 0x9edae5,
+];
+const SET1: &[u32] = &[0xe41a1c, 0x377eb8, 0x4daf4a, 0x984ea3, 0xff7f00, 0xffff33, 0xa65628, 0xf781bf, 0x999999];
+const SET2: &[u32] = &[0x66c2a5, 0xfc8d62, 0x8da0cb, 0xe78ac3, 0xa6d854, 0xffd92f, 0xe5c494, 0xb3b3b3];
+const SET3: &[u32] = &[
+    0x8dd3c7, 0xffffb3, 0xbebada, 0xfb8072, 0x80b1d3, 0xfdb462, 0xb3de69, 0xfccde5, 0xd9d9d9, 0xbc80bd,
+    0xccebc5, 0xffed6f,
+];
+const PAIRED: &[u32] = &[
+    0xa6cee3, 0x1f78b4, 0xb2df8a, 0x33a02c, 0xfb9a99, 0xe31a1c, 0xfdbf6f, 0xff7f00, 0xcab2d6, 0x6a3d9a,
+    0xffff99, 0xb15928,
+];
+const PASTEL1: &[u32] = &[0xfbb4ae, 0xb3cde3, 0xccebc5, 0xdecbe4, 0xfed9a6, 0xffffcc, 0xe5d8bd, 0xfddaec, 0xf2f2f2];
+const PASTEL2: &[u32] = &[0xb3e2cd, 0xfdcdac, 0xcbd5e8, 0xf4cae4, 0xe6f5c9, 0xfff2ae, 0xf1e2cc, 0xcccccc];
+const DARK2: &[u32] = &[0x1b9e77, 0xd95f02, 0x7570b3, 0xe7298a, 0x66a61e, 0xe6ab02, 0xa6761d, 0x666666];
+const ACCENT: &[u32] = &[0x7fc97f, 0xbeaed4, 0xfdc086, 0xffff99, 0x386cb0, 0xf0027f, 0xbf5b17, 0x666666];
+
+/// Colormaps registered by name at runtime, looked up before the built-in ones
+static REGISTRY: Mutex<Vec<(String, Colormap)>> = Mutex::new(Vec::new());
+
+impl Colormap {
+    /// Sample a color from the colormap at position t (0.0 to 1.0).
+    /// Positions below 0 or above 1 give the under/over colours, NaN gives the bad colour.
+    pub fn sample(&self, t: f64) -> Vec4 {
+        if t.is_nan() {
+            self.bad_color()
+        } else if t < 0.0 {
+            self.under_color()
+        } else if t > 1.0 {
+            self.over_color()
+        } else {
+            self.sample_in_range(t)
+        }
+    }
+
+    /// Colour at t, which must already be inside [0, 1]
+    fn sample_in_range(&self, t: f64) -> Vec4 {
+        match self {
+            Colormap::Viridis => {
+                // Perceptually uniform purple-green-yellow
+                let r = 0.267 + t * 0.329 - t * t * 0.5 + t * t * t * 0.9;
+                let g = 0.004 + t * 0.873;
+                let b = 0.329 + t * 0.5 - t * t * 0.6;
+                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
+            }
+            Colormap::Plasma => {
+                // Purple-red-yellow
+                let r = 0.05 + t * 0.9 + t * t * 0.05;
+                let g = t * t * 0.9;
+                let b = 0.53 + (1.0 - t) * 0.47 - t * t * 0.6;
+                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
+            }
+            Colormap::Inferno => {
+                // Black-purple-red-yellow
+                let r = t * t * 1.2;
+                let g = t * t * t * 1.5;
+                let b = (1.0 - t) * t * 2.0 + 0.1 * t;
+                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
+            }
+            Colormap::Magma => {
+                // Black-purple-pink-white
+                let r = t * t * 0.8 + t * 0.2;
+                let g = t * t * t * 1.2;
+                let b = t * 0.5 + (1.0 - t) * t * 1.0;
+                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
+            }
+            Colormap::Cividis => {
+                // Colorblind-friendly blue-yellow
+                let r = -0.01 + t * 1.0 + t * t * 0.01;
+                let g = 0.14 + t * 0.72;
+                let b = 0.35 + t * 0.1 - t * t * 0.35;
+                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
+            }
+            Colormap::Coolwarm => {
+                // Blue-white-red diverging
+                let r = if t < 0.5 { 0.2 + t * 1.6 } else { 1.0 };
+                let g = if t < 0.5 { 0.2 + t * 1.0 } else { 1.0 - (t - 0.5) * 1.6 };
+                let b = if t < 0.5 { 1.0 } else { 1.0 - (t - 0.5) * 1.6 };
+                vec4(r as f32, g as f32, b as f32, 1.0)
+            }
+            Colormap::RdBu => {
+                // Red-white-blue diverging (red=high, blue=low)
+                let r = if t < 0.5 { 0.1 + t * 1.8 } else { 1.0 - (t - 0.5) * 1.4 };
+                let g = if t < 0.5 { t * 1.8 } else { 0.9 - (t - 0.5) * 1.6 };
+                let b = if t < 0.5 { 1.0 - t * 0.2 } else { 0.9 - (t - 0.5) * 1.0 };
+                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
+            }
+            Colormap::Spectral => {
+                // Red-orange-yellow-green-blue (diverging rainbow)
+                let (r, g, b) = if t < 0.25 {
+                    let s = t / 0.25;
+                    (0.62 + s * 0.38, 0.0 + s * 0.5, 0.26 * (1.0 - s))
+                } else if t < 0.5 {
+                    let s = (t - 0.25) / 0.25;
+                    (1.0, 0.5 + s * 0.5, 0.0)
+                } else if t < 0.75 {
+                    let s = (t - 0.5) / 0.25;
+                    (1.0 - s * 0.5, 1.0 - s * 0.2, s * 0.4)
+                } else {
+                    let s = (t - 0.75) / 0.25;
+                    (0.5 - s * 0.3, 0.8 - s * 0.4, 0.4 + s * 0.6)
+                };
+                vec4(r as f32, g as f32, b as f32, 1.0)
+            }
+            Colormap::Blues => {
+                let r = 1.0 - t * 0.8;
+                let g = 1.0 - t * 0.5;
+                let b = 1.0;
+                vec4(r as f32, g as f32, b as f32, 1.0)
+            }
+            Colormap::Greens => {
+                let r = 1.0 - t * 0.75;
+                let g = 1.0 - t * 0.15;
+                let b = 1.0 - t * 0.7;
+                vec4(r as f32, g as f32, b as f32, 1.0)
+            }
+            Colormap::Oranges => {
+                let r = 1.0;
+                let g = 1.0 - t * 0.6;
+                let b = 1.0 - t * 0.85;
+                vec4(r as f32, g as f32, b as f32, 1.0)
+            }
+            Colormap::Reds => {
+                let r = 1.0;
+                let g = 1.0 - t * 0.85;
+                let b = 1.0 - t * 0.85;
+                vec4(r as f32, g as f32, b as f32, 1.0)
+            }
+            Colormap::Greys => {
+                let v = 1.0 - t * 0.9;
+                vec4(v as f32, v as f32, v as f32, 1.0)
+            }
+            Colormap::Jet => {
+                // Classic rainbow: blue-cyan-green-yellow-red
+                let (r, g, b) = if t < 0.125 {
+                    (0.0, 0.0, 0.5 + t * 4.0)
+                } else if t < 0.375 {
+                    let s = (t - 0.125) / 0.25;
+                    (0.0, s, 1.0)
+                } else if t < 0.625 {
+                    let s = (t - 0.375) / 0.25;
+                    (s, 1.0, 1.0 - s)
+                } else if t < 0.875 {
+                    let s = (t - 0.625) / 0.25;
+                    (1.0, 1.0 - s, 0.0)
+                } else {
+                    let s = (t - 0.875) / 0.125;
+                    (1.0 - s * 0.5, 0.0, 0.0)
+                };
+                vec4(r as f32, g as f32, b as f32, 1.0)
+            }
+            Colormap::Hot => {
+                // Black-red-yellow-white
+                let (r, g, b) = if t < 0.33 {
+                    (t * 3.0, 0.0, 0.0)
+                } else if t < 0.67 {
+                    let s = (t - 0.33) / 0.34;
+                    (1.0, s, 0.0)
+                } else {
+                    let s = (t - 0.67) / 0.33;
+                    (1.0, 1.0, s)
+                };
+                vec4(r as f32, g as f32, b as f32, 1.0)
+            }
+            Colormap::Turbo => {
+                // Improved rainbow with better perceptual uniformity
+                let r = 0.13572 + t * (4.6153 + t * (-42.66 + t * (132.13 + t * (-152.95 + t * 56.31))));
+                let g = 0.09140 + t * (2.1745 + t * (4.8321 + t * (-36.60 + t * (43.05 + t * (-13.22)))));
+                let b = 0.10667 + t * (12.755 + t * (-60.58 + t * (109.33 + t * (-87.15 + t * 25.25))));
+                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
+            }
+            Colormap::Tab10 | Colormap::Tab20 | Colormap::Set1 | Colormap::Set2 | Colormap::Set3
+            | Colormap::Paired | Colormap::Pastel1 | Colormap::Pastel2 | Colormap::Dark2 | Colormap::Accent => {
+                let table = self.table().unwrap_or_default();
+                let index = ((t * table.len() as f64) as usize).min(table.len().saturating_sub(1));
+                table.get(index).map(|hex| hex_color(*hex)).unwrap_or(vec4(0.5, 0.5, 0.5, 1.0))
+            }
+            Colormap::Listed(colors) => {
+                let index = ((t * colors.len() as f64) as usize).min(colors.len().saturating_sub(1));
+                colors.get(index).copied().unwrap_or(vec4(0.5, 0.5, 0.5, 1.0))
+            }
+            Colormap::Reversed(base) => base.sample_in_range(1.0 - t),
+            Colormap::Blended(a, b, weight) => mix(a.sample_in_range(t), b.sample_in_range(t), *weight),
+            Colormap::WithAlpha(base, alpha) => {
+                let c = base.sample_in_range(t);
+                vec4(c.x, c.y, c.z, *alpha)
+            }
+            Colormap::Extremes(base, _) => base.sample_in_range(t),
+            Colormap::Custom(stops) => {
+                if stops.is_empty() {
+                    return vec4(0.5, 0.5, 0.5, 1.0);
+                }
+                if stops.len() == 1 {
+                    return stops[0].1;
+                }
+                // Find surrounding stops and interpolate
+                for i in 0..stops.len() - 1 {
+                    if t <= stops[i + 1].0 {
+                        let t0 = stops[i].0;
+                        let t1 = stops[i + 1].0;
+                        let c0 = stops[i].1;
+                        let c1 = stops[i + 1].1;
+                        let s = if t1 > t0 { (t - t0) / (t1 - t0) } else { 0.0 };
+                        return vec4(
+                            c0.x + (c1.x - c0.x) * s as f32,
+                            c0.y + (c1.y - c0.y) * s as f32,
+                            c0.z + (c1.z - c0.z) * s as f32,
+                            c0.w + (c1.w - c0.w) * s as f32,
+                        );
+                    }
+                }
+                stops.last().unwrap().1
+            }
+        }
+    }
+
+    /// Get a list of all named colormaps
+    pub fn all_named() -> Vec<Colormap> {
+        vec![
+            Colormap::Viridis,
+            Colormap::Plasma,
+            Colormap::Inferno,
+            Colormap::Magma,
+            Colormap::Cividis,
+            Colormap::Coolwarm,
+            Colormap::RdBu,
+            Colormap::Spectral,
+            Colormap::Blues,
+            Colormap::Greens,
+            Colormap::Oranges,
+            Colormap::Reds,
+            Colormap::Greys,
+            Colormap::Jet,
+            Colormap::Hot,
+            Colormap::Turbo,
+        ]
+    }
+
+    /// Get a list of all qualitative (listed) colormaps
+    pub fn all_qualitative() -> Vec<Colormap> {
+        vec![
+            Colormap::Tab10,
+            Colormap::Tab20,
+            Colormap::Set1,
+            Colormap::Set2,
+            Colormap::Set3,
+            Colormap::Paired,
+            Colormap::Pastel1,
+            Colormap::Pastel2,
+            Colormap::Dark2,
+            Colormap::Accent,
+        ]
+    }
+
+    /// Get the name of this colormap
+    pub fn name(&self) -> &'static str {
+        match self {
+            Colormap::Viridis => "Viridis",
+            Colormap::Plasma => "Plasma",
+            Colormap::Inferno => "Inferno",
+            Colormap::Magma => "Magma",
+            Colormap::Cividis => "Cividis",
+            Colormap::Coolwarm => "Coolwarm",
+            Colormap::RdBu => "RdBu",
+            Colormap::Spectral => "Spectral",
+            Colormap::Blues => "Blues",
+            Colormap::Greens => "Greens",
+            Colormap::Oranges => "Oranges",
+            Colormap::Reds => "Reds",
+            Colormap::Greys => "Greys",
+            Colormap::Jet => "Jet",
+            Colormap::Hot => "Hot",
+            Colormap::Turbo => "Turbo",
+            Colormap::Tab10 => "tab10",
+            Colormap::Tab20 => "tab20",
+            Colormap::Set1 => "Set1",
+            Colormap::Set2 => "Set2",
+            Colormap::Set3 => "Set3",
+            Colormap::Paired => "Paired",
+            Colormap::Pastel1 => "Pastel1",
+            Colormap::Pastel2 => "Pastel2",
+            Colormap::Dark2 => "Dark2",
+            Colormap::Accent => "Accent",
+            Colormap::Custom(_) => "Custom",
+            Colormap::Listed(_) => "Listed",
+            Colormap::Reversed(_) => "Reversed",
+            Colormap::Blended(..) => "Blended",
+            Colormap::WithAlpha(base, _) | Colormap::Extremes(base, _) => base.name(),
+        }
+    }
+
+    /// Create a custom colormap from color stops
+    /// Stops should be (position, color) pairs where position is 0.0 to 1.0
+    pub fn custom(stops: Vec<(f64, Vec4)>) -> Self {
+        Colormap::Custom(stops)
+    }
+
+    /// Create a qualitative colormap: [0, 1] is split into one equal slot per colour
+    pub fn listed(colors: Vec<Vec4>) -> Self {
+        Colormap::Listed(colors)
+    }
+
+    /// Look a colormap up by name, case-insensitively. Registered maps win over built-in ones,
+    /// and a `_r` suffix gives the reversed map, e.g. "viridis_r".
+    pub fn from_name(name: &str) -> Option<Colormap> {
+        let registered = REGISTRY.lock().ok().and_then(|registry| {
+            registry.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, cmap)| cmap.clone())
+        });
+        if registered.is_some() {
+            return registered;
+        }
+        if let Some(base) = name.strip_suffix("_r") {
+            return Self::from_name(base).map(|cmap| cmap.reversed());
+        }
+        Self::all_named()
+            .into_iter()
+            .chain(Self::all_qualitative())
+            .find(|cmap| cmap.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Make a colormap available to `from_name`, replacing any earlier one with the same name
+    pub fn register(name: impl Into<String>, colormap: Colormap) {
+        let name = name.into();
+        if let Ok(mut registry) = REGISTRY.lock() {
+            registry.retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+            registry.push((name, colormap));
+        }
+    }
+
+    /// Names of the colormaps added through `register`
+    pub fn registered_names() -> Vec<String> {
+        REGISTRY.lock().map(|registry| registry.iter().map(|(n, _)| n.clone()).collect()).unwrap_or_default()
+    }
+
+    /// The same map running from 1 to 0; over and under colours swap too
+    pub fn reversed(&self) -> Colormap {
+        match self {
+            Colormap::Reversed(base) => (**base).clone(),
+            Colormap::Custom(stops) => Colormap::Custom(stops.iter().rev().map(|(t, c)| (1.0 - t, *c)).collect()),
+            Colormap::Listed(colors) => Colormap::Listed(colors.iter().rev().copied().collect()),
+            Colormap::Extremes(base, extremes) => Colormap::Extremes(
+                Box::new(base.reversed()),
+                ColormapExtremes { over: extremes.under, under: extremes.over, bad: extremes.bad },
+            ),
+            _ => Colormap::Reversed(Box::new(self.clone())),
+        }
+    }
+
+    /// Discrete version of the map with `n` colours sampled evenly from end to end
+    pub fn resampled(&self, n: usize) -> Colormap {
+        let colors = match n {
+            0 => Vec::new(),
+            1 => vec![self.sample_in_range(0.5)],
+            _ => (0..n).map(|i| self.sample_in_range(i as f64 / (n - 1) as f64)).collect(),
+        };
+        match self {
+            Colormap::Extremes(_, extremes) => Colormap::Extremes(Box::new(Colormap::Listed(colors)), extremes.clone()),
+            _ => Colormap::Listed(colors),
+        }
+    }
+
+    /// The same map with every colour at a fixed opacity
+    pub fn with_alpha(&self, alpha: f32) -> Colormap {
+        let alpha = alpha.clamp(0.0, 1.0);
+        match self {
+            Colormap::Custom(stops) => Colormap::Custom(stops.iter().map(|(t, c)| (*t, vec4(c.x, c.y, c.z, alpha))).collect()),
+            Colormap::Listed(colors) => Colormap::Listed(colors.iter().map(|c| vec4(c.x, c.y, c.z, alpha)).collect()),
+            Colormap::WithAlpha(base, _) => Colormap::WithAlpha(base.clone(), alpha),
+            _ => Colormap::WithAlpha(Box::new(self.clone()), alpha),
+        }
+    }
+
+    /// Mix two colormaps: `weight` 0 gives `a`, 1 gives `b`
+    pub fn blend(a: Colormap, b: Colormap, weight: f64) -> Colormap {
+        Colormap::Blended(Box::new(a), Box::new(b), weight.clamp(0.0, 1.0))
+    }
+
+    /// Colour for values above the top of the norm
+    pub fn set_over(&mut self, color: Vec4) {
+        self.extremes_mut().over = Some(color);
+    }
+
+    /// Colour for values below the bottom of the norm
+    pub fn set_under(&mut self, color: Vec4) {
+        self.extremes_mut().under = Some(color);
+    }
+
+    /// Colour for NaN values
+    pub fn set_bad(&mut self, color: Vec4) {
+        self.extremes_mut().bad = Some(color);
+    }
+
+    pub fn over_color(&self) -> Vec4 {
+        self.extreme(Extreme::Over)
+    }
+
+    pub fn under_color(&self) -> Vec4 {
+        self.extreme(Extreme::Under)
+    }
+
+    pub fn bad_color(&self) -> Vec4 {
+        self.extreme(Extreme::Bad)
+    }
+
+    fn extremes_mut(&mut self) -> &mut ColormapExtremes {
+        if !matches!(self, Colormap::Extremes(..)) {
+            let base = std::mem::take(self);
+            *self = Colormap::Extremes(Box::new(base), ColormapExtremes::default());
+        }
+        match self {
+            Colormap::Extremes(_, extremes) => extremes,
+            _ => unreachable!(),
+        }
+    }
+
+    fn extreme(&self, which: Extreme) -> Vec4 {
+        match self {
+            Colormap::Extremes(base, extremes) => {
+                let color = match which {
+                    Extreme::Over => extremes.over,
+                    Extreme::Under => extremes.under,
+                    Extreme::Bad => extremes.bad,
+                };
+                color.unwrap_or_else(|| base.extreme(which))
+            }
+            Colormap::Reversed(base) => base.extreme(match which {
+                Extreme::Over => Extreme::Under,
+                Extreme::Under => Extreme::Over,
+                Extreme::Bad => Extreme::Bad,
+            }),
+            Colormap::Blended(a, b, weight) => mix(a.extreme(which), b.extreme(which), *weight),
+            Colormap::WithAlpha(base, alpha) => {
+                let c = base.extreme(which);
+                vec4(c.x, c.y, c.z, c.w.min(*alpha))
+            }
+            _ => match which {
+                Extreme::Under => self.sample_in_range(0.0),
+                Extreme::Over => self.sample_in_range(1.0),
+                Extreme::Bad => vec4(0.0, 0.0, 0.0, 0.0),
+            },
+        }
+    }
+
+    /// Colour table of a built-in qualitative map
+    fn table(&self) -> Option<&'static [u32]> {
+        match self {
+            Colormap::Tab10 => Some(TAB10),
+            Colormap::Tab20 => Some(TAB20),
+            Colormap::Set1 => Some(SET1),
+            Colormap::Set2 => Some(SET2),
+            Colormap::Set3 => Some(SET3),
+            Colormap::Paired => Some(PAIRED),
+            Colormap::Pastel1 => Some(PASTEL1),
+            Colormap::Pastel2 => Some(PASTEL2),
+            Colormap::Dark2 => Some(DARK2),
+            Colormap::Accent => Some(ACCENT),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,257 @@
+// Data input - lets charts take slices, iterators, other number types and flat 2D buffers
+
+use std::borrow::Cow;
+
+/// A number that can be plotted
+pub trait Scalar: Copy {
+    fn to_f64(self) -> f64;
+
+    /// The slice itself when it already holds `f64`s, so it can be borrowed instead of converted
+    fn as_f64s(_values: &[Self]) -> Option<&[f64]> {
+        None
+    }
+}
+
+macro_rules! impl_scalar {
+    ($($ty:ty),*) => {
+        $(impl Scalar for $ty {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_scalar!(f32, i8, i16, i32, i64, u8, u16, u32, u64, isize, usize);
+
+impl Scalar for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn as_f64s(values: &[Self]) -> Option<&[f64]> {
+        Some(values)
+    }
+}
+
+impl<T: Scalar> Scalar for &T {
+    fn to_f64(self) -> f64 {
+        (*self).to_f64()
+    }
+}
+
+/// One dimension of data. Owned `Vec<f64>`s are moved and `f64` slices borrowed, never
+/// copied; other number types and `Values` iterators are converted.
+pub trait IntoValues {
+    fn into_values<'a>(self) -> Cow<'a, [f64]> where Self: 'a;
+}
+
+impl IntoValues for Vec<f64> {
+    fn into_values<'a>(self) -> Cow<'a, [f64]> where Self: 'a {
+        Cow::Owned(self)
+    }
+}
+
+macro_rules! impl_into_values_for_vec {
+    ($($ty:ty),*) => {
+        $(impl IntoValues for Vec<$ty> {
+            fn into_values<'a>(self) -> Cow<'a, [f64]> where Self: 'a {
+                Cow::Owned(self.into_iter().map(Scalar::to_f64).collect())
+            }
+        })*
+    };
+}
+
+impl_into_values_for_vec!(f32, i8, i16, i32, i64, u8, u16, u32, u64, isize, usize);
+
+impl<T: Scalar> IntoValues for &[T] {
+    fn into_values<'a>(self) -> Cow<'a, [f64]> where Self: 'a {
+        match T::as_f64s(self) {
+            Some(values) => Cow::Borrowed(values),
+            None => Cow::Owned(self.iter().map(|v| v.to_f64()).collect()),
+        }
+    }
+}
+
+impl<T: Scalar> IntoValues for &Vec<T> {
+    fn into_values<'a>(self) -> Cow<'a, [f64]> where Self: 'a {
+        self.as_slice().into_values()
+    }
+}
+
+impl<T: Scalar, const N: usize> IntoValues for [T; N] {
+    fn into_values<'a>(self) -> Cow<'a, [f64]> where Self: 'a {
+        Cow::Owned(self.iter().map(|v| v.to_f64()).collect())
+    }
+}
+
+impl<T: Scalar, const N: usize> IntoValues for &[T; N] {
+    fn into_values<'a>(self) -> Cow<'a, [f64]> where Self: 'a {
+        self.as_slice().into_values()
+    }
+}
+
+/// Data from any iterator of numbers, e.g. `Values((0..100).map(|i| (i as f64).sin()))`
+#[derive(Clone, Debug)]
+pub struct Values<I>(pub I);
+
+impl<I> IntoValues for Values<I>
+where
+    I: IntoIterator,
+    I::Item: Scalar,
+{
+    fn into_values<'a>(self) -> Cow<'a, [f64]> where Self: 'a {
+        Cow::Owned(self.0.into_iter().map(Scalar::to_f64).collect())
+    }
+}
+
+/// A borrowed row-major buffer with a (rows, cols) shape, such as an image or the
+/// `as_slice()` of a 2D ndarray
+#[derive(Clone, Copy, Debug)]
+pub struct Grid<'a, T> {
+    data: &'a [T],
+    rows: usize,
+    cols: usize,
+}
+
+impl<'a, T: Scalar> Grid<'a, T> {
+    /// None when the buffer length is not `rows * cols`
+    pub fn new(data: &'a [T], (rows, cols): (usize, usize)) -> Option<Self> {
+        if data.len() != rows * cols {
+            return None;
+        }
+        Some(Self { data, rows, cols })
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn row(&self, row: usize) -> &'a [T] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        self.data[row * self.cols + col].to_f64()
+    }
+}
+
+/// An owned row-major buffer with a (rows, cols) shape, the form charts keep 2D data in
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Matrix {
+    data: Vec<f64>,
+    rows: usize,
+    cols: usize,
+}
+
+impl Matrix {
+    /// None when the buffer length is not `rows * cols`
+    pub fn new(data: Vec<f64>, (rows, cols): (usize, usize)) -> Option<Self> {
+        if data.len() != rows * cols {
+            return None;
+        }
+        Some(Self { data, rows, cols })
+    }
+
+    /// Stack rows into one buffer. Short rows are padded with NaN, which charts leave blank.
+    pub fn from_rows<R: IntoValues>(rows: impl IntoIterator<Item = R>) -> Self {
+        let rows: Vec<Cow<[f64]>> = rows.into_iter().map(|row| row.into_values()).collect();
+        let cols = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut data = Vec::with_capacity(rows.len() * cols);
+        for row in &rows {
+            data.extend_from_slice(row);
+            data.resize(data.len() + cols - row.len(), f64::NAN);
+        }
+        Self { data, rows: rows.len(), cols }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.rows = 0;
+        self.cols = 0;
+    }
+
+    /// Every value, row after row
+    pub fn values(&self) -> &[f64] {
+        &self.data
+    }
+
+    pub fn row(&self, row: usize) -> &[f64] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[f64]> {
+        (0..self.rows).map(move |row| self.row(row))
+    }
+
+    /// One vec per row, as chart specs store matrices
+    pub fn to_rows(&self) -> Vec<Vec<f64>> {
+        self.iter_rows().map(<[f64]>::to_vec).collect()
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        &self.data[row * self.cols + col]
+    }
+}
+
+/// Two dimensions of data, gathered into one flat `Matrix`. Implement it for your own
+/// matrix type to pass it to heatmaps, contours and surfaces directly.
+pub trait IntoGrid {
+    fn into_matrix(self) -> Matrix;
+}
+
+impl IntoGrid for Matrix {
+    fn into_matrix(self) -> Matrix {
+        self
+    }
+}
+
+impl IntoGrid for Vec<Vec<f64>> {
+    fn into_matrix(self) -> Matrix {
+        Matrix::from_rows(self)
+    }
+}
+
+impl<T: Scalar> IntoGrid for &[Vec<T>] {
+    fn into_matrix(self) -> Matrix {
+        Matrix::from_rows(self)
+    }
+}
+
+impl<T: Scalar> IntoGrid for &Vec<Vec<T>> {
+    fn into_matrix(self) -> Matrix {
+        Matrix::from_rows(self)
+    }
+}
+
+impl<T: Scalar, const N: usize> IntoGrid for &[[T; N]] {
+    fn into_matrix(self) -> Matrix {
+        Matrix::from_rows(self)
+    }
+}
+
+impl<T: Scalar> IntoGrid for Grid<'_, T> {
+    fn into_matrix(self) -> Matrix {
+        Matrix { data: self.data.into_values().into_owned(), rows: self.rows, cols: self.cols }
+    }
+}
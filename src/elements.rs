@@ -345,6 +345,20 @@ live_design! {
             return vec4(0.0, 0.0, 0.0, 0.0);
         }
     }
+
+    // Rounded box with optional border, used behind text annotations
+    pub DrawPlotBox = {{DrawPlotBox}} {
+        fn pixel(self) -> vec4 {
+            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+            let inset = self.border_width * 0.5;
+            sdf.box(inset, inset, self.rect_size.x - self.border_width, self.rect_size.y - self.border_width, self.radius);
+            sdf.fill_keep(self.color);
+            if self.border_width > 0.0 {
+                sdf.stroke(self.border_color, self.border_width);
+            }
+            return sdf.result;
+        }
+    }
 }
 
 /// Line style enumeration
@@ -372,6 +386,69 @@ pub enum MarkerStyle {
     Star = 7,
 }
 
+impl LineStyle {
+    /// Matplotlib's `linestyle` name
+    pub fn name(&self) -> &'static str {
+        match self {
+            LineStyle::Solid => "solid",
+            LineStyle::Dashed => "dashed",
+            LineStyle::Dotted => "dotted",
+            LineStyle::DashDot => "dashdot",
+        }
+    }
+
+    /// Look a line style up by name or by its short form ("-", "--", ":", "-.")
+    pub fn from_name(name: &str) -> Option<LineStyle> {
+        match name {
+            "-" => return Some(LineStyle::Solid),
+            "--" => return Some(LineStyle::Dashed),
+            ":" => return Some(LineStyle::Dotted),
+            "-." => return Some(LineStyle::DashDot),
+            _ => {}
+        }
+        [LineStyle::Solid, LineStyle::Dashed, LineStyle::Dotted, LineStyle::DashDot]
+            .into_iter()
+            .find(|style| style.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl MarkerStyle {
+    const ALL: [MarkerStyle; 9] = [
+        MarkerStyle::None,
+        MarkerStyle::Circle,
+        MarkerStyle::Square,
+        MarkerStyle::TriangleUp,
+        MarkerStyle::TriangleDown,
+        MarkerStyle::Diamond,
+        MarkerStyle::Cross,
+        MarkerStyle::Plus,
+        MarkerStyle::Star,
+    ];
+
+    /// Matplotlib's marker code
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarkerStyle::None => "none",
+            MarkerStyle::Circle => "o",
+            MarkerStyle::Square => "s",
+            MarkerStyle::TriangleUp => "^",
+            MarkerStyle::TriangleDown => "v",
+            MarkerStyle::Diamond => "D",
+            MarkerStyle::Cross => "x",
+            MarkerStyle::Plus => "+",
+            MarkerStyle::Star => "*",
+        }
+    }
+
+    /// Look a marker up by its code; "" means no marker
+    pub fn from_name(name: &str) -> Option<MarkerStyle> {
+        if name.is_empty() {
+            return Some(MarkerStyle::None);
+        }
+        Self::ALL.into_iter().find(|marker| marker.name() == name)
+    }
+}
+
 #[derive(Live, LiveHook, LiveRegister)]
 #[repr(C)]
 pub struct DrawPlotLine {
@@ -714,3 +791,24 @@ impl DrawTriangle {
         self.draw_abs(cx, rect);
     }
 }
+
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawPlotBox {
+    #[deref] pub draw_super: DrawQuad,
+    #[live] pub color: Vec4,
+    #[live] pub border_color: Vec4,
+    #[live(0.0)] pub border_width: f32,
+    #[live(0.0)] pub radius: f32,
+}
+
+impl DrawPlotBox {
+    /// Draw a filled box with rounded corners and an optional border
+    pub fn draw_box(&mut self, cx: &mut Cx2d, rect: Rect, color: Vec4, border_color: Vec4, border_width: f64, radius: f64) {
+        self.color = color;
+        self.border_color = border_color;
+        self.border_width = border_width as f32;
+        self.radius = radius as f32;
+        self.draw_abs(cx, rect);
+    }
+}
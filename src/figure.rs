@@ -0,0 +1,391 @@
+// pyplot-like figure builder - describe subplots in code, then show them in a SubplotGrid
+// or save them without a window
+
+use std::path::Path;
+use makepad_widgets::makepad_micro_serde::*;
+use crate::spec::*;
+use crate::data::*;
+use crate::svg::figure_svg;
+
+/// A figure of subplots built up pyplot-style. Plotting calls go to the current axes,
+/// chosen with `subplot`; each axes becomes one chart, of the kind set by its first
+/// plotting call.
+///
+/// ```ignore
+/// let mut fig = Figure::new();
+/// fig.subplot(2, 1, 1)?.plot(&x, &y).label("a").color("C1");
+/// fig.xlabel("time");
+/// fig.subplot(2, 1, 2)?.hist(&samples, Some(20));
+/// fig.savefig("out.svg")?;
+/// if let Err(failed) = grid.set_figure(cx, &fig) {
+///     log!("subplots not shown: {:?}", failed);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, SerJson, DeJson)]
+pub struct Figure {
+    pub rows: usize,
+    pub cols: usize,
+    /// Size in pixels when saved
+    pub width: f64,
+    pub height: f64,
+    pub axes: Vec<Axes>,
+    /// Style preset for axes that do not set their own
+    pub style: Option<String>,
+    /// Position in `axes` of the current axes; editing state, not part of the figure
+    #[nserde(skip)]
+    current: usize,
+}
+
+/// One subplot: its place in the grid and the chart drawn there
+#[derive(Clone, Debug, PartialEq, SerJson, DeJson)]
+pub struct Axes {
+    /// 1-based position, counted along the rows like matplotlib's subplot index
+    pub index: usize,
+    pub spec: ChartSpec,
+}
+
+/// Why a subplot could not be selected or a figure could not be saved
+#[derive(Debug)]
+pub enum FigureError {
+    /// `subplot` index outside `1..=rows * cols`, or an empty grid
+    SubplotIndex { rows: usize, cols: usize, index: usize },
+    Io(std::io::Error),
+    /// Extensions other than svg and json; raster formats such as png are not written yet
+    UnsupportedFormat(String),
+}
+
+impl std::fmt::Display for FigureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FigureError::SubplotIndex { rows, cols, index } => {
+                write!(f, "subplot index {} is outside a {}x{} grid", index, rows, cols)
+            }
+            FigureError::Io(err) => write!(f, "could not write figure: {}", err),
+            FigureError::UnsupportedFormat(ext) => write!(f, "cannot save figures as \"{}\", use svg or json (png is not supported yet)", ext),
+        }
+    }
+}
+
+impl From<std::io::Error> for FigureError {
+    fn from(err: std::io::Error) -> Self {
+        FigureError::Io(err)
+    }
+}
+
+impl Default for Figure {
+    fn default() -> Self {
+        Self { rows: 1, cols: 1, width: 640.0, height: 480.0, axes: Vec::new(), style: None, current: 0 }
+    }
+}
+
+impl Figure {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_size(mut self, width: f64, height: f64) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SpecError> {
+        Self::deserialize_json(json).map_err(|err| SpecError::Json(format!("{:?}", err)))
+    }
+
+    pub fn to_json(&self) -> String {
+        self.serialize_json()
+    }
+
+    /// Make the subplot at `index` (1-based) of a `rows` x `cols` grid current, creating it
+    /// if needed, and return it. Fails with `FigureError::SubplotIndex` when `index` is not
+    /// in the grid, leaving the figure unchanged.
+    pub fn subplot(&mut self, rows: usize, cols: usize, index: usize) -> Result<&mut Axes, FigureError> {
+        if index == 0 || index > rows * cols {
+            return Err(FigureError::SubplotIndex { rows, cols, index });
+        }
+        self.rows = rows;
+        self.cols = cols;
+        Ok(self.select(index))
+    }
+
+    /// The current axes, creating a single subplot if there is none yet
+    pub fn gca(&mut self) -> &mut Axes {
+        if self.axes.is_empty() {
+            self.rows = 1;
+            self.cols = 1;
+            return self.select(1);
+        }
+        &mut self.axes[self.current]
+    }
+
+    fn select(&mut self, index: usize) -> &mut Axes {
+        self.current = match self.axes.iter().position(|axes| axes.index == index) {
+            Some(existing) => existing,
+            None => {
+                self.axes.push(Axes { index, spec: ChartSpec::default() });
+                self.axes.len() - 1
+            }
+        };
+        &mut self.axes[self.current]
+    }
+
+    /// Style preset applied to every axes without its own, see `PlotStyle::PRESETS`
+    pub fn style(&mut self, name: impl Into<String>) -> &mut Self {
+        self.style = Some(name.into());
+        self
+    }
+
+    pub fn plot(&mut self, x: impl IntoValues, y: impl IntoValues) -> SeriesBuilder<'_> {
+        self.gca().plot(x, y)
+    }
+
+    pub fn scatter(&mut self, x: impl IntoValues, y: impl IntoValues) -> SeriesBuilder<'_> {
+        self.gca().scatter(x, y)
+    }
+
+    pub fn bar(&mut self, categories: &[&str], heights: impl IntoValues) -> &mut Axes {
+        self.gca().bar(categories, heights)
+    }
+
+    pub fn hist(&mut self, values: impl IntoValues, bins: Option<usize>) -> &mut Axes {
+        self.gca().hist(values, bins)
+    }
+
+    pub fn pie(&mut self, labels: &[&str], values: impl IntoValues) -> &mut Axes {
+        self.gca().pie(labels, values)
+    }
+
+    pub fn imshow(&mut self, data: impl IntoGrid) -> &mut Axes {
+        self.gca().imshow(data)
+    }
+
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Axes {
+        self.gca().set_title(title)
+    }
+
+    pub fn xlabel(&mut self, label: impl Into<String>) -> &mut Axes {
+        self.gca().set_xlabel(label)
+    }
+
+    pub fn ylabel(&mut self, label: impl Into<String>) -> &mut Axes {
+        self.gca().set_ylabel(label)
+    }
+
+    pub fn xlim(&mut self, min: f64, max: f64) -> &mut Axes {
+        self.gca().set_xlim(min, max)
+    }
+
+    pub fn ylim(&mut self, min: f64, max: f64) -> &mut Axes {
+        self.gca().set_ylim(min, max)
+    }
+
+    pub fn xscale(&mut self, scale: &str) -> &mut Axes {
+        self.gca().set_xscale(scale)
+    }
+
+    pub fn yscale(&mut self, scale: &str) -> &mut Axes {
+        self.gca().set_yscale(scale)
+    }
+
+    pub fn legend(&mut self, loc: &str) -> &mut Axes {
+        self.gca().legend(loc)
+    }
+
+    pub fn annotate(&mut self, text: impl Into<String>, x: f64, y: f64) -> &mut Axes {
+        self.gca().annotate(text, x, y)
+    }
+
+    /// Chart specs with the figure style filled in, as they are loaded into widgets
+    pub fn specs(&self) -> Vec<(usize, ChartSpec)> {
+        self.axes.iter().map(|axes| {
+            let mut spec = axes.spec.clone();
+            if spec.style.is_none() {
+                spec.style = self.style.clone();
+            }
+            (axes.index, spec)
+        }).collect()
+    }
+
+    /// Save the figure, picking the format from the extension: svg renders it without
+    /// a window, json writes the figure itself so it can be loaded again with `from_json`.
+    ///
+    /// PNG and other raster formats fail with `FigureError::UnsupportedFormat`: rasterising
+    /// needs either an offscreen render of the widgets or an SVG renderer dependency, and
+    /// neither is in place yet. Convert the svg output with an external tool meanwhile.
+    pub fn savefig(&self, path: impl AsRef<Path>) -> Result<(), FigureError> {
+        let path = path.as_ref();
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+        let contents = match ext.as_str() {
+            "svg" => figure_svg(self),
+            "json" => self.to_json(),
+            _ => return Err(FigureError::UnsupportedFormat(ext)),
+        };
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+impl Axes {
+    /// Set the chart kind unless an earlier call already did
+    fn kind(&mut self, kind: &str) -> &mut ChartSpec {
+        if self.spec.kind.is_empty() {
+            self.spec.kind = kind.to_string();
+        }
+        &mut self.spec
+    }
+
+    /// Add a line series
+    pub fn plot(&mut self, x: impl IntoValues, y: impl IntoValues) -> SeriesBuilder<'_> {
+        let spec = self.kind("line");
+        spec.series.push(SeriesSpec { x: x.into_values().into_owned(), y: y.into_values().into_owned(), ..Default::default() });
+        SeriesBuilder { series: spec.series.last_mut().unwrap() }
+    }
+
+    /// Add a series of unconnected points
+    pub fn scatter(&mut self, x: impl IntoValues, y: impl IntoValues) -> SeriesBuilder<'_> {
+        let spec = self.kind("scatter");
+        spec.series.push(SeriesSpec { x: x.into_values().into_owned(), y: y.into_values().into_owned(), marker: Some("o".to_string()), ..Default::default() });
+        SeriesBuilder { series: spec.series.last_mut().unwrap() }
+    }
+
+    pub fn bar(&mut self, categories: &[&str], heights: impl IntoValues) -> &mut Self {
+        let spec = self.kind("bar");
+        spec.categories = categories.iter().map(|c| c.to_string()).collect();
+        spec.values = heights.into_values().into_owned();
+        self
+    }
+
+    /// Histogram of `values`; without a bin count Sturges' rule picks one
+    pub fn hist(&mut self, values: impl IntoValues, bins: Option<usize>) -> &mut Self {
+        let spec = self.kind("histogram");
+        spec.values = values.into_values().into_owned();
+        spec.bins = bins;
+        self
+    }
+
+    pub fn pie(&mut self, labels: &[&str], values: impl IntoValues) -> &mut Self {
+        let spec = self.kind("pie");
+        spec.slices = labels.iter().zip(values.into_values().iter().copied()).map(|(label, value)| SliceSpec {
+            label: label.to_string(),
+            value,
+            color: None,
+        }).collect();
+        self
+    }
+
+    /// Heatmap of a grid of values, one inner vec per row
+    pub fn imshow(&mut self, data: impl IntoGrid) -> &mut Self {
+        self.kind("heatmap").matrix = data.into_matrix().to_rows();
+        self
+    }
+
+    pub fn set_title(&mut self, title: impl Into<String>) -> &mut Self {
+        self.spec.title = title.into();
+        self
+    }
+
+    pub fn set_xlabel(&mut self, label: impl Into<String>) -> &mut Self {
+        self.spec.x_label = label.into();
+        self
+    }
+
+    pub fn set_ylabel(&mut self, label: impl Into<String>) -> &mut Self {
+        self.spec.y_label = label.into();
+        self
+    }
+
+    pub fn set_xlim(&mut self, min: f64, max: f64) -> &mut Self {
+        self.spec.xlim = Some(vec![min, max]);
+        self
+    }
+
+    pub fn set_ylim(&mut self, min: f64, max: f64) -> &mut Self {
+        self.spec.ylim = Some(vec![min, max]);
+        self
+    }
+
+    /// "linear", "log", "symlog" or "time"
+    pub fn set_xscale(&mut self, scale: &str) -> &mut Self {
+        self.spec.x_scale = Some(scale.to_string());
+        self
+    }
+
+    pub fn set_yscale(&mut self, scale: &str) -> &mut Self {
+        self.spec.y_scale = Some(scale.to_string());
+        self
+    }
+
+    /// Legend at a matplotlib `loc` name, e.g. "upper left"
+    pub fn legend(&mut self, loc: &str) -> &mut Self {
+        self.spec.legend = Some(loc.to_string());
+        self
+    }
+
+    pub fn set_style(&mut self, name: impl Into<String>) -> &mut Self {
+        self.spec.style = Some(name.into());
+        self
+    }
+
+    pub fn set_colormap(&mut self, name: impl Into<String>) -> &mut Self {
+        self.spec.colormap = Some(name.into());
+        self
+    }
+
+    /// Heatmap norm by name: "linear", "log", "symlog" or "centered"
+    pub fn set_norm(&mut self, name: impl Into<String>) -> &mut Self {
+        self.spec.norm = Some(name.into());
+        self
+    }
+
+    pub fn annotate(&mut self, text: impl Into<String>, x: f64, y: f64) -> &mut Self {
+        self.spec.annotations.push(AnnotationSpec { text: text.into(), x, y, ..Default::default() });
+        self
+    }
+}
+
+/// Sets the look of a series just added with `plot` or `scatter`
+pub struct SeriesBuilder<'a> {
+    series: &'a mut SeriesSpec,
+}
+
+impl SeriesBuilder<'_> {
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.series.label = label.into();
+        self
+    }
+
+    /// Any colour `parse_color` accepts, including "C0", "C1"... from the colour cycle
+    pub fn color(mut self, color: impl Into<String>) -> Self {
+        self.series.color = Some(color.into());
+        self
+    }
+
+    /// "-", "--", ":", "-." or their long names
+    pub fn linestyle(mut self, style: impl Into<String>) -> Self {
+        self.series.line_style = Some(style.into());
+        self
+    }
+
+    /// Marker code such as "o", "s" or "^"
+    pub fn marker(mut self, marker: impl Into<String>) -> Self {
+        self.series.marker = Some(marker.into());
+        self
+    }
+
+    pub fn linewidth(mut self, width: f64) -> Self {
+        self.series.line_width = Some(width);
+        self
+    }
+
+    pub fn markersize(mut self, size: f64) -> Self {
+        self.series.marker_size = Some(size);
+        self
+    }
+
+    /// Per-point marker radii, like `s=` in matplotlib's scatter
+    pub fn sizes(mut self, radii: &[f64]) -> Self {
+        self.series.sizes = Some(radii.to_vec());
+        self
+    }
+}
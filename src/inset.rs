@@ -0,0 +1,114 @@
+// Insets - a zoomed view of part of a chart drawn in a box inside its plot area, with
+// the zoomed region marked on the chart
+
+use makepad_widgets::*;
+use crate::plot::{PlotArea, ScaleType};
+use crate::twin::AxisRange;
+
+/// Zoomed view of a chart's own data, like matplotlib's `inset_axes` followed by
+/// `indicate_inset_zoom`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InsetAxes {
+    /// Lower-left corner, width and height as fractions of the plot area, like
+    /// matplotlib's `[x0, y0, width, height]` bounds
+    pub bounds: [f64; 4],
+    pub xlim: (f64, f64),
+    pub ylim: (f64, f64),
+    /// Outline the zoomed region on the chart and join it to the inset
+    pub indicate: bool,
+}
+
+impl InsetAxes {
+    pub fn new(bounds: [f64; 4], xlim: (f64, f64), ylim: (f64, f64)) -> Self {
+        Self { bounds, xlim, ylim, indicate: true }
+    }
+
+    pub fn with_indicator(mut self, indicate: bool) -> Self {
+        self.indicate = indicate;
+        self
+    }
+
+    /// Placement of the inset in the plot area and the mapping of its data window onto it
+    pub fn view(&self, area: &PlotArea, x_scale: ScaleType, y_scale: ScaleType) -> InsetView {
+        let [x, y, width, height] = self.bounds;
+        InsetView {
+            rect: Rect {
+                pos: dvec2(area.left + x * area.width(), area.bottom - (y + height) * area.height()),
+                size: dvec2(width * area.width(), height * area.height()),
+            },
+            x: AxisRange::new(self.xlim, x_scale),
+            y: AxisRange::new(self.ylim, y_scale),
+        }
+    }
+
+    /// Ticks along the inset's x and y axes
+    pub fn ticks(&self, x_scale: ScaleType, y_scale: ScaleType) -> (Vec<f64>, Vec<f64>) {
+        (x_scale.generate_ticks(self.xlim.0, self.xlim.1, 2), y_scale.generate_ticks(self.ylim.0, self.ylim.1, 2))
+    }
+}
+
+/// An inset laid out in pixels
+#[derive(Clone, Copy, Debug)]
+pub struct InsetView {
+    pub rect: Rect,
+    pub x: AxisRange,
+    pub y: AxisRange,
+}
+
+impl InsetView {
+    pub fn to_pixel(&self, x: f64, y: f64) -> DVec2 {
+        dvec2(
+            self.rect.pos.x + self.x.fraction(x) * self.rect.size.x,
+            self.rect.pos.y + (1.0 - self.y.fraction(y)) * self.rect.size.y,
+        )
+    }
+
+    pub fn contains(&self, p: DVec2) -> bool {
+        p.x >= self.rect.pos.x && p.x <= self.rect.pos.x + self.rect.size.x
+            && p.y >= self.rect.pos.y && p.y <= self.rect.pos.y + self.rect.size.y
+    }
+
+    /// The part of the segment from `a` to `b` inside the inset
+    pub fn clip(&self, a: DVec2, b: DVec2) -> Option<(DVec2, DVec2)> {
+        clip_segment(a, b, self.rect)
+    }
+
+    /// Lines joining the corners of the zoomed `region` on the chart to the matching
+    /// corners of the inset, picked so they run along the outside of both boxes
+    pub fn connectors(&self, region: Rect) -> [(DVec2, DVec2); 2] {
+        let corners = |r: Rect| [r.pos, r.pos + dvec2(r.size.x, 0.0), r.pos + dvec2(0.0, r.size.y), r.pos + r.size];
+        let [r_tl, r_tr, r_bl, r_br] = corners(region);
+        let [i_tl, i_tr, i_bl, i_br] = corners(self.rect);
+        let offset = (self.rect.pos + self.rect.size * 0.5) - (region.pos + region.size * 0.5);
+        let (dx, dy) = (offset.x, -offset.y);
+        // Inset up and to the right (or down and to the left): upper-left and lower-right
+        if dx * dy >= 0.0 {
+            [(r_tl, i_tl), (r_br, i_br)]
+        } else {
+            [(r_tr, i_tr), (r_bl, i_bl)]
+        }
+    }
+}
+
+/// The part of the segment from `a` to `b` inside `rect` (Liang-Barsky)
+pub fn clip_segment(a: DVec2, b: DVec2, rect: Rect) -> Option<(DVec2, DVec2)> {
+    let d = b - a;
+    let min = rect.pos;
+    let max = rect.pos + rect.size;
+    let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+    for (p, q) in [(-d.x, a.x - min.x), (d.x, max.x - a.x), (-d.y, a.y - min.y), (d.y, max.y - a.y)] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    (t0 <= t1).then(|| (a + d * t0, a + d * t1))
+}
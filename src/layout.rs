@@ -0,0 +1,342 @@
+// Axes layout - tick label collision handling and tight margins around the plot area
+
+use makepad_widgets::*;
+use crate::plot::PlotArea;
+use crate::text::*;
+
+/// Gap between the axis and the tick labels
+pub const TICK_LABEL_PAD: f64 = 5.0;
+
+/// Gap between the title and the top of the plot area
+pub const TITLE_PAD: f64 = 10.0;
+
+/// Gap between tick labels and the axis label
+const AXIS_LABEL_PAD: f64 = 6.0;
+
+/// Padding between the outermost text and the widget edge
+const OUTER_PAD: f64 = 6.0;
+
+/// Default right margin, leaves room for half of the last x tick label
+const MIN_RIGHT_MARGIN: f64 = 20.0;
+
+/// Minimum gap between two neighbouring labels
+const LABEL_GAP: f64 = 6.0;
+
+/// Maximum number of lines a label may wrap to
+const MAX_WRAP_LINES: usize = 3;
+
+/// How x tick labels are arranged when they don't fit side by side
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TickLabelStrategy {
+    /// Try horizontal, then wrapping, then 45°, then 90°, then thinning
+    #[default]
+    Auto,
+    /// Keep labels horizontal and skip every n-th label until they fit
+    Thin,
+    /// Always rotate labels by 45°
+    Rotate45,
+    /// Always rotate labels by 90°
+    Rotate90,
+    /// Always rotate labels by an arbitrary angle in degrees
+    Rotate(f64),
+    /// Wrap labels at spaces
+    Wrap,
+}
+
+/// Result of the layout pass for one row of x tick labels
+#[derive(Clone, Debug, Default)]
+pub struct TickLabelLayout {
+    /// Pixel x position and text lines of each label
+    pub labels: Vec<(f64, Vec<String>)>,
+    /// Rotation in degrees, counter-clockwise
+    pub rotation: f64,
+    /// Only every `step`-th label is drawn
+    pub step: usize,
+    pub line_height: f64,
+    /// Space needed below the anchor point
+    pub extent: f64,
+}
+
+impl TickLabelLayout {
+    /// Measure the labels against each other and pick rotation, wrapping or thinning
+    pub fn compute(cx: &mut Cx2d, label: &mut PlotLabel, labels: Vec<(f64, String)>, strategy: TickLabelStrategy) -> Self {
+        let spacing = labels
+            .windows(2)
+            .map(|w| (w[1].0 - w[0].0).abs())
+            .fold(f64::MAX, f64::min);
+
+        let sizes: Vec<DVec2> = labels.iter().map(|(_, t)| label.measure(cx, t)).collect();
+        let max_width = sizes.iter().map(|s| s.x).fold(0.0, f64::max);
+        let line_height = sizes.iter().map(|s| s.y).fold(0.0, f64::max);
+        let needed = line_height + LABEL_GAP;
+        let sin45 = std::f64::consts::FRAC_1_SQRT_2;
+
+        let steps_for = |size: f64, available: f64| -> usize {
+            if available <= 0.0 {
+                1
+            } else {
+                (size / available).ceil().max(1.0) as usize
+            }
+        };
+
+        let single_lines = || labels.iter().map(|(x, t)| (*x, vec![t.clone()])).collect::<Vec<_>>();
+
+        let (rotation, step, lines) = match strategy {
+            TickLabelStrategy::Thin => (0.0, steps_for(max_width + LABEL_GAP, spacing), single_lines()),
+            TickLabelStrategy::Rotate45 => (45.0, steps_for(needed, spacing * sin45), single_lines()),
+            TickLabelStrategy::Rotate90 => (90.0, steps_for(needed, spacing), single_lines()),
+            TickLabelStrategy::Rotate(degrees) => {
+                // Neighbouring labels are parallel; their perpendicular distance shrinks with the angle
+                let available = spacing * degrees.to_radians().sin().abs();
+                (degrees, steps_for(needed, available), single_lines())
+            }
+            TickLabelStrategy::Wrap => {
+                let wrapped = wrap_labels(cx, label, &labels, spacing - LABEL_GAP);
+                let width = wrapped_width(cx, label, &wrapped);
+                (0.0, steps_for(width + LABEL_GAP, spacing), wrapped)
+            }
+            TickLabelStrategy::Auto => {
+                if max_width + LABEL_GAP <= spacing {
+                    (0.0, 1, single_lines())
+                } else {
+                    let wrapped = wrap_labels(cx, label, &labels, spacing - LABEL_GAP);
+                    let wrap_fits = wrapped.iter().all(|(_, l)| l.len() <= MAX_WRAP_LINES)
+                        && wrapped_width(cx, label, &wrapped) + LABEL_GAP <= spacing;
+                    if wrap_fits {
+                        (0.0, 1, wrapped)
+                    } else if spacing * sin45 >= needed {
+                        (45.0, 1, single_lines())
+                    } else {
+                        (90.0, steps_for(needed, spacing), single_lines())
+                    }
+                }
+            }
+        };
+
+        let extent = if rotation == 0.0 {
+            let max_lines = lines.iter().map(|(_, l)| l.len()).max().unwrap_or(0);
+            max_lines as f64 * line_height
+        } else {
+            let r = rotation.to_radians();
+            max_width * r.sin().abs() + line_height * r.cos().abs()
+        };
+
+        Self {
+            labels: lines,
+            rotation,
+            step,
+            line_height,
+            extent,
+        }
+    }
+
+    /// Space to reserve below the axis line for these labels
+    pub fn margin(&self) -> f64 {
+        if self.labels.is_empty() {
+            0.0
+        } else {
+            TICK_LABEL_PAD + self.extent + 4.0
+        }
+    }
+
+    /// Draw the labels hanging below the axis at `axis_y`
+    pub fn draw(&self, cx: &mut Cx2d, label: &mut PlotLabel, axis_y: f64) {
+        let y = axis_y + TICK_LABEL_PAD;
+        for (i, (x, lines)) in self.labels.iter().enumerate() {
+            if i % self.step.max(1) != 0 {
+                continue;
+            }
+            if self.rotation == 0.0 {
+                for (line_idx, line) in lines.iter().enumerate() {
+                    let pos = dvec2(*x, y + line_idx as f64 * self.line_height);
+                    label.draw_at(cx, pos, line, TextAnchor::TopCenter);
+                }
+            } else {
+                // Rotated labels hang from the tick, like matplotlib's ha="right" (ha="left" for negative angles)
+                let text = lines.join(" ");
+                let anchor = if self.rotation > 0.0 { TextAnchor::MiddleRight } else { TextAnchor::MiddleLeft };
+                label.draw_rotated(cx, dvec2(*x, y + self.line_height / 2.0), &text, anchor, self.rotation);
+            }
+        }
+    }
+}
+
+/// Greedy word wrap of each label to `max_width`
+fn wrap_labels(cx: &mut Cx2d, label: &mut PlotLabel, labels: &[(f64, String)], max_width: f64) -> Vec<(f64, Vec<String>)> {
+    labels
+        .iter()
+        .map(|(x, text)| {
+            let mut lines: Vec<String> = Vec::new();
+            let mut current = String::new();
+            for word in text.split_whitespace() {
+                let candidate = if current.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{} {}", current, word)
+                };
+                if !current.is_empty() && label.measure(cx, &candidate).x > max_width {
+                    lines.push(std::mem::replace(&mut current, word.to_string()));
+                } else {
+                    current = candidate;
+                }
+            }
+            if !current.is_empty() || lines.is_empty() {
+                lines.push(current);
+            }
+            (*x, lines)
+        })
+        .collect()
+}
+
+fn wrapped_width(cx: &mut Cx2d, label: &mut PlotLabel, wrapped: &[(f64, Vec<String>)]) -> f64 {
+    let mut width: f64 = 0.0;
+    for (_, lines) in wrapped {
+        for line in lines {
+            width = width.max(label.measure(cx, line).x);
+        }
+    }
+    width
+}
+
+/// Margin overrides for the tight layout pass.
+/// Sides left as `None` are computed every frame from the text around the axes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Live, LiveHook, LiveRegister)]
+#[live_ignore]
+pub struct Margins {
+    #[live] pub left: Option<f64>,
+    #[live] pub top: Option<f64>,
+    #[live] pub right: Option<f64>,
+    #[live] pub bottom: Option<f64>,
+}
+
+impl Margins {
+    /// Fixed margins on every side, no tight layout
+    pub fn fixed(left: f64, top: f64, right: f64, bottom: f64) -> Self {
+        Self {
+            left: Some(left),
+            top: Some(top),
+            right: Some(right),
+            bottom: Some(bottom),
+        }
+    }
+
+    pub fn with_left(mut self, left: f64) -> Self {
+        self.left = Some(left);
+        self
+    }
+
+    pub fn with_top(mut self, top: f64) -> Self {
+        self.top = Some(top);
+        self
+    }
+
+    pub fn with_right(mut self, right: f64) -> Self {
+        self.right = Some(right);
+        self
+    }
+
+    pub fn with_bottom(mut self, bottom: f64) -> Self {
+        self.bottom = Some(bottom);
+        self
+    }
+
+    /// Left and right margins from the y tick labels and y axis label.
+    /// `right_extra` reserves room for a colorbar or a legend outside the axes.
+    pub fn columns(&self, cx: &mut Cx2d, label: &mut PlotLabel, text: &AxesText, right_extra: f64) -> (f64, f64) {
+        let left = match self.left {
+            Some(left) => left,
+            None => {
+                let tick_width = max_text_width(cx, label, text.y_ticks);
+                // The y label is rotated by 90°, so its rotated box gives the horizontal extent
+                let y_label_width = if text.y_label.is_empty() {
+                    0.0
+                } else {
+                    label.measure_rotated(cx, text.y_label, 90.0).x + AXIS_LABEL_PAD
+                };
+                OUTER_PAD + y_label_width + tick_width + TICK_LABEL_PAD + text.y_tick_extent
+            }
+        };
+        let right = self.right.unwrap_or(MIN_RIGHT_MARGIN + right_extra);
+        (left, right)
+    }
+
+    /// Top and bottom margins, once the x tick labels are laid out against the final width
+    pub fn rows(&self, cx: &mut Cx2d, label: &mut PlotLabel, text: &AxesText, x_ticks: &TickLabelLayout) -> (f64, f64) {
+        let line_height = label.measure(cx, "0").y;
+        let top = match self.top {
+            Some(top) => top,
+            None if text.title.is_empty() => OUTER_PAD + line_height / 2.0 + text.top_axis,
+            None => OUTER_PAD + text.measure_title(cx, label).y + TITLE_PAD + text.top_axis,
+        };
+        let bottom = match self.bottom {
+            Some(bottom) => bottom,
+            None => {
+                let x_label_height = if text.x_label.is_empty() {
+                    0.0
+                } else {
+                    label.measure(cx, text.x_label).y + AXIS_LABEL_PAD
+                };
+                OUTER_PAD + text.x_tick_extent + x_ticks.margin().max(TICK_LABEL_PAD + line_height) + x_label_height
+            }
+        };
+        (top, bottom)
+    }
+}
+
+/// Text drawn around the axes, measured by the tight layout pass
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AxesText<'a> {
+    pub title: &'a str,
+    pub x_label: &'a str,
+    pub y_label: &'a str,
+    pub y_ticks: &'a [String],
+    /// Title font size when it differs from the label's
+    pub title_size: Option<f64>,
+    /// Height of a twin x axis along the top, between the plot area and the title
+    pub top_axis: f64,
+    /// Reach of the tick marks out past the bottom and the left spine, kept clear of
+    /// the tick labels
+    pub x_tick_extent: f64,
+    pub y_tick_extent: f64,
+}
+
+impl AxesText<'_> {
+    fn measure_title(&self, cx: &mut Cx2d, label: &mut PlotLabel) -> DVec2 {
+        let size = label.font_size();
+        label.set_font_size(self.title_size.unwrap_or(size));
+        let extent = label.measure(cx, self.title);
+        label.set_font_size(size);
+        extent
+    }
+
+    /// Draw the title above and the axis labels outside the tick labels
+    pub fn draw(&self, cx: &mut Cx2d, label: &mut PlotLabel, area: &PlotArea, x_ticks: &TickLabelLayout) {
+        let center_x = (area.left + area.right) / 2.0;
+        let center_y = (area.top + area.bottom) / 2.0;
+
+        if !self.title.is_empty() {
+            let size = label.font_size();
+            label.set_font_size(self.title_size.unwrap_or(size));
+            label.draw_at(cx, dvec2(center_x, area.top - TITLE_PAD - self.top_axis), self.title, TextAnchor::BottomCenter);
+            label.set_font_size(size);
+        }
+
+        if !self.x_label.is_empty() {
+            let line_height = label.measure(cx, "0").y;
+            let y = area.bottom + self.x_tick_extent + x_ticks.margin().max(TICK_LABEL_PAD + line_height) + AXIS_LABEL_PAD / 2.0;
+            label.draw_at(cx, dvec2(center_x, y), self.x_label, TextAnchor::TopCenter);
+        }
+
+        if !self.y_label.is_empty() {
+            let tick_width = max_text_width(cx, label, self.y_ticks);
+            let x = area.left - self.y_tick_extent - TICK_LABEL_PAD - tick_width - AXIS_LABEL_PAD;
+            // Rotated to read bottom-to-top, centred on the axis
+            label.draw_rotated(cx, dvec2(x, center_y), self.y_label, TextAnchor::BottomCenter, 90.0);
+        }
+    }
+}
+
+/// Width of the widest of a set of labels
+pub fn max_text_width(cx: &mut Cx2d, label: &mut PlotLabel, texts: &[String]) -> f64 {
+    texts.iter().map(|t| label.measure(cx, t).x).fold(0.0, f64::max)
+}
@@ -0,0 +1,444 @@
+// Legend component shared by the charts - entries, placement, columns and hit-testing
+
+use makepad_widgets::*;
+use crate::elements::*;
+use crate::text::*;
+use crate::plot::PlotArea;
+use crate::layout::max_text_width;
+use crate::style::PlotStyle;
+use crate::color::{contrasting_text, mix};
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+
+    use crate::elements::DrawPlotLine;
+    use crate::elements::DrawPlotPoint;
+    use crate::elements::DrawPlotBar;
+    use crate::elements::DrawPlotBox;
+    use crate::text::PlotLabel;
+
+    pub PlotLegend = {{PlotLegend}} {
+        label: <PlotLabel> {}
+    }
+}
+
+/// Padding inside the legend box
+const LEGEND_PADDING: f64 = 8.0;
+
+/// Space between the text of two legend rows
+const LEGEND_ROW_GAP: f64 = 4.0;
+
+/// Smallest row height, so symbols fit next to small text
+const LEGEND_MIN_ROW_HEIGHT: f64 = 12.0;
+
+/// Width of the symbol drawn before each label
+const LEGEND_SYMBOL_WIDTH: f64 = 20.0;
+
+/// Gap between the symbol and the label
+const LEGEND_SYMBOL_GAP: f64 = 6.0;
+
+/// Gap between two columns of entries
+const LEGEND_COLUMN_GAP: f64 = 12.0;
+
+/// Distance from the axes edges (inside) or from the axes (outside)
+const LEGEND_OFFSET: f64 = 10.0;
+
+/// Legend location, like matplotlib's `loc` plus placement outside the axes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum LegendPosition {
+    #[default]
+    #[pick]
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+    TopCenter,
+    BottomCenter,
+    CenterLeft,
+    CenterRight,
+    Center,
+    /// The inside location that covers the fewest data points
+    Best,
+    /// Outside the axes, to the right of the plot area
+    OutsideRight,
+    /// Outside the axes, along the bottom of the widget
+    OutsideBottom,
+    None, // Hidden
+}
+
+impl LegendPosition {
+    /// Candidates tried by `Best`, in matplotlib's order of preference
+    const BEST_CANDIDATES: [LegendPosition; 9] = [
+        LegendPosition::TopRight,
+        LegendPosition::TopLeft,
+        LegendPosition::BottomLeft,
+        LegendPosition::BottomRight,
+        LegendPosition::CenterRight,
+        LegendPosition::CenterLeft,
+        LegendPosition::BottomCenter,
+        LegendPosition::TopCenter,
+        LegendPosition::Center,
+    ];
+
+    pub fn is_outside(&self) -> bool {
+        matches!(self, LegendPosition::OutsideRight | LegendPosition::OutsideBottom)
+    }
+
+    /// Name as used by matplotlib's `loc`, e.g. "upper left"
+    pub fn name(&self) -> &'static str {
+        match self {
+            LegendPosition::TopRight => "upper right",
+            LegendPosition::TopLeft => "upper left",
+            LegendPosition::BottomRight => "lower right",
+            LegendPosition::BottomLeft => "lower left",
+            LegendPosition::TopCenter => "upper center",
+            LegendPosition::BottomCenter => "lower center",
+            LegendPosition::CenterLeft => "center left",
+            LegendPosition::CenterRight => "center right",
+            LegendPosition::Center => "center",
+            LegendPosition::Best => "best",
+            LegendPosition::OutsideRight => "outside right",
+            LegendPosition::OutsideBottom => "outside bottom",
+            LegendPosition::None => "none",
+        }
+    }
+
+    /// Look a position up by its `loc` name, case-insensitively
+    pub fn from_name(name: &str) -> Option<LegendPosition> {
+        Self::BEST_CANDIDATES
+            .into_iter()
+            .chain([LegendPosition::Best, LegendPosition::OutsideRight, LegendPosition::OutsideBottom, LegendPosition::None])
+            .find(|position| position.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Symbol drawn in front of a legend label
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LegendSymbol {
+    /// Line sample with an optional marker in the middle (line series)
+    Line { style: LineStyle, width: f64, marker: MarkerStyle },
+    /// Marker only (scatter and bubble series)
+    Marker(MarkerStyle),
+    /// Filled square (bars, slices, areas, fills and spans)
+    Patch,
+    /// Line with a capped error bar (series with error bars)
+    ErrorBar,
+}
+
+/// One row of the legend
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegendEntry {
+    pub label: String,
+    pub color: Vec4,
+    pub symbol: LegendSymbol,
+    /// Hidden entries are drawn greyed out
+    pub visible: bool,
+}
+
+impl LegendEntry {
+    pub fn new(label: impl Into<String>, color: Vec4, symbol: LegendSymbol) -> Self {
+        Self {
+            label: label.into(),
+            color,
+            symbol,
+            visible: true,
+        }
+    }
+
+    pub fn line(label: impl Into<String>, color: Vec4) -> Self {
+        Self::new(label, color, LegendSymbol::Line { style: LineStyle::Solid, width: 2.0, marker: MarkerStyle::None })
+    }
+
+    pub fn marker(label: impl Into<String>, color: Vec4, marker: MarkerStyle) -> Self {
+        Self::new(label, color, LegendSymbol::Marker(marker))
+    }
+
+    pub fn patch(label: impl Into<String>, color: Vec4) -> Self {
+        Self::new(label, color, LegendSymbol::Patch)
+    }
+
+    pub fn error_bar(label: impl Into<String>, color: Vec4) -> Self {
+        Self::new(label, color, LegendSymbol::ErrorBar)
+    }
+
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+}
+
+/// Legend box drawn over (or next to) a chart.
+/// The chart rebuilds the entries every frame and passes its plot area and data points.
+#[derive(Live, LiveHook, LiveRegister)]
+pub struct PlotLegend {
+    #[live] draw_bg: DrawPlotBox,
+    #[live] draw_line: DrawPlotLine,
+    #[live] draw_point: DrawPlotPoint,
+    #[live] draw_patch: DrawPlotBar,
+    #[live] label: PlotLabel,
+    #[live] pub position: LegendPosition,
+    #[rust(1)] pub columns: usize,
+    #[live] pub title: String,
+    /// Label size in points; the chart style's font size when 0
+    #[live] pub font_size: f64,
+    #[rust] pub highlighted: Option<usize>,  // Entry drawn emphasised, e.g. while hovered
+    #[rust] entries: Vec<LegendEntry>,
+    #[rust] entry_rects: Vec<Rect>,
+    #[rust] style: PlotStyle,
+}
+
+impl PlotLegend {
+    pub fn set_position(&mut self, position: LegendPosition) {
+        self.position = position;
+    }
+
+    /// Number of columns; entries fill each column top to bottom before the next
+    pub fn set_columns(&mut self, columns: usize) {
+        self.columns = columns.max(1);
+    }
+
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = title.into();
+    }
+
+    /// Label size in points, overriding the style's font size; 0 follows the style
+    pub fn set_font_size(&mut self, font_size: f64) {
+        self.font_size = font_size;
+    }
+
+    /// Back to the default position, one column, no title and the style's font size
+    pub fn reset(&mut self) {
+        self.position = LegendPosition::default();
+        self.columns = 1;
+        self.title.clear();
+        self.font_size = 0.0;
+    }
+
+    /// Take the box and text colours from the chart's style
+    pub fn set_style(&mut self, style: &PlotStyle) {
+        self.style = style.clone();
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<LegendEntry>) {
+        self.entries = entries;
+    }
+
+    pub fn entries(&self) -> &[LegendEntry] {
+        &self.entries
+    }
+
+    pub fn is_shown(&self) -> bool {
+        self.position != LegendPosition::None && !self.entries.is_empty()
+    }
+
+    /// Index of the entry under `p`, as laid out by the last draw
+    pub fn entry_at(&self, p: DVec2) -> Option<usize> {
+        self.entry_rects.iter().position(|rect| rect.contains(p))
+    }
+
+    fn rows(&self) -> usize {
+        self.entries.len().div_ceil(self.columns.max(1))
+    }
+
+    /// Width of each column, the height of the title row and the height of an entry row
+    fn measure(&mut self, cx: &mut Cx2d) -> (Vec<f64>, f64, f64) {
+        let font_size = if self.font_size > 0.0 { self.font_size } else { self.style.font_size };
+        self.label.set_font_size(font_size);
+        let row_height = (self.label.measure(cx, "Ag").y + LEGEND_ROW_GAP).max(LEGEND_MIN_ROW_HEIGHT);
+        let rows = self.rows().max(1);
+        let mut widths = Vec::new();
+        for column in self.entries.chunks(rows) {
+            let labels: Vec<String> = column.iter().map(|e| e.label.clone()).collect();
+            let text = max_text_width(cx, &mut self.label, &labels);
+            widths.push(LEGEND_SYMBOL_WIDTH + LEGEND_SYMBOL_GAP + text);
+        }
+        let title_height = if self.title.is_empty() {
+            0.0
+        } else {
+            self.label.set_weight(TextWeight::Bold);
+            let height = self.label.measure(cx, &self.title).y + 4.0;
+            self.label.set_weight(TextWeight::Normal);
+            height
+        };
+        (widths, title_height, row_height)
+    }
+
+    /// Size of the legend box
+    pub fn size(&mut self, cx: &mut Cx2d) -> DVec2 {
+        if !self.is_shown() {
+            return dvec2(0.0, 0.0);
+        }
+        let (widths, title_height, row_height) = self.measure(cx);
+        let columns_width: f64 = widths.iter().sum::<f64>() + LEGEND_COLUMN_GAP * (widths.len().saturating_sub(1)) as f64;
+        let mut width = columns_width;
+        if !self.title.is_empty() {
+            self.label.set_weight(TextWeight::Bold);
+            width = width.max(self.label.measure(cx, &self.title).x);
+            self.label.set_weight(TextWeight::Normal);
+        }
+        dvec2(
+            width + LEGEND_PADDING * 2.0,
+            title_height + self.rows() as f64 * row_height + LEGEND_PADDING * 2.0,
+        )
+    }
+
+    /// Extra right and bottom margin the chart should reserve for an outside legend
+    pub fn outside_margins(&mut self, cx: &mut Cx2d) -> (f64, f64) {
+        match self.position {
+            LegendPosition::OutsideRight if self.is_shown() => (self.size(cx).x + LEGEND_OFFSET, 0.0),
+            LegendPosition::OutsideBottom if self.is_shown() => (0.0, self.size(cx).y + LEGEND_OFFSET),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    /// Top-left corner of the legend box for a location inside the axes
+    fn inside_origin(position: LegendPosition, size: DVec2, area: &PlotArea) -> DVec2 {
+        let left = area.left + LEGEND_OFFSET;
+        let right = area.right - size.x - LEGEND_OFFSET;
+        let center_x = (area.left + area.right - size.x) / 2.0;
+        let top = area.top + LEGEND_OFFSET;
+        let bottom = area.bottom - size.y - LEGEND_OFFSET;
+        let center_y = (area.top + area.bottom - size.y) / 2.0;
+        match position {
+            LegendPosition::TopLeft => dvec2(left, top),
+            LegendPosition::TopCenter => dvec2(center_x, top),
+            LegendPosition::BottomRight => dvec2(right, bottom),
+            LegendPosition::BottomLeft => dvec2(left, bottom),
+            LegendPosition::BottomCenter => dvec2(center_x, bottom),
+            LegendPosition::CenterLeft => dvec2(left, center_y),
+            LegendPosition::CenterRight => dvec2(right, center_y),
+            LegendPosition::Center => dvec2(center_x, center_y),
+            _ => dvec2(right, top),
+        }
+    }
+
+    /// Top-left corner of the legend box
+    fn origin(&self, size: DVec2, area: &PlotArea, bounds: Rect, data: &[DVec2]) -> DVec2 {
+        match self.position {
+            LegendPosition::OutsideRight => dvec2(area.right + LEGEND_OFFSET, area.top),
+            LegendPosition::OutsideBottom => dvec2(
+                (area.left + area.right - size.x) / 2.0,
+                bounds.pos.y + bounds.size.y - size.y - LEGEND_OFFSET / 2.0,
+            ),
+            LegendPosition::Best => {
+                // Fewest covered data points wins, ties go to the earlier candidate
+                let mut best = (usize::MAX, dvec2(0.0, 0.0));
+                for candidate in LegendPosition::BEST_CANDIDATES {
+                    let origin = Self::inside_origin(candidate, size, area);
+                    let rect = Rect { pos: origin, size };
+                    let covered = data.iter().filter(|p| rect.contains(**p)).count();
+                    if covered < best.0 {
+                        best = (covered, origin);
+                    }
+                }
+                best.1
+            }
+            position => Self::inside_origin(position, size, area),
+        }
+    }
+
+    /// Draw the legend for a chart. `bounds` is the widget rect, `data` the pixel
+    /// positions of the plotted points, used by `Best`.
+    pub fn draw(&mut self, cx: &mut Cx2d, area: &PlotArea, bounds: Rect, data: &[DVec2]) {
+        self.entry_rects.clear();
+        if !self.is_shown() {
+            return;
+        }
+
+        let size = self.size(cx);
+        let (widths, title_height, row_height) = self.measure(cx);
+        let origin = self.origin(size, area, bounds, data);
+
+        // Background and border
+        self.draw_bg.draw_box(
+            cx,
+            Rect { pos: origin, size },
+            self.style.legend_background,
+            self.style.legend_border,
+            1.0,
+            3.0,
+        );
+
+        if !self.title.is_empty() {
+            self.label.set_color(self.style.text_color);
+            self.label.set_weight(TextWeight::Bold);
+            let title = self.title.clone();
+            self.label.draw_at(cx, dvec2(origin.x + size.x / 2.0, origin.y + LEGEND_PADDING), &title, TextAnchor::TopCenter);
+            self.label.set_weight(TextWeight::Normal);
+        }
+
+        let rows = self.rows().max(1);
+        let top = origin.y + LEGEND_PADDING + title_height;
+        let mut column_x = origin.x + LEGEND_PADDING;
+        let entries = self.entries.clone();
+        // Hidden entries fade towards the box colour; the hovered one gets the strongest contrast
+        let faded = mix(self.style.text_color, self.style.legend_background, 0.6);
+        let emphasis = contrasting_text(self.style.legend_background);
+        for (column, chunk) in entries.chunks(rows).enumerate() {
+            let column_width = widths.get(column).copied().unwrap_or(0.0);
+            for (row, entry) in chunk.iter().enumerate() {
+                let index = column * rows + row;
+                let entry_top = top + row as f64 * row_height;
+                let entry_y = entry_top + row_height / 2.0;
+                self.entry_rects.push(Rect {
+                    pos: dvec2(column_x, entry_top),
+                    size: dvec2(column_width, row_height),
+                });
+
+                let color = if entry.visible { entry.color } else { faded };
+                self.draw_symbol(cx, entry.symbol, color, dvec2(column_x, entry_y));
+
+                let text_color = if !entry.visible {
+                    faded
+                } else if self.highlighted == Some(index) {
+                    emphasis
+                } else {
+                    self.style.text_color
+                };
+                self.label.set_color(text_color);
+                self.label.draw_at(
+                    cx,
+                    dvec2(column_x + LEGEND_SYMBOL_WIDTH + LEGEND_SYMBOL_GAP, entry_y),
+                    &entry.label,
+                    TextAnchor::MiddleLeft,
+                );
+            }
+            column_x += column_width + LEGEND_COLUMN_GAP;
+        }
+    }
+
+    /// Draw a symbol in the slot starting at `left`, vertically centred on `left.y`
+    fn draw_symbol(&mut self, cx: &mut Cx2d, symbol: LegendSymbol, color: Vec4, left: DVec2) {
+        let start = left;
+        let end = dvec2(left.x + LEGEND_SYMBOL_WIDTH, left.y);
+        let middle = dvec2(left.x + LEGEND_SYMBOL_WIDTH / 2.0, left.y);
+        match symbol {
+            LegendSymbol::Line { style, width, marker } => {
+                self.draw_line.color = color;
+                self.draw_line.draw_line_styled(cx, start, end, width, style, 0.0);
+                self.draw_point.color = color;
+                self.draw_point.draw_marker(cx, middle, 3.5, marker);
+            }
+            LegendSymbol::Marker(marker) => {
+                self.draw_point.color = color;
+                self.draw_point.draw_marker(cx, middle, 5.0, marker);
+            }
+            LegendSymbol::Patch => {
+                self.draw_patch.color = color;
+                self.draw_patch.draw_bar(cx, Rect {
+                    pos: dvec2(middle.x - 5.0, middle.y - 5.0),
+                    size: dvec2(10.0, 10.0),
+                });
+            }
+            LegendSymbol::ErrorBar => {
+                self.draw_line.color = color;
+                self.draw_line.draw_line(cx, start, end, 1.5);
+                self.draw_line.draw_line(cx, dvec2(middle.x, middle.y - 5.0), dvec2(middle.x, middle.y + 5.0), 1.0);
+                self.draw_line.draw_line(cx, dvec2(middle.x - 3.0, middle.y - 5.0), dvec2(middle.x + 3.0, middle.y - 5.0), 1.0);
+                self.draw_line.draw_line(cx, dvec2(middle.x - 3.0, middle.y + 5.0), dvec2(middle.x + 3.0, middle.y + 5.0), 1.0);
+            }
+        }
+    }
+}
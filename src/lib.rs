@@ -3,10 +3,43 @@
 pub mod plot;
 pub mod elements;
 pub mod text;
+pub mod layout;
+pub mod legend;
+pub mod colorbar;
+pub mod norm;
+pub mod colormap;
+pub mod color;
+pub mod style;
+pub mod spec;
+pub mod vega;
+pub mod data;
+pub mod figure;
+pub mod svg;
+pub mod twin;
+pub mod secondary;
+pub mod broken;
+pub mod inset;
+pub mod spine;
 
 pub use plot::*;
 pub use elements::*;
 pub use text::*;
+pub use layout::*;
+pub use legend::*;
+pub use colorbar::*;
+pub use norm::*;
+pub use colormap::*;
+pub use color::*;
+pub use style::*;
+pub use spec::*;
+pub use data::*;
+pub use figure::*;
+pub use svg::*;
+pub use twin::*;
+pub use secondary::*;
+pub use broken::*;
+pub use inset::*;
+pub use spine::*;
 
 use makepad_widgets::*;
 
@@ -14,5 +47,7 @@ pub fn live_design(cx: &mut Cx) {
     math_widget::math::live_design(cx);  // Register math widget first
     crate::elements::live_design(cx);
     crate::text::live_design(cx);
+    crate::legend::live_design(cx);
+    crate::colorbar::live_design(cx);
     crate::plot::live_design(cx);
 }
@@ -0,0 +1,593 @@
+// Colour normalization - maps data values to [0, 1] ahead of a colormap lookup
+
+use crate::plot::ScaleType;
+
+/// Number of intervals aimed for by the linear tick locator
+const TICK_COUNT: usize = 5;
+
+/// Maps data values to [0, 1] for a colormap, like matplotlib's `Normalize` family.
+/// Charts take any norm through `set_norm`; the colorbar asks it for ticks and bands.
+pub trait Norm {
+    /// Map a value to [0, 1]
+    fn normalize(&self, value: f64) -> f64;
+
+    /// Map a position in [0, 1] back to a value
+    fn inverse(&self, t: f64) -> f64;
+
+    /// Values at the two ends of the scale
+    fn range(&self) -> (f64, f64);
+
+    /// Colorbar tick values and their labels
+    fn ticks(&self) -> Vec<(f64, String)> {
+        let (vmin, vmax) = self.range();
+        linear_ticks(vmin, vmax)
+    }
+
+    /// Band edges of a discrete norm; the colorbar draws one equal-size band per interval
+    fn boundaries(&self) -> Option<&[f64]> {
+        None
+    }
+
+    /// Fill in limits that were left unset, from the data range
+    fn autoscale(&mut self, _vmin: f64, _vmax: f64) {}
+
+    fn clone_box(&self) -> Box<dyn Norm>;
+}
+
+impl Clone for Box<dyn Norm> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// The norm a chart maps its values through: the one it was given, autoscaled to the data,
+/// or a linear norm over the data range
+pub fn resolve_norm(norm: &Option<Box<dyn Norm>>, vmin: f64, vmax: f64) -> Box<dyn Norm> {
+    match norm {
+        Some(norm) => {
+            let mut norm = norm.clone();
+            norm.autoscale(vmin, vmax);
+            norm
+        }
+        None => Box::new(Normalize::new(vmin, vmax)),
+    }
+}
+
+/// A norm by matplotlib name ("linear", "log", "symlog" or "centered") over [vmin, vmax]
+pub fn norm_from_name(name: &str, vmin: f64, vmax: f64) -> Option<Box<dyn Norm>> {
+    let norm: Box<dyn Norm> = match name {
+        "linear" => Box::new(Normalize::new(vmin, vmax)),
+        "log" => Box::new(LogNorm::new(vmin, vmax)),
+        "symlog" => Box::new(SymLogNorm::new(vmin, vmax, 1.0)),
+        "centered" => {
+            let mut norm = CenteredNorm::default();
+            norm.autoscale(vmin, vmax);
+            Box::new(norm)
+        }
+        _ => return None,
+    };
+    Some(norm)
+}
+
+/// Lets a norm chosen at runtime, e.g. by `norm_from_name`, go through `set_norm`
+impl Norm for Box<dyn Norm> {
+    fn normalize(&self, value: f64) -> f64 {
+        (**self).normalize(value)
+    }
+    fn inverse(&self, t: f64) -> f64 {
+        (**self).inverse(t)
+    }
+    fn range(&self) -> (f64, f64) {
+        (**self).range()
+    }
+    fn ticks(&self) -> Vec<(f64, String)> {
+        (**self).ticks()
+    }
+    fn boundaries(&self) -> Option<&[f64]> {
+        (**self).boundaries()
+    }
+    fn autoscale(&mut self, vmin: f64, vmax: f64) {
+        (**self).autoscale(vmin, vmax)
+    }
+    fn clone_box(&self) -> Box<dyn Norm> {
+        (**self).clone_box()
+    }
+}
+
+/// Ticks at multiples of 1, 2, 2.5 or 5 times a power of ten, and the chosen step
+fn nice_ticks(min: f64, max: f64, count: usize) -> (Vec<f64>, f64) {
+    let range = max - min;
+    if range <= 0.0 || !range.is_finite() {
+        return (vec![min], 1.0);
+    }
+    let raw = range / count.max(1) as f64;
+    let magnitude = 10.0_f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 2.5, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|s| *s >= raw)
+        .unwrap_or(10.0 * magnitude);
+    let first = (min / step).ceil() * step;
+    let ticks = (0..)
+        .map(|i| first + i as f64 * step)
+        .take_while(|v| *v <= max + step * 1e-9)
+        .collect();
+    (ticks, step)
+}
+
+/// Format a value with just enough decimals to tell neighbouring ticks apart
+fn format_value(value: f64, step: f64) -> String {
+    let decimals = if step > 0.0 { (-step.log10().floor()).max(0.0) as usize } else { 1 };
+    // Avoid "-0"
+    let value = if value.abs() < step * 1e-9 { 0.0 } else { value };
+    format!("{:.*}", decimals.min(6), value)
+}
+
+pub(crate) fn linear_ticks(vmin: f64, vmax: f64) -> Vec<(f64, String)> {
+    let (ticks, step) = nice_ticks(vmin, vmax, TICK_COUNT);
+    ticks.into_iter().map(|v| (v, format_value(v, step))).collect()
+}
+
+pub(crate) fn scale_ticks(scale: ScaleType, vmin: f64, vmax: f64) -> Vec<(f64, String)> {
+    scale
+        .generate_ticks(vmin, vmax, TICK_COUNT)
+        .into_iter()
+        .map(|v| (v, scale.format_tick(v)))
+        .collect()
+}
+
+/// Linear normalization: maps [vmin, vmax] to [0, 1]
+#[derive(Clone, Debug)]
+pub struct Normalize {
+    pub vmin: f64,
+    pub vmax: f64,
+    pub clip: bool,
+}
+
+impl Normalize {
+    pub fn new(vmin: f64, vmax: f64) -> Self {
+        Self { vmin, vmax, clip: true }
+    }
+
+    pub fn with_clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+
+    /// Normalize a value to [0, 1]
+    pub fn normalize(&self, value: f64) -> f64 {
+        if self.vmax == self.vmin {
+            return 0.5;
+        }
+        let t = (value - self.vmin) / (self.vmax - self.vmin);
+        if self.clip { t.clamp(0.0, 1.0) } else { t }
+    }
+
+    /// Inverse: convert [0, 1] back to original scale
+    pub fn inverse(&self, t: f64) -> f64 {
+        self.vmin + t * (self.vmax - self.vmin)
+    }
+}
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Self { vmin: 0.0, vmax: 1.0, clip: true }
+    }
+}
+
+impl Norm for Normalize {
+    fn normalize(&self, value: f64) -> f64 {
+        Normalize::normalize(self, value)
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        Normalize::inverse(self, t)
+    }
+
+    fn range(&self) -> (f64, f64) {
+        (self.vmin, self.vmax)
+    }
+
+    fn clone_box(&self) -> Box<dyn Norm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Logarithmic normalization: maps [vmin, vmax] to [0, 1] on log scale
+/// Values must be positive
+#[derive(Clone, Debug)]
+pub struct LogNorm {
+    pub vmin: f64,
+    pub vmax: f64,
+    pub clip: bool,
+    log_vmin: f64,
+    log_vmax: f64,
+}
+
+impl LogNorm {
+    pub fn new(vmin: f64, vmax: f64) -> Self {
+        let vmin = vmin.max(1e-10);  // Ensure positive
+        let vmax = vmax.max(vmin + 1e-10);
+        Self {
+            vmin,
+            vmax,
+            clip: true,
+            log_vmin: vmin.log10(),
+            log_vmax: vmax.log10(),
+        }
+    }
+
+    pub fn with_clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+
+    /// Normalize a value to [0, 1] on log scale
+    pub fn normalize(&self, value: f64) -> f64 {
+        if value <= 0.0 {
+            return 0.0;
+        }
+        let log_val = value.log10();
+        let t = (log_val - self.log_vmin) / (self.log_vmax - self.log_vmin);
+        if self.clip { t.clamp(0.0, 1.0) } else { t }
+    }
+
+    /// Inverse: convert [0, 1] back to original scale
+    pub fn inverse(&self, t: f64) -> f64 {
+        10.0_f64.powf(self.log_vmin + t * (self.log_vmax - self.log_vmin))
+    }
+}
+
+impl Default for LogNorm {
+    fn default() -> Self {
+        Self::new(1.0, 10.0)
+    }
+}
+
+impl Norm for LogNorm {
+    fn normalize(&self, value: f64) -> f64 {
+        LogNorm::normalize(self, value)
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        LogNorm::inverse(self, t)
+    }
+
+    fn range(&self) -> (f64, f64) {
+        (self.vmin, self.vmax)
+    }
+
+    fn ticks(&self) -> Vec<(f64, String)> {
+        scale_ticks(ScaleType::Log, self.vmin, self.vmax)
+    }
+
+    fn clone_box(&self) -> Box<dyn Norm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Symmetric log normalization: handles negative values
+#[derive(Clone, Debug)]
+pub struct SymLogNorm {
+    pub vmin: f64,
+    pub vmax: f64,
+    pub linthresh: f64,  // Linear threshold
+    pub clip: bool,
+}
+
+impl SymLogNorm {
+    pub fn new(vmin: f64, vmax: f64, linthresh: f64) -> Self {
+        Self {
+            vmin,
+            vmax,
+            linthresh: linthresh.abs().max(1e-10),
+            clip: true,
+        }
+    }
+
+    pub fn with_clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+
+    fn transform(&self, value: f64) -> f64 {
+        if value.abs() <= self.linthresh {
+            value / self.linthresh
+        } else {
+            let sign = if value >= 0.0 { 1.0 } else { -1.0 };
+            sign * (1.0 + (value.abs() / self.linthresh).log10())
+        }
+    }
+
+    /// Normalize a value to [0, 1]
+    pub fn normalize(&self, value: f64) -> f64 {
+        let t_val = self.transform(value);
+        let t_min = self.transform(self.vmin);
+        let t_max = self.transform(self.vmax);
+
+        if t_max == t_min {
+            return 0.5;
+        }
+
+        let t = (t_val - t_min) / (t_max - t_min);
+        if self.clip { t.clamp(0.0, 1.0) } else { t }
+    }
+
+    /// Inverse: convert [0, 1] back to original scale
+    pub fn inverse(&self, t: f64) -> f64 {
+        let t_min = self.transform(self.vmin);
+        let t_max = self.transform(self.vmax);
+        let t_val = t_min + t * (t_max - t_min);
+        if t_val.abs() <= 1.0 {
+            t_val * self.linthresh
+        } else {
+            t_val.signum() * self.linthresh * 10.0_f64.powf(t_val.abs() - 1.0)
+        }
+    }
+}
+
+impl Default for SymLogNorm {
+    fn default() -> Self {
+        Self::new(-10.0, 10.0, 1.0)
+    }
+}
+
+impl Norm for SymLogNorm {
+    fn normalize(&self, value: f64) -> f64 {
+        SymLogNorm::normalize(self, value)
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        SymLogNorm::inverse(self, t)
+    }
+
+    fn range(&self) -> (f64, f64) {
+        (self.vmin, self.vmax)
+    }
+
+    fn ticks(&self) -> Vec<(f64, String)> {
+        scale_ticks(ScaleType::SymLog, self.vmin, self.vmax)
+    }
+
+    fn clone_box(&self) -> Box<dyn Norm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Power-law normalization: linear in [vmin, vmax], then raised to `gamma`.
+/// gamma < 1 spreads out the low end, gamma > 1 the high end.
+#[derive(Clone, Debug)]
+pub struct PowerNorm {
+    pub vmin: f64,
+    pub vmax: f64,
+    pub gamma: f64,
+    pub clip: bool,
+}
+
+impl PowerNorm {
+    pub fn new(gamma: f64, vmin: f64, vmax: f64) -> Self {
+        Self { vmin, vmax, gamma: gamma.max(1e-10), clip: true }
+    }
+
+    pub fn with_clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+}
+
+impl Default for PowerNorm {
+    fn default() -> Self {
+        Self::new(1.0, 0.0, 1.0)
+    }
+}
+
+impl Norm for PowerNorm {
+    fn normalize(&self, value: f64) -> f64 {
+        if self.vmax == self.vmin {
+            return 0.5;
+        }
+        // Below vmin there is nothing to raise to a power
+        let t = ((value - self.vmin) / (self.vmax - self.vmin)).max(0.0).powf(self.gamma);
+        if self.clip { t.clamp(0.0, 1.0) } else { t }
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        self.vmin + t.max(0.0).powf(1.0 / self.gamma) * (self.vmax - self.vmin)
+    }
+
+    fn range(&self) -> (f64, f64) {
+        (self.vmin, self.vmax)
+    }
+
+    fn clone_box(&self) -> Box<dyn Norm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Discrete normalization: values falling between two boundaries share one colour.
+/// The bands are spread evenly over the colormap, whatever their width in data units.
+#[derive(Clone, Debug, Default)]
+pub struct BoundaryNorm {
+    boundaries: Vec<f64>,
+}
+
+impl BoundaryNorm {
+    /// Boundaries are sorted; non-finite and repeated values are dropped
+    pub fn new(mut boundaries: Vec<f64>) -> Self {
+        boundaries.retain(|v| v.is_finite());
+        boundaries.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        boundaries.dedup();
+        Self { boundaries }
+    }
+
+    /// Number of colour bands
+    pub fn bands(&self) -> usize {
+        self.boundaries.len().saturating_sub(1)
+    }
+
+    /// Index of the band holding `value`; values outside the boundaries go to the end bands
+    pub fn band(&self, value: f64) -> usize {
+        let index = self.boundaries.iter().filter(|b| **b <= value).count();
+        index.saturating_sub(1).min(self.bands().saturating_sub(1))
+    }
+}
+
+impl Norm for BoundaryNorm {
+    fn normalize(&self, value: f64) -> f64 {
+        let bands = self.bands();
+        if bands < 2 {
+            return 0.5;
+        }
+        self.band(value) as f64 / (bands - 1) as f64
+    }
+
+    /// Middle of the band at `t`
+    fn inverse(&self, t: f64) -> f64 {
+        let bands = self.bands();
+        if bands == 0 {
+            return self.boundaries.first().copied().unwrap_or(0.0);
+        }
+        let band = (t.clamp(0.0, 1.0) * (bands - 1) as f64).round() as usize;
+        (self.boundaries[band] + self.boundaries[band + 1]) / 2.0
+    }
+
+    fn range(&self) -> (f64, f64) {
+        match (self.boundaries.first(), self.boundaries.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => (0.0, 1.0),
+        }
+    }
+
+    fn ticks(&self) -> Vec<(f64, String)> {
+        let step = self.boundaries.windows(2).map(|w| w[1] - w[0]).fold(f64::MAX, f64::min);
+        self.boundaries.iter().map(|b| (*b, format_value(*b, step))).collect()
+    }
+
+    fn boundaries(&self) -> Option<&[f64]> {
+        Some(&self.boundaries)
+    }
+
+    fn clone_box(&self) -> Box<dyn Norm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Diverging normalization with a different slope either side of a centre value:
+/// vmin maps to 0, vcenter to 0.5 and vmax to 1
+#[derive(Clone, Debug)]
+pub struct TwoSlopeNorm {
+    pub vmin: f64,
+    pub vcenter: f64,
+    pub vmax: f64,
+}
+
+impl TwoSlopeNorm {
+    pub fn new(vmin: f64, vcenter: f64, vmax: f64) -> Self {
+        Self { vmin: vmin.min(vcenter), vcenter, vmax: vmax.max(vcenter) }
+    }
+}
+
+impl Default for TwoSlopeNorm {
+    fn default() -> Self {
+        Self::new(-1.0, 0.0, 1.0)
+    }
+}
+
+impl Norm for TwoSlopeNorm {
+    fn normalize(&self, value: f64) -> f64 {
+        let t = if value < self.vcenter {
+            if self.vcenter == self.vmin { 0.5 } else { 0.5 * (value - self.vmin) / (self.vcenter - self.vmin) }
+        } else if self.vmax == self.vcenter {
+            0.5
+        } else {
+            0.5 + 0.5 * (value - self.vcenter) / (self.vmax - self.vcenter)
+        };
+        t.clamp(0.0, 1.0)
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        if t < 0.5 {
+            self.vmin + t * 2.0 * (self.vcenter - self.vmin)
+        } else {
+            self.vcenter + (t - 0.5) * 2.0 * (self.vmax - self.vcenter)
+        }
+    }
+
+    fn range(&self) -> (f64, f64) {
+        (self.vmin, self.vmax)
+    }
+
+    /// Round ticks on each side separately so the centre is always labelled
+    fn ticks(&self) -> Vec<(f64, String)> {
+        let mut ticks = linear_ticks(self.vmin, self.vcenter);
+        ticks.extend(linear_ticks(self.vcenter, self.vmax).into_iter().filter(|(v, _)| *v > self.vcenter));
+        ticks
+    }
+
+    fn clone_box(&self) -> Box<dyn Norm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Linear normalization symmetric around a centre value.
+/// Without an explicit half range it covers the largest deviation in the data.
+#[derive(Clone, Debug)]
+pub struct CenteredNorm {
+    pub vcenter: f64,
+    pub halfrange: Option<f64>,
+    pub clip: bool,
+    data_halfrange: f64,
+}
+
+impl CenteredNorm {
+    pub fn new(vcenter: f64) -> Self {
+        Self { vcenter, halfrange: None, clip: true, data_halfrange: 1.0 }
+    }
+
+    pub fn with_halfrange(mut self, halfrange: f64) -> Self {
+        self.halfrange = Some(halfrange.abs());
+        self
+    }
+
+    pub fn with_clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+
+    fn halfrange(&self) -> f64 {
+        self.halfrange.unwrap_or(self.data_halfrange)
+    }
+}
+
+impl Default for CenteredNorm {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl Norm for CenteredNorm {
+    fn normalize(&self, value: f64) -> f64 {
+        let halfrange = self.halfrange();
+        if halfrange == 0.0 {
+            return 0.5;
+        }
+        let t = 0.5 + (value - self.vcenter) / (2.0 * halfrange);
+        if self.clip { t.clamp(0.0, 1.0) } else { t }
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        self.vcenter + (t - 0.5) * 2.0 * self.halfrange()
+    }
+
+    fn range(&self) -> (f64, f64) {
+        (self.vcenter - self.halfrange(), self.vcenter + self.halfrange())
+    }
+
+    fn autoscale(&mut self, vmin: f64, vmax: f64) {
+        self.data_halfrange = (vmin - self.vcenter).abs().max((vmax - self.vcenter).abs());
+    }
+
+    fn clone_box(&self) -> Box<dyn Norm> {
+        Box::new(self.clone())
+    }
+}
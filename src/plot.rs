@@ -3,7 +3,24 @@
 use makepad_widgets::*;
 use crate::elements::*;
 use crate::text::*;
-use math_widget::math::Math;
+use crate::layout::*;
+use crate::legend::*;
+use crate::colorbar::*;
+use crate::norm::*;
+use crate::colormap::*;
+use crate::color::*;
+use crate::style::*;
+use crate::data::*;
+use crate::twin::*;
+use crate::secondary::*;
+use crate::broken::*;
+use crate::inset::*;
+use crate::spine::*;
+use crate::figure::Figure;
+use crate::spec::{
+    ChartSpec, SpecError, SeriesSpec, BarGroupSpec, SliceSpec, CandleSpec, SankeyNodeSpec, SankeyLinkSpec,
+    WaterfallEntrySpec, AnnotationSpec, style_name, scale_name, legend_name, colormap_name, limits_spec, margins_spec,
+};
 
 // Re-export styling enums
 pub use crate::elements::{LineStyle, MarkerStyle};
@@ -25,6 +42,7 @@ pub struct VLine {
     pub color: Vec4,
     pub line_width: f64,
     pub line_style: LineStyle,
+    pub draggable: bool,
 }
 
 /// Horizontal line annotation
@@ -34,6 +52,7 @@ pub struct HLine {
     pub color: Vec4,
     pub line_width: f64,
     pub line_style: LineStyle,
+    pub draggable: bool,
 }
 
 /// Vertical span (shaded region)
@@ -42,6 +61,9 @@ pub struct VSpan {
     pub x1: f64,
     pub x2: f64,
     pub color: Vec4,
+    pub draggable: bool,  // Edges can be dragged
+    pub label: String,    // Legend label, empty for no entry
+    pub visible: bool,    // Toggled from the legend
 }
 
 /// Horizontal span (shaded region)
@@ -50,6 +72,69 @@ pub struct HSpan {
     pub y1: f64,
     pub y2: f64,
     pub color: Vec4,
+    pub draggable: bool,  // Edges can be dragged
+    pub label: String,    // Legend label, empty for no entry
+    pub visible: bool,    // Toggled from the legend
+}
+
+/// Reference line, span or annotation of a LinePlot, by index in the order it was added
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Draggable {
+    VLine(usize),
+    HLine(usize),
+    VSpan(usize),
+    HSpan(usize),
+    Annotation(usize),
+}
+
+impl Draggable {
+    fn cursor(&self) -> MouseCursor {
+        match self {
+            Draggable::VLine(_) | Draggable::VSpan(_) => MouseCursor::ColResize,
+            Draggable::HLine(_) | Draggable::HSpan(_) => MouseCursor::RowResize,
+            Draggable::Annotation(_) => MouseCursor::Hand,
+        }
+    }
+}
+
+/// Actions emitted by LinePlot; moved values are in data coordinates
+#[derive(Clone, Debug, DefaultNone)]
+pub enum LinePlotAction {
+    VLineMoved { index: usize, x: f64 },
+    HLineMoved { index: usize, y: f64 },
+    VSpanMoved { index: usize, x1: f64, x2: f64 },
+    HSpanMoved { index: usize, y1: f64, y2: f64 },
+    /// Position in the annotation's own coordinate systems
+    AnnotationMoved { index: usize, x: f64, y: f64 },
+    /// A legend entry was clicked
+    SeriesToggled { index: usize, visible: bool },
+    /// The legend entry of a twin series, fill or span was clicked
+    LegendItemToggled { item: LegendItem, visible: bool },
+    None,
+}
+
+/// What a LinePlot legend entry stands for, by index in the order it was added
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LegendItem {
+    Series(usize),
+    TwinSeries(TwinSide, usize),
+    Fill(usize),
+    VSpan(usize),
+    HSpan(usize),
+}
+
+/// Pixel distance within which a line or span edge can be grabbed
+const DRAG_TOLERANCE: f64 = 5.0;
+
+/// Opacity of the other series while a legend entry is hovered
+const LEGEND_DIM_ALPHA: f32 = 0.25;
+
+/// Element being dragged
+#[derive(Clone, Copy, Debug)]
+struct DragState {
+    item: Draggable,
+    edge: usize,   // 0 for the first edge of a span, 1 for the second
+    grab: DVec2,   // Offset from the annotation anchor to the finger
 }
 
 live_design! {
@@ -61,49 +146,57 @@ live_design! {
     use crate::elements::DrawPlotPoint;
     use crate::elements::DrawPlotBar;
     use crate::elements::DrawPlotFill;
+    use crate::elements::DrawPlotBox;
     use crate::text::PlotLabel;
-    use math_widget::math::Math;
+    use crate::legend::PlotLegend;
+    use crate::colorbar::PlotColorbar;
 
-    // Simple line plot widget
+    // Simple line plot widget. Titles, labels, limits, scales, grid and legend can be set here
+    // as well as from Rust, e.g. <LinePlot> { title: "Latency", y_scale: Log, legend_position: TopLeft }
     pub LinePlot = {{LinePlot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
-        math_label: <Math> {
-            color: #000000,
-            font_size: 14.0
-        }
+        legend: <PlotLegend> {}
     }
 
     // Simple bar plot widget
     pub BarPlot = {{BarPlot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        legend: <PlotLegend> {}
     }
 
     // Scatter plot widget
     pub ScatterPlot = {{ScatterPlot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        legend: <PlotLegend> {}
     }
 
     // Pie chart widget
     pub PieChart = {{PieChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        legend: <PlotLegend> {}
     }
 
     // Histogram chart widget
     pub HistogramChart = {{HistogramChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -112,6 +205,7 @@ live_design! {
     pub BoxPlotChart = {{BoxPlotChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -120,22 +214,27 @@ live_design! {
     pub HeatmapChart = {{HeatmapChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        colorbar: <PlotColorbar> {}
     }
 
     // Stem plot widget (lollipop chart)
     pub StemPlot = {{StemPlot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        legend: <PlotLegend> {}
     }
 
     // Violin plot widget (KDE distribution)
     pub ViolinPlot = {{ViolinPlot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -144,6 +243,7 @@ live_design! {
     pub PolarPlot = {{PolarPlot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -152,14 +252,17 @@ live_design! {
     pub ContourPlot = {{ContourPlot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        colorbar: <PlotColorbar> {}
     }
 
     // Quiver plot widget (vector field)
     pub QuiverPlot = {{QuiverPlot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -168,14 +271,17 @@ live_design! {
     pub Surface3D = {{Surface3D}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        colorbar: <PlotColorbar> {}
     }
 
     // 3D Scatter plot widget
     pub Scatter3D = {{Scatter3D}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -184,6 +290,7 @@ live_design! {
     pub Line3D = {{Line3D}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -193,11 +300,20 @@ live_design! {
         height: Fill,
         flow: Down,
         spacing: 10.0,
+
+        // Templates for the charts a Figure creates
+        line_plot: <LinePlot> {}
+        scatter_plot: <ScatterPlot> {}
+        bar_plot: <BarPlot> {}
+        histogram_chart: <HistogramChart> {}
+        pie_chart: <PieChart> {}
+        heatmap_chart: <HeatmapChart> {}
     }
 
     pub LinePlotDual = {{LinePlotDual}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -206,14 +322,17 @@ live_design! {
     pub CandlestickChart = {{CandlestickChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        legend: <PlotLegend> {}
     }
 
     // Radar/Spider chart (multi-axis comparison)
     pub RadarChart = {{RadarChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -222,6 +341,7 @@ live_design! {
     pub WaterfallChart = {{WaterfallChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -230,6 +350,7 @@ live_design! {
     pub GaugeChart = {{GaugeChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -238,6 +359,7 @@ live_design! {
     pub FunnelChart = {{FunnelChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -246,14 +368,17 @@ live_design! {
     pub Heatmap = {{Heatmap}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        colorbar: <PlotColorbar> {}
     }
 
     // Treemap (hierarchical data)
     pub Treemap = {{Treemap}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -262,14 +387,17 @@ live_design! {
     pub BubbleChart = {{BubbleChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        legend: <PlotLegend> {}
     }
 
     // Donut chart (pie with center hole) - uses proper arc shader
     pub DonutChart = {{DonutChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         draw_arc: {}
         label: <PlotLabel> {}
@@ -279,14 +407,17 @@ live_design! {
     pub AreaChart = {{AreaChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        legend: <PlotLegend> {}
     }
 
     // Step plot (discrete steps)
     pub StepPlot = {{StepPlot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
     }
@@ -295,14 +426,17 @@ live_design! {
     pub CandlestickChart = {{CandlestickChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
 
         label: <PlotLabel> {}
+        legend: <PlotLegend> {}
     }
 
     // Stackplot (stacked area chart)
     pub Stackplot = {{Stackplot}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
         label: <PlotLabel> {}
     }
 
@@ -310,13 +444,16 @@ live_design! {
     pub HexbinChart = {{HexbinChart}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
         label: <PlotLabel> {}
+        colorbar: <PlotColorbar> {}
     }
 
     // Streamgraph (centered stacked area)
     pub Streamgraph = {{Streamgraph}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
         label: <PlotLabel> {}
     }
 
@@ -324,6 +461,7 @@ live_design! {
     pub SankeyDiagram = {{SankeyDiagram}} {
         width: Fill,
         height: Fill,
+        theme_background: (THEME_COLOR_BG_APP),
         label: <PlotLabel> {}
     }
 }
@@ -381,9 +519,11 @@ pub fn gradient_pair_custom(color: Vec4, lighten_amount: f32, darken_amount: f32
 }
 
 /// Scale type for axis transformation
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Live, LiveHook)]
+#[live_ignore]
 pub enum ScaleType {
     #[default]
+    #[pick]
     Linear,
     Log,      // Logarithmic (base 10)
     SymLog,   // Symmetric log (handles negative values)
@@ -391,6 +531,23 @@ pub enum ScaleType {
 }
 
 impl ScaleType {
+    /// Name as used by matplotlib's `set_xscale`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScaleType::Linear => "linear",
+            ScaleType::Log => "log",
+            ScaleType::SymLog => "symlog",
+            ScaleType::Time => "time",
+        }
+    }
+
+    /// Look a scale up by name, case-insensitively
+    pub fn from_name(name: &str) -> Option<ScaleType> {
+        [ScaleType::Linear, ScaleType::Log, ScaleType::SymLog, ScaleType::Time]
+            .into_iter()
+            .find(|scale| scale.name().eq_ignore_ascii_case(name))
+    }
+
     /// Transform a value according to the scale type
     pub fn transform(&self, value: f64) -> f64 {
         match self {
@@ -565,7 +722,7 @@ impl ScaleType {
                 if value > 0.0 {
                     let exp = value.log10().round() as i32;
                     if (10.0_f64.powi(exp) - value).abs() < 1e-10 {
-                        format!("10^{}", exp)
+                        format!("$10^{{{}}}$", exp)
                     } else {
                         format!("{:.1}", value)
                     }
@@ -580,9 +737,9 @@ impl ScaleType {
                     let exp = value.abs().log10().round() as i32;
                     if (10.0_f64.powi(exp) - value.abs()).abs() < 1e-10 {
                         if value < 0.0 {
-                            format!("-10^{}", exp)
+                            format!("$-10^{{{}}}$", exp)
                         } else {
-                            format!("10^{}", exp)
+                            format!("$10^{{{}}}$", exp)
                         }
                     } else {
                         format!("{:.1}", value)
@@ -595,15 +752,73 @@ impl ScaleType {
     }
 }
 
-/// Legend position options
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
-pub enum LegendPosition {
-    #[default]
-    TopRight,
-    TopLeft,
-    BottomRight,
-    BottomLeft,
-    None, // Hidden
+/// Categorical axis: maps an ordered list of names to positions 0, 1, 2, ...
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CategoryAxis {
+    pub categories: Vec<String>,
+}
+
+impl CategoryAxis {
+    pub fn new<S: Into<String>>(categories: Vec<S>) -> Self {
+        Self {
+            categories: categories.into_iter().map(|c| c.into()).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.categories.len()
+    }
+
+    /// Position of a category, appending it to the axis if it has not been seen yet
+    pub fn position(&mut self, name: &str) -> f64 {
+        match self.categories.iter().position(|c| c == name) {
+            Some(i) => i as f64,
+            None => {
+                self.categories.push(name.to_string());
+                (self.categories.len() - 1) as f64
+            }
+        }
+    }
+
+    /// Positions for a list of category names
+    pub fn positions(&mut self, names: &[String]) -> Vec<f64> {
+        names.iter().map(|n| self.position(n)).collect()
+    }
+
+    /// Move series that carry category names onto their slots on this axis
+    pub fn place(&mut self, series: &mut [Series]) {
+        for s in series {
+            if let Some(ref names) = s.x_categories {
+                s.x = self.positions(names);
+            }
+        }
+    }
+
+    /// Category name at a tick position
+    pub fn label(&self, value: f64) -> Option<&str> {
+        let rounded = value.round();
+        if (value - rounded).abs() > 1e-6 || rounded < 0.0 {
+            return None;
+        }
+        self.categories.get(rounded as usize).map(|c| c.as_str())
+    }
+
+    /// Data range with half a slot either side so points sit in the middle of their slot
+    pub fn range(&self) -> (f64, f64) {
+        (-0.5, self.categories.len() as f64 - 0.5)
+    }
+
+    /// Positions of the categories visible between min and max
+    pub fn ticks(&self, min: f64, max: f64) -> Vec<f64> {
+        (0..self.categories.len())
+            .map(|i| i as f64)
+            .filter(|&v| v >= min && v <= max)
+            .collect()
+    }
 }
 
 /// Data series for plotting
@@ -623,6 +838,14 @@ pub struct Series {
     pub xerr_plus: Option<Vec<f64>>,
     pub yerr_minus: Option<Vec<f64>>,
     pub yerr_plus: Option<Vec<f64>>,
+    // Category names for each point; resolved to x positions by the plot
+    pub x_categories: Option<Vec<String>>,
+    // Hidden series are skipped when drawing and auto-ranging
+    pub visible: bool,
+    // Per-point values mapped through the plot's colormap and norm (scatter `c=`)
+    pub color_values: Option<Vec<f64>>,
+    // Per-point marker radii in pixels (scatter `s=`)
+    pub sizes: Option<Vec<f64>>,
 }
 
 impl Series {
@@ -641,11 +864,24 @@ impl Series {
             xerr_plus: None,
             yerr_minus: None,
             yerr_plus: None,
+            x_categories: None,
+            visible: true,
+            color_values: None,
+            sizes: None,
         }
     }
 
-    pub fn with_data(mut self, x: Vec<f64>, y: Vec<f64>) -> Self {
-        self.x = x;
+    pub fn with_data(mut self, x: impl IntoValues, y: impl IntoValues) -> Self {
+        self.x = x.into_values().into_owned();
+        self.y = y.into_values().into_owned();
+        self
+    }
+
+    /// Use category names instead of numeric x values (e.g. weekdays or build IDs)
+    pub fn with_categories<S: Into<String>>(mut self, categories: Vec<S>, y: Vec<f64>) -> Self {
+        let categories: Vec<String> = categories.into_iter().map(|c| c.into()).collect();
+        self.x = (0..categories.len()).map(|i| i as f64).collect();
+        self.x_categories = Some(categories);
         self.y = y;
         self
     }
@@ -655,6 +891,18 @@ impl Series {
         self
     }
 
+    /// Colour each point by a value instead of a single series colour
+    pub fn with_color_values(mut self, values: impl IntoValues) -> Self {
+        self.color_values = Some(values.into_values().into_owned());
+        self
+    }
+
+    /// Size each point separately instead of using the plot's point radius
+    pub fn with_sizes(mut self, radii: impl IntoValues) -> Self {
+        self.sizes = Some(radii.into_values().into_owned());
+        self
+    }
+
     pub fn with_line_style(mut self, style: LineStyle) -> Self {
         self.line_style = style;
         self
@@ -680,31 +928,38 @@ impl Series {
         self
     }
 
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
     /// Add symmetric y error bars
-    pub fn with_yerr(mut self, yerr: Vec<f64>) -> Self {
+    pub fn with_yerr(mut self, yerr: impl IntoValues) -> Self {
+        let yerr = yerr.into_values().into_owned();
         self.yerr_minus = Some(yerr.clone());
         self.yerr_plus = Some(yerr);
         self
     }
 
     /// Add asymmetric y error bars
-    pub fn with_yerr_asymmetric(mut self, yerr_minus: Vec<f64>, yerr_plus: Vec<f64>) -> Self {
-        self.yerr_minus = Some(yerr_minus);
-        self.yerr_plus = Some(yerr_plus);
+    pub fn with_yerr_asymmetric(mut self, yerr_minus: impl IntoValues, yerr_plus: impl IntoValues) -> Self {
+        self.yerr_minus = Some(yerr_minus.into_values().into_owned());
+        self.yerr_plus = Some(yerr_plus.into_values().into_owned());
         self
     }
 
     /// Add symmetric x error bars
-    pub fn with_xerr(mut self, xerr: Vec<f64>) -> Self {
+    pub fn with_xerr(mut self, xerr: impl IntoValues) -> Self {
+        let xerr = xerr.into_values().into_owned();
         self.xerr_minus = Some(xerr.clone());
         self.xerr_plus = Some(xerr);
         self
     }
 
     /// Add asymmetric x error bars
-    pub fn with_xerr_asymmetric(mut self, xerr_minus: Vec<f64>, xerr_plus: Vec<f64>) -> Self {
-        self.xerr_minus = Some(xerr_minus);
-        self.xerr_plus = Some(xerr_plus);
+    pub fn with_xerr_asymmetric(mut self, xerr_minus: impl IntoValues, xerr_plus: impl IntoValues) -> Self {
+        self.xerr_minus = Some(xerr_minus.into_values().into_owned());
+        self.xerr_plus = Some(xerr_plus.into_values().into_owned());
         self
     }
 }
@@ -743,6 +998,115 @@ pub struct FillRegion {
     pub y1: Vec<f64>,
     pub y2: Vec<f64>,
     pub color: Vec4,
+    pub label: String,  // Legend label, empty for no entry
+    pub visible: bool,  // Toggled from the legend
+}
+
+/// Coordinate system of one component of an annotation position,
+/// like matplotlib's `xycoords` / `textcoords`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CoordSpace {
+    /// Data units, moves with pan and zoom
+    #[default]
+    Data,
+    /// 0..1 across the plot area, (0, 0) is the bottom-left corner
+    AxesFraction,
+    /// 0..1 across the whole widget, (0, 0) is the bottom-left corner
+    FigureFraction,
+    /// Pixels from the reference point, y pointing up (matplotlib's "offset points").
+    /// For an arrow the reference is the other end; for plain text it is the plot area's bottom-left corner.
+    OffsetPixels,
+}
+
+/// Coordinate systems for the x and y components of a position.
+/// Mixing them gives blended transforms, e.g. data x with axes-fraction y.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Coords {
+    pub x: CoordSpace,
+    pub y: CoordSpace,
+}
+
+impl Coords {
+    pub fn new(x: CoordSpace, y: CoordSpace) -> Self {
+        Self { x, y }
+    }
+
+    pub fn data() -> Self {
+        Self::new(CoordSpace::Data, CoordSpace::Data)
+    }
+
+    pub fn axes_fraction() -> Self {
+        Self::new(CoordSpace::AxesFraction, CoordSpace::AxesFraction)
+    }
+
+    pub fn figure_fraction() -> Self {
+        Self::new(CoordSpace::FigureFraction, CoordSpace::FigureFraction)
+    }
+
+    pub fn offset_pixels() -> Self {
+        Self::new(CoordSpace::OffsetPixels, CoordSpace::OffsetPixels)
+    }
+}
+
+/// Path of an arrow between its two ends, like matplotlib's `connectionstyle`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ArrowStyle {
+    #[default]
+    Straight,
+    /// Quadratic curve bowing sideways by `rad` times the arrow length ("arc3,rad=...")
+    Arc3 { rad: f64 },
+    /// Two straight legs leaving the start at `angle_a` and entering the end at `angle_b`, in degrees ("angle")
+    Angle { angle_a: f64, angle_b: f64 },
+}
+
+impl ArrowStyle {
+    /// Polyline from `start` to `end` in pixel space
+    pub fn path(&self, start: DVec2, end: DVec2) -> Vec<DVec2> {
+        match *self {
+            ArrowStyle::Straight => vec![start, end],
+            ArrowStyle::Arc3 { rad } => {
+                let d = end - start;
+                // Matches matplotlib's sign convention with y pointing down
+                let control = (start + end) / 2.0 + dvec2(-d.y, d.x) * rad;
+                const STEPS: usize = 24;
+                (0..=STEPS)
+                    .map(|i| {
+                        let t = i as f64 / STEPS as f64;
+                        start * ((1.0 - t) * (1.0 - t)) + control * (2.0 * (1.0 - t) * t) + end * (t * t)
+                    })
+                    .collect()
+            }
+            ArrowStyle::Angle { angle_a, angle_b } => {
+                let dir = |degrees: f64| {
+                    let r = degrees.to_radians();
+                    dvec2(r.cos(), -r.sin())
+                };
+                let (da, db) = (dir(angle_a), dir(angle_b));
+                // Corner where the line through start along da meets the line through end along db
+                let denom = da.x * db.y - da.y * db.x;
+                if denom.abs() < 1e-9 {
+                    return vec![start, end];
+                }
+                let d = end - start;
+                let t = (d.x * db.y - d.y * db.x) / denom;
+                vec![start, start + da * t, end]
+            }
+        }
+    }
+}
+
+/// Shape drawn at the end of an arrow
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ArrowHead {
+    /// Two strokes forming a V ("->")
+    #[default]
+    Open,
+    /// Filled triangle ("-|>")
+    Filled,
+    /// Bar across the end ("-[")
+    Bar,
+    /// No head ("-")
+    None,
 }
 
 /// Text annotation on the plot
@@ -754,6 +1118,109 @@ pub struct TextAnnotation {
     pub color: Vec4,
     pub font_size: f64,
     pub is_math: bool,  // If true, render as LaTeX using Math widget
+    pub rotation: f64,  // Degrees, counter-clockwise (single-line plain text only)
+    pub slope: Option<(f64, f64)>,  // Data-space direction to align the text with, overrides rotation
+    pub bbox: Option<TextBox>,  // Background box behind the text
+    pub align: LineAlign,  // Alignment of the lines of multi-line text
+    pub weight: TextWeight,
+    pub slant: TextSlant,
+    pub coords: Coords,  // Coordinate systems of x and y
+    pub draggable: bool,
+}
+
+impl TextAnnotation {
+    /// Plain text at a data coordinate. Newlines start a new line and
+    /// `$...$` runs are rendered as LaTeX, e.g. `"Peak at $\\alpha=0.3$"`.
+    pub fn new(text: impl Into<String>, x: f64, y: f64) -> Self {
+        Self {
+            text: text.into(),
+            x,
+            y,
+            color: vec4(0.2, 0.2, 0.2, 1.0),
+            font_size: 12.0,
+            is_math: false,
+            rotation: 0.0,
+            slope: None,
+            bbox: None,
+            align: LineAlign::default(),
+            weight: TextWeight::default(),
+            slant: TextSlant::default(),
+            coords: Coords::default(),
+            draggable: false,
+        }
+    }
+
+    /// Render the whole text as LaTeX
+    pub fn math(mut self) -> Self {
+        self.is_math = true;
+        self
+    }
+
+    pub fn with_color(mut self, color: Vec4) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_font_size(mut self, size: f64) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    pub fn with_rotation(mut self, degrees: f64) -> Self {
+        self.rotation = degrees;
+        self
+    }
+
+    pub fn with_slope(mut self, dx: f64, dy: f64) -> Self {
+        self.slope = Some((dx, dy));
+        self
+    }
+
+    pub fn with_bbox(mut self, bbox: TextBox) -> Self {
+        self.bbox = Some(bbox);
+        self
+    }
+
+    pub fn with_align(mut self, align: LineAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Interpret x and y in other coordinate systems, e.g. `Coords::axes_fraction()`
+    pub fn with_coords(mut self, coords: Coords) -> Self {
+        self.coords = coords;
+        self
+    }
+
+    /// Let the user move the annotation with the mouse
+    pub fn draggable(mut self) -> Self {
+        self.draggable = true;
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.weight = TextWeight::Bold;
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.slant = TextSlant::Italic;
+        self
+    }
+
+    /// Needs the block layout: a box or several lines
+    fn is_block(&self) -> bool {
+        self.bbox.is_some() || self.text.contains('\n')
+    }
+
+    /// Text as passed to PlotLabel, with whole-math annotations wrapped in `$...$`
+    fn display_text(&self) -> String {
+        if self.is_math {
+            format!("${}$", self.text)
+        } else {
+            self.text.clone()
+        }
+    }
 }
 
 /// Arrow annotation pointing from one location to another
@@ -767,6 +1234,10 @@ pub struct ArrowAnnotation {
     pub line_width: f64,
     pub head_size: f64,
     pub text: Option<String>,  // Optional label near the arrow start
+    pub start_coords: Coords,  // Coordinate systems of the start (matplotlib's textcoords)
+    pub end_coords: Coords,    // Coordinate systems of the end (matplotlib's xycoords)
+    pub style: ArrowStyle,
+    pub head: ArrowHead,
 }
 
 impl ArrowAnnotation {
@@ -780,6 +1251,10 @@ impl ArrowAnnotation {
             line_width: 1.5,
             head_size: 8.0,
             text: None,
+            start_coords: Coords::default(),
+            end_coords: Coords::default(),
+            style: ArrowStyle::default(),
+            head: ArrowHead::default(),
         }
     }
 
@@ -788,6 +1263,23 @@ impl ArrowAnnotation {
         self
     }
 
+    /// Coordinate systems of the start and the end point
+    pub fn with_coords(mut self, start: Coords, end: Coords) -> Self {
+        self.start_coords = start;
+        self.end_coords = end;
+        self
+    }
+
+    pub fn with_style(mut self, style: ArrowStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_head(mut self, head: ArrowHead) -> Self {
+        self.head = head;
+        self
+    }
+
     pub fn with_text(mut self, text: impl Into<String>) -> Self {
         self.text = Some(text.into());
         self
@@ -819,11 +1311,21 @@ pub struct LinePlot {
     #[live]
     draw_fill: DrawPlotFill,
 
+    #[live]
+    draw_box: DrawPlotBox,
+
+    #[live]
+    draw_triangle: DrawTriangle,
+
     #[live]
     label: PlotLabel,
 
     #[live]
-    math_label: Math,
+    legend: PlotLegend,
+
+    // Flat DSL form of `legend: { position: .. }`
+    #[live]
+    legend_position: Option<LegendPosition>,
 
     #[rust]
     series: Vec<Series>,
@@ -837,56 +1339,93 @@ pub struct LinePlot {
     #[rust]
     arrow_annotations: Vec<ArrowAnnotation>,
 
+    // Second y axis on the right and/or x axis along the top
+    #[rust]
+    twins: TwinAxes,
+
+    // The x and y axes again on the opposite spines, in other units
+    #[rust]
+    secondary_axes: SecondaryAxes,
+
+    // Y axis split into two ranges, replacing the y limits while set
+    #[rust]
+    y_break: Option<AxisBreak>,
+
+    // Zoomed views drawn inside the plot area
+    #[rust]
+    insets: Vec<InsetAxes>,
+
     #[rust]
     plot_area: PlotArea,
 
+    #[rust]
+    figure_rect: Rect,
+
+    #[rust]
+    annotation_rects: Vec<Rect>,  // Screen boxes of the text annotations, for hit-testing
+
+    #[rust]
+    drag: Option<DragState>,
+
+    // Legend entry under the finger and the one pressed, by entry index
+    #[rust]
+    hovered_entry: Option<usize>,
+
+    #[rust]
+    pressed_entry: Option<usize>,
+
     #[rust]
     x_range: (f64, f64),
 
     #[rust]
     y_range: (f64, f64),
 
-    #[rust]
+    // Fixed limits from the DSL or set_xlim/set_ylim, kept when the data is rescaled
+    #[live]
+    xlim: Option<DVec2>,
+
+    #[live]
+    ylim: Option<DVec2>,
+
+    #[live]
     title: String,
 
-    #[rust]
+    #[live]
     x_label: String,
 
-    #[rust]
+    #[live]
     y_label: String,
 
-    #[rust(true)]
+    #[live(true)]
     show_grid: bool,
 
-    #[rust(true)]
+    #[live(true)]
     show_points: bool,
 
-    #[rust(4.0)]
-    point_radius: f64,
-
-    #[rust(2.0)]
-    line_width: f64,
+    // Unset sizes come from the style
+    #[live]
+    point_radius: Option<f64>,
 
-    #[rust(50.0)]
-    left_margin: f64,
+    #[live]
+    line_width: Option<f64>,
 
-    #[rust(30.0)]
-    bottom_margin: f64,
+    #[live]
+    margins: Margins,
 
-    #[rust(20.0)]
-    right_margin: f64,
+    #[live]
+    x_scale: ScaleType,
 
-    #[rust(30.0)]
-    top_margin: f64,
+    #[live]
+    y_scale: ScaleType,
 
     #[rust]
-    legend_position: LegendPosition,
+    x_categories: CategoryAxis,
 
     #[rust]
-    x_scale: ScaleType,
+    x_tick_strategy: TickLabelStrategy,
 
     #[rust]
-    y_scale: ScaleType,
+    x_tick_layout: TickLabelLayout,
 
     // Pan/zoom state (disabled by default - enable with set_interactive(true))
     #[rust]
@@ -904,6 +1443,10 @@ pub struct LinePlot {
     #[rust]
     initial_y_range: (f64, f64),
 
+    // Set by a pan or zoom, so toggling legend entries keeps the user's view
+    #[rust]
+    view_moved: bool,
+
     // Reference lines and spans
     #[rust]
     vlines: Vec<VLine>,
@@ -916,23 +1459,51 @@ pub struct LinePlot {
 
     #[rust]
     hspans: Vec<HSpan>,
+
+    // Spines, tick marks and grid lines
+    #[rust]
+    spines: Spines,
+
+    #[rust]
+    tick_params: TickParams,
+
+    #[rust]
+    grid_style: GridStyle,
+
+    #[rust]
+    prop_cycle: Option<PropCycle>,
+
+    #[live]
+    plot_style: String,
+
+    // Background of the host theme, for the automatic light/dark style
+    #[live]
+    theme_background: Vec4,
+
+    #[rust]
+    style: PlotStyle,
+
+    #[rust]
+    style_override: Option<PlotStyle>,
 }
 
 impl Widget for LinePlot {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
-        if !self.interactive {
+        let hit = event.hits(cx, self.view.area());
+        if self.handle_legend(cx, &hit, scope) || self.handle_drag(cx, &hit, scope) || !self.interactive {
             return;
         }
 
         // Handle pan/zoom events
-        match event.hits(cx, self.view.area()) {
+        match hit {
             Hit::FingerDown(fe) => {
                 self.is_dragging = true;
                 self.drag_start = fe.abs;
                 self.initial_x_range = self.x_range;
                 self.initial_y_range = self.y_range;
+                self.twins.begin_pan();
             }
             Hit::FingerMove(fe) => {
                 if self.is_dragging && self.plot_area.width() > 0.0 && self.plot_area.height() > 0.0 {
@@ -956,6 +1527,8 @@ impl Widget for LinePlot {
                         self.initial_y_range.0 + dy_data,
                         self.initial_y_range.1 + dy_data,
                     );
+                    self.twins.pan(dvec2(dx_pixels, dy_pixels), &self.plot_area);
+                    self.view_moved = true;
 
                     self.redraw(cx);
                 }
@@ -994,6 +1567,8 @@ impl Widget for LinePlot {
                         data_y - rel_y * new_y_range,
                         data_y + (1.0 - rel_y) * new_y_range,
                     );
+                    self.twins.zoom(dvec2(rel_x, rel_y), zoom_factor);
+                    self.view_moved = true;
 
                     self.redraw(cx);
                 }
@@ -1010,16 +1585,27 @@ impl Widget for LinePlot {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        // Applied once per live update, so later set_legend calls still win
+        if let Some(position) = self.legend_position.take() {
+            self.set_legend(position);
+        }
+        self.style.apply(&mut self.view, &mut self.label);
+        self.legend.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
 
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 {
-            self.update_plot_area(rect);
+            self.figure_rect = rect;
+            self.update_plot_area(cx, rect);
             self.draw_grid(cx);
             self.draw_axes(cx);
             self.draw_series(cx);
+            self.draw_y_break(cx);
+            self.draw_insets(cx);
             self.draw_annotations(cx);
             self.draw_labels(cx);
+            self.draw_twins(cx);
             self.draw_legend(cx);
         }
 
@@ -1029,7 +1615,19 @@ impl Widget for LinePlot {
 
 impl LinePlot {
     /// Add a data series to the plot
-    pub fn add_series(&mut self, series: Series) {
+    pub fn add_series(&mut self, mut series: Series) {
+        if let Some(ref categories) = series.x_categories {
+            series.x = self.x_categories.positions(categories);
+            self.x_scale = ScaleType::Linear;
+        }
+        // Styles left at their defaults come from the property cycle
+        let index = self.series.len();
+        if series.line_style == LineStyle::Solid {
+            series.line_style = self.style.cycle(&self.prop_cycle).line_style(index).unwrap_or(series.line_style);
+        }
+        if series.marker_style == MarkerStyle::None {
+            series.marker_style = self.style.cycle(&self.prop_cycle).marker(index).unwrap_or(series.marker_style);
+        }
         self.series.push(series);
         self.auto_range();
     }
@@ -1037,6 +1635,90 @@ impl LinePlot {
     /// Clear all series
     pub fn clear(&mut self) {
         self.series.clear();
+        self.x_categories = CategoryAxis::default();
+        self.twins.iter_mut().for_each(TwinAxis::clear);
+    }
+
+    /// Clear the series and put the title, limits, scales, legend, margins, style and
+    /// everything drawn on top of the series back to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.fill_regions.clear();
+        self.annotations.clear();
+        self.arrow_annotations.clear();
+        self.vlines.clear();
+        self.hlines.clear();
+        self.vspans.clear();
+        self.hspans.clear();
+        self.insets.clear();
+        self.y_break = None;
+        self.twins = TwinAxes::default();
+        self.secondary_axes = SecondaryAxes::default();
+        self.xlim = None;
+        self.ylim = None;
+        self.x_scale = ScaleType::default();
+        self.y_scale = ScaleType::default();
+        self.view_moved = false;
+        self.title.clear();
+        self.x_label.clear();
+        self.y_label.clear();
+        self.margins = Margins::default();
+        self.legend.reset();
+        self.legend_position = None;
+        self.spines = Spines::default();
+        self.tick_params = TickParams::default();
+        self.grid_style = GridStyle::default();
+        self.prop_cycle = None;
+        self.style_override = None;
+    }
+
+    /// The plot as a "line" spec. Only data-space annotations are kept; twin and secondary
+    /// axes, fills, reference lines, spans, the y break and insets have no spec field.
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            x_label: self.x_label.clone(),
+            y_label: self.y_label.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            x_scale: scale_name(self.x_scale),
+            y_scale: scale_name(self.y_scale),
+            xlim: limits_spec(self.xlim),
+            ylim: limits_spec(self.ylim),
+            legend: legend_name(self.legend.position),
+            margins: margins_spec(&self.margins),
+            categories: self.x_categories.categories.clone(),
+            series: self.series.iter().map(SeriesSpec::from).collect(),
+            annotations: self.annotations.iter()
+                .filter(|a| a.coords == Coords::default())
+                .map(AnnotationSpec::from)
+                .collect(),
+            ..ChartSpec::new("line")
+        }
+    }
+
+    /// Use a categorical x axis with the given category order.
+    /// Series added with `Series::with_categories` are placed by name, including
+    /// those added before this call.
+    pub fn set_x_categories<S: Into<String>>(&mut self, categories: Vec<S>) {
+        self.x_categories = CategoryAxis::new(categories);
+        self.x_categories.place(&mut self.series);
+        for twin in self.twins.iter_mut().filter(|t| t.side == TwinSide::Right) {
+            self.x_categories.place(&mut twin.series);
+        }
+        self.x_scale = ScaleType::Linear;
+        if !self.series.is_empty() {
+            self.auto_range();
+        }
+    }
+
+    /// Choose how x tick labels are rotated, wrapped or thinned when they overlap
+    pub fn set_xtick_strategy(&mut self, strategy: TickLabelStrategy) {
+        self.x_tick_strategy = strategy;
+    }
+
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
     }
 
     /// Set plot title
@@ -1056,11 +1738,13 @@ impl LinePlot {
 
     /// Set X range manually
     pub fn set_xlim(&mut self, min: f64, max: f64) {
+        self.xlim = Some(dvec2(min, max));
         self.x_range = (min, max);
     }
 
     /// Set Y range manually
     pub fn set_ylim(&mut self, min: f64, max: f64) {
+        self.ylim = Some(dvec2(min, max));
         self.y_range = (min, max);
     }
 
@@ -1071,66 +1755,90 @@ impl LinePlot {
 
     /// Set line width
     pub fn set_line_width(&mut self, width: f64) {
-        self.line_width = width;
+        self.line_width = Some(width);
     }
 
     /// Add a filled region between y1 and y2 values at each x
     /// Similar to matplotlib's fill_between
-    pub fn fill_between(&mut self, x: Vec<f64>, y1: Vec<f64>, y2: Vec<f64>, color: Vec4) {
-        self.fill_regions.push(FillRegion { x, y1, y2, color });
+    pub fn fill_between(&mut self, x: impl IntoValues, y1: impl IntoValues, y2: impl IntoValues, color: Vec4) {
+        self.fill_between_labeled(x, y1, y2, color, "");
+    }
+
+    /// Add a filled region with a legend entry
+    pub fn fill_between_labeled(&mut self, x: impl IntoValues, y1: impl IntoValues, y2: impl IntoValues, color: Vec4, label: impl Into<String>) {
+        let (x, y1, y2) = (x.into_values().into_owned(), y1.into_values().into_owned(), y2.into_values().into_owned());
+        self.fill_regions.push(FillRegion { x, y1, y2, color, label: label.into(), visible: true });
         self.auto_range();
     }
 
     /// Add a filled region between a curve and a constant baseline
-    pub fn fill_between_baseline(&mut self, x: Vec<f64>, y: Vec<f64>, baseline: f64, color: Vec4) {
+    pub fn fill_between_baseline(&mut self, x: impl IntoValues, y: impl IntoValues, baseline: f64, color: Vec4) {
+        let (x, y) = (x.into_values().into_owned(), y.into_values().into_owned());
         let y2 = vec![baseline; x.len()];
-        self.fill_regions.push(FillRegion { x, y1: y, y2, color });
+        self.fill_regions.push(FillRegion { x, y1: y, y2, color, label: String::new(), visible: true });
         self.auto_range();
     }
 
     /// Add a text annotation at a specific data coordinate
     /// Add a plain text annotation at a specific data coordinate
     pub fn annotate(&mut self, text: impl Into<String>, x: f64, y: f64, color: Vec4, font_size: f64) {
-        self.annotations.push(TextAnnotation {
-            text: text.into(),
-            x,
-            y,
-            color,
-            font_size,
-            is_math: false,
-        });
+        self.annotations.push(TextAnnotation::new(text, x, y).with_color(color).with_font_size(font_size));
     }
 
     /// Add a LaTeX math annotation at a specific data coordinate
     pub fn annotate_math(&mut self, latex: impl Into<String>, x: f64, y: f64, color: Vec4, font_size: f64) {
-        self.annotations.push(TextAnnotation {
-            text: latex.into(),
-            x,
-            y,
-            color,
-            font_size,
-            is_math: true,
-        });
+        self.annotations.push(TextAnnotation::new(latex, x, y).math().with_color(color).with_font_size(font_size));
+    }
+
+    /// Add a plain text annotation rotated by `degrees` (counter-clockwise)
+    pub fn annotate_rotated(&mut self, text: impl Into<String>, x: f64, y: f64, color: Vec4, font_size: f64, degrees: f64) {
+        self.annotations.push(TextAnnotation::new(text, x, y).with_color(color).with_font_size(font_size).with_rotation(degrees));
+    }
+
+    /// Add a plain text annotation that follows the data-space direction (dx, dy),
+    /// e.g. the slope of a line. The angle is recomputed when the axes change.
+    pub fn annotate_along(&mut self, text: impl Into<String>, x: f64, y: f64, direction: (f64, f64), color: Vec4, font_size: f64) {
+        self.annotations.push(
+            TextAnnotation::new(text, x, y)
+                .with_color(color)
+                .with_font_size(font_size)
+                .with_slope(direction.0, direction.1),
+        );
+    }
+
+    /// Add a styled annotation, e.g. with a background box, bold text or several lines
+    pub fn add_annotation(&mut self, annotation: TextAnnotation) {
+        self.annotations.push(annotation);
     }
 
     /// Add a vertical line at x position (like matplotlib axvline)
     pub fn axvline(&mut self, x: f64, color: Vec4, line_width: f64, line_style: LineStyle) {
-        self.vlines.push(VLine { x, color, line_width, line_style });
+        self.vlines.push(VLine { x, color, line_width, line_style, draggable: false });
     }
 
     /// Add a horizontal line at y position (like matplotlib axhline)
     pub fn axhline(&mut self, y: f64, color: Vec4, line_width: f64, line_style: LineStyle) {
-        self.hlines.push(HLine { y, color, line_width, line_style });
+        self.hlines.push(HLine { y, color, line_width, line_style, draggable: false });
     }
 
     /// Add a vertical shaded span between x1 and x2 (like matplotlib axvspan)
     pub fn axvspan(&mut self, x1: f64, x2: f64, color: Vec4) {
-        self.vspans.push(VSpan { x1, x2, color });
+        self.axvspan_labeled(x1, x2, color, "");
+    }
+
+    /// Add a vertical span with a legend entry
+    pub fn axvspan_labeled(&mut self, x1: f64, x2: f64, color: Vec4, label: impl Into<String>) {
+        self.vspans.push(VSpan { x1, x2, color, draggable: false, label: label.into(), visible: true });
     }
 
     /// Add a horizontal shaded span between y1 and y2 (like matplotlib axhspan)
     pub fn axhspan(&mut self, y1: f64, y2: f64, color: Vec4) {
-        self.hspans.push(HSpan { y1, y2, color });
+        self.axhspan_labeled(y1, y2, color, "");
+    }
+
+    /// Add a horizontal span with a legend entry
+    pub fn axhspan_labeled(&mut self, y1: f64, y2: f64, color: Vec4, label: impl Into<String>) {
+        self.hspans.push(HSpan { y1, y2, color, draggable: false, label: label.into(), visible: true });
     }
 
     /// Add an arrow annotation (like matplotlib annotate with arrow)
@@ -1142,25 +1850,9 @@ impl LinePlot {
     pub fn annotate_with_arrow(&mut self, text: impl Into<String>, text_x: f64, text_y: f64,
                                 point_x: f64, point_y: f64, color: Vec4) {
         // Add text annotation
-        self.annotations.push(TextAnnotation {
-            text: text.into(),
-            x: text_x,
-            y: text_y,
-            color,
-            font_size: 12.0,
-            is_math: false,
-        });
+        self.annotations.push(TextAnnotation::new(text, text_x, text_y).with_color(color));
         // Add arrow from text to point
-        self.arrow_annotations.push(ArrowAnnotation {
-            start_x: text_x,
-            start_y: text_y,
-            end_x: point_x,
-            end_y: point_y,
-            color,
-            line_width: 1.5,
-            head_size: 8.0,
-            text: None,
-        });
+        self.arrow_annotations.push(ArrowAnnotation::new(text_x, text_y, point_x, point_y).with_color(color));
     }
 
     /// Clear all reference lines and spans
@@ -1171,6 +1863,302 @@ impl LinePlot {
         self.hlines.clear();
         self.vspans.clear();
         self.hspans.clear();
+        self.drag = None;
+    }
+
+    /// Let the user drag a reference line, the edges of a span or an annotation.
+    /// Each move emits a `LinePlotAction` with the new value.
+    pub fn set_draggable(&mut self, item: Draggable, draggable: bool) {
+        match item {
+            Draggable::VLine(i) => if let Some(l) = self.vlines.get_mut(i) { l.draggable = draggable },
+            Draggable::HLine(i) => if let Some(l) = self.hlines.get_mut(i) { l.draggable = draggable },
+            Draggable::VSpan(i) => if let Some(s) = self.vspans.get_mut(i) { s.draggable = draggable },
+            Draggable::HSpan(i) => if let Some(s) = self.hspans.get_mut(i) { s.draggable = draggable },
+            Draggable::Annotation(i) => if let Some(a) = self.annotations.get_mut(i) { a.draggable = draggable },
+        }
+    }
+
+    /// Current x of a vertical reference line
+    pub fn vline_x(&self, index: usize) -> Option<f64> {
+        self.vlines.get(index).map(|l| l.x)
+    }
+
+    /// Current y of a horizontal reference line
+    pub fn hline_y(&self, index: usize) -> Option<f64> {
+        self.hlines.get(index).map(|l| l.y)
+    }
+
+    /// Current (x1, x2) of a vertical span
+    pub fn vspan_range(&self, index: usize) -> Option<(f64, f64)> {
+        self.vspans.get(index).map(|s| (s.x1, s.x2))
+    }
+
+    /// Current (y1, y2) of a horizontal span
+    pub fn hspan_range(&self, index: usize) -> Option<(f64, f64)> {
+        self.hspans.get(index).map(|s| (s.y1, s.y2))
+    }
+
+    /// Current position of a text annotation, in its own coordinate systems
+    pub fn annotation_position(&self, index: usize) -> Option<(f64, f64)> {
+        self.annotations.get(index).map(|a| (a.x, a.y))
+    }
+
+    /// Show or hide a series; hidden series are left out of auto-ranging. The axes are
+    /// refitted unless the view has been panned or zoomed since the last reset.
+    pub fn set_series_visible(&mut self, index: usize, visible: bool) {
+        if let Some(series) = self.series.get_mut(index) {
+            series.visible = visible;
+            if !self.view_moved {
+                self.auto_range();
+            }
+        }
+    }
+
+    pub fn is_series_visible(&self, index: usize) -> bool {
+        self.series.get(index).is_some_and(|s| s.visible)
+    }
+
+    /// Colours, line styles and markers handed out in turn to series that don't set their own.
+    /// Line styles and markers are assigned when a series is added, so set the cycle first.
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    /// Legend entry under `p`
+    fn legend_entry_at(&self, p: DVec2) -> Option<usize> {
+        self.legend.entry_at(p).filter(|&index| index < self.legend_items().len())
+    }
+
+    /// What each legend entry stands for, in the order legend_entries lists them
+    fn legend_items(&self) -> Vec<LegendItem> {
+        let mut items: Vec<LegendItem> = (0..self.series.len()).map(LegendItem::Series).collect();
+        for twin in self.twins.iter() {
+            items.extend((0..twin.series.len()).map(|i| LegendItem::TwinSeries(twin.side, i)));
+        }
+        // Fills and spans only get an entry when they were given a label
+        let fills = self.fill_regions.iter().enumerate().map(|(i, f)| (LegendItem::Fill(i), &f.label));
+        let vspans = self.vspans.iter().enumerate().map(|(i, s)| (LegendItem::VSpan(i), &s.label));
+        let hspans = self.hspans.iter().enumerate().map(|(i, s)| (LegendItem::HSpan(i), &s.label));
+        items.extend(fills.chain(vspans).chain(hspans).filter(|(_, label)| !label.is_empty()).map(|(item, _)| item));
+        items
+    }
+
+    /// Flip the visibility of a legend item, returning whether it is now shown
+    fn toggle_legend_item(&mut self, item: LegendItem) -> bool {
+        match item {
+            LegendItem::Series(index) => {
+                let visible = !self.is_series_visible(index);
+                self.set_series_visible(index, visible);
+                visible
+            }
+            LegendItem::TwinSeries(side, index) => {
+                let Some(twin) = self.twins.get_mut(side) else { return false };
+                let Some(series) = twin.series.get_mut(index) else { return false };
+                series.visible = !series.visible;
+                let visible = series.visible;
+                if !self.view_moved {
+                    twin.auto_range();
+                }
+                visible
+            }
+            LegendItem::Fill(index) => {
+                let Some(fill) = self.fill_regions.get_mut(index) else { return false };
+                fill.visible = !fill.visible;
+                let visible = fill.visible;
+                if !self.view_moved {
+                    self.auto_range();
+                }
+                visible
+            }
+            LegendItem::VSpan(index) => self.vspans.get_mut(index).is_some_and(|s| {
+                s.visible = !s.visible;
+                s.visible
+            }),
+            LegendItem::HSpan(index) => self.hspans.get_mut(index).is_some_and(|s| {
+                s.visible = !s.visible;
+                s.visible
+            }),
+        }
+    }
+
+    /// Host series emphasised by hovering its legend entry
+    fn hovered_series(&self) -> Option<usize> {
+        match self.legend_items().get(self.hovered_entry?) {
+            Some(LegendItem::Series(index)) => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Hover emphasis and click-to-toggle on legend entries. Returns true if the event was used.
+    fn handle_legend(&mut self, cx: &mut Cx, hit: &Hit, scope: &mut Scope) -> bool {
+        match hit {
+            Hit::FingerHoverIn(fe) | Hit::FingerHoverOver(fe) => {
+                let entry = self.legend_entry_at(fe.abs);
+                if entry != self.hovered_entry {
+                    self.hovered_entry = entry;
+                    self.redraw(cx);
+                }
+                if entry.is_some() {
+                    cx.set_cursor(MouseCursor::Hand);
+                }
+                entry.is_some()
+            }
+            Hit::FingerHoverOut(_) => {
+                if self.hovered_entry.take().is_some() {
+                    self.redraw(cx);
+                }
+                false
+            }
+            Hit::FingerDown(fe) => {
+                self.pressed_entry = self.legend_entry_at(fe.abs);
+                self.pressed_entry.is_some()
+            }
+            Hit::FingerUp(fe) => {
+                let Some(entry) = self.pressed_entry.take() else {
+                    return false;
+                };
+                if self.legend_entry_at(fe.abs) == Some(entry) {
+                    if let Some(item) = self.legend_items().get(entry).copied() {
+                        let visible = self.toggle_legend_item(item);
+                        let action = match item {
+                            LegendItem::Series(index) => LinePlotAction::SeriesToggled { index, visible },
+                            item => LinePlotAction::LegendItemToggled { item, visible },
+                        };
+                        cx.widget_action(self.widget_uid(), &scope.path, action);
+                        self.redraw(cx);
+                    }
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Topmost draggable element under `p`
+    fn drag_target_at(&self, p: DVec2) -> Option<DragState> {
+        let area = &self.plot_area;
+        let origin = dvec2(area.left, area.bottom);
+
+        // Annotations are drawn on top, so they win
+        for (i, rect) in self.annotation_rects.iter().enumerate().rev() {
+            match self.annotations.get(i) {
+                Some(ann) if ann.draggable && rect.contains(p) => {
+                    let anchor = self.resolve_position(ann.x, ann.y, ann.coords, origin);
+                    return Some(DragState { item: Draggable::Annotation(i), edge: 0, grab: p - anchor });
+                }
+                _ => {}
+            }
+        }
+
+        let in_x = p.x >= area.left - DRAG_TOLERANCE && p.x <= area.right + DRAG_TOLERANCE;
+        let in_y = p.y >= area.top - DRAG_TOLERANCE && p.y <= area.bottom + DRAG_TOLERANCE;
+        let near_x = |x: f64| in_y && (self.data_to_pixel(x, self.y_range.0).x - p.x).abs() <= DRAG_TOLERANCE;
+        let near_y = |y: f64| in_x && (self.data_to_pixel(self.x_range.0, y).y - p.y).abs() <= DRAG_TOLERANCE;
+        let hit = |item, edge| Some(DragState { item, edge, grab: DVec2::default() });
+
+        for (i, l) in self.vlines.iter().enumerate().rev() {
+            if l.draggable && near_x(l.x) {
+                return hit(Draggable::VLine(i), 0);
+            }
+        }
+        for (i, l) in self.hlines.iter().enumerate().rev() {
+            if l.draggable && near_y(l.y) {
+                return hit(Draggable::HLine(i), 0);
+            }
+        }
+        for (i, s) in self.vspans.iter().enumerate().rev().filter(|(_, s)| s.visible) {
+            if s.draggable && near_x(s.x1) {
+                return hit(Draggable::VSpan(i), 0);
+            }
+            if s.draggable && near_x(s.x2) {
+                return hit(Draggable::VSpan(i), 1);
+            }
+        }
+        for (i, s) in self.hspans.iter().enumerate().rev().filter(|(_, s)| s.visible) {
+            if s.draggable && near_y(s.y1) {
+                return hit(Draggable::HSpan(i), 0);
+            }
+            if s.draggable && near_y(s.y2) {
+                return hit(Draggable::HSpan(i), 1);
+            }
+        }
+        None
+    }
+
+    /// Move the dragged element to the finger at `p`
+    fn apply_drag(&mut self, drag: DragState, p: DVec2) -> Option<LinePlotAction> {
+        let (x, y) = self.pixel_to_data(p);
+        match drag.item {
+            Draggable::VLine(index) => {
+                self.vlines.get_mut(index)?.x = x;
+                Some(LinePlotAction::VLineMoved { index, x })
+            }
+            Draggable::HLine(index) => {
+                self.hlines.get_mut(index)?.y = y;
+                Some(LinePlotAction::HLineMoved { index, y })
+            }
+            Draggable::VSpan(index) => {
+                let span = self.vspans.get_mut(index)?;
+                if drag.edge == 0 { span.x1 = x } else { span.x2 = x }
+                Some(LinePlotAction::VSpanMoved { index, x1: span.x1, x2: span.x2 })
+            }
+            Draggable::HSpan(index) => {
+                let span = self.hspans.get_mut(index)?;
+                if drag.edge == 0 { span.y1 = y } else { span.y2 = y }
+                Some(LinePlotAction::HSpanMoved { index, y1: span.y1, y2: span.y2 })
+            }
+            Draggable::Annotation(index) => {
+                let coords = self.annotations.get(index)?.coords;
+                let origin = dvec2(self.plot_area.left, self.plot_area.bottom);
+                let (x, y) = self.unresolve_position(p - drag.grab, coords, origin);
+                let ann = &mut self.annotations[index];
+                ann.x = x;
+                ann.y = y;
+                Some(LinePlotAction::AnnotationMoved { index, x, y })
+            }
+        }
+    }
+
+    /// Hit-test, move and report draggable elements. Returns true if the event was used.
+    fn handle_drag(&mut self, cx: &mut Cx, hit: &Hit, scope: &mut Scope) -> bool {
+        match hit {
+            Hit::FingerHoverIn(fe) | Hit::FingerHoverOver(fe) => {
+                if self.drag.is_some() {
+                    return true;
+                }
+                match self.drag_target_at(fe.abs) {
+                    Some(target) => {
+                        cx.set_cursor(target.item.cursor());
+                        true
+                    }
+                    None => {
+                        cx.set_cursor(if self.interactive { MouseCursor::Move } else { MouseCursor::Default });
+                        false
+                    }
+                }
+            }
+            Hit::FingerDown(fe) => {
+                self.drag = self.drag_target_at(fe.abs);
+                self.drag.is_some()
+            }
+            Hit::FingerMove(fe) => {
+                let Some(drag) = self.drag else {
+                    return false;
+                };
+                if let Some(action) = self.apply_drag(drag, fe.abs) {
+                    cx.widget_action(self.widget_uid(), &scope.path, action);
+                    self.redraw(cx);
+                }
+                true
+            }
+            Hit::FingerUp(_) => self.drag.take().is_some(),
+            _ => false,
+        }
     }
 
     fn auto_range(&mut self) {
@@ -1179,7 +2167,7 @@ impl LinePlot {
         let mut y_min = f64::MAX;
         let mut y_max = f64::MIN;
 
-        for s in &self.series {
+        for s in self.series.iter().filter(|s| s.visible) {
             for &x in &s.x {
                 x_min = x_min.min(x);
                 x_max = x_max.max(x);
@@ -1191,7 +2179,7 @@ impl LinePlot {
         }
 
         // Include fill_regions in auto_range
-        for fr in &self.fill_regions {
+        for fr in self.fill_regions.iter().filter(|f| f.visible) {
             for &x in &fr.x {
                 x_min = x_min.min(x);
                 x_max = x_max.max(x);
@@ -1206,45 +2194,83 @@ impl LinePlot {
             }
         }
 
+        // Nothing visible, e.g. every series toggled off in the legend: keep the current
+        // range, or (0, 1) before there is one
+        let keep = |range: (f64, f64)| if range.0 < range.1 { range } else { (0.0, 1.0) };
+
         // Apply scale-aware padding
-        match self.x_scale {
-            ScaleType::Log => {
-                // For log scale, use multiplicative padding
-                if x_min > 0.0 && x_max > 0.0 {
-                    self.x_range = (x_min / 1.5, x_max * 1.5);
-                } else {
-                    self.x_range = (x_min, x_max);
+        if !self.x_categories.is_empty() {
+            self.x_range = self.x_categories.range();
+        } else if x_min > x_max {
+            self.x_range = keep(self.x_range);
+        } else {
+            match self.x_scale {
+                ScaleType::Log => {
+                    // For log scale, use multiplicative padding
+                    if x_min > 0.0 && x_max > 0.0 {
+                        self.x_range = (x_min / 1.5, x_max * 1.5);
+                    } else {
+                        self.x_range = (x_min, x_max);
+                    }
+                }
+                _ => {
+                    let x_pad = (x_max - x_min) * 0.05;
+                    self.x_range = (x_min - x_pad, x_max + x_pad);
                 }
-            }
-            _ => {
-                let x_pad = (x_max - x_min) * 0.05;
-                self.x_range = (x_min - x_pad, x_max + x_pad);
             }
         }
 
-        match self.y_scale {
-            ScaleType::Log => {
-                // For log scale, use multiplicative padding
-                if y_min > 0.0 && y_max > 0.0 {
-                    self.y_range = (y_min / 1.5, y_max * 1.5);
-                } else {
-                    self.y_range = (y_min, y_max);
+        if y_min > y_max {
+            self.y_range = keep(self.y_range);
+        } else {
+            match self.y_scale {
+                ScaleType::Log => {
+                    // For log scale, use multiplicative padding
+                    if y_min > 0.0 && y_max > 0.0 {
+                        self.y_range = (y_min / 1.5, y_max * 1.5);
+                    } else {
+                        self.y_range = (y_min, y_max);
+                    }
+                }
+                _ => {
+                    let y_pad = (y_max - y_min) * 0.05;
+                    self.y_range = (y_min - y_pad, y_max + y_pad);
                 }
             }
-            _ => {
-                let y_pad = (y_max - y_min) * 0.05;
-                self.y_range = (y_min - y_pad, y_max + y_pad);
-            }
+        }
+
+        // Fixed limits win over the data range
+        if let Some(lim) = self.xlim {
+            self.x_range = (lim.x, lim.y);
+        }
+        if let Some(lim) = self.ylim {
+            self.y_range = (lim.x, lim.y);
         }
     }
 
-    fn update_plot_area(&mut self, rect: Rect) {
+    fn update_plot_area(&mut self, cx: &mut Cx2d, rect: Rect) {
+        // Tight layout: derive the plot area from the measured text around the axes
+        let y_ticks = self.y_tick_labels();
+        let entries = self.legend_entries();
+        self.legend.set_entries(entries);
+        let (legend_right, legend_bottom) = self.legend.outside_margins(cx);
+        let (side_right, side_top) = self.side_axes_margins(cx);
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let text = AxesText { title: &self.title, x_label: &self.x_label, y_label: &self.y_label, y_ticks: &y_ticks, title_size: Some(self.style.title_font_size), top_axis: side_top, x_tick_extent, y_tick_extent };
+        let (left, right) = self.margins.columns(cx, &mut self.label, &text, legend_right + side_right);
         self.plot_area = PlotArea::new(
-            rect.pos.x + self.left_margin,
-            rect.pos.y + self.top_margin,
-            rect.pos.x + rect.size.x - self.right_margin,
-            rect.pos.y + rect.size.y - self.bottom_margin,
+            rect.pos.x + left,
+            rect.pos.y,
+            rect.pos.x + rect.size.x - right,
+            rect.pos.y + rect.size.y,
         );
+
+        // X tick labels depend on the final width, the bottom margin on their layout
+        let x_ticks = self.x_tick_labels();
+        self.x_tick_layout = TickLabelLayout::compute(cx, &mut self.label, x_ticks, self.x_tick_strategy);
+        let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &self.x_tick_layout);
+        self.plot_area.top = rect.pos.y + top;
+        self.plot_area.bottom = rect.pos.y + rect.size.y - bottom - legend_bottom;
     }
 
     fn data_to_pixel(&self, x: f64, y: f64) -> DVec2 {
@@ -1257,51 +2283,123 @@ impl LinePlot {
         let ty_max = self.y_scale.transform(self.y_range.1);
 
         let px = self.plot_area.left + (tx - tx_min) / (tx_max - tx_min) * self.plot_area.width();
-        let py = self.plot_area.bottom - (ty - ty_min) / (ty_max - ty_min) * self.plot_area.height();
+        let py = match self.y_break {
+            Some(ref axis_break) => axis_break.pixel_y(y, self.y_scale, &self.plot_area),
+            None => self.plot_area.bottom - (ty - ty_min) / (ty_max - ty_min) * self.plot_area.height(),
+        };
         dvec2(px, py)
     }
 
-    fn draw_grid(&mut self, cx: &mut Cx2d) {
-        if !self.show_grid {
-            return;
-        }
+    /// Pixel position of (x, y) with each component in its own coordinate system.
+    /// `reference` is the origin of `OffsetPixels` components.
+    fn resolve_position(&self, x: f64, y: f64, coords: Coords, reference: DVec2) -> DVec2 {
+        let data = self.data_to_pixel(x, y);
+        let area = &self.plot_area;
+        let figure = self.figure_rect;
+        let px = match coords.x {
+            CoordSpace::Data => data.x,
+            CoordSpace::AxesFraction => area.left + x * area.width(),
+            CoordSpace::FigureFraction => figure.pos.x + x * figure.size.x,
+            CoordSpace::OffsetPixels => reference.x + x,
+        };
+        let py = match coords.y {
+            CoordSpace::Data => data.y,
+            CoordSpace::AxesFraction => area.bottom - y * area.height(),
+            CoordSpace::FigureFraction => figure.pos.y + (1.0 - y) * figure.size.y,
+            CoordSpace::OffsetPixels => reference.y - y,
+        };
+        dvec2(px, py)
+    }
 
-        self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
+    /// Data coordinates of a pixel position, the inverse of data_to_pixel
+    fn pixel_to_data(&self, p: DVec2) -> (f64, f64) {
+        let tx_min = self.x_scale.transform(self.x_range.0);
+        let tx_max = self.x_scale.transform(self.x_range.1);
+        let ty_min = self.y_scale.transform(self.y_range.0);
+        let ty_max = self.y_scale.transform(self.y_range.1);
 
-        // Horizontal grid lines - use scale-aware tick generation
-        let y_ticks = self.y_scale.generate_ticks(self.y_range.0, self.y_range.1, 5);
-        for y in &y_ticks {
-            let p1 = self.data_to_pixel(self.x_range.0, *y);
-            let p2 = self.data_to_pixel(self.x_range.1, *y);
-            self.draw_line.draw_line(cx, p1, p2, 0.5);
-        }
+        let tx = tx_min + (p.x - self.plot_area.left) / self.plot_area.width() * (tx_max - tx_min);
+        let ty = ty_min + (self.plot_area.bottom - p.y) / self.plot_area.height() * (ty_max - ty_min);
+        let y = match self.y_break {
+            Some(ref axis_break) => axis_break.value_at(p.y, self.y_scale, &self.plot_area),
+            None => self.y_scale.inverse(ty),
+        };
+        (self.x_scale.inverse(tx), y)
+    }
+
+    /// Position of pixel `p` in the given coordinate systems, the inverse of resolve_position
+    fn unresolve_position(&self, p: DVec2, coords: Coords, reference: DVec2) -> (f64, f64) {
+        let data = self.pixel_to_data(p);
+        let area = &self.plot_area;
+        let figure = self.figure_rect;
+        let x = match coords.x {
+            CoordSpace::Data => data.0,
+            CoordSpace::AxesFraction => (p.x - area.left) / area.width(),
+            CoordSpace::FigureFraction => (p.x - figure.pos.x) / figure.size.x,
+            CoordSpace::OffsetPixels => p.x - reference.x,
+        };
+        let y = match coords.y {
+            CoordSpace::Data => data.1,
+            CoordSpace::AxesFraction => (area.bottom - p.y) / area.height(),
+            CoordSpace::FigureFraction => 1.0 - (p.y - figure.pos.y) / figure.size.y,
+            CoordSpace::OffsetPixels => reference.y - p.y,
+        };
+        (x, y)
+    }
 
-        // Vertical grid lines - use scale-aware tick generation
-        let x_ticks = self.x_scale.generate_ticks(self.x_range.0, self.x_range.1, 5);
-        for x in &x_ticks {
-            let p1 = self.data_to_pixel(*x, self.y_range.0);
-            let p2 = self.data_to_pixel(*x, self.y_range.1);
-            self.draw_line.draw_line(cx, p1, p2, 0.5);
-        }
+    /// Major and minor tick positions in pixels: x, y, x minor, y minor
+    fn frame_ticks(&self) -> [Vec<f64>; 4] {
+        let x_major = self.x_ticks();
+        let y_major = self.y_ticks();
+        // Category slots and the two ranges of a broken axis get no minor ticks
+        let x_minor = if self.x_categories.is_empty() { minor_ticks(self.x_scale, &x_major, self.x_range) } else { Vec::new() };
+        let y_minor = if self.y_break.is_none() { minor_ticks(self.y_scale, &y_major, self.y_range) } else { Vec::new() };
+        let to_x = |xs: Vec<f64>| -> Vec<f64> { xs.into_iter().map(|x| self.data_to_pixel(x, self.y_range.0).x).collect() };
+        let to_y = |ys: Vec<f64>| -> Vec<f64> { ys.into_iter().map(|y| self.data_to_pixel(self.x_range.0, y).y).collect() };
+        [to_x(x_major), to_y(y_major), to_x(x_minor), to_y(y_minor)]
     }
 
-    fn draw_axes(&mut self, cx: &mut Cx2d) {
-        self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+    /// Pixel x of the left spine or pixel y of the bottom one, where the tick labels hang from
+    fn spine_offset(&self, side: SpineSide) -> f64 {
+        self.spines.get(side).offset(side, &self.plot_area, AxisRange::new(self.x_range, self.x_scale), AxisRange::new(self.y_range, self.y_scale))
+    }
 
-        // X axis
-        let x1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let x2 = dvec2(self.plot_area.right, self.plot_area.bottom);
-        self.draw_line.draw_line(cx, x1, x2, 1.0);
+    fn draw_grid(&mut self, cx: &mut Cx2d) {
+        if !self.show_grid {
+            return;
+        }
+        let [x_ticks, y_ticks, x_minor, y_minor] = self.frame_ticks();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x: AxisRange::new(self.x_range, self.x_scale),
+            y: AxisRange::new(self.y_range, self.y_scale),
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.grid_style.draw(cx, &frame, &mut self.draw_line);
+    }
 
-        // Y axis
-        let y1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let y2 = dvec2(self.plot_area.left, self.plot_area.top);
-        self.draw_line.draw_line(cx, y1, y2, 1.0);
+    fn draw_axes(&mut self, cx: &mut Cx2d) {
+        let [x_ticks, y_ticks, x_minor, y_minor] = self.frame_ticks();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x: AxisRange::new(self.x_range, self.x_scale),
+            y: AxisRange::new(self.y_range, self.y_scale),
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.spines.draw(cx, &frame, &self.tick_params, &mut self.draw_line);
     }
 
     fn draw_series(&mut self, cx: &mut Cx2d) {
         // 1. Draw horizontal spans (hspans) - background layer
-        for hs in &self.hspans {
+        for hs in self.hspans.iter().filter(|s| s.visible) {
             self.draw_fill.color = hs.color;
             let p1 = self.data_to_pixel(self.x_range.0, hs.y1);
             let p2 = self.data_to_pixel(self.x_range.1, hs.y2);
@@ -1311,7 +2409,7 @@ impl LinePlot {
         }
 
         // 2. Draw vertical spans (vspans) - background layer
-        for vs in &self.vspans {
+        for vs in self.vspans.iter().filter(|s| s.visible) {
             self.draw_fill.color = vs.color;
             let p1 = self.data_to_pixel(vs.x1, self.y_range.0);
             let p2 = self.data_to_pixel(vs.x2, self.y_range.1);
@@ -1321,7 +2419,7 @@ impl LinePlot {
         }
 
         // 3. Draw fill regions (fill_between)
-        for fr in &self.fill_regions {
+        for fr in self.fill_regions.iter().filter(|f| f.visible) {
             self.draw_fill.color = fr.color;
             if fr.x.len() >= 2 {
                 for i in 0..fr.x.len() - 1 {
@@ -1381,10 +2479,21 @@ impl LinePlot {
         }
 
         // 6. Draw data series
+        let hovered = self.hovered_series();
         for (idx, series) in self.series.iter().enumerate() {
-            let color = series.color.unwrap_or_else(|| get_color(idx));
-            let line_width = series.line_width.unwrap_or(self.line_width);
-            let marker_size = series.marker_size.unwrap_or(self.point_radius);
+            if !series.visible {
+                continue;
+            }
+            let mut color = series.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
+            let mut line_width = series.line_width.or(self.line_width).unwrap_or(self.style.line_width);
+            let marker_size = series.marker_size.or(self.point_radius).unwrap_or(self.style.marker_size);
+
+            // Hovering a legend entry emphasises its series and dims the others
+            match hovered {
+                Some(hovered) if hovered == idx => line_width *= 1.5,
+                Some(_) => color.w *= LEGEND_DIM_ALPHA,
+                None => {}
+            }
 
             self.draw_line.color = color;
             self.draw_point.color = color;
@@ -1522,35 +2631,82 @@ impl LinePlot {
         }
     }
 
-    fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+    fn y_ticks(&self) -> Vec<f64> {
+        match self.y_break {
+            Some(ref axis_break) => axis_break.ticks(self.y_scale),
+            None => self.y_scale.generate_ticks(self.y_range.0, self.y_range.1, 5),
+        }
+    }
 
-        // X axis tick labels - use scale-aware tick generation and formatting
-        let x_ticks = self.x_scale.generate_ticks(self.x_range.0, self.x_range.1, 5);
-        for x in &x_ticks {
-            let p = self.data_to_pixel(*x, self.y_range.0);
-            let label = self.x_scale.format_tick(*x);
-            self.label.draw_at(cx, dvec2(p.x, p.y + 5.0), &label, TextAnchor::TopCenter);
+    fn x_ticks(&self) -> Vec<f64> {
+        if self.x_categories.is_empty() {
+            self.x_scale.generate_ticks(self.x_range.0, self.x_range.1, 5)
+        } else {
+            self.x_categories.ticks(self.x_range.0, self.x_range.1)
         }
+    }
+
+    /// Pixel x position and text of each x tick label
+    fn x_tick_labels(&self) -> Vec<(f64, String)> {
+        self.x_ticks().iter().filter_map(|&x| {
+            let text = if self.x_categories.is_empty() {
+                Some(self.x_scale.format_tick(x))
+            } else {
+                // Category labels sit centred under each slot
+                self.x_categories.label(x).map(|name| name.to_string())
+            };
+            text.map(|t| (self.data_to_pixel(x, self.y_range.0).x, t))
+        }).collect()
+    }
+
+    fn y_tick_labels(&self) -> Vec<String> {
+        self.y_ticks().iter().map(|y| self.y_scale.format_tick(*y)).collect()
+    }
+
+    /// Reach of the tick marks past the bottom and the left spine
+    fn tick_extents(&self) -> (f64, f64) {
+        (
+            self.tick_params.outward_extent(SpineSide::Bottom, &self.style),
+            self.tick_params.outward_extent(SpineSide::Left, &self.style),
+        )
+    }
+
+    fn draw_labels(&mut self, cx: &mut Cx2d) {
+        self.label.set_color(self.style.text_color);
+
+        // X axis tick labels - laid out by update_plot_area, hung below the tick marks
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let axis_y = self.spine_offset(SpineSide::Bottom);
+        self.x_tick_layout.draw(cx, &mut self.label, axis_y + x_tick_extent);
 
         // Y axis tick labels - use scale-aware tick generation and formatting
-        let y_ticks = self.y_scale.generate_ticks(self.y_range.0, self.y_range.1, 5);
+        let y_ticks = self.y_ticks();
+        let axis_x = self.spine_offset(SpineSide::Left);
         for y in &y_ticks {
             let p = self.data_to_pixel(self.x_range.0, *y);
             let label = self.y_scale.format_tick(*y);
-            self.label.draw_at(cx, dvec2(p.x - 5.0, p.y), &label, TextAnchor::MiddleRight);
+            self.label.draw_at(cx, dvec2(axis_x - y_tick_extent - TICK_LABEL_PAD, p.y), &label, TextAnchor::MiddleRight);
         }
 
-        // Title
-        if !self.title.is_empty() {
-            let center_x = (self.plot_area.left + self.plot_area.right) / 2.0;
-            self.label.draw_at(cx, dvec2(center_x, self.plot_area.top - 10.0), &self.title, TextAnchor::BottomCenter);
-        }
+        // Title and axis labels
+        let y_tick_labels = self.y_tick_labels();
+        let (_, side_top) = self.side_axes_margins(cx);
+        let text = AxesText { title: &self.title, x_label: &self.x_label, y_label: &self.y_label, y_ticks: &y_tick_labels, title_size: Some(self.style.title_font_size), top_axis: side_top, x_tick_extent, y_tick_extent };
+        text.draw(cx, &mut self.label, &self.plot_area, &self.x_tick_layout);
     }
 
     /// Set legend position
     pub fn set_legend(&mut self, position: LegendPosition) {
-        self.legend_position = position;
+        self.legend.set_position(position);
+    }
+
+    /// Lay the legend entries out in several columns
+    pub fn set_legend_columns(&mut self, columns: usize) {
+        self.legend.set_columns(columns);
+    }
+
+    pub fn set_legend_title(&mut self, title: impl Into<String>) {
+        self.legend.set_title(title);
     }
 
     /// Set X axis scale type
@@ -1571,6 +2727,26 @@ impl LinePlot {
         }
     }
 
+    /// Show, hide, recolour or move one spine, e.g.
+    /// `set_spine(SpineSide::Left, Spine::default().with_position(SpinePosition::Zero))`
+    pub fn set_spine(&mut self, side: SpineSide, spine: Spine) {
+        *self.spines.get_mut(side) = spine;
+    }
+
+    pub fn set_spines(&mut self, spines: Spines) {
+        self.spines = spines;
+    }
+
+    /// Direction, sizes and sides of the tick marks, and whether minor ticks are drawn
+    pub fn set_tick_params(&mut self, params: TickParams) {
+        self.tick_params = params;
+    }
+
+    /// Major and minor grid line styles; `show_grid` still turns the whole grid off
+    pub fn set_grid_style(&mut self, grid: GridStyle) {
+        self.grid_style = grid;
+    }
+
     /// Enable or disable pan/zoom interactivity
     pub fn set_interactive(&mut self, interactive: bool) {
         self.interactive = interactive;
@@ -1578,40 +2754,56 @@ impl LinePlot {
 
     /// Reset view to auto-fit all data
     pub fn reset_view(&mut self) {
+        self.view_moved = false;
         self.auto_range();
+        self.twins.iter_mut().for_each(TwinAxis::auto_range);
     }
 
     fn draw_annotations(&mut self, cx: &mut Cx2d) {
         // Draw arrow annotations first (so text appears on top)
         let arrows = self.arrow_annotations.clone();
+        let origin = dvec2(self.plot_area.left, self.plot_area.bottom);
         for arrow in &arrows {
-            let start = self.data_to_pixel(arrow.start_x, arrow.start_y);
-            let end = self.data_to_pixel(arrow.end_x, arrow.end_y);
-
-            // Draw arrow line
+            // Offsets on one end are measured from the other end
+            let start_base = self.resolve_position(arrow.start_x, arrow.start_y, arrow.start_coords, origin);
+            let end_base = self.resolve_position(arrow.end_x, arrow.end_y, arrow.end_coords, origin);
+            let start = self.resolve_position(arrow.start_x, arrow.start_y, arrow.start_coords, end_base);
+            let end = self.resolve_position(arrow.end_x, arrow.end_y, arrow.end_coords, start_base);
+
+            // Draw arrow path
+            let path = arrow.style.path(start, end);
             self.draw_line.color = arrow.color;
-            self.draw_line.draw_line(cx, start, end, arrow.line_width);
+            for pair in path.windows(2) {
+                self.draw_line.draw_line(cx, pair[0], pair[1], arrow.line_width);
+            }
 
-            // Draw arrowhead at the end point
-            let dx = end.x - start.x;
-            let dy = end.y - start.y;
-            let len = (dx * dx + dy * dy).sqrt();
+            // Draw arrowhead at the end point, along the last leg of the path
+            let tail = path[path.len() - 2];
+            let d = end - tail;
+            let len = d.length();
             if len > 0.0 {
-                let ux = dx / len;
-                let uy = dy / len;
+                let u = d / len;
                 let head_size = arrow.head_size;
-
-                // Arrowhead points
-                let head_base = dvec2(end.x - ux * head_size, end.y - uy * head_size);
-                let perp_x = -uy * head_size * 0.5;
-                let perp_y = ux * head_size * 0.5;
-
-                let left = dvec2(head_base.x + perp_x, head_base.y + perp_y);
-                let right = dvec2(head_base.x - perp_x, head_base.y - perp_y);
-
-                // Draw arrowhead as two lines forming a V
-                self.draw_line.draw_line(cx, end, left, arrow.line_width);
-                self.draw_line.draw_line(cx, end, right, arrow.line_width);
+                let head_base = end - u * head_size;
+                let perp = dvec2(-u.y, u.x) * (head_size * 0.5);
+                let left = head_base + perp;
+                let right = head_base - perp;
+
+                match arrow.head {
+                    ArrowHead::Open => {
+                        // Two lines forming a V
+                        self.draw_line.draw_line(cx, end, left, arrow.line_width);
+                        self.draw_line.draw_line(cx, end, right, arrow.line_width);
+                    }
+                    ArrowHead::Filled => {
+                        self.draw_triangle.color = arrow.color;
+                        self.draw_triangle.draw_triangle(cx, end, left, right);
+                    }
+                    ArrowHead::Bar => {
+                        self.draw_line.draw_line(cx, end + perp, end - perp, arrow.line_width);
+                    }
+                    ArrowHead::None => {}
+                }
             }
 
             // Draw optional text near the start
@@ -1624,100 +2816,351 @@ impl LinePlot {
 
         // Draw text annotations
         let annotations = self.annotations.clone();
+        self.annotation_rects.clear();
         for ann in &annotations {
-            let p = self.data_to_pixel(ann.x, ann.y);
-            if ann.is_math {
-                // Use Math widget for LaTeX rendering
-                self.math_label.set_text(cx, &ann.text);
-                // Draw the math widget at the annotation position (centered)
-                let mut walk = Walk::default();
-                // Offset to approximately center the math text
-                walk.abs_pos = Some(dvec2(p.x - 40.0, p.y - 8.0));
-                let _ = self.math_label.draw_walk(cx, &mut Scope::empty(), walk);
+            let p = self.resolve_position(ann.x, ann.y, ann.coords, origin);
+            self.label.set_color(ann.color);
+            self.label.set_font_size(ann.font_size);
+            self.label.set_weight(ann.weight);
+            self.label.set_slant(ann.slant);
+            let rect = if ann.is_block() {
+                self.draw_text_block(cx, ann, p)
             } else {
-                // Use plain text label
-                self.label.set_color(ann.color);
-                self.label.set_font_size(ann.font_size);
-                self.label.draw_at(cx, p, &ann.text, TextAnchor::Center);
+                // Single line, rotated around its centre
+                let rotation = match ann.slope {
+                    Some((dx, dy)) => upright_angle(self.data_to_pixel(ann.x + dx, ann.y + dy) - p),
+                    None => ann.rotation,
+                };
+                let text = ann.display_text();
+                let size = self.label.measure_rotated(cx, &text, rotation);
+                self.label.draw_rotated(cx, p, &text, TextAnchor::Center, rotation);
+                Rect { pos: p - size / 2.0, size }
+            };
+            self.annotation_rects.push(rect);
+        }
+        self.label.set_weight(TextWeight::Normal);
+        self.label.set_slant(TextSlant::Normal);
+    }
+
+    /// Draw a multi-line block centred on `center`, with its background box behind it.
+    /// Returns the screen box of the block.
+    fn draw_text_block(&mut self, cx: &mut Cx2d, ann: &TextAnnotation, center: DVec2) -> Rect {
+        let text = ann.display_text();
+        let lines: Vec<&str> = text.split('\n').collect();
+
+        // Measure every line first so the block can be aligned and boxed
+        let sizes: Vec<DVec2> = lines.iter().map(|line| self.label.measure(cx, line)).collect();
+        let block = dvec2(
+            sizes.iter().map(|s| s.x).fold(0.0, f64::max),
+            sizes.iter().map(|s| s.y).sum(),
+        );
+        let origin = center - block / 2.0;
+        let mut rect = Rect { pos: origin, size: block };
+
+        if let Some(bbox) = ann.bbox {
+            let (fill, border) = bbox.colors();
+            let pad = dvec2(bbox.padding, bbox.padding);
+            rect = Rect {
+                pos: origin - pad,
+                size: block + pad * 2.0,
+            };
+            self.draw_box.draw_box(cx, rect, fill, border, bbox.border_width, bbox.radius);
+        }
+
+        let mut y = origin.y;
+        for (line, size) in lines.iter().zip(&sizes) {
+            let x = origin.x + ann.align.offset(size.x, block.x);
+            self.label.draw_rotated(cx, dvec2(x, y), line, TextAnchor::TopLeft, 0.0);
+            y += size.y;
+        }
+        rect
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        let mut entries: Vec<LegendEntry> = self.series.iter().enumerate().map(|(idx, series)| {
+            let color = series.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
+            let has_errors = series.yerr_minus.is_some() || series.yerr_plus.is_some()
+                || series.xerr_minus.is_some() || series.xerr_plus.is_some();
+            let symbol = if has_errors {
+                LegendSymbol::ErrorBar
+            } else {
+                LegendSymbol::Line {
+                    style: series.line_style,
+                    width: series.line_width.or(self.line_width).unwrap_or(self.style.line_width),
+                    marker: series.marker_style,
+                }
+            };
+            LegendEntry::new(series.label.clone(), color, symbol).with_visible(series.visible)
+        }).collect();
+        entries.extend(self.twins.legend_entries(&self.twin_draw()));
+
+        // Fills and spans only get an entry when they were given a label
+        let patches = self.fill_regions.iter().map(|f| (&f.label, f.color, f.visible))
+            .chain(self.vspans.iter().map(|s| (&s.label, s.color, s.visible)))
+            .chain(self.hspans.iter().map(|s| (&s.label, s.color, s.visible)));
+        for (label, color, visible) in patches {
+            if !label.is_empty() {
+                entries.push(LegendEntry::patch(label.clone(), color).with_visible(visible));
             }
         }
+        entries
     }
 
     fn draw_legend(&mut self, cx: &mut Cx2d) {
-        if self.legend_position == LegendPosition::None || self.series.is_empty() {
-            return;
+        let mut points: Vec<DVec2> = self.series.iter()
+            .filter(|s| s.visible)
+            .flat_map(|s| s.x.iter().zip(&s.y).map(|(x, y)| self.data_to_pixel(*x, *y)))
+            .collect();
+        points.extend(self.twins.data_points(&self.twin_draw()));
+        self.legend.highlighted = self.hovered_entry;
+        self.legend.draw(cx, &self.plot_area, self.figure_rect, &points);
+    }
+
+    /// Add a second y axis on the right sharing x, like matplotlib's `twinx`, or return
+    /// the one already there. Its series are added with `add_twinx_series`.
+    pub fn twinx(&mut self) -> &mut TwinAxis {
+        self.twins.get_or_insert(TwinSide::Right)
+    }
+
+    /// Add a second x axis along the top sharing y, like matplotlib's `twiny`
+    pub fn twiny(&mut self) -> &mut TwinAxis {
+        self.twins.get_or_insert(TwinSide::Top)
+    }
+
+    /// Attach a twin axis built up front, e.g. `TwinAxis::twinx().with_label("Rate")`,
+    /// replacing any twin on the same side
+    pub fn set_twin(&mut self, axis: TwinAxis) {
+        self.twins.set(axis);
+    }
+
+    /// Add a series plotted against the twin y axis on the right
+    pub fn add_twinx_series(&mut self, mut series: Series) {
+        if let Some(ref categories) = series.x_categories {
+            series.x = self.x_categories.positions(categories);
         }
+        self.twinx().add_series(series);
+    }
 
-        // Calculate legend dimensions
-        let padding = 8.0;
-        let line_height = 16.0;
-        let marker_size = 10.0;
-        let marker_text_gap = 6.0;
-        let legend_height = self.series.len() as f64 * line_height + padding * 2.0;
-        let legend_width = 100.0; // Fixed width for simplicity
-
-        // Position legend based on setting
-        let (legend_x, legend_y) = match self.legend_position {
-            LegendPosition::TopRight => (
-                self.plot_area.right - legend_width - 10.0,
-                self.plot_area.top + 10.0,
-            ),
-            LegendPosition::TopLeft => (
-                self.plot_area.left + 10.0,
-                self.plot_area.top + 10.0,
-            ),
-            LegendPosition::BottomRight => (
-                self.plot_area.right - legend_width - 10.0,
-                self.plot_area.bottom - legend_height - 10.0,
-            ),
-            LegendPosition::BottomLeft => (
-                self.plot_area.left + 10.0,
-                self.plot_area.bottom - legend_height - 10.0,
-            ),
-            LegendPosition::None => return,
-        };
+    /// Add a series plotted against the twin x axis along the top
+    pub fn add_twiny_series(&mut self, series: Series) {
+        self.twiny().add_series(series);
+    }
+
+    pub fn clear_twins(&mut self) {
+        self.twins.clear();
+    }
+
+    /// Show the x axis again along the top in other units, like matplotlib's
+    /// `secondary_xaxis`. `forward` converts x values into the new units and `inverse`
+    /// converts back, e.g. `secondary_xaxis(|f| 1.0 / f, |p| 1.0 / p)` for period over frequency.
+    pub fn secondary_xaxis(
+        &mut self,
+        forward: impl Fn(f64) -> f64 + 'static,
+        inverse: impl Fn(f64) -> f64 + 'static,
+    ) -> &mut SecondaryAxis {
+        self.secondary_axes.x.insert(SecondaryAxis::x(forward, inverse))
+    }
+
+    /// Show the y axis again on the right in other units, like `secondary_yaxis`
+    pub fn secondary_yaxis(
+        &mut self,
+        forward: impl Fn(f64) -> f64 + 'static,
+        inverse: impl Fn(f64) -> f64 + 'static,
+    ) -> &mut SecondaryAxis {
+        self.secondary_axes.y.insert(SecondaryAxis::y(forward, inverse))
+    }
+
+    /// Attach a secondary axis built up front, replacing any on the same side
+    pub fn set_secondary_axis(&mut self, axis: SecondaryAxis) {
+        self.secondary_axes.set(axis);
+    }
 
-        // Draw legend background
-        self.draw_line.color = vec4(0.95, 0.95, 0.95, 0.9);
-        let bg_rect = Rect {
-            pos: dvec2(legend_x, legend_y),
-            size: dvec2(legend_width, legend_height),
+    pub fn clear_secondary_axes(&mut self) {
+        self.secondary_axes.clear();
+    }
+
+    /// Split the y axis into a lower and an upper range with a gap between them, e.g.
+    /// `AxisBreak::new((0.0, 40.0), (900.0, 1000.0)).unwrap()` for latencies with rare huge outliers.
+    /// The break replaces the y limits until cleared; pan and zoom still move x.
+    pub fn set_y_break(&mut self, axis_break: AxisBreak) {
+        self.y_break = Some(axis_break);
+    }
+
+    pub fn clear_y_break(&mut self) {
+        self.y_break = None;
+    }
+
+    /// Add a zoomed view of `xlim` x `ylim` in a box at `bounds` (fractions of the plot area:
+    /// lower-left x, y, width, height), like matplotlib's `inset_axes`. The region is
+    /// outlined on the chart and joined to the inset unless `indicate` is turned off.
+    pub fn inset_axes(&mut self, bounds: [f64; 4], xlim: (f64, f64), ylim: (f64, f64)) -> &mut InsetAxes {
+        self.insets.push(InsetAxes::new(bounds, xlim, ylim));
+        self.insets.last_mut().unwrap()
+    }
+
+    pub fn add_inset(&mut self, inset: InsetAxes) {
+        self.insets.push(inset);
+    }
+
+    pub fn clear_insets(&mut self) {
+        self.insets.clear();
+    }
+
+    /// Blank out the gap of a broken y axis, cutting the spines and any lines crossing it,
+    /// and mark the break on both spines
+    fn draw_y_break(&mut self, cx: &mut Cx2d) {
+        let Some(axis_break) = self.y_break else {
+            return;
         };
-        self.draw_line.draw_abs(cx, bg_rect);
-
-        // Draw legend border
-        self.draw_line.color = vec4(0.8, 0.8, 0.8, 1.0);
-        // Top border
-        self.draw_line.draw_line(cx, dvec2(legend_x, legend_y), dvec2(legend_x + legend_width, legend_y), 1.0);
-        // Bottom border
-        self.draw_line.draw_line(cx, dvec2(legend_x, legend_y + legend_height), dvec2(legend_x + legend_width, legend_y + legend_height), 1.0);
-        // Left border
-        self.draw_line.draw_line(cx, dvec2(legend_x, legend_y), dvec2(legend_x, legend_y + legend_height), 1.0);
-        // Right border
-        self.draw_line.draw_line(cx, dvec2(legend_x + legend_width, legend_y), dvec2(legend_x + legend_width, legend_y + legend_height), 1.0);
-
-        // Draw legend entries
-        for (idx, series) in self.series.iter().enumerate() {
-            let color = series.color.unwrap_or_else(|| get_color(idx));
-            let entry_y = legend_y + padding + idx as f64 * line_height + line_height / 2.0;
+        let gap = axis_break.gap_rect(&self.plot_area, self.style.axes_width);
+        self.draw_fill.color = self.style.backdrop(self.theme_background);
+        self.draw_fill.draw_fill_strip(cx, gap.pos.x, gap.size.x, gap.pos.y, gap.pos.y + gap.size.y);
+
+        self.draw_line.color = self.style.axes_color;
+        for (a, b) in axis_break.marks(&self.plot_area) {
+            self.draw_line.draw_line(cx, a, b, self.style.axes_width);
+        }
+    }
+
+    fn draw_insets(&mut self, cx: &mut Cx2d) {
+        let area = self.plot_area;
+        let area_rect = Rect { pos: dvec2(area.left, area.top), size: dvec2(area.width(), area.height()) };
+        for inset_index in 0..self.insets.len() {
+            let inset = self.insets[inset_index];
+            let view = inset.view(&area, self.x_scale, self.y_scale);
+            let rect = view.rect;
+
+            // Zoomed region on the chart, joined to the inset; both cut to the plot area
+            // when the region has been panned partly out of view
+            if inset.indicate {
+                let corner = self.data_to_pixel(inset.xlim.0, inset.ylim.1);
+                let far = self.data_to_pixel(inset.xlim.1, inset.ylim.0);
+                let region = Rect { pos: corner, size: far - corner };
+                self.draw_line.color = self.style.axes_color;
+                let (tl, br) = (region.pos, region.pos + region.size);
+                let (tr, bl) = (dvec2(br.x, tl.y), dvec2(tl.x, br.y));
+                for (a, b) in [(tl, tr), (tr, br), (br, bl), (bl, tl)] {
+                    if let Some((a, b)) = clip_segment(a, b, area_rect) {
+                        self.draw_line.draw_line(cx, a, b, self.style.axes_width);
+                    }
+                }
+                for (a, b) in view.connectors(region) {
+                    if let Some((start, end)) = clip_segment(a, b, area_rect) {
+                        // Dashes stay anchored to the region corner as the line is cut
+                        self.draw_line.draw_line_styled(cx, start, end, 1.0, LineStyle::Dashed, (start - a).length());
+                    }
+                }
+            }
 
-            // Draw color marker (small rectangle)
-            self.draw_point.color = color;
-            self.draw_point.draw_point(cx, dvec2(legend_x + padding + marker_size / 2.0, entry_y), marker_size / 2.0);
+            self.draw_fill.color = self.style.backdrop(self.theme_background);
+            self.draw_fill.draw_fill_strip(cx, rect.pos.x, rect.size.x, rect.pos.y, rect.pos.y + rect.size.y);
 
-            // Draw label
-            self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
-            self.label.draw_at(
-                cx,
-                dvec2(legend_x + padding + marker_size + marker_text_gap, entry_y),
-                &series.label,
-                TextAnchor::MiddleLeft,
-            );
+            // The chart's own series, cut to the inset window
+            for (idx, series) in self.series.iter().enumerate() {
+                if !series.visible {
+                    continue;
+                }
+                let color = series.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
+                let line_width = series.line_width.or(self.line_width).unwrap_or(self.style.line_width);
+                self.draw_line.color = color;
+                self.draw_point.color = color;
+
+                let points: Vec<DVec2> = series.x.iter().zip(&series.y)
+                    .filter(|(x, y)| x.is_finite() && y.is_finite())
+                    .map(|(x, y)| view.to_pixel(*x, *y))
+                    .collect();
+                // Dash offset runs along the whole line, clipped parts included, so the
+                // pattern matches the main plot
+                let mut dash_offset = 0.0;
+                for pair in points.windows(2) {
+                    if let Some((a, b)) = view.clip(pair[0], pair[1]) {
+                        let offset = dash_offset + (a - pair[0]).length();
+                        self.draw_line.draw_line_styled(cx, a, b, line_width, series.line_style, offset);
+                    }
+                    dash_offset += (pair[1] - pair[0]).length();
+                }
+                if series.marker_style != MarkerStyle::None || self.show_points {
+                    let marker = if series.marker_style != MarkerStyle::None { series.marker_style } else { MarkerStyle::Circle };
+                    let marker_size = series.marker_size.or(self.point_radius).unwrap_or(self.style.marker_size);
+                    for p in points.iter().filter(|p| view.contains(**p)) {
+                        self.draw_point.draw_marker(cx, *p, marker_size, marker);
+                    }
+                }
+            }
+
+            self.draw_line.color = self.style.axes_color;
+            self.draw_box_outline(cx, rect);
+
+            // A few ticks below and left of the inset
+            self.label.set_color(self.style.text_color);
+            let (x_ticks, y_ticks) = inset.ticks(self.x_scale, self.y_scale);
+            for x in x_ticks {
+                let p = view.to_pixel(x, inset.ylim.0);
+                self.label.draw_at(cx, dvec2(p.x, p.y + TICK_LABEL_PAD), &self.x_scale.format_tick(x), TextAnchor::TopCenter);
+            }
+            for y in y_ticks {
+                let p = view.to_pixel(inset.xlim.0, y);
+                self.label.draw_at(cx, dvec2(p.x - TICK_LABEL_PAD, p.y), &self.y_scale.format_tick(y), TextAnchor::MiddleRight);
+            }
+        }
+    }
+
+    fn draw_box_outline(&mut self, cx: &mut Cx2d, rect: Rect) {
+        let tl = rect.pos;
+        let br = rect.pos + rect.size;
+        let tr = dvec2(br.x, tl.y);
+        let bl = dvec2(tl.x, br.y);
+        for (a, b) in [(tl, tr), (tr, br), (br, bl), (bl, tl)] {
+            self.draw_line.draw_line(cx, a, b, self.style.axes_width);
+        }
+    }
+
+    /// Room for the twin axes plus the secondary axes stacked outside them, (right, top)
+    fn side_axes_margins(&mut self, cx: &mut Cx2d) -> (f64, f64) {
+        let (twin_right, twin_top) = self.twins.margins(cx, &mut self.label, &self.style);
+        let x = AxisRange::new(self.x_range, self.x_scale);
+        let y = AxisRange::new(self.y_range, self.y_scale);
+        let (secondary_right, secondary_top) = self.secondary_axes.margins(cx, &mut self.label, &self.style, x, y);
+        (twin_right + secondary_right, twin_top + secondary_top)
+    }
+
+    /// What the twin axes share with this chart
+    fn twin_draw(&self) -> TwinDraw<'_> {
+        TwinDraw {
+            area: self.plot_area,
+            x: AxisRange::new(self.x_range, self.x_scale),
+            y: AxisRange::new(self.y_range, self.y_scale),
+            style: &self.style,
+            cycle: self.style.cycle(&self.prop_cycle),
+            first_color: self.series.len(),
+            markers_only: false,
         }
     }
+
+    fn draw_twins(&mut self, cx: &mut Cx2d) {
+        // Built field by field so the draw primitives can be borrowed alongside
+        let ctx = TwinDraw {
+            area: self.plot_area,
+            x: AxisRange::new(self.x_range, self.x_scale),
+            y: AxisRange::new(self.y_range, self.y_scale),
+            style: &self.style,
+            cycle: self.style.cycle(&self.prop_cycle),
+            first_color: self.series.len(),
+            markers_only: false,
+        };
+        self.twins.draw(cx, &ctx, &mut self.draw_line, &mut self.draw_point, &mut self.label);
+        let twin_margins = self.twins.margins(cx, &mut self.label, &self.style);
+        self.secondary_axes.draw(cx, &ctx, twin_margins, &mut self.draw_line, &mut self.label);
+    }
 }
 
 impl LinePlotRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reset();
+        }
+    }
+
     pub fn add_series(&self, series: Series) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.add_series(series);
@@ -1730,6 +3173,78 @@ impl LinePlotRef {
         }
     }
 
+    pub fn set_twin(&self, axis: TwinAxis) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_twin(axis);
+        }
+    }
+
+    pub fn add_twinx_series(&self, series: Series) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_twinx_series(series);
+        }
+    }
+
+    pub fn add_twiny_series(&self, series: Series) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_twiny_series(series);
+        }
+    }
+
+    pub fn clear_twins(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_twins();
+        }
+    }
+
+    pub fn set_secondary_axis(&self, axis: SecondaryAxis) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_secondary_axis(axis);
+        }
+    }
+
+    pub fn clear_secondary_axes(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_secondary_axes();
+        }
+    }
+
+    pub fn set_y_break(&self, axis_break: AxisBreak) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_y_break(axis_break);
+        }
+    }
+
+    pub fn clear_y_break(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_y_break();
+        }
+    }
+
+    pub fn add_inset(&self, inset: InsetAxes) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_inset(inset);
+        }
+    }
+
+    pub fn clear_insets(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_insets();
+        }
+    }
+
+    pub fn set_x_categories<S: Into<String>>(&self, categories: Vec<S>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_x_categories(categories);
+        }
+    }
+
+    pub fn set_xtick_strategy(&self, strategy: TickLabelStrategy) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_xtick_strategy(strategy);
+        }
+    }
+
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_title(title);
@@ -1754,6 +3269,18 @@ impl LinePlotRef {
         }
     }
 
+    pub fn set_legend_columns(&self, columns: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_columns(columns);
+        }
+    }
+
+    pub fn set_legend_title(&self, title: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_title(title);
+        }
+    }
+
     pub fn set_x_scale(&self, scale: ScaleType) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_x_scale(scale);
@@ -1766,6 +3293,30 @@ impl LinePlotRef {
         }
     }
 
+    pub fn set_spine(&self, side: SpineSide, spine: Spine) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spine(side, spine);
+        }
+    }
+
+    pub fn set_spines(&self, spines: Spines) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spines(spines);
+        }
+    }
+
+    pub fn set_tick_params(&self, params: TickParams) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_tick_params(params);
+        }
+    }
+
+    pub fn set_grid_style(&self, grid: GridStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_grid_style(grid);
+        }
+    }
+
     pub fn set_interactive(&self, interactive: bool) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_interactive(interactive);
@@ -1802,18 +3353,24 @@ impl LinePlotRef {
         }
     }
 
-    pub fn fill_between(&self, x: Vec<f64>, y1: Vec<f64>, y2: Vec<f64>, color: Vec4) {
+    pub fn fill_between(&self, x: impl IntoValues, y1: impl IntoValues, y2: impl IntoValues, color: Vec4) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.fill_between(x, y1, y2, color);
         }
     }
 
-    pub fn fill_between_baseline(&self, x: Vec<f64>, y: Vec<f64>, baseline: f64, color: Vec4) {
+    pub fn fill_between_baseline(&self, x: impl IntoValues, y: impl IntoValues, baseline: f64, color: Vec4) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.fill_between_baseline(x, y, baseline, color);
         }
     }
 
+    pub fn fill_between_labeled(&self, x: impl IntoValues, y1: impl IntoValues, y2: impl IntoValues, color: Vec4, label: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.fill_between_labeled(x, y1, y2, color, label);
+        }
+    }
+
     pub fn annotate(&self, text: impl Into<String>, x: f64, y: f64, color: Vec4, font_size: f64) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.annotate(text, x, y, color, font_size);
@@ -1827,6 +3384,84 @@ impl LinePlotRef {
         }
     }
 
+    pub fn annotate_rotated(&self, text: impl Into<String>, x: f64, y: f64, color: Vec4, font_size: f64, degrees: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.annotate_rotated(text, x, y, color, font_size, degrees);
+        }
+    }
+
+    pub fn annotate_along(&self, text: impl Into<String>, x: f64, y: f64, direction: (f64, f64), color: Vec4, font_size: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.annotate_along(text, x, y, direction, color, font_size);
+        }
+    }
+
+    pub fn add_annotation(&self, annotation: TextAnnotation) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_annotation(annotation);
+        }
+    }
+
+    pub fn set_draggable(&self, item: Draggable, draggable: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_draggable(item, draggable);
+        }
+    }
+
+    pub fn vline_x(&self, index: usize) -> Option<f64> {
+        self.borrow().and_then(|inner| inner.vline_x(index))
+    }
+
+    pub fn hline_y(&self, index: usize) -> Option<f64> {
+        self.borrow().and_then(|inner| inner.hline_y(index))
+    }
+
+    pub fn vspan_range(&self, index: usize) -> Option<(f64, f64)> {
+        self.borrow().and_then(|inner| inner.vspan_range(index))
+    }
+
+    pub fn hspan_range(&self, index: usize) -> Option<(f64, f64)> {
+        self.borrow().and_then(|inner| inner.hspan_range(index))
+    }
+
+    pub fn annotation_position(&self, index: usize) -> Option<(f64, f64)> {
+        self.borrow().and_then(|inner| inner.annotation_position(index))
+    }
+
+    /// The action this plot emitted in `actions`, if any
+    pub fn action(&self, actions: &Actions) -> Option<LinePlotAction> {
+        match actions.find_widget_action(self.widget_uid())?.cast() {
+            LinePlotAction::None => None,
+            action => Some(action),
+        }
+    }
+
+    /// Index and new visibility of the series whose legend entry was clicked
+    pub fn series_toggled(&self, actions: &Actions) -> Option<(usize, bool)> {
+        match self.action(actions)? {
+            LinePlotAction::SeriesToggled { index, visible } => Some((index, visible)),
+            _ => None,
+        }
+    }
+
+    /// Twin series, fill or span whose legend entry was clicked, and its new visibility
+    pub fn legend_item_toggled(&self, actions: &Actions) -> Option<(LegendItem, bool)> {
+        match self.action(actions)? {
+            LinePlotAction::LegendItemToggled { item, visible } => Some((item, visible)),
+            _ => None,
+        }
+    }
+
+    pub fn set_series_visible(&self, index: usize, visible: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_series_visible(index, visible);
+        }
+    }
+
+    pub fn is_series_visible(&self, index: usize) -> bool {
+        self.borrow().is_some_and(|inner| inner.is_series_visible(index))
+    }
+
     /// Add a vertical line at x position
     pub fn axvline(&self, x: f64, color: Vec4, line_width: f64, line_style: LineStyle) {
         if let Some(mut inner) = self.borrow_mut() {
@@ -1855,6 +3490,18 @@ impl LinePlotRef {
         }
     }
 
+    pub fn axvspan_labeled(&self, x1: f64, x2: f64, color: Vec4, label: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.axvspan_labeled(x1, x2, color, label);
+        }
+    }
+
+    pub fn axhspan_labeled(&self, y1: f64, y2: f64, color: Vec4, label: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.axhspan_labeled(y1, y2, color, label);
+        }
+    }
+
     /// Add an arrow annotation
     pub fn add_arrow(&self, arrow: ArrowAnnotation) {
         if let Some(mut inner) = self.borrow_mut() {
@@ -1875,6 +3522,24 @@ impl LinePlotRef {
             inner.redraw(cx);
         }
     }
+
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_margins(margins);
+        }
+    }
+
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_prop_cycle(cycle);
+        }
+    }
+
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_style(style);
+        }
+    }
 }
 
 // =============================================================================
@@ -1890,8 +3555,8 @@ pub struct BarGroup {
 }
 
 impl BarGroup {
-    pub fn new(label: impl Into<String>, values: Vec<f64>) -> Self {
-        Self { label: label.into(), values, color: None }
+    pub fn new(label: impl Into<String>, values: impl IntoValues) -> Self {
+        Self { label: label.into(), values: values.into_values().into_owned(), color: None }
     }
 
     pub fn with_color(mut self, color: Vec4) -> Self {
@@ -1912,51 +3577,92 @@ pub struct BarPlot {
     #[live]
     draw_line: DrawPlotLine,
 
-    #[live]
-    label: PlotLabel,
+    // Markers of twin axis series
+    #[live]
+    draw_point: DrawPlotPoint,
+
+    #[live]
+    label: PlotLabel,
+
+    #[rust]
+    categories: Vec<String>,
+
+    #[rust]
+    values: Vec<f64>,
+
+    #[live]
+    bar_color: Option<Vec4>,
+
+    // Second value axis on the right and/or x axis along the top
+    #[rust]
+    twins: TwinAxes,
+
+    #[rust]
+    plot_area: PlotArea,
+
+    #[live]
+    title: String,
+
+    #[live]
+    margins: Margins,
+
+    #[rust(0.8)]
+    bar_width_ratio: f64,
+
+    // New fields for enhanced bar charts
+    #[rust]
+    horizontal: bool,
+
+    #[rust]
+    stacked: bool,
 
     #[rust]
-    categories: Vec<String>,
+    groups: Vec<BarGroup>,
 
     #[rust]
-    values: Vec<f64>,
+    show_bar_labels: bool,
 
     #[rust]
-    bar_color: Option<Vec4>,
+    x_tick_strategy: TickLabelStrategy,
 
     #[rust]
-    plot_area: PlotArea,
+    x_tick_layout: TickLabelLayout,
 
+    // Spines, tick marks and grid lines
     #[rust]
-    title: String,
+    spines: Spines,
 
-    #[rust(50.0)]
-    left_margin: f64,
+    #[rust]
+    tick_params: TickParams,
 
-    #[rust(40.0)]
-    bottom_margin: f64,
+    #[rust]
+    grid_style: GridStyle,
 
-    #[rust(20.0)]
-    right_margin: f64,
+    #[live]
+    legend: PlotLegend,
 
-    #[rust(30.0)]
-    top_margin: f64,
+    // Flat DSL form of `legend: { position: .. }`
+    #[live]
+    legend_position: Option<LegendPosition>,
 
-    #[rust(0.8)]
-    bar_width_ratio: f64,
+    #[live]
+    show_legend: bool,
 
-    // New fields for enhanced bar charts
     #[rust]
-    horizontal: bool,
+    prop_cycle: Option<PropCycle>,
 
-    #[rust]
-    stacked: bool,
+    #[live]
+    plot_style: String,
+
+    // Background of the host theme, for the automatic light/dark style
+    #[live]
+    theme_background: Vec4,
 
     #[rust]
-    groups: Vec<BarGroup>,
+    style: PlotStyle,
 
     #[rust]
-    show_bar_labels: bool,
+    style_override: Option<PlotStyle>,
 }
 
 impl Widget for BarPlot {
@@ -1965,16 +3671,25 @@ impl Widget for BarPlot {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        // Applied once per live update, so later set_legend calls still win
+        if let Some(position) = self.legend_position.take() {
+            self.set_legend(position);
+        }
+        self.style.apply(&mut self.view, &mut self.label);
+        self.legend.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
 
         let rect = cx.turtle().rect();
         let has_data = !self.values.is_empty() || !self.groups.is_empty();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && has_data {
-            self.update_plot_area(rect);
+            self.update_plot_area(cx, rect);
             self.draw_grid(cx);
             self.draw_axes(cx);
             self.draw_bars(cx);
             self.draw_labels(cx);
+            self.draw_twins(cx);
+            self.draw_legend(cx, rect);
         }
 
         DrawStep::done()
@@ -1983,9 +3698,9 @@ impl Widget for BarPlot {
 
 impl BarPlot {
     /// Set bar data (simple mode - single series)
-    pub fn set_data(&mut self, categories: Vec<String>, values: Vec<f64>) {
+    pub fn set_data(&mut self, categories: Vec<String>, values: impl IntoValues) {
         self.categories = categories;
-        self.values = values;
+        self.values = values.into_values().into_owned();
         self.groups.clear();
     }
 
@@ -2031,17 +3746,153 @@ impl BarPlot {
         self.categories.clear();
         self.values.clear();
         self.groups.clear();
+        self.twins.iter_mut().for_each(TwinAxis::clear);
     }
 
-    fn update_plot_area(&mut self, rect: Rect) {
-        let left_margin = if self.horizontal { 80.0 } else { self.left_margin };
-        let bottom_margin = if self.horizontal { self.bottom_margin } else { 40.0 };
+    /// Clear the bars and put the title, colour, layout, legend, margins and style back to
+    /// their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.twins = TwinAxes::default();
+        self.bar_color = None;
+        self.stacked = false;
+        self.horizontal = false;
+        self.title.clear();
+        self.margins = Margins::default();
+        self.legend.reset();
+        self.legend_position = None;
+        self.spines = Spines::default();
+        self.tick_params = TickParams::default();
+        self.grid_style = GridStyle::default();
+        self.prop_cycle = None;
+        self.style_override = None;
+    }
+
+    /// The chart as a "bar" spec; twin axes have no spec field
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            legend: legend_name(self.legend.position),
+            margins: margins_spec(&self.margins),
+            color: self.bar_color.map(to_hex),
+            stacked: self.stacked.then_some(true),
+            horizontal: self.horizontal.then_some(true),
+            categories: self.categories.clone(),
+            values: self.values.clone(),
+            groups: self.groups.iter().map(BarGroupSpec::from).collect(),
+            ..ChartSpec::new("bar")
+        }
+    }
+
+    /// Choose how category labels are rotated, wrapped or thinned when they overlap
+    pub fn set_xtick_strategy(&mut self, strategy: TickLabelStrategy) {
+        self.x_tick_strategy = strategy;
+    }
+
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
+    }
+
+    /// Show, hide, recolour or move one spine, e.g.
+    /// `set_spine(SpineSide::Left, Spine::default().with_position(SpinePosition::Zero))`
+    pub fn set_spine(&mut self, side: SpineSide, spine: Spine) {
+        *self.spines.get_mut(side) = spine;
+    }
+
+    pub fn set_spines(&mut self, spines: Spines) {
+        self.spines = spines;
+    }
+
+    /// Direction, sizes and sides of the tick marks, and whether minor ticks are drawn
+    pub fn set_tick_params(&mut self, params: TickParams) {
+        self.tick_params = params;
+    }
+
+    /// Major and minor grid line styles, drawn along the value axis
+    pub fn set_grid_style(&mut self, grid: GridStyle) {
+        self.grid_style = grid;
+    }
+
+    /// Show a legend with one entry per bar group
+    pub fn set_legend(&mut self, position: LegendPosition) {
+        self.show_legend = true;
+        self.legend.set_position(position);
+    }
+
+    /// Lay the legend entries out in several columns
+    pub fn set_legend_columns(&mut self, columns: usize) {
+        self.legend.set_columns(columns);
+    }
+
+    pub fn set_legend_title(&mut self, title: impl Into<String>) {
+        self.legend.set_title(title);
+    }
+
+    /// Colours handed out in turn to bar groups without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    fn update_plot_area(&mut self, cx: &mut Cx2d, rect: Rect) {
+        // Tight layout: derive the plot area from the measured text around the axes
+        let y_ticks = self.y_tick_labels();
+        let entries = if self.show_legend { self.legend_entries() } else { Vec::new() };
+        self.legend.set_entries(entries);
+        let (legend_right, legend_bottom) = self.legend.outside_margins(cx);
+        let (twin_right, twin_top) = self.twins.margins(cx, &mut self.label, &self.style);
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let text = AxesText { title: &self.title, y_ticks: &y_ticks, title_size: Some(self.style.title_font_size), top_axis: twin_top, x_tick_extent, y_tick_extent, ..Default::default() };
+        let (left, right) = self.margins.columns(cx, &mut self.label, &text, legend_right + twin_right);
         self.plot_area = PlotArea::new(
-            rect.pos.x + left_margin,
-            rect.pos.y + self.top_margin,
-            rect.pos.x + rect.size.x - self.right_margin,
-            rect.pos.y + rect.size.y - bottom_margin,
+            rect.pos.x + left,
+            rect.pos.y,
+            rect.pos.x + rect.size.x - right,
+            rect.pos.y + rect.size.y,
         );
+
+        // X tick labels depend on the final width, the bottom margin on their layout
+        let x_ticks = self.x_tick_labels();
+        self.x_tick_layout = TickLabelLayout::compute(cx, &mut self.label, x_ticks, self.x_tick_strategy);
+        let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &self.x_tick_layout);
+        self.plot_area.top = rect.pos.y + top;
+        self.plot_area.bottom = rect.pos.y + rect.size.y - bottom - legend_bottom;
+    }
+
+    /// Labels along the y axis: categories for horizontal bars, values otherwise
+    fn y_tick_labels(&self) -> Vec<String> {
+        if self.horizontal {
+            self.categories.clone()
+        } else {
+            let (v_min, v_max) = self.get_value_range();
+            self.generate_ticks(v_min, v_max, 5).iter().map(|v| format!("{:.0}", v)).collect()
+        }
+    }
+
+    /// Pixel x position and text of each label along the x axis:
+    /// values for horizontal bars, categories otherwise
+    fn x_tick_labels(&self) -> Vec<(f64, String)> {
+        if self.horizontal {
+            let (v_min, v_max) = self.get_value_range();
+            self.generate_ticks(v_min, v_max, 5).iter()
+                .map(|v| {
+                    let x_pixel = self.plot_area.left + (*v - v_min) / (v_max - v_min) * self.plot_area.width();
+                    (x_pixel, format!("{:.0}", v))
+                })
+                .collect()
+        } else {
+            let n = self.categories.len().max(self.values.len());
+            let band_width = self.plot_area.width() / n as f64;
+            self.categories.iter().enumerate()
+                .map(|(i, cat)| (self.plot_area.left + (i as f64 + 0.5) * band_width, cat.clone()))
+                .collect()
+        }
     }
 
     fn get_value_range(&self) -> (f64, f64) {
@@ -2071,55 +3922,74 @@ impl BarPlot {
         }
     }
 
-    fn draw_grid(&mut self, cx: &mut Cx2d) {
-        self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
-
+    /// Major and minor tick positions in pixels: x, y, x minor, y minor. Each category
+    /// gets a major tick and no minor ones.
+    fn frame_ticks(&self) -> [Vec<f64>; 4] {
         let (v_min, v_max) = self.get_value_range();
-        let v_ticks = self.generate_ticks(v_min, v_max, 5);
-
+        let values = self.generate_ticks(v_min, v_max, 5);
+        let minor = minor_ticks(ScaleType::Linear, &values, (v_min, v_max));
+        let slots: Vec<f64> = (0..self.categories.len().max(self.values.len())).map(|i| i as f64).collect();
+        let (x, y) = self.twin_axes();
+        let to_x = |xs: &[f64]| -> Vec<f64> { xs.iter().map(|v| self.plot_area.left + x.fraction(*v) * self.plot_area.width()).collect() };
+        let to_y = |ys: &[f64]| -> Vec<f64> { ys.iter().map(|v| self.plot_area.bottom - y.fraction(*v) * self.plot_area.height()).collect() };
         if self.horizontal {
-            // Vertical grid lines for horizontal bars
-            for v in &v_ticks {
-                let x_pixel = self.plot_area.left + (*v - v_min) / (v_max - v_min) * self.plot_area.width();
-                let p1 = dvec2(x_pixel, self.plot_area.top);
-                let p2 = dvec2(x_pixel, self.plot_area.bottom);
-                self.draw_line.draw_line(cx, p1, p2, 0.5);
-            }
+            [to_x(&values), to_y(&slots), to_x(&minor), Vec::new()]
         } else {
-            // Horizontal grid lines for vertical bars
-            for v in &v_ticks {
-                let y_pixel = self.plot_area.bottom - (*v - v_min) / (v_max - v_min) * self.plot_area.height();
-                let p1 = dvec2(self.plot_area.left, y_pixel);
-                let p2 = dvec2(self.plot_area.right, y_pixel);
-                self.draw_line.draw_line(cx, p1, p2, 0.5);
-            }
+            [to_x(&slots), to_y(&values), Vec::new(), to_y(&minor)]
         }
     }
+    /// Pixel x of the left spine or pixel y of the bottom one, where the tick labels hang from
+    fn spine_offset(&self, side: SpineSide) -> f64 {
+        let (x, y) = self.twin_axes();
+        self.spines.get(side).offset(side, &self.plot_area, x, y)
+    }
 
-    fn draw_axes(&mut self, cx: &mut Cx2d) {
-        self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+    /// Reach of the tick marks past the bottom and the left spine
+    fn tick_extents(&self) -> (f64, f64) {
+        (
+            self.tick_params.outward_extent(SpineSide::Bottom, &self.style),
+            self.tick_params.outward_extent(SpineSide::Left, &self.style),
+        )
+    }
 
+    fn draw_grid(&mut self, cx: &mut Cx2d) {
+        // Lines along the value axis only
+        let [mut x_ticks, mut y_ticks, mut x_minor, mut y_minor] = self.frame_ticks();
         if self.horizontal {
-            // X axis (bottom)
-            let x1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-            let x2 = dvec2(self.plot_area.right, self.plot_area.bottom);
-            self.draw_line.draw_line(cx, x1, x2, 1.0);
-
-            // Y axis (left)
-            let y1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-            let y2 = dvec2(self.plot_area.left, self.plot_area.top);
-            self.draw_line.draw_line(cx, y1, y2, 1.0);
+            y_ticks.clear();
+            y_minor.clear();
         } else {
-            // X axis
-            let x1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-            let x2 = dvec2(self.plot_area.right, self.plot_area.bottom);
-            self.draw_line.draw_line(cx, x1, x2, 1.0);
-
-            // Y axis
-            let y1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-            let y2 = dvec2(self.plot_area.left, self.plot_area.top);
-            self.draw_line.draw_line(cx, y1, y2, 1.0);
+            x_ticks.clear();
+            x_minor.clear();
         }
+        let (x, y) = self.twin_axes();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.grid_style.draw(cx, &frame, &mut self.draw_line);
+    }
+
+    fn draw_axes(&mut self, cx: &mut Cx2d) {
+        let [x_ticks, y_ticks, x_minor, y_minor] = self.frame_ticks();
+        let (x, y) = self.twin_axes();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.spines.draw(cx, &frame, &self.tick_params, &mut self.draw_line);
     }
 
     fn draw_bars(&mut self, cx: &mut Cx2d) {
@@ -2143,7 +4013,7 @@ impl BarPlot {
             let bar_height = band_height * self.bar_width_ratio;
 
             for (i, value) in self.values.iter().enumerate() {
-                let color = self.bar_color.unwrap_or_else(|| get_color(0));
+                let color = self.bar_color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(0));
                 self.draw_bar.color = color;
 
                 let y_center = self.plot_area.top + (i as f64 + 0.5) * band_height;
@@ -2157,7 +4027,7 @@ impl BarPlot {
 
                 // Bar label
                 if self.show_bar_labels {
-                    self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+                    self.label.set_color(self.style.text_color);
                     let label = format!("{:.1}", value);
                     self.label.draw_at(cx, dvec2(self.plot_area.left + bar_width + 5.0, y_center), &label, TextAnchor::MiddleLeft);
                 }
@@ -2167,7 +4037,7 @@ impl BarPlot {
             let bar_width = band_width * self.bar_width_ratio;
 
             for (i, value) in self.values.iter().enumerate() {
-                let color = self.bar_color.unwrap_or_else(|| get_color(0));
+                let color = self.bar_color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(0));
                 self.draw_bar.color = color;
 
                 let x_center = self.plot_area.left + (i as f64 + 0.5) * band_width;
@@ -2182,7 +4052,7 @@ impl BarPlot {
 
                 // Bar label
                 if self.show_bar_labels {
-                    self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+                    self.label.set_color(self.style.text_color);
                     let label = format!("{:.1}", value);
                     self.label.draw_at(cx, dvec2(x_center, bar_top - 5.0), &label, TextAnchor::BottomCenter);
                 }
@@ -2217,7 +4087,7 @@ impl BarPlot {
 
                 for (group_idx, group) in self.groups.iter().enumerate() {
                     if let Some(&value) = group.values.get(cat_idx) {
-                        let color = group.color.unwrap_or_else(|| get_color(group_idx));
+                        let color = group.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(group_idx));
                         self.draw_bar.color = color;
 
                         let bar_width = (value - v_min) / (v_max - v_min) * self.plot_area.width();
@@ -2240,7 +4110,7 @@ impl BarPlot {
 
                 for (group_idx, group) in self.groups.iter().enumerate() {
                     if let Some(&value) = group.values.get(cat_idx) {
-                        let color = group.color.unwrap_or_else(|| get_color(group_idx));
+                        let color = group.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(group_idx));
                         self.draw_bar.color = color;
 
                         let bar_height = (value - v_min) / (v_max - v_min) * self.plot_area.height();
@@ -2271,7 +4141,7 @@ impl BarPlot {
 
                 for (group_idx, group) in self.groups.iter().enumerate() {
                     if let Some(&value) = group.values.get(cat_idx) {
-                        let color = group.color.unwrap_or_else(|| get_color(group_idx));
+                        let color = group.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(group_idx));
                         self.draw_bar.color = color;
 
                         let y_pos = y_start + group_idx as f64 * group_height;
@@ -2294,7 +4164,7 @@ impl BarPlot {
 
                 for (group_idx, group) in self.groups.iter().enumerate() {
                     if let Some(&value) = group.values.get(cat_idx) {
-                        let color = group.color.unwrap_or_else(|| get_color(group_idx));
+                        let color = group.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(group_idx));
                         self.draw_bar.color = color;
 
                         let x_pos = x_start + group_idx as f64 * group_width;
@@ -2312,58 +4182,173 @@ impl BarPlot {
     }
 
     fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+        self.label.set_color(self.style.text_color);
 
         let n = self.categories.len().max(self.values.len());
         let (v_min, v_max) = self.get_value_range();
 
+        // X axis labels (categories, or values for horizontal bars) - laid out by
+        // update_plot_area, hung below the tick marks
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let axis_y = self.spine_offset(SpineSide::Bottom);
+        self.x_tick_layout.draw(cx, &mut self.label, axis_y + x_tick_extent);
+        let label_x = self.spine_offset(SpineSide::Left) - y_tick_extent - TICK_LABEL_PAD;
+
         if self.horizontal {
             // Category labels on Y axis
             let band_height = self.plot_area.height() / n as f64;
             for (i, cat) in self.categories.iter().enumerate() {
                 let y = self.plot_area.top + (i as f64 + 0.5) * band_height;
-                self.label.draw_at(cx, dvec2(self.plot_area.left - 5.0, y), cat, TextAnchor::MiddleRight);
-            }
-
-            // Value tick labels on X axis
-            let v_ticks = self.generate_ticks(v_min, v_max, 5);
-            for v in &v_ticks {
-                let x_pixel = self.plot_area.left + (*v - v_min) / (v_max - v_min) * self.plot_area.width();
-                let label = format!("{:.0}", v);
-                self.label.draw_at(cx, dvec2(x_pixel, self.plot_area.bottom + 5.0), &label, TextAnchor::TopCenter);
+                self.label.draw_at(cx, dvec2(label_x, y), cat, TextAnchor::MiddleRight);
             }
         } else {
-            // Category labels on X axis
-            let band_width = self.plot_area.width() / n as f64;
-            for (i, cat) in self.categories.iter().enumerate() {
-                let x = self.plot_area.left + (i as f64 + 0.5) * band_width;
-                self.label.draw_at(cx, dvec2(x, self.plot_area.bottom + 5.0), cat, TextAnchor::TopCenter);
-            }
-
             // Value tick labels on Y axis
             let v_ticks = self.generate_ticks(v_min, v_max, 5);
             for v in &v_ticks {
                 let y_pixel = self.plot_area.bottom - (*v - v_min) / (v_max - v_min) * self.plot_area.height();
                 let label = format!("{:.0}", v);
-                self.label.draw_at(cx, dvec2(self.plot_area.left - 5.0, y_pixel), &label, TextAnchor::MiddleRight);
+                self.label.draw_at(cx, dvec2(label_x, y_pixel), &label, TextAnchor::MiddleRight);
             }
         }
 
         // Title
-        if !self.title.is_empty() {
-            let center_x = (self.plot_area.left + self.plot_area.right) / 2.0;
-            self.label.draw_at(cx, dvec2(center_x, self.plot_area.top - 10.0), &self.title, TextAnchor::BottomCenter);
-        }
+        let (_, twin_top) = self.twins.margins(cx, &mut self.label, &self.style);
+        let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), top_axis: twin_top, x_tick_extent, y_tick_extent, ..Default::default() };
+        text.draw(cx, &mut self.label, &self.plot_area, &self.x_tick_layout);
     }
 
     fn generate_ticks(&self, min: f64, max: f64, count: usize) -> Vec<f64> {
         let step = (max - min) / count as f64;
         (0..=count).map(|i| min + i as f64 * step).collect()
     }
+
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        self.groups.iter().enumerate().map(|(group_idx, group)| {
+            let color = group.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(group_idx));
+            LegendEntry::patch(group.label.clone(), color)
+        }).chain(self.twins.legend_entries(&self.twin_draw())).collect()
+    }
+
+    fn draw_legend(&mut self, cx: &mut Cx2d, rect: Rect) {
+        // Bars are anchored at the value axis, so "best" avoids their tops
+        let mut points = self.bar_tops();
+        points.extend(self.twins.data_points(&self.twin_draw()));
+        self.legend.draw(cx, &self.plot_area, rect, &points);
+    }
+
+    /// Add a second value axis on the right, like matplotlib's `twinx`, or return the
+    /// one already there
+    pub fn twinx(&mut self) -> &mut TwinAxis {
+        self.twins.get_or_insert(TwinSide::Right)
+    }
+
+    /// Add a second x axis along the top, like matplotlib's `twiny`
+    pub fn twiny(&mut self) -> &mut TwinAxis {
+        self.twins.get_or_insert(TwinSide::Top)
+    }
+
+    /// Attach a twin axis built up front, replacing any twin on the same side
+    pub fn set_twin(&mut self, axis: TwinAxis) {
+        self.twins.set(axis);
+    }
+
+    /// Add a series plotted against the twin y axis, e.g. a cumulative line over the
+    /// bars. Its x values are category indices, or category names with `Series::with_categories`.
+    pub fn add_twinx_series(&mut self, mut series: Series) {
+        if let Some(ref names) = series.x_categories {
+            series.x = names.iter()
+                .map(|name| self.categories.iter().position(|c| c == name).map_or(f64::NAN, |i| i as f64))
+                .collect();
+        }
+        self.twinx().add_series(series);
+    }
+
+    /// Add a series plotted against the twin x axis along the top
+    pub fn add_twiny_series(&mut self, series: Series) {
+        self.twiny().add_series(series);
+    }
+
+    pub fn clear_twins(&mut self) {
+        self.twins.clear();
+    }
+
+    /// Category positions run from -0.5 to n - 0.5 so index i sits in the middle of its band;
+    /// horizontal bars list the categories from the top
+    fn twin_axes(&self) -> (AxisRange, AxisRange) {
+        let n = self.categories.len().max(self.values.len()) as f64;
+        let values = AxisRange::linear(self.get_value_range());
+        if self.horizontal {
+            (values, AxisRange::linear((n - 0.5, -0.5)))
+        } else {
+            (AxisRange::linear((-0.5, n - 0.5)), values)
+        }
+    }
+
+    /// What the twin axes share with this chart; their colours follow the bar groups'
+    fn twin_draw(&self) -> TwinDraw<'_> {
+        let (x, y) = self.twin_axes();
+        TwinDraw {
+            area: self.plot_area,
+            x,
+            y,
+            style: &self.style,
+            cycle: self.style.cycle(&self.prop_cycle),
+            first_color: self.groups.len().max(1),
+            markers_only: false,
+        }
+    }
+
+    fn draw_twins(&mut self, cx: &mut Cx2d) {
+        // Built field by field so the draw primitives can be borrowed alongside
+        let (x, y) = self.twin_axes();
+        let ctx = TwinDraw {
+            area: self.plot_area,
+            x,
+            y,
+            style: &self.style,
+            cycle: self.style.cycle(&self.prop_cycle),
+            first_color: self.groups.len().max(1),
+            markers_only: false,
+        };
+        self.twins.draw(cx, &ctx, &mut self.draw_line, &mut self.draw_point, &mut self.label);
+    }
+
+    fn bar_tops(&self) -> Vec<DVec2> {
+        let num_cats = self.categories.len().max(self.values.len());
+        if num_cats == 0 {
+            return Vec::new();
+        }
+        let (v_min, v_max) = self.get_value_range();
+        let v_range = (v_max - v_min).max(f64::EPSILON);
+        let mut points = Vec::new();
+        for cat_idx in 0..num_cats {
+            let band = (cat_idx as f64 + 0.5) / num_cats as f64;
+            let value = if self.groups.is_empty() {
+                self.values.get(cat_idx).copied().unwrap_or(0.0)
+            } else if self.stacked {
+                self.groups.iter().filter_map(|g| g.values.get(cat_idx)).sum::<f64>()
+            } else {
+                self.groups.iter().filter_map(|g| g.values.get(cat_idx).copied()).fold(v_min, f64::max)
+            };
+            let t = (value - v_min) / v_range;
+            points.push(if self.horizontal {
+                dvec2(self.plot_area.left + t * self.plot_area.width(), self.plot_area.top + band * self.plot_area.height())
+            } else {
+                dvec2(self.plot_area.left + band * self.plot_area.width(), self.plot_area.bottom - t * self.plot_area.height())
+            });
+        }
+        points
+    }
 }
 
 impl BarPlotRef {
-    pub fn set_data(&self, categories: Vec<String>, values: Vec<f64>) {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reset();
+        }
+    }
+
+    pub fn set_data(&self, categories: Vec<String>, values: impl IntoValues) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_data(categories, values);
         }
@@ -2417,11 +4402,101 @@ impl BarPlotRef {
         }
     }
 
+    pub fn set_twin(&self, axis: TwinAxis) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_twin(axis);
+        }
+    }
+
+    pub fn add_twinx_series(&self, series: Series) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_twinx_series(series);
+        }
+    }
+
+    pub fn add_twiny_series(&self, series: Series) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_twiny_series(series);
+        }
+    }
+
+    pub fn clear_twins(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_twins();
+        }
+    }
+
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.redraw(cx);
         }
     }
+
+    pub fn set_xtick_strategy(&self, strategy: TickLabelStrategy) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_xtick_strategy(strategy);
+        }
+    }
+
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_margins(margins);
+        }
+    }
+
+    pub fn set_spine(&self, side: SpineSide, spine: Spine) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spine(side, spine);
+        }
+    }
+
+    pub fn set_spines(&self, spines: Spines) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spines(spines);
+        }
+    }
+
+    pub fn set_tick_params(&self, params: TickParams) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_tick_params(params);
+        }
+    }
+
+    pub fn set_grid_style(&self, grid: GridStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_grid_style(grid);
+        }
+    }
+
+    pub fn set_legend(&self, position: LegendPosition) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend(position);
+        }
+    }
+
+    pub fn set_legend_columns(&self, columns: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_columns(columns);
+        }
+    }
+
+    pub fn set_legend_title(&self, title: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_title(title);
+        }
+    }
+
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_prop_cycle(cycle);
+        }
+    }
+
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_style(style);
+        }
+    }
 }
 
 // =============================================================================
@@ -2452,6 +4527,16 @@ pub struct ScatterPlot {
     #[rust]
     use_gradient: bool,
 
+    #[rust]
+    colormap: Colormap,
+
+    #[rust]
+    norm: Option<Box<dyn Norm>>,
+
+    // Second y axis on the right and/or x axis along the top
+    #[rust]
+    twins: TwinAxes,
+
     #[rust]
     plot_area: PlotArea,
 
@@ -2461,35 +4546,46 @@ pub struct ScatterPlot {
     #[rust]
     y_range: (f64, f64),
 
-    #[rust]
+    // Fixed limits from the DSL or set_xlim/set_ylim, kept when the data is rescaled
+    #[live]
+    xlim: Option<DVec2>,
+
+    #[live]
+    ylim: Option<DVec2>,
+
+    #[live]
     title: String,
 
-    #[rust]
+    #[live]
     x_label: String,
 
-    #[rust]
+    #[live]
     y_label: String,
 
-    #[rust(true)]
+    #[live(true)]
     show_grid: bool,
 
-    #[rust(5.0)]
+    #[live(5.0)]
     point_radius: f64,
 
-    #[rust(50.0)]
-    left_margin: f64,
+    #[live]
+    margins: Margins,
+
+    #[live]
+    legend: PlotLegend,
 
-    #[rust(30.0)]
-    bottom_margin: f64,
+    // Flat DSL form of `legend: { position: .. }`
+    #[live]
+    legend_position: Option<LegendPosition>,
 
-    #[rust(20.0)]
-    right_margin: f64,
+    #[rust]
+    x_categories: CategoryAxis,
 
-    #[rust(30.0)]
-    top_margin: f64,
+    #[rust]
+    x_tick_strategy: TickLabelStrategy,
 
     #[rust]
-    legend_position: LegendPosition,
+    x_tick_layout: TickLabelLayout,
 
     // Pan/zoom state
     #[rust]
@@ -2506,6 +4602,32 @@ pub struct ScatterPlot {
 
     #[rust]
     initial_y_range: (f64, f64),
+
+    // Spines, tick marks and grid lines
+    #[rust]
+    spines: Spines,
+
+    #[rust]
+    tick_params: TickParams,
+
+    #[rust]
+    grid_style: GridStyle,
+
+    #[rust]
+    prop_cycle: Option<PropCycle>,
+
+    #[live]
+    plot_style: String,
+
+    // Background of the host theme, for the automatic light/dark style
+    #[live]
+    theme_background: Vec4,
+
+    #[rust]
+    style: PlotStyle,
+
+    #[rust]
+    style_override: Option<PlotStyle>,
 }
 
 impl Widget for ScatterPlot {
@@ -2522,6 +4644,7 @@ impl Widget for ScatterPlot {
                 self.drag_start = fe.abs;
                 self.initial_x_range = self.x_range;
                 self.initial_y_range = self.y_range;
+                self.twins.begin_pan();
             }
             Hit::FingerMove(fe) => {
                 if self.is_dragging && self.plot_area.width() > 0.0 && self.plot_area.height() > 0.0 {
@@ -2542,6 +4665,7 @@ impl Widget for ScatterPlot {
                         self.initial_y_range.0 + dy_data,
                         self.initial_y_range.1 + dy_data,
                     );
+                    self.twins.pan(dvec2(dx_pixels, dy_pixels), &self.plot_area);
 
                     self.redraw(cx);
                 }
@@ -2575,6 +4699,7 @@ impl Widget for ScatterPlot {
                         data_y - rel_y * new_y_range,
                         data_y + (1.0 - rel_y) * new_y_range,
                     );
+                    self.twins.zoom(dvec2(rel_x, rel_y), zoom_factor);
 
                     self.redraw(cx);
                 }
@@ -2590,16 +4715,24 @@ impl Widget for ScatterPlot {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        // Applied once per live update, so later set_legend calls still win
+        if let Some(position) = self.legend_position.take() {
+            self.set_legend(position);
+        }
+        self.style.apply(&mut self.view, &mut self.label);
+        self.legend.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
 
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 {
-            self.update_plot_area(rect);
+            self.update_plot_area(cx, rect);
             self.draw_grid(cx);
             self.draw_axes(cx);
             self.draw_points(cx);
             self.draw_labels(cx);
-            self.draw_legend(cx);
+            self.draw_twins(cx);
+            self.draw_legend(cx, rect);
         }
 
         DrawStep::done()
@@ -2608,7 +4741,10 @@ impl Widget for ScatterPlot {
 
 impl ScatterPlot {
     /// Add a data series to the plot
-    pub fn add_series(&mut self, series: Series) {
+    pub fn add_series(&mut self, mut series: Series) {
+        if let Some(ref categories) = series.x_categories {
+            series.x = self.x_categories.positions(categories);
+        }
         self.series.push(series);
         self.auto_range();
     }
@@ -2616,6 +4752,71 @@ impl ScatterPlot {
     /// Clear all series
     pub fn clear(&mut self) {
         self.series.clear();
+        self.x_categories = CategoryAxis::default();
+        self.twins.iter_mut().for_each(TwinAxis::clear);
+    }
+
+    /// Clear the series and put the title, limits, colormap, legend, margins and style back
+    /// to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.twins = TwinAxes::default();
+        self.use_gradient = false;
+        self.colormap = Colormap::default();
+        self.norm = None;
+        self.xlim = None;
+        self.ylim = None;
+        self.title.clear();
+        self.x_label.clear();
+        self.y_label.clear();
+        self.margins = Margins::default();
+        self.legend.reset();
+        self.legend_position = None;
+        self.spines = Spines::default();
+        self.tick_params = TickParams::default();
+        self.grid_style = GridStyle::default();
+        self.prop_cycle = None;
+        self.style_override = None;
+    }
+
+    /// The plot as a "scatter" spec; twin axes and norms have no spec field
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            x_label: self.x_label.clone(),
+            y_label: self.y_label.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            legend: legend_name(self.legend.position),
+            margins: margins_spec(&self.margins),
+            colormap: colormap_name(&self.colormap),
+            categories: self.x_categories.categories.clone(),
+            series: self.series.iter().map(SeriesSpec::from).collect(),
+            ..ChartSpec::new("scatter")
+        }
+    }
+
+    /// Use a categorical x axis with the given category order.
+    /// Series added with `Series::with_categories` are placed by name, including
+    /// those added before this call.
+    pub fn set_x_categories<S: Into<String>>(&mut self, categories: Vec<S>) {
+        self.x_categories = CategoryAxis::new(categories);
+        self.x_categories.place(&mut self.series);
+        for twin in self.twins.iter_mut().filter(|t| t.side == TwinSide::Right) {
+            self.x_categories.place(&mut twin.series);
+        }
+        if !self.series.is_empty() {
+            self.auto_range();
+        }
+    }
+
+    /// Choose how x tick labels are rotated, wrapped or thinned when they overlap
+    pub fn set_xtick_strategy(&mut self, strategy: TickLabelStrategy) {
+        self.x_tick_strategy = strategy;
+    }
+
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
     }
 
     /// Set plot title
@@ -2635,11 +4836,13 @@ impl ScatterPlot {
 
     /// Set X range manually
     pub fn set_xlim(&mut self, min: f64, max: f64) {
+        self.xlim = Some(dvec2(min, max));
         self.x_range = (min, max);
     }
 
     /// Set Y range manually
     pub fn set_ylim(&mut self, min: f64, max: f64) {
+        self.ylim = Some(dvec2(min, max));
         self.y_range = (min, max);
     }
 
@@ -2650,7 +4853,16 @@ impl ScatterPlot {
 
     /// Set legend position
     pub fn set_legend(&mut self, position: LegendPosition) {
-        self.legend_position = position;
+        self.legend.set_position(position);
+    }
+
+    /// Lay the legend entries out in several columns
+    pub fn set_legend_columns(&mut self, columns: usize) {
+        self.legend.set_columns(columns);
+    }
+
+    pub fn set_legend_title(&mut self, title: impl Into<String>) {
+        self.legend.set_title(title);
     }
 
     /// Enable gradient points
@@ -2658,13 +4870,40 @@ impl ScatterPlot {
         self.use_gradient = use_gradient;
     }
 
+    /// Colormap for series with per-point colour values
+    pub fn set_colormap(&mut self, colormap: Colormap) {
+        self.colormap = colormap;
+    }
+
+    /// Map per-point colour values through any norm instead of a linear scale over their range
+    pub fn set_norm(&mut self, norm: impl Norm + 'static) {
+        self.norm = Some(Box::new(norm));
+    }
+
+    /// Colours handed out in turn to series without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    /// Norm over the colour values of all visible series
+    fn color_norm(&self) -> Box<dyn Norm> {
+        let values = self.series.iter().filter(|s| s.visible).filter_map(|s| s.color_values.as_ref()).flatten();
+        let (vmin, vmax) = values.fold((f64::MAX, f64::MIN), |(lo, hi), v| (lo.min(*v), hi.max(*v)));
+        if vmin > vmax { resolve_norm(&self.norm, 0.0, 1.0) } else { resolve_norm(&self.norm, vmin, vmax) }
+    }
+
     fn auto_range(&mut self) {
         let mut x_min = f64::MAX;
         let mut x_max = f64::MIN;
         let mut y_min = f64::MAX;
         let mut y_max = f64::MIN;
 
-        for s in &self.series {
+        for s in self.series.iter().filter(|s| s.visible) {
             for &x in &s.x {
                 x_min = x_min.min(x);
                 x_max = x_max.max(x);
@@ -2679,17 +4918,45 @@ impl ScatterPlot {
         let x_pad = (x_max - x_min) * 0.1;
         let y_pad = (y_max - y_min) * 0.1;
 
-        self.x_range = (x_min - x_pad, x_max + x_pad);
+        self.x_range = if self.x_categories.is_empty() {
+            (x_min - x_pad, x_max + x_pad)
+        } else {
+            self.x_categories.range()
+        };
         self.y_range = (y_min - y_pad, y_max + y_pad);
+
+        // Fixed limits win over the data range
+        if let Some(lim) = self.xlim {
+            self.x_range = (lim.x, lim.y);
+        }
+        if let Some(lim) = self.ylim {
+            self.y_range = (lim.x, lim.y);
+        }
     }
 
-    fn update_plot_area(&mut self, rect: Rect) {
+    fn update_plot_area(&mut self, cx: &mut Cx2d, rect: Rect) {
+        // Tight layout: derive the plot area from the measured text around the axes
+        let y_ticks = self.y_tick_labels();
+        let entries = self.legend_entries();
+        self.legend.set_entries(entries);
+        let (legend_right, legend_bottom) = self.legend.outside_margins(cx);
+        let (twin_right, twin_top) = self.twins.margins(cx, &mut self.label, &self.style);
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let text = AxesText { title: &self.title, x_label: &self.x_label, y_label: &self.y_label, y_ticks: &y_ticks, title_size: Some(self.style.title_font_size), top_axis: twin_top, x_tick_extent, y_tick_extent };
+        let (left, right) = self.margins.columns(cx, &mut self.label, &text, legend_right + twin_right);
         self.plot_area = PlotArea::new(
-            rect.pos.x + self.left_margin,
-            rect.pos.y + self.top_margin,
-            rect.pos.x + rect.size.x - self.right_margin,
-            rect.pos.y + rect.size.y - self.bottom_margin,
+            rect.pos.x + left,
+            rect.pos.y,
+            rect.pos.x + rect.size.x - right,
+            rect.pos.y + rect.size.y,
         );
+
+        // X tick labels depend on the final width, the bottom margin on their layout
+        let x_ticks = self.x_tick_labels();
+        self.x_tick_layout = TickLabelLayout::compute(cx, &mut self.label, x_ticks, self.x_tick_strategy);
+        let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &self.x_tick_layout);
+        self.plot_area.top = rect.pos.y + top;
+        self.plot_area.bottom = rect.pos.y + rect.size.y - bottom - legend_bottom;
     }
 
     fn data_to_pixel(&self, x: f64, y: f64) -> DVec2 {
@@ -2698,160 +4965,248 @@ impl ScatterPlot {
         dvec2(px, py)
     }
 
+    /// Major and minor tick positions in pixels: x, y, x minor, y minor
+    fn frame_ticks(&self) -> [Vec<f64>; 4] {
+        let x_major = self.x_ticks();
+        let y_major = self.y_ticks();
+        // Category slots get no minor ticks
+        let x_minor = if self.x_categories.is_empty() { minor_ticks(ScaleType::Linear, &x_major, self.x_range) } else { Vec::new() };
+        let y_minor = minor_ticks(ScaleType::Linear, &y_major, self.y_range);
+        let to_x = |xs: Vec<f64>| -> Vec<f64> { xs.into_iter().map(|x| self.data_to_pixel(x, self.y_range.0).x).collect() };
+        let to_y = |ys: Vec<f64>| -> Vec<f64> { ys.into_iter().map(|y| self.data_to_pixel(self.x_range.0, y).y).collect() };
+        [to_x(x_major), to_y(y_major), to_x(x_minor), to_y(y_minor)]
+    }
+
+    /// Pixel x of the left spine or pixel y of the bottom one, where the tick labels hang from
+    fn spine_offset(&self, side: SpineSide) -> f64 {
+        self.spines.get(side).offset(side, &self.plot_area, AxisRange::linear(self.x_range), AxisRange::linear(self.y_range))
+    }
+
     fn draw_grid(&mut self, cx: &mut Cx2d) {
         if !self.show_grid {
             return;
         }
-
-        self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
-
-        // Horizontal grid lines
-        let y_ticks = self.generate_ticks(self.y_range.0, self.y_range.1, 5);
-        for y in &y_ticks {
-            let p1 = self.data_to_pixel(self.x_range.0, *y);
-            let p2 = self.data_to_pixel(self.x_range.1, *y);
-            self.draw_line.draw_line(cx, p1, p2, 0.5);
-        }
-
-        // Vertical grid lines
-        let x_ticks = self.generate_ticks(self.x_range.0, self.x_range.1, 5);
-        for x in &x_ticks {
-            let p1 = self.data_to_pixel(*x, self.y_range.0);
-            let p2 = self.data_to_pixel(*x, self.y_range.1);
-            self.draw_line.draw_line(cx, p1, p2, 0.5);
-        }
+        let [x_ticks, y_ticks, x_minor, y_minor] = self.frame_ticks();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x: AxisRange::linear(self.x_range),
+            y: AxisRange::linear(self.y_range),
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.grid_style.draw(cx, &frame, &mut self.draw_line);
     }
 
     fn draw_axes(&mut self, cx: &mut Cx2d) {
-        self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
-
-        // X axis
-        let x1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let x2 = dvec2(self.plot_area.right, self.plot_area.bottom);
-        self.draw_line.draw_line(cx, x1, x2, 1.0);
-
-        // Y axis
-        let y1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let y2 = dvec2(self.plot_area.left, self.plot_area.top);
-        self.draw_line.draw_line(cx, y1, y2, 1.0);
+        let [x_ticks, y_ticks, x_minor, y_minor] = self.frame_ticks();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x: AxisRange::linear(self.x_range),
+            y: AxisRange::linear(self.y_range),
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.spines.draw(cx, &frame, &self.tick_params, &mut self.draw_line);
     }
 
     fn draw_points(&mut self, cx: &mut Cx2d) {
+        let norm = self.color_norm();
         for (idx, series) in self.series.iter().enumerate() {
-            let color = series.color.unwrap_or_else(|| get_color(idx));
+            if !series.visible {
+                continue;
+            }
+            let series_color = series.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
 
             for i in 0..series.x.len() {
                 let p = self.data_to_pixel(series.x[i], series.y[i]);
+                let color = match series.color_values.as_ref().and_then(|values| values.get(i)) {
+                    Some(value) => self.colormap.sample(norm.normalize(*value)),
+                    None => series_color,
+                };
+                let radius = series.sizes.as_ref().and_then(|sizes| sizes.get(i)).copied().unwrap_or(self.point_radius);
 
                 if self.use_gradient {
                     // Radial gradient using same-hue lighter/darker colors
                     let (center_color, outer_color) = gradient_pair(color);
                     self.draw_point_gradient.color = color;
-                    self.draw_point_gradient.draw_point_gradient(cx, p, self.point_radius, center_color, outer_color);
+                    self.draw_point_gradient.draw_point_gradient(cx, p, radius, center_color, outer_color);
                 } else {
                     self.draw_point.color = color;
-                    self.draw_point.draw_point(cx, p, self.point_radius);
+                    self.draw_point.draw_point(cx, p, radius);
                 }
             }
         }
     }
 
-    fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+    fn y_ticks(&self) -> Vec<f64> {
+        self.generate_ticks(self.y_range.0, self.y_range.1, 5)
+    }
 
-        // X axis tick labels
-        let x_ticks = self.generate_ticks(self.x_range.0, self.x_range.1, 5);
-        for x in &x_ticks {
-            let p = self.data_to_pixel(*x, self.y_range.0);
-            let label = format!("{:.1}", x);
-            self.label.draw_at(cx, dvec2(p.x, p.y + 5.0), &label, TextAnchor::TopCenter);
+    fn x_ticks(&self) -> Vec<f64> {
+        if self.x_categories.is_empty() {
+            self.generate_ticks(self.x_range.0, self.x_range.1, 5)
+        } else {
+            self.x_categories.ticks(self.x_range.0, self.x_range.1)
         }
+    }
+
+    /// Pixel x position and text of each x tick label
+    fn x_tick_labels(&self) -> Vec<(f64, String)> {
+        self.x_ticks().iter().filter_map(|&x| {
+            let text = if self.x_categories.is_empty() {
+                Some(format!("{:.1}", x))
+            } else {
+                // Category labels sit centred under each slot
+                self.x_categories.label(x).map(|name| name.to_string())
+            };
+            text.map(|t| (self.data_to_pixel(x, self.y_range.0).x, t))
+        }).collect()
+    }
+
+    fn y_tick_labels(&self) -> Vec<String> {
+        self.y_ticks().iter().map(|y| format!("{:.1}", y)).collect()
+    }
+
+    /// Reach of the tick marks past the bottom and the left spine
+    fn tick_extents(&self) -> (f64, f64) {
+        (
+            self.tick_params.outward_extent(SpineSide::Bottom, &self.style),
+            self.tick_params.outward_extent(SpineSide::Left, &self.style),
+        )
+    }
+
+    fn draw_labels(&mut self, cx: &mut Cx2d) {
+        self.label.set_color(self.style.text_color);
+
+        // X axis tick labels - laid out by update_plot_area, hung below the tick marks
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let axis_y = self.spine_offset(SpineSide::Bottom);
+        self.x_tick_layout.draw(cx, &mut self.label, axis_y + x_tick_extent);
 
         // Y axis tick labels
         let y_ticks = self.generate_ticks(self.y_range.0, self.y_range.1, 5);
+        let axis_x = self.spine_offset(SpineSide::Left);
         for y in &y_ticks {
             let p = self.data_to_pixel(self.x_range.0, *y);
             let label = format!("{:.1}", y);
-            self.label.draw_at(cx, dvec2(p.x - 5.0, p.y), &label, TextAnchor::MiddleRight);
+            self.label.draw_at(cx, dvec2(axis_x - y_tick_extent - TICK_LABEL_PAD, p.y), &label, TextAnchor::MiddleRight);
         }
 
-        // Title
-        if !self.title.is_empty() {
-            let center_x = (self.plot_area.left + self.plot_area.right) / 2.0;
-            self.label.draw_at(cx, dvec2(center_x, self.plot_area.top - 10.0), &self.title, TextAnchor::BottomCenter);
-        }
+        // Title and axis labels
+        let y_tick_labels = self.y_tick_labels();
+        let (_, twin_top) = self.twins.margins(cx, &mut self.label, &self.style);
+        let text = AxesText { title: &self.title, x_label: &self.x_label, y_label: &self.y_label, y_ticks: &y_tick_labels, title_size: Some(self.style.title_font_size), top_axis: twin_top, x_tick_extent, y_tick_extent };
+        text.draw(cx, &mut self.label, &self.plot_area, &self.x_tick_layout);
     }
 
-    fn draw_legend(&mut self, cx: &mut Cx2d) {
-        if self.legend_position == LegendPosition::None || self.series.is_empty() {
-            return;
-        }
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        self.series.iter().enumerate().map(|(idx, series)| {
+            let color = series.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
+            let marker = if series.marker_style == MarkerStyle::None { MarkerStyle::Circle } else { series.marker_style };
+            LegendEntry::marker(series.label.clone(), color, marker).with_visible(series.visible)
+        }).chain(self.twins.legend_entries(&self.twin_draw())).collect()
+    }
 
-        let padding = 8.0;
-        let line_height = 16.0;
-        let marker_size = 10.0;
-        let marker_text_gap = 6.0;
-        let legend_height = self.series.len() as f64 * line_height + padding * 2.0;
-        let legend_width = 100.0;
-
-        let (legend_x, legend_y) = match self.legend_position {
-            LegendPosition::TopRight => (
-                self.plot_area.right - legend_width - 10.0,
-                self.plot_area.top + 10.0,
-            ),
-            LegendPosition::TopLeft => (
-                self.plot_area.left + 10.0,
-                self.plot_area.top + 10.0,
-            ),
-            LegendPosition::BottomRight => (
-                self.plot_area.right - legend_width - 10.0,
-                self.plot_area.bottom - legend_height - 10.0,
-            ),
-            LegendPosition::BottomLeft => (
-                self.plot_area.left + 10.0,
-                self.plot_area.bottom - legend_height - 10.0,
-            ),
-            LegendPosition::None => return,
-        };
+    fn draw_legend(&mut self, cx: &mut Cx2d, rect: Rect) {
+        let mut points: Vec<DVec2> = self.series.iter()
+            .filter(|s| s.visible)
+            .flat_map(|s| s.x.iter().zip(&s.y).map(|(x, y)| self.data_to_pixel(*x, *y)))
+            .collect();
+        points.extend(self.twins.data_points(&self.twin_draw()));
+        self.legend.draw(cx, &self.plot_area, rect, &points);
+    }
 
-        // Draw legend background
-        self.draw_line.color = vec4(0.95, 0.95, 0.95, 0.9);
-        let bg_rect = Rect {
-            pos: dvec2(legend_x, legend_y),
-            size: dvec2(legend_width, legend_height),
-        };
-        self.draw_line.draw_abs(cx, bg_rect);
+    /// Add a second y axis on the right sharing x, like matplotlib's `twinx`, or return
+    /// the one already there
+    pub fn twinx(&mut self) -> &mut TwinAxis {
+        self.twins.get_or_insert(TwinSide::Right)
+    }
 
-        // Draw legend border
-        self.draw_line.color = vec4(0.8, 0.8, 0.8, 1.0);
-        self.draw_line.draw_line(cx, dvec2(legend_x, legend_y), dvec2(legend_x + legend_width, legend_y), 1.0);
-        self.draw_line.draw_line(cx, dvec2(legend_x, legend_y + legend_height), dvec2(legend_x + legend_width, legend_y + legend_height), 1.0);
-        self.draw_line.draw_line(cx, dvec2(legend_x, legend_y), dvec2(legend_x, legend_y + legend_height), 1.0);
-        self.draw_line.draw_line(cx, dvec2(legend_x + legend_width, legend_y), dvec2(legend_x + legend_width, legend_y + legend_height), 1.0);
+    /// Add a second x axis along the top sharing y, like matplotlib's `twiny`
+    pub fn twiny(&mut self) -> &mut TwinAxis {
+        self.twins.get_or_insert(TwinSide::Top)
+    }
 
-        // Draw legend entries
-        for (idx, series) in self.series.iter().enumerate() {
-            let color = series.color.unwrap_or_else(|| get_color(idx));
-            let entry_y = legend_y + padding + idx as f64 * line_height + line_height / 2.0;
+    /// Attach a twin axis built up front, replacing any twin on the same side
+    pub fn set_twin(&mut self, axis: TwinAxis) {
+        self.twins.set(axis);
+    }
 
-            self.draw_point.color = color;
-            self.draw_point.draw_point(cx, dvec2(legend_x + padding + marker_size / 2.0, entry_y), marker_size / 2.0);
+    /// Add points plotted against the twin y axis on the right
+    pub fn add_twinx_series(&mut self, mut series: Series) {
+        if let Some(ref categories) = series.x_categories {
+            series.x = self.x_categories.positions(categories);
+        }
+        self.twinx().add_series(series);
+    }
 
-            self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
-            self.label.draw_at(
-                cx,
-                dvec2(legend_x + padding + marker_size + marker_text_gap, entry_y),
-                &series.label,
-                TextAnchor::MiddleLeft,
-            );
+    /// Add points plotted against the twin x axis along the top
+    pub fn add_twiny_series(&mut self, series: Series) {
+        self.twiny().add_series(series);
+    }
+
+    pub fn clear_twins(&mut self) {
+        self.twins.clear();
+    }
+
+    /// What the twin axes share with this chart; scatter data is always placed linearly
+    fn twin_draw(&self) -> TwinDraw<'_> {
+        TwinDraw {
+            area: self.plot_area,
+            x: AxisRange::linear(self.x_range),
+            y: AxisRange::linear(self.y_range),
+            style: &self.style,
+            cycle: self.style.cycle(&self.prop_cycle),
+            first_color: self.series.len(),
+            markers_only: true,
         }
     }
 
+    fn draw_twins(&mut self, cx: &mut Cx2d) {
+        // Built field by field so the draw primitives can be borrowed alongside
+        let ctx = TwinDraw {
+            area: self.plot_area,
+            x: AxisRange::linear(self.x_range),
+            y: AxisRange::linear(self.y_range),
+            style: &self.style,
+            cycle: self.style.cycle(&self.prop_cycle),
+            first_color: self.series.len(),
+            markers_only: true,
+        };
+        self.twins.draw(cx, &ctx, &mut self.draw_line, &mut self.draw_point, &mut self.label);
+    }
+
     fn generate_ticks(&self, min: f64, max: f64, count: usize) -> Vec<f64> {
         let step = (max - min) / count as f64;
         (0..=count).map(|i| min + i as f64 * step).collect()
     }
 
+    /// Show, hide, recolour or move one spine, e.g.
+    /// `set_spine(SpineSide::Left, Spine::default().with_position(SpinePosition::Zero))`
+    pub fn set_spine(&mut self, side: SpineSide, spine: Spine) {
+        *self.spines.get_mut(side) = spine;
+    }
+
+    pub fn set_spines(&mut self, spines: Spines) {
+        self.spines = spines;
+    }
+
+    /// Direction, sizes and sides of the tick marks, and whether minor ticks are drawn
+    pub fn set_tick_params(&mut self, params: TickParams) {
+        self.tick_params = params;
+    }
+
+    /// Major and minor grid line styles; `show_grid` still turns the whole grid off
+    pub fn set_grid_style(&mut self, grid: GridStyle) {
+        self.grid_style = grid;
+    }
+
     /// Enable or disable interactive pan/zoom
     pub fn set_interactive(&mut self, interactive: bool) {
         self.interactive = interactive;
@@ -2860,6 +5215,7 @@ impl ScatterPlot {
     /// Reset view to auto-fit all data
     pub fn reset_view(&mut self) {
         self.auto_range();
+        self.twins.iter_mut().for_each(TwinAxis::auto_range);
     }
 
     fn redraw(&mut self, cx: &mut Cx) {
@@ -2868,6 +5224,12 @@ impl ScatterPlot {
 }
 
 impl ScatterPlotRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reset();
+        }
+    }
+
     pub fn add_series(&self, series: Series) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.add_series(series);
@@ -2880,6 +5242,42 @@ impl ScatterPlotRef {
         }
     }
 
+    pub fn set_twin(&self, axis: TwinAxis) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_twin(axis);
+        }
+    }
+
+    pub fn add_twinx_series(&self, series: Series) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_twinx_series(series);
+        }
+    }
+
+    pub fn add_twiny_series(&self, series: Series) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_twiny_series(series);
+        }
+    }
+
+    pub fn clear_twins(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_twins();
+        }
+    }
+
+    pub fn set_x_categories<S: Into<String>>(&self, categories: Vec<S>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_x_categories(categories);
+        }
+    }
+
+    pub fn set_xtick_strategy(&self, strategy: TickLabelStrategy) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_xtick_strategy(strategy);
+        }
+    }
+
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_title(title);
@@ -2910,6 +5308,42 @@ impl ScatterPlotRef {
         }
     }
 
+    pub fn set_legend_columns(&self, columns: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_columns(columns);
+        }
+    }
+
+    pub fn set_legend_title(&self, title: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_title(title);
+        }
+    }
+
+    pub fn set_spine(&self, side: SpineSide, spine: Spine) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spine(side, spine);
+        }
+    }
+
+    pub fn set_spines(&self, spines: Spines) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spines(spines);
+        }
+    }
+
+    pub fn set_tick_params(&self, params: TickParams) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_tick_params(params);
+        }
+    }
+
+    pub fn set_grid_style(&self, grid: GridStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_grid_style(grid);
+        }
+    }
+
     pub fn set_interactive(&self, interactive: bool) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_interactive(interactive);
@@ -2922,6 +5356,18 @@ impl ScatterPlotRef {
         }
     }
 
+    pub fn set_colormap(&self, colormap: Colormap) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_colormap(colormap);
+        }
+    }
+
+    pub fn set_norm(&self, norm: impl Norm + 'static) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_norm(norm);
+        }
+    }
+
     pub fn reset_view(&self) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.reset_view();
@@ -2933,6 +5379,24 @@ impl ScatterPlotRef {
             inner.redraw(cx);
         }
     }
+
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_margins(margins);
+        }
+    }
+
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_prop_cycle(cycle);
+        }
+    }
+
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_style(style);
+        }
+    }
 }
 
 // =============================================================================
@@ -2980,7 +5444,7 @@ pub struct PieChart {
     #[rust]
     slices: Vec<PieSlice>,
 
-    #[rust]
+    #[live]
     title: String,
 
     #[rust(0.8)]
@@ -2989,11 +5453,31 @@ pub struct PieChart {
     #[rust]
     show_labels: bool,
 
-    #[rust]
+    #[live]
     show_percentages: bool,
 
+    #[live]
+    legend: PlotLegend,
+
+    // Flat DSL form of `legend: { position: .. }`
+    #[live]
+    legend_position: Option<LegendPosition>,
+
+    #[rust]
+    prop_cycle: Option<PropCycle>,
+
+    #[live]
+    plot_style: String,
+
+    // Background of the host theme, for the automatic light/dark style
+    #[live]
+    theme_background: Vec4,
+
     #[rust]
-    legend_position: LegendPosition,
+    style: PlotStyle,
+
+    #[rust]
+    style_override: Option<PlotStyle>,
 }
 
 impl Widget for PieChart {
@@ -3002,11 +5486,23 @@ impl Widget for PieChart {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        // Applied once per live update, so later set_legend calls still win
+        if let Some(position) = self.legend_position.take() {
+            self.set_legend(position);
+        }
+        self.style.apply(&mut self.view, &mut self.label);
+        self.legend.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
 
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.slices.is_empty() {
-            self.draw_pie(cx, rect);
+            let entries = self.legend_entries();
+            self.legend.set_entries(entries);
+            // An outside legend takes its room from the pie, not from the title
+            let (right, bottom) = self.legend.outside_margins(cx);
+            let pie_rect = Rect { pos: rect.pos, size: rect.size - dvec2(right, bottom) };
+            self.draw_pie(cx, pie_rect);
             self.draw_title(cx, rect);
             self.draw_legend(cx, rect);
         }
@@ -3024,14 +5520,35 @@ impl PieChart {
         self.slices = slices;
     }
 
-    pub fn set_data(&mut self, labels: Vec<String>, values: Vec<f64>) {
-        self.slices = labels.into_iter().zip(values).map(|(l, v)| PieSlice::new(l, v)).collect();
+    pub fn set_data(&mut self, labels: Vec<String>, values: impl IntoValues) {
+        self.slices = labels.into_iter().zip(values.into_values().iter().copied()).map(|(l, v)| PieSlice::new(l, v)).collect();
     }
 
     pub fn clear(&mut self) {
         self.slices.clear();
     }
 
+    /// Clear the slices and put the title, legend and style back to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.title.clear();
+        self.legend.reset();
+        self.legend_position = None;
+        self.prop_cycle = None;
+        self.style_override = None;
+    }
+
+    /// The chart as a "pie" spec
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            legend: legend_name(self.legend.position),
+            slices: self.slices.iter().map(SliceSpec::from).collect(),
+            ..ChartSpec::new("pie")
+        }
+    }
+
     pub fn set_title(&mut self, title: impl Into<String>) {
         self.title = title.into();
     }
@@ -3045,7 +5562,26 @@ impl PieChart {
     }
 
     pub fn set_legend(&mut self, position: LegendPosition) {
-        self.legend_position = position;
+        self.legend.set_position(position);
+    }
+
+    /// Lay the legend entries out in several columns
+    pub fn set_legend_columns(&mut self, columns: usize) {
+        self.legend.set_columns(columns);
+    }
+
+    pub fn set_legend_title(&mut self, title: impl Into<String>) {
+        self.legend.set_title(title);
+    }
+
+    /// Colours handed out in turn to slices without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
     }
 
     fn draw_pie(&mut self, cx: &mut Cx2d, rect: Rect) {
@@ -3075,7 +5611,7 @@ impl PieChart {
             let shader_start = (start_angle + std::f64::consts::TAU) % std::f64::consts::TAU;
             let shader_end = shader_start + slice_angle;
 
-            let color = slice.color.unwrap_or_else(|| get_color(idx));
+            let color = slice.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
             self.draw_slice.color = color;
             self.draw_slice.draw_slice(cx, center, radius, shader_start, shader_end);
 
@@ -3099,79 +5635,33 @@ impl PieChart {
     fn draw_title(&mut self, cx: &mut Cx2d, rect: Rect) {
         if !self.title.is_empty() {
             let center_x = rect.pos.x + rect.size.x / 2.0;
-            self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+            self.label.set_color(self.style.text_color);
             self.label.draw_at(cx, dvec2(center_x, rect.pos.y + 10.0), &self.title, TextAnchor::TopCenter);
         }
     }
 
-    fn draw_legend(&mut self, cx: &mut Cx2d, rect: Rect) {
-        if self.legend_position == LegendPosition::None || self.slices.is_empty() {
-            return;
-        }
-
-        let padding = 8.0;
-        let line_height = 16.0;
-        let marker_size = 10.0;
-        let marker_text_gap = 6.0;
-        let legend_height = self.slices.len() as f64 * line_height + padding * 2.0;
-        let legend_width = 100.0;
-
-        let (legend_x, legend_y) = match self.legend_position {
-            LegendPosition::TopRight => (
-                rect.pos.x + rect.size.x - legend_width - 10.0,
-                rect.pos.y + 30.0,
-            ),
-            LegendPosition::TopLeft => (
-                rect.pos.x + 10.0,
-                rect.pos.y + 30.0,
-            ),
-            LegendPosition::BottomRight => (
-                rect.pos.x + rect.size.x - legend_width - 10.0,
-                rect.pos.y + rect.size.y - legend_height - 10.0,
-            ),
-            LegendPosition::BottomLeft => (
-                rect.pos.x + 10.0,
-                rect.pos.y + rect.size.y - legend_height - 10.0,
-            ),
-            LegendPosition::None => return,
-        };
-
-        self.draw_line.color = vec4(0.95, 0.95, 0.95, 0.9);
-        let bg_rect = Rect {
-            pos: dvec2(legend_x, legend_y),
-            size: dvec2(legend_width, legend_height),
-        };
-        self.draw_line.draw_abs(cx, bg_rect);
-
-        self.draw_line.color = vec4(0.8, 0.8, 0.8, 1.0);
-        self.draw_line.draw_line(cx, dvec2(legend_x, legend_y), dvec2(legend_x + legend_width, legend_y), 1.0);
-        self.draw_line.draw_line(cx, dvec2(legend_x, legend_y + legend_height), dvec2(legend_x + legend_width, legend_y + legend_height), 1.0);
-        self.draw_line.draw_line(cx, dvec2(legend_x, legend_y), dvec2(legend_x, legend_y + legend_height), 1.0);
-        self.draw_line.draw_line(cx, dvec2(legend_x + legend_width, legend_y), dvec2(legend_x + legend_width, legend_y + legend_height), 1.0);
-
-        for (idx, slice) in self.slices.iter().enumerate() {
-            let color = slice.color.unwrap_or_else(|| get_color(idx));
-            let entry_y = legend_y + padding + idx as f64 * line_height + line_height / 2.0;
-
-            self.draw_line.color = color;
-            let marker_rect = Rect {
-                pos: dvec2(legend_x + padding, entry_y - marker_size / 2.0),
-                size: dvec2(marker_size, marker_size),
-            };
-            self.draw_line.draw_abs(cx, marker_rect);
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        self.slices.iter().enumerate().map(|(idx, slice)| {
+            let color = slice.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
+            LegendEntry::patch(slice.label.clone(), color)
+        }).collect()
+    }
 
-            self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
-            self.label.draw_at(
-                cx,
-                dvec2(legend_x + padding + marker_size + marker_text_gap, entry_y),
-                &slice.label,
-                TextAnchor::MiddleLeft,
-            );
-        }
+    fn draw_legend(&mut self, cx: &mut Cx2d, rect: Rect) {
+        let (right, bottom) = self.legend.outside_margins(cx);
+        let top = if self.title.is_empty() { 10.0 } else { 30.0 };
+        let area = PlotArea::new(rect.pos.x + 10.0, rect.pos.y + top, rect.pos.x + rect.size.x - right - 10.0, rect.pos.y + rect.size.y - bottom - 10.0);
+        self.legend.draw(cx, &area, rect, &[]);
     }
 }
 
 impl PieChartRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reset();
+        }
+    }
+
     pub fn add_slice(&self, slice: PieSlice) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.add_slice(slice);
@@ -3184,7 +5674,7 @@ impl PieChartRef {
         }
     }
 
-    pub fn set_data(&self, labels: Vec<String>, values: Vec<f64>) {
+    pub fn set_data(&self, labels: Vec<String>, values: impl IntoValues) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_data(labels, values);
         }
@@ -3214,9 +5704,33 @@ impl PieChartRef {
         }
     }
 
+    pub fn set_legend_columns(&self, columns: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_columns(columns);
+        }
+    }
+
+    pub fn set_legend_title(&self, title: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_title(title);
+        }
+    }
+
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() {
-            inner.redraw(cx);
+            inner.redraw(cx);
+        }
+    }
+
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_prop_cycle(cycle);
+        }
+    }
+
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_style(style);
         }
     }
 }
@@ -3260,32 +5774,52 @@ pub struct HistogramChart {
     #[rust]
     plot_area: PlotArea,
 
-    #[rust]
+    #[live]
     title: String,
 
-    #[rust]
+    #[live]
     x_label: String,
 
-    #[rust]
+    #[live]
     y_label: String,
 
-    #[rust(true)]
+    #[live(true)]
     show_grid: bool,
 
-    #[rust]
+    #[live]
     bar_color: Option<Vec4>,
 
-    #[rust(50.0)]
-    left_margin: f64,
+    #[rust]
+    x_tick_layout: TickLabelLayout,
+
+    #[live]
+    margins: Margins,
+
+    // Spines, tick marks and grid lines
+    #[rust]
+    spines: Spines,
+
+    #[rust]
+    tick_params: TickParams,
+
+    #[rust]
+    grid_style: GridStyle,
+
+    #[rust]
+    prop_cycle: Option<PropCycle>,
+
+    #[live]
+    plot_style: String,
 
-    #[rust(30.0)]
-    bottom_margin: f64,
+    // Background of the host theme, for the automatic light/dark style
+    #[live]
+    theme_background: Vec4,
 
-    #[rust(20.0)]
-    right_margin: f64,
+    #[rust]
+    style: PlotStyle,
 
-    #[rust(30.0)]
-    top_margin: f64,
+    #[rust]
+    style_override: Option<PlotStyle>,
 }
 
 impl Widget for HistogramChart {
@@ -3294,11 +5828,13 @@ impl Widget for HistogramChart {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
 
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.bins.is_empty() {
-            self.update_plot_area(rect);
+            self.update_plot_area(cx, rect);
             self.draw_grid(cx);
             self.draw_axes(cx);
             self.draw_bars(cx);
@@ -3310,8 +5846,8 @@ impl Widget for HistogramChart {
 }
 
 impl HistogramChart {
-    pub fn set_values(&mut self, values: Vec<f64>) {
-        self.values = values;
+    pub fn set_values(&mut self, values: impl IntoValues) {
+        self.values = values.into_values().into_owned();
         self.compute_bins();
     }
 
@@ -3338,11 +5874,77 @@ impl HistogramChart {
         self.bar_color = Some(color);
     }
 
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
+    }
+
     pub fn clear(&mut self) {
         self.values.clear();
         self.bins.clear();
     }
 
+    /// Clear the samples and put the title, bins, colour, margins and style back to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.num_bins = None;
+        self.bar_color = None;
+        self.title.clear();
+        self.x_label.clear();
+        self.y_label.clear();
+        self.margins = Margins::default();
+        self.spines = Spines::default();
+        self.tick_params = TickParams::default();
+        self.grid_style = GridStyle::default();
+        self.prop_cycle = None;
+        self.style_override = None;
+    }
+
+    /// The chart as a "histogram" spec
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            x_label: self.x_label.clone(),
+            y_label: self.y_label.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            margins: margins_spec(&self.margins),
+            color: self.bar_color.map(to_hex),
+            bins: self.num_bins,
+            values: self.values.clone(),
+            ..ChartSpec::new("histogram")
+        }
+    }
+
+    /// Colour cycle; the bars take its first colour unless a bar colour is set
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    /// Show, hide, recolour or move one spine, e.g.
+    /// `set_spine(SpineSide::Left, Spine::default().with_position(SpinePosition::Zero))`
+    pub fn set_spine(&mut self, side: SpineSide, spine: Spine) {
+        *self.spines.get_mut(side) = spine;
+    }
+
+    pub fn set_spines(&mut self, spines: Spines) {
+        self.spines = spines;
+    }
+
+    /// Direction, sizes and sides of the tick marks, and whether minor ticks are drawn
+    pub fn set_tick_params(&mut self, params: TickParams) {
+        self.tick_params = params;
+    }
+
+    /// Major and minor grid line styles, drawn along the value axis; `show_grid` still turns the whole grid off
+    pub fn set_grid_style(&mut self, grid: GridStyle) {
+        self.grid_style = grid;
+    }
+
     fn compute_bins(&mut self) {
         if self.values.is_empty() {
             self.bins.clear();
@@ -3372,13 +5974,38 @@ impl HistogramChart {
         }
     }
 
-    fn update_plot_area(&mut self, rect: Rect) {
+    fn update_plot_area(&mut self, cx: &mut Cx2d, rect: Rect) {
+        // Tight layout: derive the plot area from the measured text around the axes
+        let y_ticks = self.y_tick_labels();
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let text = AxesText { title: &self.title, x_label: &self.x_label, y_label: &self.y_label, y_ticks: &y_ticks, title_size: Some(self.style.title_font_size), x_tick_extent, y_tick_extent, ..Default::default() };
+        let (left, right) = self.margins.columns(cx, &mut self.label, &text, 0.0);
         self.plot_area = PlotArea::new(
-            rect.pos.x + self.left_margin,
-            rect.pos.y + self.top_margin,
-            rect.pos.x + rect.size.x - self.right_margin,
-            rect.pos.y + rect.size.y - self.bottom_margin,
+            rect.pos.x + left,
+            rect.pos.y,
+            rect.pos.x + rect.size.x - right,
+            rect.pos.y + rect.size.y,
         );
+
+        // X tick labels depend on the final width, the bottom margin on their layout
+        let x_ticks = self.x_tick_labels();
+        self.x_tick_layout = TickLabelLayout::compute(cx, &mut self.label, x_ticks, TickLabelStrategy::Auto);
+        let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &self.x_tick_layout);
+        self.plot_area.top = rect.pos.y + top;
+        self.plot_area.bottom = rect.pos.y + rect.size.y - bottom;
+    }
+
+    fn y_tick_labels(&self) -> Vec<String> {
+        let (_, (y_min, y_max)) = self.get_ranges();
+        self.generate_ticks(y_min, y_max, 5).iter().map(|y| format!("{:.0}", y)).collect()
+    }
+
+    /// Pixel x position and text of each x tick label
+    fn x_tick_labels(&self) -> Vec<(f64, String)> {
+        let ((x_min, x_max), (y_min, _)) = self.get_ranges();
+        self.generate_ticks(x_min, x_max, 5).iter()
+            .map(|x| (self.data_to_pixel(*x, y_min).x, format!("{:.1}", x)))
+            .collect()
     }
 
     fn get_ranges(&self) -> ((f64, f64), (f64, f64)) {
@@ -3400,36 +6027,77 @@ impl HistogramChart {
         dvec2(px, py)
     }
 
+    fn axis_ranges(&self) -> (AxisRange, AxisRange) {
+        let (x, y) = self.get_ranges();
+        (AxisRange::linear(x), AxisRange::linear(y))
+    }
+
+    /// Major and minor tick positions in pixels: x, y, x minor, y minor
+    fn frame_ticks(&self) -> [Vec<f64>; 4] {
+        let ((x_min, x_max), (y_min, y_max)) = self.get_ranges();
+        let x_major = self.generate_ticks(x_min, x_max, 5);
+        let y_major = self.generate_ticks(y_min, y_max, 5);
+        let x_minor = minor_ticks(ScaleType::Linear, &x_major, (x_min, x_max));
+        let y_minor = minor_ticks(ScaleType::Linear, &y_major, (y_min, y_max));
+        let to_x = |xs: Vec<f64>| -> Vec<f64> { xs.into_iter().map(|x| self.data_to_pixel(x, y_min).x).collect() };
+        let to_y = |ys: Vec<f64>| -> Vec<f64> { ys.into_iter().map(|y| self.data_to_pixel(x_min, y).y).collect() };
+        [to_x(x_major), to_y(y_major), to_x(x_minor), to_y(y_minor)]
+    }
+
+    /// Pixel x of the left spine or pixel y of the bottom one, where the tick labels hang from
+    fn spine_offset(&self, side: SpineSide) -> f64 {
+        let (x, y) = self.axis_ranges();
+        self.spines.get(side).offset(side, &self.plot_area, x, y)
+    }
+
+    /// Reach of the tick marks past the bottom and the left spine
+    fn tick_extents(&self) -> (f64, f64) {
+        (
+            self.tick_params.outward_extent(SpineSide::Bottom, &self.style),
+            self.tick_params.outward_extent(SpineSide::Left, &self.style),
+        )
+    }
+
     fn draw_grid(&mut self, cx: &mut Cx2d) {
         if !self.show_grid {
             return;
         }
 
-        self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
-        let (_, (y_min, y_max)) = self.get_ranges();
-
-        let y_ticks = self.generate_ticks(y_min, y_max, 5);
-        for y in &y_ticks {
-            let p1 = self.data_to_pixel(self.bins.first().map(|b| b.left).unwrap_or(0.0), *y);
-            let p2 = self.data_to_pixel(self.bins.last().map(|b| b.right).unwrap_or(1.0), *y);
-            self.draw_line.draw_line(cx, p1, p2, 0.5);
-        }
+        // Lines along the value axis only
+        let [_, y_ticks, _, y_minor] = self.frame_ticks();
+        let (x_ticks, x_minor): (Vec<f64>, Vec<f64>) = (Vec::new(), Vec::new());
+        let (x, y) = self.axis_ranges();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.grid_style.draw(cx, &frame, &mut self.draw_line);
     }
 
     fn draw_axes(&mut self, cx: &mut Cx2d) {
-        self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
-
-        let x1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let x2 = dvec2(self.plot_area.right, self.plot_area.bottom);
-        self.draw_line.draw_line(cx, x1, x2, 1.0);
-
-        let y1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let y2 = dvec2(self.plot_area.left, self.plot_area.top);
-        self.draw_line.draw_line(cx, y1, y2, 1.0);
+        let [x_ticks, y_ticks, x_minor, y_minor] = self.frame_ticks();
+        let (x, y) = self.axis_ranges();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.spines.draw(cx, &frame, &self.tick_params, &mut self.draw_line);
     }
 
     fn draw_bars(&mut self, cx: &mut Cx2d) {
-        let color = self.bar_color.unwrap_or_else(|| get_color(0));
+        let color = self.bar_color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(0));
         self.draw_bar.color = color;
 
         for bin in &self.bins {
@@ -3445,28 +6113,26 @@ impl HistogramChart {
     }
 
     fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+        self.label.set_color(self.style.text_color);
 
-        let ((x_min, x_max), (y_min, y_max)) = self.get_ranges();
+        let ((x_min, _), (y_min, y_max)) = self.get_ranges();
 
-        let x_ticks = self.generate_ticks(x_min, x_max, 5);
-        for x in &x_ticks {
-            let p = self.data_to_pixel(*x, y_min);
-            let label = format!("{:.1}", x);
-            self.label.draw_at(cx, dvec2(p.x, p.y + 5.0), &label, TextAnchor::TopCenter);
-        }
+        // X tick labels - laid out by update_plot_area, hung below the tick marks
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let axis_y = self.spine_offset(SpineSide::Bottom);
+        self.x_tick_layout.draw(cx, &mut self.label, axis_y + x_tick_extent);
 
+        let label_x = self.spine_offset(SpineSide::Left) - y_tick_extent - TICK_LABEL_PAD;
         let y_ticks = self.generate_ticks(y_min, y_max, 5);
         for y in &y_ticks {
             let p = self.data_to_pixel(x_min, *y);
             let label = format!("{:.0}", y);
-            self.label.draw_at(cx, dvec2(p.x - 5.0, p.y), &label, TextAnchor::MiddleRight);
+            self.label.draw_at(cx, dvec2(label_x, p.y), &label, TextAnchor::MiddleRight);
         }
 
-        if !self.title.is_empty() {
-            let center_x = (self.plot_area.left + self.plot_area.right) / 2.0;
-            self.label.draw_at(cx, dvec2(center_x, self.plot_area.top - 10.0), &self.title, TextAnchor::BottomCenter);
-        }
+        let y_tick_labels = self.y_tick_labels();
+        let text = AxesText { title: &self.title, x_label: &self.x_label, y_label: &self.y_label, y_ticks: &y_tick_labels, title_size: Some(self.style.title_font_size), x_tick_extent, y_tick_extent, ..Default::default() };
+        text.draw(cx, &mut self.label, &self.plot_area, &self.x_tick_layout);
     }
 
     fn generate_ticks(&self, min: f64, max: f64, count: usize) -> Vec<f64> {
@@ -3476,7 +6142,13 @@ impl HistogramChart {
 }
 
 impl HistogramChartRef {
-    pub fn set_values(&self, values: Vec<f64>) {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reset();
+        }
+    }
+
+    pub fn set_values(&self, values: impl IntoValues) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_values(values);
         }
@@ -3523,6 +6195,48 @@ impl HistogramChartRef {
             inner.redraw(cx);
         }
     }
+
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_margins(margins);
+        }
+    }
+
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_prop_cycle(cycle);
+        }
+    }
+
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_style(style);
+        }
+    }
+
+    pub fn set_spine(&self, side: SpineSide, spine: Spine) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spine(side, spine);
+        }
+    }
+
+    pub fn set_spines(&self, spines: Spines) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spines(spines);
+        }
+    }
+
+    pub fn set_tick_params(&self, params: TickParams) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_tick_params(params);
+        }
+    }
+
+    pub fn set_grid_style(&self, grid: GridStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_grid_style(grid);
+        }
+    }
 }
 
 // =============================================================================
@@ -3631,29 +6345,52 @@ pub struct BoxPlotChart {
     #[rust]
     plot_area: PlotArea,
 
-    #[rust]
+    #[live]
     title: String,
 
-    #[rust(true)]
+    #[live(true)]
     show_grid: bool,
 
     #[rust(true)]
     show_outliers: bool,
 
-    #[rust(50.0)]
-    left_margin: f64,
+    #[live]
+    margins: Margins,
+
+    #[rust]
+    x_tick_strategy: TickLabelStrategy,
+
+    #[rust]
+    x_tick_layout: TickLabelLayout,
 
-    #[rust(40.0)]
-    bottom_margin: f64,
+    // Spines, tick marks and grid lines
+    #[rust]
+    spines: Spines,
 
-    #[rust(20.0)]
-    right_margin: f64,
+    #[rust]
+    tick_params: TickParams,
 
-    #[rust(30.0)]
-    top_margin: f64,
+    #[rust]
+    grid_style: GridStyle,
 
     #[rust(0.6)]
     box_width_ratio: f64,
+
+    #[rust]
+    prop_cycle: Option<PropCycle>,
+
+    #[live]
+    plot_style: String,
+
+    // Background of the host theme, for the automatic light/dark style
+    #[live]
+    theme_background: Vec4,
+
+    #[rust]
+    style: PlotStyle,
+
+    #[rust]
+    style_override: Option<PlotStyle>,
 }
 
 impl Widget for BoxPlotChart {
@@ -3662,11 +6399,13 @@ impl Widget for BoxPlotChart {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
 
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.items.is_empty() {
-            self.update_plot_area(rect);
+            self.update_plot_area(cx, rect);
             self.draw_grid(cx);
             self.draw_axes(cx);
             self.draw_boxes(cx);
@@ -3700,13 +6439,77 @@ impl BoxPlotChart {
         self.show_outliers = show;
     }
 
-    fn update_plot_area(&mut self, rect: Rect) {
+    /// Colours handed out in turn to boxes without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    pub fn set_xtick_strategy(&mut self, strategy: TickLabelStrategy) {
+        self.x_tick_strategy = strategy;
+    }
+
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
+    }
+
+    /// Show, hide, recolour or move one spine, e.g.
+    /// `set_spine(SpineSide::Left, Spine::default().with_position(SpinePosition::Zero))`
+    pub fn set_spine(&mut self, side: SpineSide, spine: Spine) {
+        *self.spines.get_mut(side) = spine;
+    }
+
+    pub fn set_spines(&mut self, spines: Spines) {
+        self.spines = spines;
+    }
+
+    /// Direction, sizes and sides of the tick marks, and whether minor ticks are drawn
+    pub fn set_tick_params(&mut self, params: TickParams) {
+        self.tick_params = params;
+    }
+
+    /// Major and minor grid line styles, drawn along the value axis; `show_grid` still turns the whole grid off
+    pub fn set_grid_style(&mut self, grid: GridStyle) {
+        self.grid_style = grid;
+    }
+
+    fn update_plot_area(&mut self, cx: &mut Cx2d, rect: Rect) {
+        // Tight layout: derive the plot area from the measured text around the axes
+        let y_ticks = self.y_tick_labels();
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let text = AxesText { title: &self.title, y_ticks: &y_ticks, title_size: Some(self.style.title_font_size), x_tick_extent, y_tick_extent, ..Default::default() };
+        let (left, right) = self.margins.columns(cx, &mut self.label, &text, 0.0);
         self.plot_area = PlotArea::new(
-            rect.pos.x + self.left_margin,
-            rect.pos.y + self.top_margin,
-            rect.pos.x + rect.size.x - self.right_margin,
-            rect.pos.y + rect.size.y - self.bottom_margin,
+            rect.pos.x + left,
+            rect.pos.y,
+            rect.pos.x + rect.size.x - right,
+            rect.pos.y + rect.size.y,
         );
+
+        // Category labels depend on the final width, the bottom margin on their layout
+        let x_ticks = self.x_tick_labels();
+        self.x_tick_layout = TickLabelLayout::compute(cx, &mut self.label, x_ticks, self.x_tick_strategy);
+        let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &self.x_tick_layout);
+        self.plot_area.top = rect.pos.y + top;
+        self.plot_area.bottom = rect.pos.y + rect.size.y - bottom;
+    }
+
+    fn y_tick_labels(&self) -> Vec<String> {
+        let (y_min, y_max) = self.get_y_range();
+        self.generate_ticks(y_min, y_max, 5).iter().map(|y| format!("{:.0}", y)).collect()
+    }
+
+    /// Pixel x position and label of each box
+    fn x_tick_labels(&self) -> Vec<(f64, String)> {
+        let band_width = self.plot_area.width() / self.items.len().max(1) as f64;
+        self.items.iter().enumerate()
+            .map(|(i, item)| (self.plot_area.left + (i as f64 + 0.5) * band_width, item.label.clone()))
+            .collect()
     }
 
     fn get_y_range(&self) -> (f64, f64) {
@@ -3728,33 +6531,73 @@ impl BoxPlotChart {
         (min - padding, max + padding)
     }
 
+    /// Box slots along x, values along y
+    fn axis_ranges(&self) -> (AxisRange, AxisRange) {
+        (AxisRange::linear((-0.5, self.items.len() as f64 - 0.5)), AxisRange::linear(self.get_y_range()))
+    }
+
+    /// Major and minor tick positions in pixels: x, y, x minor, y minor. Each box gets
+    /// a major tick and no minor ones.
+    fn frame_ticks(&self) -> [Vec<f64>; 4] {
+        let (y_min, y_max) = self.get_y_range();
+        let y_major = self.generate_ticks(y_min, y_max, 5);
+        let y_minor = minor_ticks(ScaleType::Linear, &y_major, (y_min, y_max));
+        let (x, y) = self.axis_ranges();
+        let to_y = |ys: Vec<f64>| -> Vec<f64> { ys.into_iter().map(|v| self.plot_area.bottom - y.fraction(v) * self.plot_area.height()).collect() };
+        let x_major = (0..self.items.len()).map(|i| self.plot_area.left + x.fraction(i as f64) * self.plot_area.width()).collect();
+        [x_major, to_y(y_major), Vec::new(), to_y(y_minor)]
+    }
+
+    /// Pixel x of the left spine or pixel y of the bottom one, where the tick labels hang from
+    fn spine_offset(&self, side: SpineSide) -> f64 {
+        let (x, y) = self.axis_ranges();
+        self.spines.get(side).offset(side, &self.plot_area, x, y)
+    }
+
+    /// Reach of the tick marks past the bottom and the left spine
+    fn tick_extents(&self) -> (f64, f64) {
+        (
+            self.tick_params.outward_extent(SpineSide::Bottom, &self.style),
+            self.tick_params.outward_extent(SpineSide::Left, &self.style),
+        )
+    }
+
     fn draw_grid(&mut self, cx: &mut Cx2d) {
         if !self.show_grid {
             return;
         }
 
-        self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
-        let (y_min, y_max) = self.get_y_range();
-
-        let y_ticks = self.generate_ticks(y_min, y_max, 5);
-        for y in &y_ticks {
-            let y_pixel = self.plot_area.bottom - (*y - y_min) / (y_max - y_min) * self.plot_area.height();
-            let p1 = dvec2(self.plot_area.left, y_pixel);
-            let p2 = dvec2(self.plot_area.right, y_pixel);
-            self.draw_line.draw_line(cx, p1, p2, 0.5);
-        }
+        // Lines along the value axis only
+        let [_, y_ticks, _, y_minor] = self.frame_ticks();
+        let (x_ticks, x_minor): (Vec<f64>, Vec<f64>) = (Vec::new(), Vec::new());
+        let (x, y) = self.axis_ranges();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.grid_style.draw(cx, &frame, &mut self.draw_line);
     }
 
     fn draw_axes(&mut self, cx: &mut Cx2d) {
-        self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
-
-        let x1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let x2 = dvec2(self.plot_area.right, self.plot_area.bottom);
-        self.draw_line.draw_line(cx, x1, x2, 1.0);
-
-        let y1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let y2 = dvec2(self.plot_area.left, self.plot_area.top);
-        self.draw_line.draw_line(cx, y1, y2, 1.0);
+        let [x_ticks, y_ticks, x_minor, y_minor] = self.frame_ticks();
+        let (x, y) = self.axis_ranges();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.spines.draw(cx, &frame, &self.tick_params, &mut self.draw_line);
     }
 
     fn draw_boxes(&mut self, cx: &mut Cx2d) {
@@ -3768,7 +6611,7 @@ impl BoxPlotChart {
         let box_width = band_width * self.box_width_ratio;
 
         for (i, item) in self.items.iter().enumerate() {
-            let color = item.color.unwrap_or_else(|| get_color(i));
+            let color = item.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(i));
             let x_center = self.plot_area.left + (i as f64 + 0.5) * band_width;
 
             let y_to_pixel = |y: f64| -> f64 {
@@ -3799,7 +6642,7 @@ impl BoxPlotChart {
             );
 
             // Draw whiskers
-            self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+            self.draw_line.color = self.style.axes_color;
 
             // Lower whisker
             self.draw_line.draw_line(
@@ -3843,478 +6686,117 @@ impl BoxPlotChart {
     }
 
     fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
-
-        let n = self.items.len();
-        let band_width = self.plot_area.width() / n as f64;
+        self.label.set_color(self.style.text_color);
 
-        // Category labels
-        for (i, item) in self.items.iter().enumerate() {
-            let x = self.plot_area.left + (i as f64 + 0.5) * band_width;
-            let y = self.plot_area.bottom + 5.0;
-            self.label.draw_at(cx, dvec2(x, y), &item.label, TextAnchor::TopCenter);
-        }
+        // Category labels - laid out by update_plot_area, hung below the tick marks
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let axis_y = self.spine_offset(SpineSide::Bottom);
+        self.x_tick_layout.draw(cx, &mut self.label, axis_y + x_tick_extent);
 
         // Y axis tick labels
         let (y_min, y_max) = self.get_y_range();
+        let label_x = self.spine_offset(SpineSide::Left) - y_tick_extent - TICK_LABEL_PAD;
         let y_ticks = self.generate_ticks(y_min, y_max, 5);
         for y in &y_ticks {
             let y_pixel = self.plot_area.bottom - (*y - y_min) / (y_max - y_min) * self.plot_area.height();
             let label = format!("{:.0}", y);
-            self.label.draw_at(cx, dvec2(self.plot_area.left - 5.0, y_pixel), &label, TextAnchor::MiddleRight);
+            self.label.draw_at(cx, dvec2(label_x, y_pixel), &label, TextAnchor::MiddleRight);
         }
 
         // Title
-        if !self.title.is_empty() {
-            let center_x = (self.plot_area.left + self.plot_area.right) / 2.0;
-            self.label.draw_at(cx, dvec2(center_x, self.plot_area.top - 10.0), &self.title, TextAnchor::BottomCenter);
-        }
+        let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), x_tick_extent, y_tick_extent, ..Default::default() };
+        text.draw(cx, &mut self.label, &self.plot_area, &self.x_tick_layout);
     }
 
     fn generate_ticks(&self, min: f64, max: f64, count: usize) -> Vec<f64> {
-        let step = (max - min) / count as f64;
-        (0..=count).map(|i| min + i as f64 * step).collect()
-    }
-}
-
-impl BoxPlotChartRef {
-    pub fn add_item(&self, item: BoxPlotItem) {
-        if let Some(mut inner) = self.borrow_mut() {
-            inner.add_item(item);
-        }
-    }
-
-    pub fn add_from_values(&self, label: impl Into<String>, values: &[f64]) {
-        if let Some(mut inner) = self.borrow_mut() {
-            inner.add_from_values(label, values);
-        }
-    }
-
-    pub fn clear(&self) {
-        if let Some(mut inner) = self.borrow_mut() {
-            inner.clear();
-        }
-    }
-
-    pub fn set_title(&self, title: impl Into<String>) {
-        if let Some(mut inner) = self.borrow_mut() {
-            inner.set_title(title);
-        }
-    }
-
-    pub fn set_show_outliers(&self, show: bool) {
-        if let Some(mut inner) = self.borrow_mut() {
-            inner.set_show_outliers(show);
-        }
-    }
-
-    pub fn redraw(&self, cx: &mut Cx) {
-        if let Some(mut inner) = self.borrow_mut() {
-            inner.redraw(cx);
-        }
-    }
-}
-
-// =============================================================================
-// HeatmapChart Widget
-// =============================================================================
-
-/// Colormap for heatmap and other visualizations
-#[derive(Clone, Debug, PartialEq)]
-pub enum Colormap {
-    // Perceptually uniform sequential
-    Viridis,
-    Plasma,
-    Inferno,
-    Magma,
-    Cividis,  // Colorblind-friendly
-    // Diverging
-    Coolwarm,
-    RdBu,     // Red-Blue diverging
-    Spectral,
-    // Sequential
-    Blues,
-    Greens,
-    Oranges,
-    Reds,
-    Greys,
-    // Classic
-    Jet,      // Rainbow (legacy)
-    Hot,      // Black-Red-Yellow-White
-    // Special
-    Turbo,    // Improved rainbow
-    Custom(Vec<(f64, Vec4)>),  // User-defined color stops
-}
-
-impl Default for Colormap {
-    fn default() -> Self {
-        Colormap::Viridis
-    }
-}
-
-impl Colormap {
-    /// Sample a color from the colormap at position t (0.0 to 1.0)
-    pub fn sample(&self, t: f64) -> Vec4 {
-        let t = t.clamp(0.0, 1.0);
-        match self {
-            Colormap::Viridis => {
-                // Perceptually uniform purple-green-yellow
-                let r = 0.267 + t * 0.329 - t * t * 0.5 + t * t * t * 0.9;
-                let g = 0.004 + t * 0.873;
-                let b = 0.329 + t * 0.5 - t * t * 0.6;
-                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
-            }
-            Colormap::Plasma => {
-                // Purple-red-yellow
-                let r = 0.05 + t * 0.9 + t * t * 0.05;
-                let g = t * t * 0.9;
-                let b = 0.53 + (1.0 - t) * 0.47 - t * t * 0.6;
-                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
-            }
-            Colormap::Inferno => {
-                // Black-purple-red-yellow
-                let r = t * t * 1.2;
-                let g = t * t * t * 1.5;
-                let b = (1.0 - t) * t * 2.0 + 0.1 * t;
-                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
-            }
-            Colormap::Magma => {
-                // Black-purple-pink-white
-                let r = t * t * 0.8 + t * 0.2;
-                let g = t * t * t * 1.2;
-                let b = t * 0.5 + (1.0 - t) * t * 1.0;
-                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
-            }
-            Colormap::Cividis => {
-                // Colorblind-friendly blue-yellow
-                let r = -0.01 + t * 1.0 + t * t * 0.01;
-                let g = 0.14 + t * 0.72;
-                let b = 0.35 + t * 0.1 - t * t * 0.35;
-                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
-            }
-            Colormap::Coolwarm => {
-                // Blue-white-red diverging
-                let r = if t < 0.5 { 0.2 + t * 1.6 } else { 1.0 };
-                let g = if t < 0.5 { 0.2 + t * 1.0 } else { 1.0 - (t - 0.5) * 1.6 };
-                let b = if t < 0.5 { 1.0 } else { 1.0 - (t - 0.5) * 1.6 };
-                vec4(r as f32, g as f32, b as f32, 1.0)
-            }
-            Colormap::RdBu => {
-                // Red-white-blue diverging (red=high, blue=low)
-                let r = if t < 0.5 { 0.1 + t * 1.8 } else { 1.0 - (t - 0.5) * 1.4 };
-                let g = if t < 0.5 { t * 1.8 } else { 0.9 - (t - 0.5) * 1.6 };
-                let b = if t < 0.5 { 1.0 - t * 0.2 } else { 0.9 - (t - 0.5) * 1.0 };
-                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
-            }
-            Colormap::Spectral => {
-                // Red-orange-yellow-green-blue (diverging rainbow)
-                let (r, g, b) = if t < 0.25 {
-                    let s = t / 0.25;
-                    (0.62 + s * 0.38, 0.0 + s * 0.5, 0.26 * (1.0 - s))
-                } else if t < 0.5 {
-                    let s = (t - 0.25) / 0.25;
-                    (1.0, 0.5 + s * 0.5, 0.0)
-                } else if t < 0.75 {
-                    let s = (t - 0.5) / 0.25;
-                    (1.0 - s * 0.5, 1.0 - s * 0.2, s * 0.4)
-                } else {
-                    let s = (t - 0.75) / 0.25;
-                    (0.5 - s * 0.3, 0.8 - s * 0.4, 0.4 + s * 0.6)
-                };
-                vec4(r as f32, g as f32, b as f32, 1.0)
-            }
-            Colormap::Blues => {
-                let r = 1.0 - t * 0.8;
-                let g = 1.0 - t * 0.5;
-                let b = 1.0;
-                vec4(r as f32, g as f32, b as f32, 1.0)
-            }
-            Colormap::Greens => {
-                let r = 1.0 - t * 0.75;
-                let g = 1.0 - t * 0.15;
-                let b = 1.0 - t * 0.7;
-                vec4(r as f32, g as f32, b as f32, 1.0)
-            }
-            Colormap::Oranges => {
-                let r = 1.0;
-                let g = 1.0 - t * 0.6;
-                let b = 1.0 - t * 0.85;
-                vec4(r as f32, g as f32, b as f32, 1.0)
-            }
-            Colormap::Reds => {
-                let r = 1.0;
-                let g = 1.0 - t * 0.85;
-                let b = 1.0 - t * 0.85;
-                vec4(r as f32, g as f32, b as f32, 1.0)
-            }
-            Colormap::Greys => {
-                let v = 1.0 - t * 0.9;
-                vec4(v as f32, v as f32, v as f32, 1.0)
-            }
-            Colormap::Jet => {
-                // Classic rainbow: blue-cyan-green-yellow-red
-                let (r, g, b) = if t < 0.125 {
-                    (0.0, 0.0, 0.5 + t * 4.0)
-                } else if t < 0.375 {
-                    let s = (t - 0.125) / 0.25;
-                    (0.0, s, 1.0)
-                } else if t < 0.625 {
-                    let s = (t - 0.375) / 0.25;
-                    (s, 1.0, 1.0 - s)
-                } else if t < 0.875 {
-                    let s = (t - 0.625) / 0.25;
-                    (1.0, 1.0 - s, 0.0)
-                } else {
-                    let s = (t - 0.875) / 0.125;
-                    (1.0 - s * 0.5, 0.0, 0.0)
-                };
-                vec4(r as f32, g as f32, b as f32, 1.0)
-            }
-            Colormap::Hot => {
-                // Black-red-yellow-white
-                let (r, g, b) = if t < 0.33 {
-                    (t * 3.0, 0.0, 0.0)
-                } else if t < 0.67 {
-                    let s = (t - 0.33) / 0.34;
-                    (1.0, s, 0.0)
-                } else {
-                    let s = (t - 0.67) / 0.33;
-                    (1.0, 1.0, s)
-                };
-                vec4(r as f32, g as f32, b as f32, 1.0)
-            }
-            Colormap::Turbo => {
-                // Improved rainbow with better perceptual uniformity
-                let r = 0.13572 + t * (4.6153 + t * (-42.66 + t * (132.13 + t * (-152.95 + t * 56.31))));
-                let g = 0.09140 + t * (2.1745 + t * (4.8321 + t * (-36.60 + t * (43.05 + t * (-13.22)))));
-                let b = 0.10667 + t * (12.755 + t * (-60.58 + t * (109.33 + t * (-87.15 + t * 25.25))));
-                vec4(r.clamp(0.0, 1.0) as f32, g.clamp(0.0, 1.0) as f32, b.clamp(0.0, 1.0) as f32, 1.0)
-            }
-            Colormap::Custom(stops) => {
-                if stops.is_empty() {
-                    return vec4(0.5, 0.5, 0.5, 1.0);
-                }
-                if stops.len() == 1 {
-                    return stops[0].1;
-                }
-                // Find surrounding stops and interpolate
-                for i in 0..stops.len() - 1 {
-                    if t <= stops[i + 1].0 {
-                        let t0 = stops[i].0;
-                        let t1 = stops[i + 1].0;
-                        let c0 = stops[i].1;
-                        let c1 = stops[i + 1].1;
-                        let s = if t1 > t0 { (t - t0) / (t1 - t0) } else { 0.0 };
-                        return vec4(
-                            c0.x + (c1.x - c0.x) * s as f32,
-                            c0.y + (c1.y - c0.y) * s as f32,
-                            c0.z + (c1.z - c0.z) * s as f32,
-                            c0.w + (c1.w - c0.w) * s as f32,
-                        );
-                    }
-                }
-                stops.last().unwrap().1
-            }
-        }
-    }
-
-    /// Get a list of all named colormaps
-    pub fn all_named() -> Vec<Colormap> {
-        vec![
-            Colormap::Viridis,
-            Colormap::Plasma,
-            Colormap::Inferno,
-            Colormap::Magma,
-            Colormap::Cividis,
-            Colormap::Coolwarm,
-            Colormap::RdBu,
-            Colormap::Spectral,
-            Colormap::Blues,
-            Colormap::Greens,
-            Colormap::Oranges,
-            Colormap::Reds,
-            Colormap::Greys,
-            Colormap::Jet,
-            Colormap::Hot,
-            Colormap::Turbo,
-        ]
-    }
-
-    /// Get the name of this colormap
-    pub fn name(&self) -> &'static str {
-        match self {
-            Colormap::Viridis => "Viridis",
-            Colormap::Plasma => "Plasma",
-            Colormap::Inferno => "Inferno",
-            Colormap::Magma => "Magma",
-            Colormap::Cividis => "Cividis",
-            Colormap::Coolwarm => "Coolwarm",
-            Colormap::RdBu => "RdBu",
-            Colormap::Spectral => "Spectral",
-            Colormap::Blues => "Blues",
-            Colormap::Greens => "Greens",
-            Colormap::Oranges => "Oranges",
-            Colormap::Reds => "Reds",
-            Colormap::Greys => "Greys",
-            Colormap::Jet => "Jet",
-            Colormap::Hot => "Hot",
-            Colormap::Turbo => "Turbo",
-            Colormap::Custom(_) => "Custom",
-        }
-    }
-
-    /// Create a custom colormap from color stops
-    /// Stops should be (position, color) pairs where position is 0.0 to 1.0
-    pub fn custom(stops: Vec<(f64, Vec4)>) -> Self {
-        Colormap::Custom(stops)
-    }
-}
-
-// =============================================================================
-// Normalization for Colormaps
-// =============================================================================
-
-/// Linear normalization: maps [vmin, vmax] to [0, 1]
-#[derive(Clone, Debug)]
-pub struct Normalize {
-    pub vmin: f64,
-    pub vmax: f64,
-    pub clip: bool,
-}
-
-impl Normalize {
-    pub fn new(vmin: f64, vmax: f64) -> Self {
-        Self { vmin, vmax, clip: true }
+        let step = (max - min) / count as f64;
+        (0..=count).map(|i| min + i as f64 * step).collect()
     }
+}
 
-    pub fn with_clip(mut self, clip: bool) -> Self {
-        self.clip = clip;
-        self
+impl BoxPlotChartRef {
+    pub fn add_item(&self, item: BoxPlotItem) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_item(item);
+        }
     }
 
-    /// Normalize a value to [0, 1]
-    pub fn normalize(&self, value: f64) -> f64 {
-        if self.vmax == self.vmin {
-            return 0.5;
+    pub fn add_from_values(&self, label: impl Into<String>, values: &[f64]) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_from_values(label, values);
         }
-        let t = (value - self.vmin) / (self.vmax - self.vmin);
-        if self.clip { t.clamp(0.0, 1.0) } else { t }
     }
 
-    /// Inverse: convert [0, 1] back to original scale
-    pub fn inverse(&self, t: f64) -> f64 {
-        self.vmin + t * (self.vmax - self.vmin)
+    pub fn clear(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear();
+        }
     }
-}
 
-impl Default for Normalize {
-    fn default() -> Self {
-        Self { vmin: 0.0, vmax: 1.0, clip: true }
+    pub fn set_title(&self, title: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_title(title);
+        }
     }
-}
 
-/// Logarithmic normalization: maps [vmin, vmax] to [0, 1] on log scale
-/// Values must be positive
-#[derive(Clone, Debug)]
-pub struct LogNorm {
-    pub vmin: f64,
-    pub vmax: f64,
-    pub clip: bool,
-    log_vmin: f64,
-    log_vmax: f64,
-}
-
-impl LogNorm {
-    pub fn new(vmin: f64, vmax: f64) -> Self {
-        let vmin = vmin.max(1e-10);  // Ensure positive
-        let vmax = vmax.max(vmin + 1e-10);
-        Self {
-            vmin,
-            vmax,
-            clip: true,
-            log_vmin: vmin.log10(),
-            log_vmax: vmax.log10(),
+    pub fn set_show_outliers(&self, show: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_show_outliers(show);
         }
     }
 
-    pub fn with_clip(mut self, clip: bool) -> Self {
-        self.clip = clip;
-        self
+    pub fn redraw(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.redraw(cx);
+        }
     }
 
-    /// Normalize a value to [0, 1] on log scale
-    pub fn normalize(&self, value: f64) -> f64 {
-        if value <= 0.0 {
-            return 0.0;
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_prop_cycle(cycle);
         }
-        let log_val = value.log10();
-        let t = (log_val - self.log_vmin) / (self.log_vmax - self.log_vmin);
-        if self.clip { t.clamp(0.0, 1.0) } else { t }
     }
 
-    /// Inverse: convert [0, 1] back to original scale
-    pub fn inverse(&self, t: f64) -> f64 {
-        10.0_f64.powf(self.log_vmin + t * (self.log_vmax - self.log_vmin))
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_style(style);
+        }
     }
-}
 
-impl Default for LogNorm {
-    fn default() -> Self {
-        Self::new(1.0, 10.0)
+    pub fn set_xtick_strategy(&self, strategy: TickLabelStrategy) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_xtick_strategy(strategy);
+        }
     }
-}
-
-/// Symmetric log normalization: handles negative values
-#[derive(Clone, Debug)]
-pub struct SymLogNorm {
-    pub vmin: f64,
-    pub vmax: f64,
-    pub linthresh: f64,  // Linear threshold
-    pub clip: bool,
-}
 
-impl SymLogNorm {
-    pub fn new(vmin: f64, vmax: f64, linthresh: f64) -> Self {
-        Self {
-            vmin,
-            vmax,
-            linthresh: linthresh.abs().max(1e-10),
-            clip: true,
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_margins(margins);
         }
     }
 
-    pub fn with_clip(mut self, clip: bool) -> Self {
-        self.clip = clip;
-        self
+    pub fn set_spine(&self, side: SpineSide, spine: Spine) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spine(side, spine);
+        }
     }
 
-    fn transform(&self, value: f64) -> f64 {
-        if value.abs() <= self.linthresh {
-            value / self.linthresh
-        } else {
-            let sign = if value >= 0.0 { 1.0 } else { -1.0 };
-            sign * (1.0 + (value.abs() / self.linthresh).log10())
+    pub fn set_spines(&self, spines: Spines) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spines(spines);
         }
     }
 
-    /// Normalize a value to [0, 1]
-    pub fn normalize(&self, value: f64) -> f64 {
-        let t_val = self.transform(value);
-        let t_min = self.transform(self.vmin);
-        let t_max = self.transform(self.vmax);
-
-        if t_max == t_min {
-            return 0.5;
+    pub fn set_tick_params(&self, params: TickParams) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_tick_params(params);
         }
-
-        let t = (t_val - t_min) / (t_max - t_min);
-        if self.clip { t.clamp(0.0, 1.0) } else { t }
     }
-}
 
-impl Default for SymLogNorm {
-    fn default() -> Self {
-        Self::new(-10.0, 10.0, 1.0)
+    pub fn set_grid_style(&self, grid: GridStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_grid_style(grid);
+        }
     }
 }
 
@@ -4349,41 +6831,72 @@ pub struct StemPlot {
     #[rust]
     y_range: (f64, f64),
 
-    #[rust]
+    // Fixed limits from the DSL or set_xlim/set_ylim, kept when the data is rescaled
+    #[live]
+    xlim: Option<DVec2>,
+
+    #[live]
+    ylim: Option<DVec2>,
+
+    #[live]
     title: String,
 
-    #[rust]
+    #[live]
     x_label: String,
 
-    #[rust]
+    #[live]
     y_label: String,
 
     #[rust(0.0)]
     baseline: f64,
 
-    #[rust(true)]
+    #[live(true)]
     show_grid: bool,
 
-    #[rust(6.0)]
+    #[live(6.0)]
     marker_size: f64,
 
-    #[rust(1.5)]
+    #[live(1.5)]
     stem_width: f64,
 
-    #[rust(50.0)]
-    left_margin: f64,
+    #[rust]
+    x_tick_layout: TickLabelLayout,
+
+    #[live]
+    margins: Margins,
+
+    // Spines, tick marks and grid lines
+    #[rust]
+    spines: Spines,
+
+    #[rust]
+    tick_params: TickParams,
+
+    #[rust]
+    grid_style: GridStyle,
+
+    #[live]
+    legend: PlotLegend,
+
+    // Flat DSL form of `legend: { position: .. }`
+    #[live]
+    legend_position: Option<LegendPosition>,
+
+    #[rust]
+    prop_cycle: Option<PropCycle>,
 
-    #[rust(30.0)]
-    bottom_margin: f64,
+    #[live]
+    plot_style: String,
 
-    #[rust(20.0)]
-    right_margin: f64,
+    // Background of the host theme, for the automatic light/dark style
+    #[live]
+    theme_background: Vec4,
 
-    #[rust(30.0)]
-    top_margin: f64,
+    #[rust]
+    style: PlotStyle,
 
     #[rust]
-    legend_position: LegendPosition,
+    style_override: Option<PlotStyle>,
 }
 
 impl Widget for StemPlot {
@@ -4392,16 +6905,23 @@ impl Widget for StemPlot {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        // Applied once per live update, so later set_legend calls still win
+        if let Some(position) = self.legend_position.take() {
+            self.set_legend(position);
+        }
+        self.style.apply(&mut self.view, &mut self.label);
+        self.legend.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
 
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.series.is_empty() {
-            self.update_plot_area(rect);
+            self.update_plot_area(cx, rect);
             self.draw_grid(cx);
             self.draw_axes(cx);
             self.draw_stems(cx);
             self.draw_labels(cx);
-            self.draw_legend(cx);
+            self.draw_legend(cx, rect);
         }
 
         DrawStep::done()
@@ -4431,10 +6951,12 @@ impl StemPlot {
     }
 
     pub fn set_xlim(&mut self, min: f64, max: f64) {
+        self.xlim = Some(dvec2(min, max));
         self.x_range = (min, max);
     }
 
     pub fn set_ylim(&mut self, min: f64, max: f64) {
+        self.ylim = Some(dvec2(min, max));
         self.y_range = (min, max);
     }
 
@@ -4451,7 +6973,51 @@ impl StemPlot {
     }
 
     pub fn set_legend(&mut self, position: LegendPosition) {
-        self.legend_position = position;
+        self.legend.set_position(position);
+    }
+
+    /// Lay the legend entries out in several columns
+    pub fn set_legend_columns(&mut self, columns: usize) {
+        self.legend.set_columns(columns);
+    }
+
+    pub fn set_legend_title(&mut self, title: impl Into<String>) {
+        self.legend.set_title(title);
+    }
+
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
+    }
+
+    /// Colours handed out in turn to series without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    /// Show, hide, recolour or move one spine, e.g.
+    /// `set_spine(SpineSide::Left, Spine::default().with_position(SpinePosition::Zero))`
+    pub fn set_spine(&mut self, side: SpineSide, spine: Spine) {
+        *self.spines.get_mut(side) = spine;
+    }
+
+    pub fn set_spines(&mut self, spines: Spines) {
+        self.spines = spines;
+    }
+
+    /// Direction, sizes and sides of the tick marks, and whether minor ticks are drawn
+    pub fn set_tick_params(&mut self, params: TickParams) {
+        self.tick_params = params;
+    }
+
+    /// Major and minor grid line styles, drawn along the value axis; `show_grid` still turns the whole grid off
+    pub fn set_grid_style(&mut self, grid: GridStyle) {
+        self.grid_style = grid;
     }
 
     fn auto_range(&mut self) {
@@ -4481,15 +7047,54 @@ impl StemPlot {
 
         self.x_range = (x_min - x_pad, x_max + x_pad);
         self.y_range = (y_min - y_pad, y_max + y_pad);
+
+        // Fixed limits win over the data range
+        if let Some(lim) = self.xlim {
+            self.x_range = (lim.x, lim.y);
+        }
+        if let Some(lim) = self.ylim {
+            self.y_range = (lim.x, lim.y);
+        }
     }
 
-    fn update_plot_area(&mut self, rect: Rect) {
+    fn update_plot_area(&mut self, cx: &mut Cx2d, rect: Rect) {
+        // Tight layout: derive the plot area from the measured text around the axes
+        let y_ticks = self.y_tick_labels();
+        let entries = self.legend_entries();
+        self.legend.set_entries(entries);
+        let (legend_right, legend_bottom) = self.legend.outside_margins(cx);
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let text = AxesText { title: &self.title, x_label: &self.x_label, y_label: &self.y_label, y_ticks: &y_ticks, title_size: Some(self.style.title_font_size), x_tick_extent, y_tick_extent, ..Default::default() };
+        let (left, right) = self.margins.columns(cx, &mut self.label, &text, legend_right);
         self.plot_area = PlotArea::new(
-            rect.pos.x + self.left_margin,
-            rect.pos.y + self.top_margin,
-            rect.pos.x + rect.size.x - self.right_margin,
-            rect.pos.y + rect.size.y - self.bottom_margin,
+            rect.pos.x + left,
+            rect.pos.y,
+            rect.pos.x + rect.size.x - right,
+            rect.pos.y + rect.size.y,
         );
+
+        // X tick labels depend on the final width, the bottom margin on their layout
+        let x_ticks = self.x_tick_labels();
+        self.x_tick_layout = TickLabelLayout::compute(cx, &mut self.label, x_ticks, TickLabelStrategy::Auto);
+        let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &self.x_tick_layout);
+        self.plot_area.top = rect.pos.y + top;
+        self.plot_area.bottom = rect.pos.y + rect.size.y - bottom - legend_bottom;
+    }
+
+    fn y_tick_labels(&self) -> Vec<String> {
+        let y_step = (self.y_range.1 - self.y_range.0) / 5.0;
+        (0..=5).map(|i| format!("{:.1}", self.y_range.0 + i as f64 * y_step)).collect()
+    }
+
+    /// Pixel x position and text of each x tick label
+    fn x_tick_labels(&self) -> Vec<(f64, String)> {
+        let x_step = (self.x_range.1 - self.x_range.0) / 5.0;
+        (0..=5)
+            .map(|i| {
+                let x = self.x_range.0 + i as f64 * x_step;
+                (self.data_to_pixel(x, self.y_range.0).x, format!("{:.1}", x))
+            })
+            .collect()
     }
 
     fn data_to_pixel(&self, x: f64, y: f64) -> DVec2 {
@@ -4502,35 +7107,72 @@ impl StemPlot {
         )
     }
 
+    fn axis_ranges(&self) -> (AxisRange, AxisRange) {
+        (AxisRange::linear(self.x_range), AxisRange::linear(self.y_range))
+    }
+
+    /// Major and minor tick positions in pixels: x, y, x minor, y minor
+    fn frame_ticks(&self) -> [Vec<f64>; 4] {
+        let even = |(min, max): (f64, f64)| -> Vec<f64> { (0..=5).map(|i| min + i as f64 * (max - min) / 5.0).collect() };
+        let x_major = even(self.x_range);
+        let y_major = even(self.y_range);
+        let x_minor = minor_ticks(ScaleType::Linear, &x_major, self.x_range);
+        let y_minor = minor_ticks(ScaleType::Linear, &y_major, self.y_range);
+        let to_x = |xs: Vec<f64>| -> Vec<f64> { xs.into_iter().map(|x| self.data_to_pixel(x, self.y_range.0).x).collect() };
+        let to_y = |ys: Vec<f64>| -> Vec<f64> { ys.into_iter().map(|y| self.data_to_pixel(self.x_range.0, y).y).collect() };
+        [to_x(x_major), to_y(y_major), to_x(x_minor), to_y(y_minor)]
+    }
+
+    /// Pixel x of the left spine or pixel y of the bottom one, where the tick labels hang from
+    fn spine_offset(&self, side: SpineSide) -> f64 {
+        let (x, y) = self.axis_ranges();
+        self.spines.get(side).offset(side, &self.plot_area, x, y)
+    }
+
+    /// Reach of the tick marks past the bottom and the left spine
+    fn tick_extents(&self) -> (f64, f64) {
+        (
+            self.tick_params.outward_extent(SpineSide::Bottom, &self.style),
+            self.tick_params.outward_extent(SpineSide::Left, &self.style),
+        )
+    }
+
     fn draw_grid(&mut self, cx: &mut Cx2d) {
         if !self.show_grid {
             return;
         }
 
-        self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
-
-        // Horizontal grid lines
-        let y_step = (self.y_range.1 - self.y_range.0) / 5.0;
-        for i in 0..=5 {
-            let y = self.y_range.0 + i as f64 * y_step;
-            let p1 = self.data_to_pixel(self.x_range.0, y);
-            let p2 = self.data_to_pixel(self.x_range.1, y);
-            self.draw_line.draw_line(cx, p1, p2, 0.5);
-        }
+        // Lines along the value axis only
+        let [_, y_ticks, _, y_minor] = self.frame_ticks();
+        let (x_ticks, x_minor): (Vec<f64>, Vec<f64>) = (Vec::new(), Vec::new());
+        let (x, y) = self.axis_ranges();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.grid_style.draw(cx, &frame, &mut self.draw_line);
     }
 
     fn draw_axes(&mut self, cx: &mut Cx2d) {
-        self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
-
-        // X axis
-        let x1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let x2 = dvec2(self.plot_area.right, self.plot_area.bottom);
-        self.draw_line.draw_line(cx, x1, x2, 1.0);
-
-        // Y axis
-        let y1 = dvec2(self.plot_area.left, self.plot_area.bottom);
-        let y2 = dvec2(self.plot_area.left, self.plot_area.top);
-        self.draw_line.draw_line(cx, y1, y2, 1.0);
+        let [x_ticks, y_ticks, x_minor, y_minor] = self.frame_ticks();
+        let (x, y) = self.axis_ranges();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.spines.draw(cx, &frame, &self.tick_params, &mut self.draw_line);
 
         // Baseline (if different from y_range.0)
         if self.baseline > self.y_range.0 && self.baseline < self.y_range.1 {
@@ -4543,7 +7185,7 @@ impl StemPlot {
 
     fn draw_stems(&mut self, cx: &mut Cx2d) {
         for (idx, series) in self.series.iter().enumerate() {
-            let color = series.color.unwrap_or_else(|| get_color(idx));
+            let color = series.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
             let marker_style = if series.marker_style != MarkerStyle::None {
                 series.marker_style
             } else {
@@ -4571,64 +7213,42 @@ impl StemPlot {
     }
 
     fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+        self.label.set_color(self.style.text_color);
 
-        // X axis tick labels
-        let x_step = (self.x_range.1 - self.x_range.0) / 5.0;
-        for i in 0..=5 {
-            let x = self.x_range.0 + i as f64 * x_step;
-            let p = self.data_to_pixel(x, self.y_range.0);
-            let label = format!("{:.1}", x);
-            self.label.draw_at(cx, dvec2(p.x, p.y + 5.0), &label, TextAnchor::TopCenter);
-        }
+        // X axis tick labels - laid out by update_plot_area, hung below the tick marks
+        let (x_tick_extent, y_tick_extent) = self.tick_extents();
+        let axis_y = self.spine_offset(SpineSide::Bottom);
+        self.x_tick_layout.draw(cx, &mut self.label, axis_y + x_tick_extent);
 
         // Y axis tick labels
+        let label_x = self.spine_offset(SpineSide::Left) - y_tick_extent - TICK_LABEL_PAD;
         let y_step = (self.y_range.1 - self.y_range.0) / 5.0;
         for i in 0..=5 {
             let y = self.y_range.0 + i as f64 * y_step;
             let p = self.data_to_pixel(self.x_range.0, y);
             let label = format!("{:.1}", y);
-            self.label.draw_at(cx, dvec2(p.x - 5.0, p.y), &label, TextAnchor::MiddleRight);
+            self.label.draw_at(cx, dvec2(label_x, p.y), &label, TextAnchor::MiddleRight);
         }
 
-        // Title
-        if !self.title.is_empty() {
-            let center_x = (self.plot_area.left + self.plot_area.right) / 2.0;
-            self.label.draw_at(cx, dvec2(center_x, self.plot_area.top - 10.0), &self.title, TextAnchor::BottomCenter);
-        }
+        // Title and axis labels
+        let y_tick_labels = self.y_tick_labels();
+        let text = AxesText { title: &self.title, x_label: &self.x_label, y_label: &self.y_label, y_ticks: &y_tick_labels, title_size: Some(self.style.title_font_size), x_tick_extent, y_tick_extent, ..Default::default() };
+        text.draw(cx, &mut self.label, &self.plot_area, &self.x_tick_layout);
     }
 
-    fn draw_legend(&mut self, cx: &mut Cx2d) {
-        if self.legend_position == LegendPosition::None || self.series.len() <= 1 {
-            return;
-        }
-
-        // Calculate legend position
-        let (legend_x, legend_y) = match self.legend_position {
-            LegendPosition::TopRight => (self.plot_area.right - 100.0, self.plot_area.top + 10.0),
-            LegendPosition::TopLeft => (self.plot_area.left + 10.0, self.plot_area.top + 10.0),
-            LegendPosition::BottomRight => (self.plot_area.right - 100.0, self.plot_area.bottom - 50.0),
-            LegendPosition::BottomLeft => (self.plot_area.left + 10.0, self.plot_area.bottom - 50.0),
-            LegendPosition::None => return,
-        };
-
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
-
-        for (idx, series) in self.series.iter().enumerate() {
-            if series.label.is_empty() {
-                continue;
-            }
-
-            let y_offset = legend_y + idx as f64 * 18.0;
-            let color = series.color.unwrap_or_else(|| get_color(idx));
-
-            // Draw marker
-            self.draw_point.color = color;
-            self.draw_point.draw_marker(cx, dvec2(legend_x + 8.0, y_offset), 4.0, MarkerStyle::Circle);
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        self.series.iter().enumerate().map(|(idx, series)| {
+            let color = series.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
+            let marker = if series.marker_style == MarkerStyle::None { MarkerStyle::Circle } else { series.marker_style };
+            LegendEntry::new(series.label.clone(), color, LegendSymbol::Line { style: LineStyle::Solid, width: 1.5, marker })
+        }).collect()
+    }
 
-            // Draw label
-            self.label.draw_at(cx, dvec2(legend_x + 20.0, y_offset), &series.label, TextAnchor::MiddleLeft);
-        }
+    fn draw_legend(&mut self, cx: &mut Cx2d, rect: Rect) {
+        let points: Vec<DVec2> = self.series.iter()
+            .flat_map(|s| s.x.iter().zip(&s.y).map(|(x, y)| self.data_to_pixel(*x, *y)))
+            .collect();
+        self.legend.draw(cx, &self.plot_area, rect, &points);
     }
 }
 
@@ -4681,11 +7301,65 @@ impl StemPlotRef {
         }
     }
 
+    pub fn set_legend_columns(&self, columns: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_columns(columns);
+        }
+    }
+
+    pub fn set_legend_title(&self, title: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_legend_title(title);
+        }
+    }
+
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.redraw(cx);
         }
     }
+
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_margins(margins);
+        }
+    }
+
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_prop_cycle(cycle);
+        }
+    }
+
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_style(style);
+        }
+    }
+
+    pub fn set_spine(&self, side: SpineSide, spine: Spine) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spine(side, spine);
+        }
+    }
+
+    pub fn set_spines(&self, spines: Spines) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spines(spines);
+        }
+    }
+
+    pub fn set_tick_params(&self, params: TickParams) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_tick_params(params);
+        }
+    }
+
+    pub fn set_grid_style(&self, grid: GridStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_grid_style(grid);
+        }
+    }
 }
 
 // =============================================================================
@@ -4708,7 +7382,7 @@ pub struct HeatmapChart {
     label: PlotLabel,
 
     #[rust]
-    data: Vec<Vec<f64>>,
+    data: Matrix,
 
     #[rust]
     x_labels: Option<Vec<String>>,
@@ -4719,7 +7393,7 @@ pub struct HeatmapChart {
     #[rust]
     plot_area: PlotArea,
 
-    #[rust]
+    #[live]
     title: String,
 
     #[rust]
@@ -4731,20 +7405,39 @@ pub struct HeatmapChart {
     #[rust]
     vmax: Option<f64>,
 
-    #[rust(true)]
+    #[live(true)]
     show_values: bool,
 
-    #[rust(60.0)]
-    left_margin: f64,
+    #[rust]
+    x_tick_strategy: TickLabelStrategy,
+
+    #[rust]
+    x_tick_layout: TickLabelLayout,
+
+    #[live]
+    margins: Margins,
+
+    #[live]
+    colorbar: PlotColorbar,
+
+    #[rust(true)]
+    show_colorbar: bool,
+
+    #[rust]
+    norm: Option<Box<dyn Norm>>,
+
+    #[live]
+    plot_style: String,
 
-    #[rust(30.0)]
-    bottom_margin: f64,
+    // Background of the host theme, for the automatic light/dark style
+    #[live]
+    theme_background: Vec4,
 
-    #[rust(60.0)]
-    right_margin: f64,
+    #[rust]
+    style: PlotStyle,
 
-    #[rust(30.0)]
-    top_margin: f64,
+    #[rust]
+    style_override: Option<PlotStyle>,
 }
 
 impl Widget for HeatmapChart {
@@ -4753,14 +7446,19 @@ impl Widget for HeatmapChart {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
+        self.colorbar.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
 
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.data.is_empty() {
-            self.update_plot_area(rect);
+            self.update_plot_area(cx, rect);
             self.draw_cells(cx);
             self.draw_labels(cx);
-            self.draw_colorbar(cx, rect);
+            if self.show_colorbar {
+                self.colorbar.draw(cx, &self.plot_area, rect);
+            }
         }
 
         DrawStep::done()
@@ -4768,8 +7466,8 @@ impl Widget for HeatmapChart {
 }
 
 impl HeatmapChart {
-    pub fn set_data(&mut self, data: Vec<Vec<f64>>) {
-        self.data = data;
+    pub fn set_data(&mut self, data: impl IntoGrid) {
+        self.data = data.into_matrix();
     }
 
     pub fn set_x_labels(&mut self, labels: Vec<String>) {
@@ -4800,55 +7498,144 @@ impl HeatmapChart {
         self.show_values = show;
     }
 
+    pub fn set_show_colorbar(&mut self, show: bool) {
+        self.show_colorbar = show;
+    }
+
+    pub fn set_colorbar_orientation(&mut self, orientation: ColorbarOrientation) {
+        self.colorbar.set_orientation(orientation);
+    }
+
+    pub fn set_colorbar_label(&mut self, label: impl Into<String>) {
+        self.colorbar.set_label(label);
+    }
+
+    /// Draw arrows on the colorbar for values beyond vmin and/or vmax
+    pub fn set_colorbar_extend(&mut self, extend: ColorbarExtend) {
+        self.colorbar.set_extend(extend);
+    }
+
+    /// Map values to colours through any norm instead of a linear scale over vmin..vmax
+    pub fn set_norm(&mut self, norm: impl Norm + 'static) {
+        self.norm = Some(Box::new(norm));
+    }
+
+    /// Colour cells in discrete bands between these boundaries instead of a continuous scale
+    pub fn set_levels(&mut self, levels: Vec<f64>) {
+        self.set_norm(BoundaryNorm::new(levels));
+    }
+
+    /// Choose how column labels are rotated, wrapped or thinned when they overlap
+    pub fn set_xtick_strategy(&mut self, strategy: TickLabelStrategy) {
+        self.x_tick_strategy = strategy;
+    }
+
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
+    }
+
     pub fn clear(&mut self) {
         self.data.clear();
         self.x_labels = None;
         self.y_labels = None;
     }
 
-    fn update_plot_area(&mut self, rect: Rect) {
+    /// Clear the grid and put the title, colormap, value range, norm, margins and style back
+    /// to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.colormap = Colormap::default();
+        self.vmin = None;
+        self.vmax = None;
+        self.norm = None;
+        self.title.clear();
+        self.margins = Margins::default();
+        self.style_override = None;
+    }
+
+    /// The chart as a "heatmap" spec; norms and the value range have no spec field
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            margins: margins_spec(&self.margins),
+            colormap: colormap_name(&self.colormap),
+            categories: self.x_labels.clone().unwrap_or_default(),
+            y_categories: self.y_labels.clone().unwrap_or_default(),
+            matrix: self.data.to_rows(),
+            ..ChartSpec::new("heatmap")
+        }
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    fn update_plot_area(&mut self, cx: &mut Cx2d, rect: Rect) {
+        // Tight layout: derive the plot area from the measured text around the axes
+        let y_ticks: Vec<String> = self.y_labels.iter().flatten().take(self.data.rows()).cloned().collect();
+        let text = AxesText { title: &self.title, y_ticks: &y_ticks, title_size: Some(self.style.title_font_size), ..Default::default() };
+        // The colorbar maps the cells too, so it gets the colormap and range even when hidden
+        let (vmin, vmax) = self.get_value_range();
+        self.colorbar.set_colormap(self.colormap.clone());
+        self.colorbar.set_norm(resolve_norm(&self.norm, vmin, vmax));
+        let (colorbar_right, colorbar_bottom) = if self.show_colorbar { self.colorbar.margins(cx) } else { (0.0, 0.0) };
+        let (left, right) = self.margins.columns(cx, &mut self.label, &text, colorbar_right);
         self.plot_area = PlotArea::new(
-            rect.pos.x + self.left_margin,
-            rect.pos.y + self.top_margin,
-            rect.pos.x + rect.size.x - self.right_margin,
-            rect.pos.y + rect.size.y - self.bottom_margin,
+            rect.pos.x + left,
+            rect.pos.y,
+            rect.pos.x + rect.size.x - right,
+            rect.pos.y + rect.size.y,
         );
+
+        // Column labels depend on the final width, the bottom margin on their layout
+        let x_ticks = self.x_tick_labels();
+        self.x_tick_layout = TickLabelLayout::compute(cx, &mut self.label, x_ticks, self.x_tick_strategy);
+        let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &self.x_tick_layout);
+        self.plot_area.top = rect.pos.y + top;
+        self.plot_area.bottom = rect.pos.y + rect.size.y - bottom - colorbar_bottom;
+    }
+
+    /// Pixel x position and text of each column label
+    fn x_tick_labels(&self) -> Vec<(f64, String)> {
+        let cols = self.data.cols();
+        let cell_width = self.plot_area.width() / cols.max(1) as f64;
+        self.x_labels.iter().flatten().take(cols).enumerate()
+            .map(|(i, label)| (self.plot_area.left + (i as f64 + 0.5) * cell_width, label.clone()))
+            .collect()
     }
 
     fn get_value_range(&self) -> (f64, f64) {
         let mut min = f64::MAX;
         let mut max = f64::MIN;
 
-        for row in &self.data {
-            for &val in row {
-                min = min.min(val);
-                max = max.max(val);
-            }
+        for &val in self.data.values() {
+            min = min.min(val);
+            max = max.max(val);
         }
 
         (self.vmin.unwrap_or(min), self.vmax.unwrap_or(max))
     }
 
     fn draw_cells(&mut self, cx: &mut Cx2d) {
-        let rows = self.data.len();
-        if rows == 0 {
+        let (rows, cols) = self.data.shape();
+        if rows == 0 || cols == 0 {
             return;
         }
-        let cols = self.data[0].len();
-        if cols == 0 {
-            return;
-        }
-
-        let (vmin, vmax) = self.get_value_range();
-        let range = (vmax - vmin).max(1e-10);
 
         let cell_width = self.plot_area.width() / cols as f64;
         let cell_height = self.plot_area.height() / rows as f64;
 
-        for (row_idx, row) in self.data.iter().enumerate() {
-            for (col_idx, &value) in row.iter().enumerate() {
-                let t = (value - vmin) / range;
-                let color = self.colormap.sample(t);
+        for row_idx in 0..rows {
+            for col_idx in 0..cols {
+                let value = self.data[(row_idx, col_idx)];
+                // Padding of ragged rows and missing values stay blank
+                if value.is_nan() {
+                    continue;
+                }
+                let color = self.colorbar.color(value);
                 self.draw_bar.color = color;
 
                 let x = self.plot_area.left + col_idx as f64 * cell_width;
@@ -4862,12 +7649,9 @@ impl HeatmapChart {
 
                 // Draw value text in cell
                 if self.show_values {
-                    let text_color = if t > 0.5 {
-                        vec4(0.0, 0.0, 0.0, 1.0)
-                    } else {
-                        vec4(1.0, 1.0, 1.0, 1.0)
-                    };
-                    self.label.set_color(text_color);
+                    // Pick black or white by WCAG contrast against the cell as it appears on the backdrop
+                    let backdrop = self.style.backdrop(self.theme_background);
+                    self.label.set_color(contrasting_text(composite(color, backdrop)));
                     let label = format!("{:.1}", value);
                     self.label.draw_at(
                         cx,
@@ -4881,22 +7665,13 @@ impl HeatmapChart {
     }
 
     fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+        self.label.set_color(self.style.text_color);
 
-        let rows = self.data.len();
-        let cols = if rows > 0 { self.data[0].len() } else { 0 };
-
-        let cell_width = self.plot_area.width() / cols.max(1) as f64;
+        let rows = self.data.rows();
         let cell_height = self.plot_area.height() / rows.max(1) as f64;
 
-        // X labels (column labels)
-        if let Some(ref labels) = self.x_labels {
-            for (i, label) in labels.iter().enumerate().take(cols) {
-                let x = self.plot_area.left + (i as f64 + 0.5) * cell_width;
-                let y = self.plot_area.bottom + 5.0;
-                self.label.draw_at(cx, dvec2(x, y), label, TextAnchor::TopCenter);
-            }
-        }
+        // X labels (column labels) - laid out by update_plot_area
+        self.x_tick_layout.draw(cx, &mut self.label, self.plot_area.bottom);
 
         // Y labels (row labels)
         if let Some(ref labels) = self.y_labels {
@@ -4908,50 +7683,19 @@ impl HeatmapChart {
         }
 
         // Title
-        if !self.title.is_empty() {
-            let center_x = (self.plot_area.left + self.plot_area.right) / 2.0;
-            self.label.draw_at(cx, dvec2(center_x, self.plot_area.top - 10.0), &self.title, TextAnchor::BottomCenter);
-        }
+        let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), ..Default::default() };
+        text.draw(cx, &mut self.label, &self.plot_area, &self.x_tick_layout);
     }
+}
 
-    fn draw_colorbar(&mut self, cx: &mut Cx2d, rect: Rect) {
-        let bar_width = 15.0;
-        let bar_x = rect.pos.x + rect.size.x - self.right_margin + 10.0;
-        let bar_top = self.plot_area.top;
-        let bar_height = self.plot_area.height();
-
-        // Draw colorbar gradient
-        let steps = 50;
-        let step_height = bar_height / steps as f64;
-
-        for i in 0..steps {
-            let t = 1.0 - i as f64 / steps as f64;
-            let color = self.colormap.sample(t);
-            self.draw_bar.color = color;
-
-            let rect = Rect {
-                pos: dvec2(bar_x, bar_top + i as f64 * step_height),
-                size: dvec2(bar_width, step_height + 1.0),
-            };
-            self.draw_bar.draw_bar(cx, rect);
+impl HeatmapChartRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reset();
         }
-
-        // Draw colorbar labels
-        let (vmin, vmax) = self.get_value_range();
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
-
-        let label_max = format!("{:.1}", vmax);
-        let label_min = format!("{:.1}", vmin);
-        let label_mid = format!("{:.1}", (vmin + vmax) / 2.0);
-
-        self.label.draw_at(cx, dvec2(bar_x + bar_width + 3.0, bar_top), &label_max, TextAnchor::MiddleLeft);
-        self.label.draw_at(cx, dvec2(bar_x + bar_width + 3.0, bar_top + bar_height / 2.0), &label_mid, TextAnchor::MiddleLeft);
-        self.label.draw_at(cx, dvec2(bar_x + bar_width + 3.0, bar_top + bar_height), &label_min, TextAnchor::MiddleLeft);
     }
-}
 
-impl HeatmapChartRef {
-    pub fn set_data(&self, data: Vec<Vec<f64>>) {
+    pub fn set_data(&self, data: impl IntoGrid) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_data(data);
         }
@@ -4998,6 +7742,60 @@ impl HeatmapChartRef {
             inner.redraw(cx);
         }
     }
+
+    pub fn set_xtick_strategy(&self, strategy: TickLabelStrategy) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_xtick_strategy(strategy);
+        }
+    }
+
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_margins(margins);
+        }
+    }
+
+    pub fn set_show_colorbar(&self, show: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_show_colorbar(show);
+        }
+    }
+
+    pub fn set_colorbar_orientation(&self, orientation: ColorbarOrientation) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_colorbar_orientation(orientation);
+        }
+    }
+
+    pub fn set_colorbar_label(&self, label: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_colorbar_label(label);
+        }
+    }
+
+    pub fn set_colorbar_extend(&self, extend: ColorbarExtend) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_colorbar_extend(extend);
+        }
+    }
+
+    pub fn set_levels(&self, levels: Vec<f64>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_levels(levels);
+        }
+    }
+
+    pub fn set_norm(&self, norm: impl Norm + 'static) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_norm(norm);
+        }
+    }
+
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_style(style);
+        }
+    }
 }
 
 // =============================================================================
@@ -5012,8 +7810,8 @@ pub struct ViolinItem {
 }
 
 impl ViolinItem {
-    pub fn new(label: impl Into<String>, values: Vec<f64>) -> Self {
-        Self { label: label.into(), values, color: None }
+    pub fn new(label: impl Into<String>, values: impl IntoValues) -> Self {
+        Self { label: label.into(), values: values.into_values().into_owned(), color: None }
     }
 
     pub fn with_color(mut self, color: Vec4) -> Self {
@@ -5029,7 +7827,7 @@ pub struct ViolinPlot {
     #[live] draw_line: DrawPlotLine,
     #[live] draw_point: DrawPlotPoint,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] items: Vec<ViolinItem>,
     #[rust] show_box: bool,
     #[rust] show_median: bool,
@@ -5039,6 +7837,11 @@ pub struct ViolinPlot {
     #[live(30.0)] right_margin: f64,
     #[live(30.0)] top_margin: f64,
     #[live(50.0)] bottom_margin: f64,
+    #[rust] prop_cycle: Option<PropCycle>,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Widget for ViolinPlot {
@@ -5047,6 +7850,8 @@ impl Widget for ViolinPlot {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.items.is_empty() {
@@ -5068,6 +7873,12 @@ impl ViolinPlot {
     pub fn set_show_median(&mut self, show: bool) { self.show_median = show; }
     pub fn clear(&mut self) { self.items.clear(); }
 
+    /// Colours handed out in turn to violins without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) { self.prop_cycle = Some(cycle); }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) { self.style_override = Some(style); }
+
     fn update_plot_area(&mut self, rect: Rect) {
         self.plot_area = PlotArea::new(rect.pos.x + self.left_margin, rect.pos.y + self.top_margin,
             rect.pos.x + rect.size.x - self.right_margin, rect.pos.y + rect.size.y - self.bottom_margin);
@@ -5099,7 +7910,7 @@ impl ViolinPlot {
         let std = (all.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / all.len() as f64).sqrt();
         let bw = if self.bandwidth > 0.0 { self.bandwidth } else { 1.06 * std * (all.len() as f64).powf(-0.2) };
 
-        self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+        self.draw_line.color = self.style.axes_color;
         self.draw_line.draw_line(cx, dvec2(self.plot_area.left, self.plot_area.bottom), dvec2(self.plot_area.right, self.plot_area.bottom), 1.0);
         self.draw_line.draw_line(cx, dvec2(self.plot_area.left, self.plot_area.bottom), dvec2(self.plot_area.left, self.plot_area.top), 1.0);
 
@@ -5110,7 +7921,7 @@ impl ViolinPlot {
             let kde = self.compute_kde(&item.values, bw, y_min, y_max, 50);
             let max_d = kde.iter().map(|(_, d)| *d).fold(0.0f64, f64::max);
             if max_d <= 0.0 { continue; }
-            let color = item.color.unwrap_or_else(|| get_color(i));
+            let color = item.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(i));
             self.draw_fill.color = vec4(color.x, color.y, color.z, 0.6);
 
             for j in 0..kde.len() - 1 {
@@ -5147,7 +7958,7 @@ impl ViolinPlot {
     }
 
     fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+        self.label.set_color(self.style.text_color);
         if !self.title.is_empty() {
             self.label.draw_at(cx, dvec2((self.plot_area.left + self.plot_area.right) / 2.0, self.plot_area.top - 15.0), &self.title, TextAnchor::Center);
         }
@@ -5166,6 +7977,8 @@ impl ViolinPlotRef {
     pub fn set_show_box(&self, show: bool) { if let Some(mut inner) = self.borrow_mut() { inner.set_show_box(show); } }
     pub fn clear(&self) { if let Some(mut inner) = self.borrow_mut() { inner.clear(); } }
     pub fn redraw(&self, cx: &mut Cx) { if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); } }
+    pub fn set_prop_cycle(&self, cycle: PropCycle) { if let Some(mut inner) = self.borrow_mut() { inner.set_prop_cycle(cycle); } }
+    pub fn set_style(&self, style: PlotStyle) { if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); } }
 }
 
 // =============================================================================
@@ -5182,7 +7995,7 @@ impl PolarSeries {
     pub fn new(label: impl Into<String>) -> Self {
         Self { label: label.into(), theta: Vec::new(), r: Vec::new(), color: None, marker_style: MarkerStyle::None, fill: false }
     }
-    pub fn with_data(mut self, theta: Vec<f64>, r: Vec<f64>) -> Self { self.theta = theta; self.r = r; self }
+    pub fn with_data(mut self, theta: impl IntoValues, r: impl IntoValues) -> Self { self.theta = theta.into_values().into_owned(); self.r = r.into_values().into_owned(); self }
     pub fn with_color(mut self, color: Vec4) -> Self { self.color = Some(color); self }
     pub fn with_fill(mut self, fill: bool) -> Self { self.fill = fill; self }
     pub fn with_marker(mut self, style: MarkerStyle) -> Self { self.marker_style = style; self }
@@ -5195,17 +8008,24 @@ pub struct PolarPlot {
     #[live] draw_point: DrawPlotPoint,
     #[live] draw_fill: DrawPlotFill,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] series: Vec<PolarSeries>,
     #[rust] r_max: Option<f64>,
     #[rust] plot_center: DVec2,
     #[rust] plot_radius: f64,
     #[live(20.0)] margin: f64,
+    #[rust] prop_cycle: Option<PropCycle>,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Widget for PolarPlot {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) { self.view.handle_event(cx, event, scope); }
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.series.is_empty() {
@@ -5226,6 +8046,12 @@ impl PolarPlot {
     pub fn set_r_max(&mut self, r_max: f64) { self.r_max = Some(r_max); }
     pub fn clear(&mut self) { self.series.clear(); }
 
+    /// Colours handed out in turn to series without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) { self.prop_cycle = Some(cycle); }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) { self.style_override = Some(style); }
+
     fn get_r_max(&self) -> f64 {
         self.r_max.unwrap_or_else(|| self.series.iter().flat_map(|s| s.r.iter()).cloned().fold(0.0f64, f64::max) * 1.1)
     }
@@ -5236,7 +8062,7 @@ impl PolarPlot {
     }
 
     fn draw_grid(&mut self, cx: &mut Cx2d) {
-        self.draw_line.color = vec4(0.85, 0.85, 0.85, 1.0);
+        self.draw_line.color = self.style.grid_color;
         for i in 1..=5 {
             let r = i as f64 / 5.0 * self.plot_radius;
             for j in 0..64 {
@@ -5250,7 +8076,7 @@ impl PolarPlot {
             let t = i as f64 * std::f64::consts::PI / 6.0;
             self.draw_line.draw_line(cx, self.plot_center, dvec2(self.plot_center.x + self.plot_radius * t.cos(), self.plot_center.y - self.plot_radius * t.sin()), 1.0);
         }
-        self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+        self.draw_line.color = self.style.axes_color;
         for j in 0..64 {
             let t1 = j as f64 / 64.0 * 2.0 * std::f64::consts::PI;
             let t2 = (j + 1) as f64 / 64.0 * 2.0 * std::f64::consts::PI;
@@ -5263,7 +8089,7 @@ impl PolarPlot {
         let r_max = self.get_r_max();
         for (idx, s) in self.series.iter().enumerate() {
             if s.theta.len() != s.r.len() || s.theta.is_empty() { continue; }
-            let color = s.color.unwrap_or_else(|| get_color(idx));
+            let color = s.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
             self.draw_line.color = color;
             for i in 0..s.theta.len() {
                 let next = (i + 1) % s.theta.len();
@@ -5279,7 +8105,7 @@ impl PolarPlot {
     }
 
     fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+        self.label.set_color(self.style.text_color);
         if !self.title.is_empty() {
             self.label.draw_at(cx, dvec2(self.plot_center.x, self.plot_center.y - self.plot_radius - 20.0), &self.title, TextAnchor::Center);
         }
@@ -5297,6 +8123,8 @@ impl PolarPlotRef {
     pub fn set_r_max(&self, r_max: f64) { if let Some(mut inner) = self.borrow_mut() { inner.set_r_max(r_max); } }
     pub fn clear(&self) { if let Some(mut inner) = self.borrow_mut() { inner.clear(); } }
     pub fn redraw(&self, cx: &mut Cx) { if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); } }
+    pub fn set_prop_cycle(&self, cycle: PropCycle) { if let Some(mut inner) = self.borrow_mut() { inner.set_prop_cycle(cycle); } }
+    pub fn set_style(&self, style: PlotStyle) { if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); } }
 }
 
 // =============================================================================
@@ -5309,29 +8137,46 @@ pub struct ContourPlot {
     #[live] draw_line: DrawPlotLine,
     #[live] draw_fill: DrawPlotFill,
     #[live] label: PlotLabel,
-    #[rust] title: String,
-    #[rust] data: Vec<Vec<f64>>,
+    #[live] title: String,
+    #[rust] data: Matrix,
     #[rust] x_range: (f64, f64),
     #[rust] y_range: (f64, f64),
     #[rust] filled: bool,
     #[rust] colormap: Colormap,
     #[rust] plot_area: PlotArea,
+    #[rust] show_labels: bool,
     #[live(50.0)] left_margin: f64,
     #[live(30.0)] right_margin: f64,
     #[live(30.0)] top_margin: f64,
     #[live(50.0)] bottom_margin: f64,
+    #[live] colorbar: PlotColorbar,
+    #[rust] show_colorbar: bool,
+    #[rust] norm: Option<Box<dyn Norm>>,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
+/// Number of contour levels between the data minimum and maximum
+const CONTOUR_LEVELS: usize = 10;
+
 impl Widget for ContourPlot {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) { self.view.handle_event(cx, event, scope); }
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
+        self.colorbar.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.data.is_empty() {
+            self.update_colorbar();
+            let (colorbar_right, colorbar_bottom) = if self.show_colorbar { self.colorbar.margins(cx) } else { (0.0, 0.0) };
             self.plot_area = PlotArea::new(rect.pos.x + self.left_margin, rect.pos.y + self.top_margin,
-                rect.pos.x + rect.size.x - self.right_margin, rect.pos.y + rect.size.y - self.bottom_margin);
+                rect.pos.x + rect.size.x - self.right_margin - colorbar_right, rect.pos.y + rect.size.y - self.bottom_margin - colorbar_bottom);
             self.draw_contours(cx);
             self.draw_labels(cx);
+            if self.show_colorbar { self.colorbar.draw(cx, &self.plot_area, rect); }
         }
         DrawStep::done()
     }
@@ -5339,39 +8184,73 @@ impl Widget for ContourPlot {
 
 impl ContourPlot {
     pub fn set_title(&mut self, title: impl Into<String>) { self.title = title.into(); }
-    pub fn set_data(&mut self, data: Vec<Vec<f64>>) { self.data = data; }
+    pub fn set_data(&mut self, data: impl IntoGrid) { self.data = data.into_matrix(); }
     pub fn set_x_range(&mut self, min: f64, max: f64) { self.x_range = (min, max); }
     pub fn set_y_range(&mut self, min: f64, max: f64) { self.y_range = (min, max); }
     pub fn set_filled(&mut self, filled: bool) { self.filled = filled; }
     pub fn set_colormap(&mut self, colormap: Colormap) { self.colormap = colormap; }
+    /// Label each contour level inline, like matplotlib's clabel
+    pub fn set_show_labels(&mut self, show: bool) { self.show_labels = show; }
+    pub fn set_show_colorbar(&mut self, show: bool) { self.show_colorbar = show; }
+    pub fn set_colorbar_orientation(&mut self, orientation: ColorbarOrientation) { self.colorbar.set_orientation(orientation); }
+    pub fn set_colorbar_label(&mut self, label: impl Into<String>) { self.colorbar.set_label(label); }
+    /// Draw arrows on the colorbar for values beyond the colour range
+    pub fn set_colorbar_extend(&mut self, extend: ColorbarExtend) { self.colorbar.set_extend(extend); }
+    /// Map values to colours through any norm instead of the default level bands
+    pub fn set_norm(&mut self, norm: impl Norm + 'static) { self.norm = Some(Box::new(norm)); }
     pub fn clear(&mut self) { self.data.clear(); }
 
-    fn draw_contours(&mut self, cx: &mut Cx2d) {
-        let rows = self.data.len(); if rows < 2 { return; }
-        let cols = self.data[0].len(); if cols < 2 { return; }
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) { self.style_override = Some(style); }
+
+    fn value_range(&self) -> (f64, f64) {
         let (mut v_min, mut v_max) = (f64::MAX, f64::MIN);
-        for row in &self.data { for &v in row { v_min = v_min.min(v); v_max = v_max.max(v); } }
-        let v_range = (v_max - v_min).max(1e-10);
+        for &v in self.data.values() { v_min = v_min.min(v); v_max = v_max.max(v); }
+        (v_min, v_max)
+    }
+
+    /// Evenly spaced levels strictly inside the data range
+    fn contour_levels(v_min: f64, v_max: f64) -> Vec<f64> {
+        (1..=CONTOUR_LEVELS).map(|lvl| v_min + lvl as f64 * (v_max - v_min) / (CONTOUR_LEVELS + 1) as f64).collect()
+    }
+
+    /// The colorbar maps the fills and lines; without a norm of their own,
+    /// filled contours use one discrete band per level interval
+    fn update_colorbar(&mut self) {
+        let (v_min, v_max) = self.value_range();
+        self.colorbar.set_colormap(self.colormap.clone());
+        if self.filled && self.norm.is_none() {
+            let levels = std::iter::once(v_min).chain(Self::contour_levels(v_min, v_max)).chain(std::iter::once(v_max)).collect();
+            self.colorbar.set_norm(Box::new(BoundaryNorm::new(levels)));
+        } else {
+            self.colorbar.set_norm(resolve_norm(&self.norm, v_min, v_max));
+        }
+    }
+
+    fn draw_contours(&mut self, cx: &mut Cx2d) {
+        let (rows, cols) = self.data.shape();
+        if rows < 2 || cols < 2 { return; }
+        let (v_min, v_max) = self.value_range();
         let cell_w = self.plot_area.width() / (cols - 1) as f64;
         let cell_h = self.plot_area.height() / (rows - 1) as f64;
 
         if self.filled {
             for row in 0..rows - 1 {
                 for col in 0..cols - 1 {
-                    let avg = (self.data[row][col] + self.data[row][col + 1] + self.data[row + 1][col] + self.data[row + 1][col + 1]) / 4.0;
-                    self.draw_fill.color = self.colormap.sample((avg - v_min) / v_range);
+                    let avg = (self.data[(row, col)] + self.data[(row, col + 1)] + self.data[(row + 1, col)] + self.data[(row + 1, col + 1)]) / 4.0;
+                    if avg.is_nan() { continue; }
+                    self.draw_fill.color = self.colorbar.color(avg);
                     self.draw_fill.draw_abs(cx, Rect { pos: dvec2(self.plot_area.left + col as f64 * cell_w, self.plot_area.top + row as f64 * cell_h), size: dvec2(cell_w, cell_h) });
                 }
             }
         }
 
-        let n_levels = 10;
-        for lvl in 1..=n_levels {
-            let level = v_min + lvl as f64 * (v_max - v_min) / (n_levels + 1) as f64;
-            self.draw_line.color = if self.filled { vec4(0.2, 0.2, 0.2, 0.8) } else { self.colormap.sample((level - v_min) / v_range) };
+        for level in Self::contour_levels(v_min, v_max) {
+            self.draw_line.color = if self.filled { vec4(0.2, 0.2, 0.2, 0.8) } else { self.colorbar.color(level) };
+            let mut segs: Vec<(DVec2, DVec2)> = Vec::new();
             for row in 0..rows - 1 {
                 for col in 0..cols - 1 {
-                    let (v00, v10, v01, v11) = (self.data[row][col], self.data[row][col + 1], self.data[row + 1][col], self.data[row + 1][col + 1]);
+                    let (v00, v10, v01, v11) = (self.data[(row, col)], self.data[(row, col + 1)], self.data[(row + 1, col)], self.data[(row + 1, col + 1)]);
                     let case = ((v00 >= level) as u8) | (((v10 >= level) as u8) << 1) | (((v01 >= level) as u8) << 2) | (((v11 >= level) as u8) << 3);
                     if case == 0 || case == 15 { continue; }
                     let x0 = self.plot_area.left + col as f64 * cell_w;
@@ -5379,27 +8258,34 @@ impl ContourPlot {
                     let interp = |a: f64, b: f64| if (b - a).abs() < 1e-10 { 0.5 } else { (level - a) / (b - a) };
                     let (tx, bx, ly, ry) = (x0 + interp(v00, v10) * cell_w, x0 + interp(v01, v11) * cell_w, y0 + interp(v00, v01) * cell_h, y0 + interp(v10, v11) * cell_h);
                     match case {
-                        1 | 14 => self.draw_line.draw_line(cx, dvec2(x0, ly), dvec2(tx, y0), 1.5),
-                        2 | 13 => self.draw_line.draw_line(cx, dvec2(tx, y0), dvec2(x0 + cell_w, ry), 1.5),
-                        3 | 12 => self.draw_line.draw_line(cx, dvec2(x0, ly), dvec2(x0 + cell_w, ry), 1.5),
-                        4 | 11 => self.draw_line.draw_line(cx, dvec2(x0, ly), dvec2(bx, y0 + cell_h), 1.5),
-                        6 | 9 => self.draw_line.draw_line(cx, dvec2(tx, y0), dvec2(bx, y0 + cell_h), 1.5),
-                        7 | 8 => self.draw_line.draw_line(cx, dvec2(bx, y0 + cell_h), dvec2(x0 + cell_w, ry), 1.5),
-                        5 => { self.draw_line.draw_line(cx, dvec2(x0, ly), dvec2(tx, y0), 1.5); self.draw_line.draw_line(cx, dvec2(bx, y0 + cell_h), dvec2(x0 + cell_w, ry), 1.5); }
-                        10 => { self.draw_line.draw_line(cx, dvec2(tx, y0), dvec2(x0 + cell_w, ry), 1.5); self.draw_line.draw_line(cx, dvec2(x0, ly), dvec2(bx, y0 + cell_h), 1.5); }
+                        1 | 14 => segs.push((dvec2(x0, ly), dvec2(tx, y0))),
+                        2 | 13 => segs.push((dvec2(tx, y0), dvec2(x0 + cell_w, ry))),
+                        3 | 12 => segs.push((dvec2(x0, ly), dvec2(x0 + cell_w, ry))),
+                        4 | 11 => segs.push((dvec2(x0, ly), dvec2(bx, y0 + cell_h))),
+                        6 | 9 => segs.push((dvec2(tx, y0), dvec2(bx, y0 + cell_h))),
+                        7 | 8 => segs.push((dvec2(bx, y0 + cell_h), dvec2(x0 + cell_w, ry))),
+                        5 => { segs.push((dvec2(x0, ly), dvec2(tx, y0))); segs.push((dvec2(bx, y0 + cell_h), dvec2(x0 + cell_w, ry))); }
+                        10 => { segs.push((dvec2(tx, y0), dvec2(x0 + cell_w, ry))); segs.push((dvec2(x0, ly), dvec2(bx, y0 + cell_h))); }
                         _ => {}
                     }
                 }
             }
+            for &(p1, p2) in &segs { self.draw_line.draw_line(cx, p1, p2, 1.5); }
+            // Inline label on the middle segment of the level, rotated to follow the line
+            if self.show_labels && !segs.is_empty() {
+                let (p1, p2) = segs[segs.len() / 2];
+                self.label.set_color(self.draw_line.color);
+                self.label.draw_rotated(cx, (p1 + p2) * 0.5, &format!("{:.1}", level), TextAnchor::Center, upright_angle(p2 - p1));
+            }
         }
 
-        self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+        self.draw_line.color = self.style.axes_color;
         self.draw_line.draw_line(cx, dvec2(self.plot_area.left, self.plot_area.bottom), dvec2(self.plot_area.right, self.plot_area.bottom), 1.0);
         self.draw_line.draw_line(cx, dvec2(self.plot_area.left, self.plot_area.bottom), dvec2(self.plot_area.left, self.plot_area.top), 1.0);
     }
 
     fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+        self.label.set_color(self.style.text_color);
         if !self.title.is_empty() {
             self.label.draw_at(cx, dvec2((self.plot_area.left + self.plot_area.right) / 2.0, self.plot_area.top - 15.0), &self.title, TextAnchor::Center);
         }
@@ -5408,13 +8294,20 @@ impl ContourPlot {
 
 impl ContourPlotRef {
     pub fn set_title(&self, title: impl Into<String>) { if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); } }
-    pub fn set_data(&self, data: Vec<Vec<f64>>) { if let Some(mut inner) = self.borrow_mut() { inner.set_data(data); } }
+    pub fn set_data(&self, data: impl IntoGrid) { if let Some(mut inner) = self.borrow_mut() { inner.set_data(data); } }
     pub fn set_x_range(&self, min: f64, max: f64) { if let Some(mut inner) = self.borrow_mut() { inner.set_x_range(min, max); } }
     pub fn set_y_range(&self, min: f64, max: f64) { if let Some(mut inner) = self.borrow_mut() { inner.set_y_range(min, max); } }
     pub fn set_filled(&self, filled: bool) { if let Some(mut inner) = self.borrow_mut() { inner.set_filled(filled); } }
     pub fn set_colormap(&self, colormap: Colormap) { if let Some(mut inner) = self.borrow_mut() { inner.set_colormap(colormap); } }
+    pub fn set_show_labels(&self, show: bool) { if let Some(mut inner) = self.borrow_mut() { inner.set_show_labels(show); } }
     pub fn clear(&self) { if let Some(mut inner) = self.borrow_mut() { inner.clear(); } }
     pub fn redraw(&self, cx: &mut Cx) { if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); } }
+    pub fn set_show_colorbar(&self, show: bool) { if let Some(mut inner) = self.borrow_mut() { inner.set_show_colorbar(show); } }
+    pub fn set_colorbar_orientation(&self, orientation: ColorbarOrientation) { if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_orientation(orientation); } }
+    pub fn set_colorbar_label(&self, label: impl Into<String>) { if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_label(label); } }
+    pub fn set_colorbar_extend(&self, extend: ColorbarExtend) { if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_extend(extend); } }
+    pub fn set_norm(&self, norm: impl Norm + 'static) { if let Some(mut inner) = self.borrow_mut() { inner.set_norm(norm); } }
+    pub fn set_style(&self, style: PlotStyle) { if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); } }
 }
 
 // =============================================================================
@@ -5426,7 +8319,7 @@ pub struct QuiverPlot {
     #[deref] #[live] view: View,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] x: Vec<f64>,
     #[rust] y: Vec<f64>,
     #[rust] u: Vec<f64>,
@@ -5438,11 +8331,17 @@ pub struct QuiverPlot {
     #[live(30.0)] right_margin: f64,
     #[live(30.0)] top_margin: f64,
     #[live(50.0)] bottom_margin: f64,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Widget for QuiverPlot {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) { self.view.handle_event(cx, event, scope); }
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.x.is_empty() {
@@ -5457,11 +8356,16 @@ impl Widget for QuiverPlot {
 
 impl QuiverPlot {
     pub fn set_title(&mut self, title: impl Into<String>) { self.title = title.into(); }
-    pub fn set_data(&mut self, x: Vec<f64>, y: Vec<f64>, u: Vec<f64>, v: Vec<f64>) { self.x = x; self.y = y; self.u = u; self.v = v; }
+    pub fn set_data(&mut self, x: impl IntoValues, y: impl IntoValues, u: impl IntoValues, v: impl IntoValues) {
+        self.x = x.into_values().into_owned(); self.y = y.into_values().into_owned(); self.u = u.into_values().into_owned(); self.v = v.into_values().into_owned();
+    }
     pub fn set_scale(&mut self, scale: f64) { self.scale = scale; }
     pub fn set_color(&mut self, color: Vec4) { self.arrow_color = color; }
     pub fn clear(&mut self) { self.x.clear(); self.y.clear(); self.u.clear(); self.v.clear(); }
 
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) { self.style_override = Some(style); }
+
     fn draw_arrows(&mut self, cx: &mut Cx2d) {
         let n = self.x.len().min(self.y.len()).min(self.u.len()).min(self.v.len());
         if n == 0 { return; }
@@ -5495,7 +8399,7 @@ impl QuiverPlot {
     }
 
     fn draw_labels(&mut self, cx: &mut Cx2d) {
-        self.label.set_color(vec4(0.3, 0.3, 0.3, 1.0));
+        self.label.set_color(self.style.text_color);
         if !self.title.is_empty() {
             self.label.draw_at(cx, dvec2((self.plot_area.left + self.plot_area.right) / 2.0, self.plot_area.top - 15.0), &self.title, TextAnchor::Center);
         }
@@ -5504,11 +8408,12 @@ impl QuiverPlot {
 
 impl QuiverPlotRef {
     pub fn set_title(&self, title: impl Into<String>) { if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); } }
-    pub fn set_data(&self, x: Vec<f64>, y: Vec<f64>, u: Vec<f64>, v: Vec<f64>) { if let Some(mut inner) = self.borrow_mut() { inner.set_data(x, y, u, v); } }
+    pub fn set_data(&self, x: impl IntoValues, y: impl IntoValues, u: impl IntoValues, v: impl IntoValues) { if let Some(mut inner) = self.borrow_mut() { inner.set_data(x, y, u, v); } }
     pub fn set_scale(&self, scale: f64) { if let Some(mut inner) = self.borrow_mut() { inner.set_scale(scale); } }
     pub fn set_color(&self, color: Vec4) { if let Some(mut inner) = self.borrow_mut() { inner.set_color(color); } }
     pub fn clear(&self) { if let Some(mut inner) = self.borrow_mut() { inner.clear(); } }
     pub fn redraw(&self, cx: &mut Cx) { if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); } }
+    pub fn set_style(&self, style: PlotStyle) { if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); } }
 }
 
 // =============================================================================
@@ -5581,8 +8486,8 @@ pub struct Surface3D {
     #[live] draw_fill: DrawPlotFill,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
-    #[rust] z_data: Vec<Vec<f64>>,
+    #[live] title: String,
+    #[rust] z_data: Matrix,
     #[rust] x_range: (f64, f64),
     #[rust] y_range: (f64, f64),
     #[rust] z_range: (f64, f64),
@@ -5594,28 +8499,34 @@ pub struct Surface3D {
     #[rust] drag_start: Option<DVec2>,
     #[rust] start_azimuth: f64,
     #[rust] start_elevation: f64,
+    #[live] colorbar: PlotColorbar,
+    #[rust] show_colorbar: bool,
+    #[rust] norm: Option<Box<dyn Norm>>,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Surface3D {
     pub fn set_title(&mut self, title: impl Into<String>) { self.title = title.into(); }
 
-    pub fn set_data(&mut self, z: Vec<Vec<f64>>) {
-        if z.is_empty() || z[0].is_empty() { return; }
+    pub fn set_data(&mut self, z: impl IntoGrid) {
+        let z = z.into_matrix();
+        if z.is_empty() { return; }
 
         // Calculate z range
         let mut z_min = f64::MAX;
         let mut z_max = f64::MIN;
-        for row in &z {
-            for &val in row {
-                if val < z_min { z_min = val; }
-                if val > z_max { z_max = val; }
-            }
+        for &val in z.values() {
+            if val < z_min { z_min = val; }
+            if val > z_max { z_max = val; }
         }
 
         self.z_data = z;
         self.z_range = (z_min, z_max);
-        self.x_range = (0.0, (self.z_data[0].len() - 1) as f64);
-        self.y_range = (0.0, (self.z_data.len() - 1) as f64);
+        self.x_range = (0.0, (self.z_data.cols() - 1) as f64);
+        self.y_range = (0.0, (self.z_data.rows() - 1) as f64);
     }
 
     pub fn set_x_range(&mut self, min: f64, max: f64) { self.x_range = (min, max); }
@@ -5626,17 +8537,27 @@ impl Surface3D {
     pub fn set_colormap(&mut self, cm: Colormap) { self.colormap = cm; }
     pub fn set_wireframe(&mut self, show: bool) { self.show_wireframe = show; }
     pub fn set_surface(&mut self, show: bool) { self.show_surface = show; }
+    pub fn set_show_colorbar(&mut self, show: bool) { self.show_colorbar = show; }
+    pub fn set_colorbar_orientation(&mut self, orientation: ColorbarOrientation) { self.colorbar.set_orientation(orientation); }
+    pub fn set_colorbar_label(&mut self, label: impl Into<String>) { self.colorbar.set_label(label); }
+    /// Draw arrows on the colorbar for values beyond the colour range
+    pub fn set_colorbar_extend(&mut self, extend: ColorbarExtend) { self.colorbar.set_extend(extend); }
+    /// Colour the surface in discrete bands between these z boundaries
+    pub fn set_levels(&mut self, levels: Vec<f64>) { self.set_norm(BoundaryNorm::new(levels)); }
+    /// Map z to colours through any norm instead of a linear scale over the z range
+    pub fn set_norm(&mut self, norm: impl Norm + 'static) { self.norm = Some(Box::new(norm)); }
     pub fn clear(&mut self) { self.z_data.clear(); }
     pub fn redraw(&mut self, cx: &mut Cx) { self.view.redraw(cx); }
 
-    fn normalize_z(&self, z: f64) -> f64 {
-        if self.z_range.1 == self.z_range.0 { return 0.5; }
-        (z - self.z_range.0) / (self.z_range.1 - self.z_range.0)
-    }
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) { self.style_override = Some(style); }
 }
 
 impl Widget for Surface3D {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
+        self.colorbar.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -5646,12 +8567,18 @@ impl Widget for Surface3D {
             if !self.show_wireframe && !self.show_surface { self.show_wireframe = true; }
             if self.zoom == 0.0 { self.zoom = 1.0; }
 
-            let cx_center = rect.pos.x + rect.size.x * 0.5;
-            let cy_center = rect.pos.y + rect.size.y * 0.5;
-            let scale = rect.size.x.min(rect.size.y) * 0.35 * self.zoom;
+            // The surface is centred in the space left beside the colorbar
+            self.colorbar.set_colormap(self.colormap.clone());
+            self.colorbar.set_norm(resolve_norm(&self.norm, self.z_range.0, self.z_range.1));
+            let (colorbar_right, colorbar_bottom) = if self.show_colorbar { self.colorbar.margins(cx) } else { (0.0, 0.0) };
+            let width = rect.size.x - colorbar_right;
+            let height = rect.size.y - colorbar_bottom;
 
-            let rows = self.z_data.len();
-            let cols = self.z_data[0].len();
+            let cx_center = rect.pos.x + width * 0.5;
+            let cy_center = rect.pos.y + height * 0.5;
+            let scale = width.min(height) * 0.35 * self.zoom;
+
+            let (rows, cols) = self.z_data.shape();
 
             // Normalize coordinates to [-1, 1] range
             let x_scale = 2.0 / (cols - 1).max(1) as f64;
@@ -5667,10 +8594,11 @@ impl Widget for Surface3D {
                 let mut quads: Vec<(f64, usize, usize, Vec4)> = Vec::new();
                 for i in 0..rows-1 {
                     for j in 0..cols-1 {
-                        let avg_z = (self.z_data[i][j] + self.z_data[i+1][j] +
-                                     self.z_data[i][j+1] + self.z_data[i+1][j+1]) * 0.25;
-                        let t = self.normalize_z(avg_z);
-                        let color = self.colormap.sample(t);
+                        let avg_z = (self.z_data[(i, j)] + self.z_data[(i+1, j)] +
+                                     self.z_data[(i, j+1)] + self.z_data[(i+1, j+1)]) * 0.25;
+                        // Quads touching a missing value are left out
+                        if avg_z.is_nan() { continue; }
+                        let color = self.colorbar.color(avg_z);
 
                         // Calculate center point for depth
                         let cx_q = (j as f64 + 0.5) * x_scale - 1.0;
@@ -5693,7 +8621,7 @@ impl Widget for Surface3D {
                     for &(cj, ci) in &corners {
                         let x = cj as f64 * x_scale - 1.0;
                         let y = ci as f64 * y_scale - 1.0;
-                        let z = (self.z_data[ci][cj] - z_offset) * z_scale;
+                        let z = (self.z_data[(ci, cj)] - z_offset) * z_scale;
                         let (sx, sy) = self.view3d.project(x, y, z);
                         pts.push(dvec2(cx_center + sx * scale, cy_center - sy * scale));
                     }
@@ -5725,12 +8653,13 @@ impl Widget for Surface3D {
 
                             let x0 = j0 as f64 * x_scale - 1.0;
                             let y0 = i0 as f64 * y_scale - 1.0;
-                            let z0 = (self.z_data[i0][j0] - z_offset) * z_scale;
+                            let z0 = (self.z_data[(i0, j0)] - z_offset) * z_scale;
                             let (sx0, sy0) = self.view3d.project(x0, y0, z0);
 
                             let x1 = j1 as f64 * x_scale - 1.0;
                             let y1 = i1 as f64 * y_scale - 1.0;
-                            let z1 = (self.z_data[i1][j1] - z_offset) * z_scale;
+                            let z1 = (self.z_data[(i1, j1)] - z_offset) * z_scale;
+                            if z0.is_nan() || z1.is_nan() { continue; }
                             let (sx1, sy1) = self.view3d.project(x1, y1, z1);
 
                             self.draw_line.draw_line(cx,
@@ -5763,6 +8692,11 @@ impl Widget for Surface3D {
             if !self.title.is_empty() {
                 self.label.draw_at(cx, dvec2(rect.pos.x + 10.0, rect.pos.y + 5.0), &self.title, TextAnchor::TopLeft);
             }
+
+            if self.show_colorbar {
+                let area = PlotArea::new(rect.pos.x + 20.0, rect.pos.y + 30.0, rect.pos.x + width - 20.0, rect.pos.y + height - 20.0);
+                self.colorbar.draw(cx, &area, rect);
+            }
         }
 
         DrawStep::done()
@@ -5803,7 +8737,7 @@ impl Widget for Surface3D {
 
 impl Surface3DRef {
     pub fn set_title(&self, title: impl Into<String>) { if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); } }
-    pub fn set_data(&self, z: Vec<Vec<f64>>) { if let Some(mut inner) = self.borrow_mut() { inner.set_data(z); } }
+    pub fn set_data(&self, z: impl IntoGrid) { if let Some(mut inner) = self.borrow_mut() { inner.set_data(z); } }
     pub fn set_x_range(&self, min: f64, max: f64) { if let Some(mut inner) = self.borrow_mut() { inner.set_x_range(min, max); } }
     pub fn set_y_range(&self, min: f64, max: f64) { if let Some(mut inner) = self.borrow_mut() { inner.set_y_range(min, max); } }
     pub fn set_view(&self, view: View3D) { if let Some(mut inner) = self.borrow_mut() { inner.set_view(view); } }
@@ -5814,6 +8748,13 @@ impl Surface3DRef {
     pub fn set_surface(&self, show: bool) { if let Some(mut inner) = self.borrow_mut() { inner.set_surface(show); } }
     pub fn clear(&self) { if let Some(mut inner) = self.borrow_mut() { inner.clear(); } }
     pub fn redraw(&self, cx: &mut Cx) { if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); } }
+    pub fn set_show_colorbar(&self, show: bool) { if let Some(mut inner) = self.borrow_mut() { inner.set_show_colorbar(show); } }
+    pub fn set_colorbar_orientation(&self, orientation: ColorbarOrientation) { if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_orientation(orientation); } }
+    pub fn set_colorbar_label(&self, label: impl Into<String>) { if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_label(label); } }
+    pub fn set_colorbar_extend(&self, extend: ColorbarExtend) { if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_extend(extend); } }
+    pub fn set_levels(&self, levels: Vec<f64>) { if let Some(mut inner) = self.borrow_mut() { inner.set_levels(levels); } }
+    pub fn set_norm(&self, norm: impl Norm + 'static) { if let Some(mut inner) = self.borrow_mut() { inner.set_norm(norm); } }
+    pub fn set_style(&self, style: PlotStyle) { if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); } }
 }
 
 // =============================================================================
@@ -5843,7 +8784,7 @@ pub struct Scatter3D {
     #[live] draw_point: DrawPlotPoint,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] points: Vec<Point3D>,
     #[rust] default_color: Vec4,
     #[rust] default_size: f64,
@@ -5855,12 +8796,17 @@ pub struct Scatter3D {
     #[rust] drag_start: Option<DVec2>,
     #[rust] start_azimuth: f64,
     #[rust] start_elevation: f64,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Scatter3D {
     pub fn set_title(&mut self, title: impl Into<String>) { self.title = title.into(); }
 
-    pub fn set_data(&mut self, x: Vec<f64>, y: Vec<f64>, z: Vec<f64>) {
+    pub fn set_data(&mut self, x: impl IntoValues, y: impl IntoValues, z: impl IntoValues) {
+        let (x, y, z) = (x.into_values(), y.into_values(), z.into_values());
         self.points.clear();
         let n = x.len().min(y.len()).min(z.len());
         for i in 0..n {
@@ -5873,6 +8819,9 @@ impl Scatter3D {
         self.points.push(p);
     }
 
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) { self.style_override = Some(style); }
+
     fn auto_range(&mut self) {
         if self.points.is_empty() { return; }
         let mut x_min = f64::MAX; let mut x_max = f64::MIN;
@@ -5902,6 +8851,8 @@ impl Scatter3D {
 
 impl Widget for Scatter3D {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -6010,7 +8961,7 @@ impl Widget for Scatter3D {
 
 impl Scatter3DRef {
     pub fn set_title(&self, title: impl Into<String>) { if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); } }
-    pub fn set_data(&self, x: Vec<f64>, y: Vec<f64>, z: Vec<f64>) { if let Some(mut inner) = self.borrow_mut() { inner.set_data(x, y, z); } }
+    pub fn set_data(&self, x: impl IntoValues, y: impl IntoValues, z: impl IntoValues) { if let Some(mut inner) = self.borrow_mut() { inner.set_data(x, y, z); } }
     pub fn set_color(&self, c: Vec4) { if let Some(mut inner) = self.borrow_mut() { inner.set_color(c); } }
     pub fn set_point_size(&self, s: f64) { if let Some(mut inner) = self.borrow_mut() { inner.set_point_size(s); } }
     pub fn set_view(&self, view: View3D) { if let Some(mut inner) = self.borrow_mut() { inner.set_view(view); } }
@@ -6018,6 +8969,7 @@ impl Scatter3DRef {
     pub fn set_elevation(&self, el: f64) { if let Some(mut inner) = self.borrow_mut() { inner.set_elevation(el); } }
     pub fn clear(&self) { if let Some(mut inner) = self.borrow_mut() { inner.clear(); } }
     pub fn redraw(&self, cx: &mut Cx) { if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); } }
+    pub fn set_style(&self, style: PlotStyle) { if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); } }
 }
 
 // =============================================================================
@@ -6039,8 +8991,8 @@ impl Line3DSeries {
         Self { label: label.into(), x: Vec::new(), y: Vec::new(), z: Vec::new(),
                color: vec4(0.12, 0.47, 0.71, 1.0), width: 1.5 }
     }
-    pub fn with_data(mut self, x: Vec<f64>, y: Vec<f64>, z: Vec<f64>) -> Self {
-        self.x = x; self.y = y; self.z = z; self
+    pub fn with_data(mut self, x: impl IntoValues, y: impl IntoValues, z: impl IntoValues) -> Self {
+        self.x = x.into_values().into_owned(); self.y = y.into_values().into_owned(); self.z = z.into_values().into_owned(); self
     }
     pub fn with_color(mut self, c: Vec4) -> Self { self.color = c; self }
     pub fn with_width(mut self, w: f64) -> Self { self.width = w; self }
@@ -6051,7 +9003,7 @@ pub struct Line3D {
     #[deref] #[live] view: View,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] series: Vec<Line3DSeries>,
     #[rust] view3d: View3D,
     #[rust] x_range: (f64, f64),
@@ -6061,6 +9013,10 @@ pub struct Line3D {
     #[rust] drag_start: Option<DVec2>,
     #[rust] start_azimuth: f64,
     #[rust] start_elevation: f64,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Line3D {
@@ -6071,6 +9027,9 @@ impl Line3D {
         self.auto_range();
     }
 
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) { self.style_override = Some(style); }
+
     fn auto_range(&mut self) {
         let mut x_min = f64::MAX; let mut x_max = f64::MIN;
         let mut y_min = f64::MAX; let mut y_max = f64::MIN;
@@ -6101,6 +9060,8 @@ impl Line3D {
 
 impl Widget for Line3D {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -6209,6 +9170,7 @@ impl Line3DRef {
     pub fn set_elevation(&self, el: f64) { if let Some(mut inner) = self.borrow_mut() { inner.set_elevation(el); } }
     pub fn clear(&self) { if let Some(mut inner) = self.borrow_mut() { inner.clear(); } }
     pub fn redraw(&self, cx: &mut Cx) { if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); } }
+    pub fn set_style(&self, style: PlotStyle) { if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); } }
 }
 
 // =============================================================================
@@ -6223,6 +9185,14 @@ pub struct SubplotGrid {
     #[rust] cols: usize,
     #[rust] h_spacing: f64,
     #[rust] v_spacing: f64,
+    #[live] line_plot: Option<LivePtr>,
+    #[live] scatter_plot: Option<LivePtr>,
+    #[live] bar_plot: Option<LivePtr>,
+    #[live] histogram_chart: Option<LivePtr>,
+    #[live] pie_chart: Option<LivePtr>,
+    #[live] heatmap_chart: Option<LivePtr>,
+    /// Charts created by `set_figure`, with their 1-based subplot index
+    #[rust] cells: Vec<(usize, WidgetRef)>,
 }
 
 impl SubplotGrid {
@@ -6236,6 +9206,37 @@ impl SubplotGrid {
         self.v_spacing = v;
     }
 
+    /// Replace the grid contents with one chart per axes of `figure`. Subplots that
+    /// fail to load, or whose kind has no template here (candlestick, sankey...), are
+    /// left empty and returned with their 1-based index and the reason.
+    pub fn set_figure(&mut self, cx: &mut Cx, figure: &Figure) -> Result<(), Vec<(usize, SpecError)>> {
+        self.set_grid(figure.rows, figure.cols);
+        self.cells.clear();
+        let mut failed = Vec::new();
+        for (index, spec) in figure.specs() {
+            let template = match spec.kind.as_str() {
+                "line" => self.line_plot,
+                "scatter" => self.scatter_plot,
+                "bar" => self.bar_plot,
+                "histogram" => self.histogram_chart,
+                "pie" => self.pie_chart,
+                "heatmap" => self.heatmap_chart,
+                _ => None,
+            };
+            if template.is_none() {
+                failed.push((index, SpecError::Unsupported(format!("{} charts in a SubplotGrid", spec.kind))));
+                continue;
+            }
+            let widget = WidgetRef::new_from_ptr(cx, template);
+            match spec.load(&widget) {
+                Ok(()) => self.cells.push((index, widget)),
+                Err(err) => failed.push((index, err)),
+            }
+        }
+        self.redraw(cx);
+        if failed.is_empty() { Ok(()) } else { Err(failed) }
+    }
+
     pub fn redraw(&mut self, cx: &mut Cx) {
         self.view.redraw(cx);
     }
@@ -6249,11 +9250,35 @@ impl Widget for SubplotGrid {
         if self.h_spacing == 0.0 { self.h_spacing = 10.0; }
         if self.v_spacing == 0.0 { self.v_spacing = 10.0; }
 
-        self.view.draw_walk(cx, scope, walk)
+        if self.cells.is_empty() {
+            return self.view.draw_walk(cx, scope, walk);
+        }
+
+        // Figure charts are laid out on the grid by subplot index
+        let rect = cx.walk_turtle(walk);
+        let cols = self.cols.max(1);
+        let rows = self.rows.max(1);
+        let cell_w = (rect.size.x - self.h_spacing * (cols - 1) as f64) / cols as f64;
+        let cell_h = (rect.size.y - self.v_spacing * (rows - 1) as f64) / rows as f64;
+        for (index, widget) in &self.cells {
+            let (row, col) = ((index - 1) / cols, (index - 1) % cols);
+            let cell = Rect {
+                pos: dvec2(
+                    rect.pos.x + col as f64 * (cell_w + self.h_spacing),
+                    rect.pos.y + row as f64 * (cell_h + self.v_spacing),
+                ),
+                size: dvec2(cell_w.max(0.0), cell_h.max(0.0)),
+            };
+            widget.draw_walk_all(cx, scope, Walk::abs_rect(cell));
+        }
+        DrawStep::done()
     }
 
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
+        for (_, widget) in &self.cells {
+            widget.handle_event(cx, event, scope);
+        }
     }
 }
 
@@ -6264,6 +9289,12 @@ impl SubplotGridRef {
     pub fn set_spacing(&self, h: f64, v: f64) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_spacing(h, v); }
     }
+    pub fn set_figure(&self, cx: &mut Cx, figure: &Figure) -> Result<(), Vec<(usize, SpecError)>> {
+        match self.borrow_mut() {
+            Some(mut inner) => inner.set_figure(cx, figure),
+            None => Ok(()),
+        }
+    }
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
@@ -6271,6 +9302,8 @@ impl SubplotGridRef {
 
 // =============================================================================
 // LinePlotDual - Line plot with dual y-axes (twinx support)
+// Kept for existing layouts; new code can use `LinePlot::twinx`, which also works on
+// scatter, bar and candlestick charts and pans and zooms with the host axes
 // =============================================================================
 
 #[derive(Live, LiveHook, Widget)]
@@ -6280,18 +9313,23 @@ pub struct LinePlotDual {
     #[live] draw_fill: DrawPlotFill,
     #[live] draw_point: DrawPlotPoint,
     #[live] label: PlotLabel,
-    #[rust] title: String,
-    #[rust] x_label: String,
-    #[rust] y_label: String,
-    #[rust] y2_label: String,
+    #[live] title: String,
+    #[live] x_label: String,
+    #[live] y_label: String,
+    #[live] y2_label: String,
     #[rust] series_left: Vec<Series>,
     #[rust] series_right: Vec<Series>,
     #[rust] x_range: (f64, f64),
     #[rust] y_range: (f64, f64),
     #[rust] y2_range: (f64, f64),
-    #[rust] show_grid: bool,
-    #[rust] show_legend: bool,
-    #[rust] legend_position: LegendPosition,
+    #[live] show_grid: bool,
+    #[live] show_legend: bool,
+    #[live] legend_position: LegendPosition,
+    #[rust] prop_cycle: Option<PropCycle>,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl LinePlotDual {
@@ -6310,6 +9348,12 @@ impl LinePlotDual {
         self.auto_range_right();
     }
 
+    /// Colours handed out in turn to series without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) { self.prop_cycle = Some(cycle); }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) { self.style_override = Some(style); }
+
     fn auto_range_left(&mut self) {
         let mut x_min = f64::MAX; let mut x_max = f64::MIN;
         let mut y_min = f64::MAX; let mut y_max = f64::MIN;
@@ -6351,6 +9395,8 @@ impl LinePlotDual {
 
 impl Widget for LinePlotDual {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -6373,7 +9419,7 @@ impl Widget for LinePlotDual {
 
                 // Draw grid
                 if self.show_grid {
-                    self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
+                    self.draw_line.color = self.style.grid_color;
                     for i in 0..=5 {
                         let t = i as f64 / 5.0;
                         let x = plot_rect.pos.x + t * plot_rect.size.x;
@@ -6395,7 +9441,7 @@ impl Widget for LinePlotDual {
 
                 // Draw left series
                 for (idx, s) in self.series_left.iter().enumerate() {
-                    let color = s.color.unwrap_or_else(|| get_color(idx));
+                    let color = s.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx));
                     let line_width = s.line_width.unwrap_or(1.5);
                     self.draw_line.color = color;
                     let n = s.x.len().min(s.y.len());
@@ -6410,7 +9456,7 @@ impl Widget for LinePlotDual {
 
                 // Draw right series (uses y2_range)
                 for (idx, s) in self.series_right.iter().enumerate() {
-                    let color = s.color.unwrap_or_else(|| get_color(idx + self.series_left.len()));
+                    let color = s.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(idx + self.series_left.len()));
                     let line_width = s.line_width.unwrap_or(1.5);
                     self.draw_line.color = color;
                     let n = s.x.len().min(s.y.len());
@@ -6478,7 +9524,7 @@ impl Widget for LinePlotDual {
 
                         for (i, s) in all_series.iter().enumerate() {
                             let y = legend_y + i as f64 * 18.0;
-                            self.draw_line.color = s.color.unwrap_or_else(|| get_color(i));
+                            self.draw_line.color = s.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(i));
                             self.draw_line.draw_line(cx, dvec2(legend_x, y + 6.0), dvec2(legend_x + 20.0, y + 6.0), 2.0);
                             self.label.draw_at(cx, dvec2(legend_x + 25.0, y), &s.label, TextAnchor::TopLeft);
                         }
@@ -6509,12 +9555,17 @@ impl LinePlotDualRef {
     pub fn set_legend(&self, pos: LegendPosition) { if let Some(mut inner) = self.borrow_mut() { inner.set_legend(pos); } }
     pub fn clear(&self) { if let Some(mut inner) = self.borrow_mut() { inner.clear(); } }
     pub fn redraw(&self, cx: &mut Cx) { if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); } }
+    pub fn set_prop_cycle(&self, cycle: PropCycle) { if let Some(mut inner) = self.borrow_mut() { inner.set_prop_cycle(cycle); } }
+    pub fn set_style(&self, style: PlotStyle) { if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); } }
 }
 
 // =============================================================================
 // CandlestickChart Widget - Financial OHLC visualization
 // =============================================================================
 
+/// Candle timestamps from here on (early 1973) are taken as Unix seconds
+const UNIX_TIME_MIN: f64 = 1.0e8;
+
 /// A single candlestick data point (OHLC)
 #[derive(Clone, Debug)]
 pub struct Candle {
@@ -6546,18 +9597,31 @@ pub struct CandlestickChart {
     #[deref] #[live] view: View,
     #[live] draw_fill: DrawPlotFill,
     #[live] draw_line: DrawPlotLine,
+    #[live] draw_point: DrawPlotPoint,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] legend: PlotLegend,
+    #[live] legend_position: Option<LegendPosition>,
+    #[live] title: String,
     #[rust] candles: Vec<Candle>,
+    // Indicators on their own axis, e.g. volume or RSI on the right
+    #[rust] twins: TwinAxes,
     #[rust] plot_area: PlotArea,
     #[rust] bullish_color: Vec4,
     #[rust] bearish_color: Vec4,
     #[rust] show_volume: bool,
     #[rust] candle_width: f64,
-    #[rust(50.0)] left_margin: f64,
-    #[rust(30.0)] bottom_margin: f64,
-    #[rust(20.0)] right_margin: f64,
-    #[rust(30.0)] top_margin: f64,
+    #[rust] x_tick_strategy: TickLabelStrategy,
+    #[rust] x_tick_layout: TickLabelLayout,
+    #[live] margins: Margins,
+    #[live] show_grid: bool,
+    // Spines, tick marks and grid lines
+    #[rust] spines: Spines,
+    #[rust] tick_params: TickParams,
+    #[rust] grid_style: GridStyle,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl CandlestickChart {
@@ -6586,8 +9650,111 @@ impl CandlestickChart {
         self.candle_width = width;
     }
 
+    /// Choose how the time labels are rotated, wrapped or thinned when they overlap
+    pub fn set_xtick_strategy(&mut self, strategy: TickLabelStrategy) {
+        self.x_tick_strategy = strategy;
+    }
+
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
+    }
+
     pub fn clear(&mut self) {
         self.candles.clear();
+        self.twins.iter_mut().for_each(TwinAxis::clear);
+    }
+
+    /// Clear the candles and put the title, colours, legend, margins and style back to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.twins = TwinAxes::default();
+        self.bullish_color = Vec4::default();
+        self.bearish_color = Vec4::default();
+        self.title.clear();
+        self.margins = Margins::default();
+        self.legend.reset();
+        self.legend_position = None;
+        self.spines = Spines::default();
+        self.tick_params = TickParams::default();
+        self.grid_style = GridStyle::default();
+        self.style_override = None;
+    }
+
+    /// The chart as a "candlestick" spec; twin axes have no spec field
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            margins: margins_spec(&self.margins),
+            candles: self.candles.iter().map(CandleSpec::from).collect(),
+            ..ChartSpec::new("candlestick")
+        }
+    }
+
+    /// Add a second y axis on the right sharing the time axis, like matplotlib's `twinx`,
+    /// or return the one already there
+    pub fn twinx(&mut self) -> &mut TwinAxis {
+        self.twins.get_or_insert(TwinSide::Right)
+    }
+
+    /// Add a second x axis along the top sharing the price axis, like matplotlib's `twiny`
+    pub fn twiny(&mut self) -> &mut TwinAxis {
+        self.twins.get_or_insert(TwinSide::Top)
+    }
+
+    /// Attach a twin axis built up front, replacing any twin on the same side
+    pub fn set_twin(&mut self, axis: TwinAxis) {
+        self.twins.set(axis);
+    }
+
+    /// Add a series against the twin y axis, with timestamps as x values
+    pub fn add_twinx_series(&mut self, series: Series) {
+        self.twinx().add_series(series);
+    }
+
+    /// Add a series against the twin x axis along the top
+    pub fn add_twiny_series(&mut self, series: Series) {
+        self.twiny().add_series(series);
+    }
+
+    pub fn clear_twins(&mut self) {
+        self.twins.clear();
+    }
+
+    /// Legend for the twin series; the candles themselves have no entry
+    pub fn set_legend(&mut self, position: LegendPosition) {
+        self.legend.set_position(position);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    /// Price grid lines; off by default
+    pub fn set_show_grid(&mut self, show: bool) {
+        self.show_grid = show;
+    }
+
+    /// Show, hide, recolour or move one spine, e.g.
+    /// `set_spine(SpineSide::Left, Spine::default().with_position(SpinePosition::Zero))`
+    pub fn set_spine(&mut self, side: SpineSide, spine: Spine) {
+        *self.spines.get_mut(side) = spine;
+    }
+
+    pub fn set_spines(&mut self, spines: Spines) {
+        self.spines = spines;
+    }
+
+    /// Direction, sizes and sides of the tick marks, and whether minor ticks are drawn
+    pub fn set_tick_params(&mut self, params: TickParams) {
+        self.tick_params = params;
+    }
+
+    /// Major and minor grid line styles, drawn along the value axis
+    pub fn set_grid_style(&mut self, grid: GridStyle) {
+        self.grid_style = grid;
     }
 
     fn compute_ranges(&self) -> (f64, f64, f64, f64) {
@@ -6612,20 +9779,151 @@ impl CandlestickChart {
 
         (x_min, x_max, y_min, y_max)
     }
+
+    /// What the twin axes share with this chart: time along x, price along y
+    fn twin_draw(&self) -> TwinDraw<'_> {
+        let (x_min, x_max, y_min, y_max) = self.compute_ranges();
+        TwinDraw {
+            area: self.plot_area,
+            x: AxisRange::linear((x_min, x_max)),
+            y: AxisRange::linear((y_min, y_max)),
+            style: &self.style,
+            cycle: &self.style.prop_cycle,
+            first_color: 0,
+            markers_only: false,
+        }
+    }
+
+    /// Time labels as (pixel x, text) for a plot area spanning `left` to `right`.
+    /// Timestamps from 1973 on are read as Unix seconds and labelled with dates,
+    /// smaller values as candle indices.
+    fn x_tick_labels(&self, left: f64, right: f64) -> Vec<(f64, String)> {
+        let (x_min, x_max, _, _) = self.compute_ranges();
+        if self.candles.is_empty() || x_max <= x_min {
+            return Vec::new();
+        }
+        let ticks = if x_min >= UNIX_TIME_MIN {
+            scale_ticks(ScaleType::Time, x_min, x_max)
+        } else {
+            linear_ticks(x_min, x_max)
+        };
+        ticks.into_iter()
+            .filter(|(t, _)| *t >= x_min && *t <= x_max)
+            .map(|(t, text)| (left + (t - x_min) / (x_max - x_min) * (right - left), text))
+            .collect()
+    }
+
+    fn y_tick_labels(&self) -> Vec<String> {
+        self.y_tick_values().iter().map(|y| format!("{:.1}", y)).collect()
+    }
+
+    fn y_tick_values(&self) -> Vec<f64> {
+        let (_, _, y_min, y_max) = self.compute_ranges();
+        let num_ticks = 5;
+        (0..=num_ticks).map(|i| y_min + (y_max - y_min) * i as f64 / num_ticks as f64).collect()
+    }
+
+    fn axis_ranges(&self) -> (AxisRange, AxisRange) {
+        let (x_min, x_max, y_min, y_max) = self.compute_ranges();
+        (AxisRange::linear((x_min, x_max)), AxisRange::linear((y_min, y_max)))
+    }
+
+    /// Major and minor tick positions in pixels: x, y, x minor, y minor. Time ticks get
+    /// no minor ones.
+    fn frame_ticks(&self) -> [Vec<f64>; 4] {
+        let (_, _, y_min, y_max) = self.compute_ranges();
+        let y_major = self.y_tick_values();
+        let y_minor = minor_ticks(ScaleType::Linear, &y_major, (y_min, y_max));
+        let (_, y) = self.axis_ranges();
+        let to_y = |ys: Vec<f64>| -> Vec<f64> { ys.into_iter().map(|v| self.plot_area.bottom - y.fraction(v) * self.plot_area.height()).collect() };
+        let x_major = self.x_tick_labels(self.plot_area.left, self.plot_area.right).into_iter().map(|(px, _)| px).collect();
+        [x_major, to_y(y_major), Vec::new(), to_y(y_minor)]
+    }
+
+    /// Pixel x of the left spine or pixel y of the bottom one, where the tick labels hang from
+    fn spine_offset(&self, side: SpineSide) -> f64 {
+        let (x, y) = self.axis_ranges();
+        self.spines.get(side).offset(side, &self.plot_area, x, y)
+    }
+
+    /// Reach of the tick marks past the bottom and the left spine
+    fn tick_extents(&self) -> (f64, f64) {
+        (
+            self.tick_params.outward_extent(SpineSide::Bottom, &self.style),
+            self.tick_params.outward_extent(SpineSide::Left, &self.style),
+        )
+    }
+
+    fn draw_grid(&mut self, cx: &mut Cx2d) {
+        if !self.show_grid {
+            return;
+        }
+
+        // Lines along the value axis only
+        let [_, y_ticks, _, y_minor] = self.frame_ticks();
+        let (x_ticks, x_minor): (Vec<f64>, Vec<f64>) = (Vec::new(), Vec::new());
+        let (x, y) = self.axis_ranges();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.grid_style.draw(cx, &frame, &mut self.draw_line);
+    }
+
+    fn draw_axes(&mut self, cx: &mut Cx2d) {
+        let [x_ticks, y_ticks, x_minor, y_minor] = self.frame_ticks();
+        let (x, y) = self.axis_ranges();
+        let frame = AxesFrame {
+            area: self.plot_area,
+            x,
+            y,
+            x_ticks: &x_ticks,
+            y_ticks: &y_ticks,
+            x_minor: &x_minor,
+            y_minor: &y_minor,
+            style: &self.style,
+        };
+        self.spines.draw(cx, &frame, &self.tick_params, &mut self.draw_line);
+    }
 }
 
 impl Widget for CandlestickChart {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        // Applied once per live update, so later set_legend calls still win
+        if let Some(position) = self.legend_position.take() {
+            self.set_legend(position);
+        }
+        self.style.apply(&mut self.view, &mut self.label);
+        self.legend.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
         if rect.size.x > 0.0 && rect.size.y > 0.0 {
+            let entries = self.twins.legend_entries(&self.twin_draw());
+            self.legend.set_entries(entries);
+            let (legend_right, legend_bottom) = self.legend.outside_margins(cx);
+            let (twin_right, twin_top) = self.twins.margins(cx, &mut self.label, &self.style);
+
+            // Tight layout: derive the plot area from the measured text around the axes
+            let y_ticks = self.y_tick_labels();
+            let (x_tick_extent, y_tick_extent) = self.tick_extents();
+            let text = AxesText { title: &self.title, y_ticks: &y_ticks, title_size: Some(self.style.title_font_size), top_axis: twin_top, x_tick_extent, y_tick_extent, ..Default::default() };
+            let (left, right) = self.margins.columns(cx, &mut self.label, &text, legend_right + twin_right);
+
+            // Time labels depend on the final width, the bottom margin on their layout
+            let x_ticks = self.x_tick_labels(rect.pos.x + left, rect.pos.x + rect.size.x - right);
+            self.x_tick_layout = TickLabelLayout::compute(cx, &mut self.label, x_ticks, self.x_tick_strategy);
+            let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &self.x_tick_layout);
             let plot_rect = Rect {
-                pos: dvec2(rect.pos.x + self.left_margin, rect.pos.y + self.top_margin),
-                size: dvec2(
-                    rect.size.x - self.left_margin - self.right_margin,
-                    rect.size.y - self.top_margin - self.bottom_margin
-                ),
+                pos: dvec2(rect.pos.x + left, rect.pos.y + top),
+                size: dvec2(rect.size.x - left - right, rect.size.y - top - bottom - legend_bottom),
             };
             self.plot_area = PlotArea::new(plot_rect.pos.x, plot_rect.pos.y,
                 plot_rect.pos.x + plot_rect.size.x, plot_rect.pos.y + plot_rect.size.y);
@@ -6640,28 +9938,23 @@ impl Widget for CandlestickChart {
 
             let (x_min, x_max, y_min, y_max) = self.compute_ranges();
 
-            // Draw title
-            if !self.title.is_empty() {
-                self.label.draw_at(cx, dvec2(rect.pos.x + rect.size.x / 2.0, rect.pos.y + 5.0),
-                    &self.title, TextAnchor::TopCenter);
-            }
+            // Draw title and time labels, hung below the tick marks
+            text.draw(cx, &mut self.label, &self.plot_area, &self.x_tick_layout);
+            let axis_y = self.spine_offset(SpineSide::Bottom);
+            self.x_tick_layout.draw(cx, &mut self.label, axis_y + x_tick_extent);
 
-            // Draw axes
-            self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
-            self.draw_line.draw_line(cx,
-                dvec2(plot_rect.pos.x, plot_rect.pos.y + plot_rect.size.y),
-                dvec2(plot_rect.pos.x + plot_rect.size.x, plot_rect.pos.y + plot_rect.size.y), 1.0);
-            self.draw_line.draw_line(cx,
-                dvec2(plot_rect.pos.x, plot_rect.pos.y),
-                dvec2(plot_rect.pos.x, plot_rect.pos.y + plot_rect.size.y), 1.0);
+            // Draw grid and axes
+            self.draw_grid(cx);
+            self.draw_axes(cx);
 
             // Draw Y axis labels
+            let label_x = self.spine_offset(SpineSide::Left) - y_tick_extent - TICK_LABEL_PAD;
             let num_ticks = 5;
             for i in 0..=num_ticks {
                 let t = i as f64 / num_ticks as f64;
                 let y_val = y_min + (y_max - y_min) * t;
                 let y_pos = plot_rect.pos.y + plot_rect.size.y - t * plot_rect.size.y;
-                self.label.draw_at(cx, dvec2(plot_rect.pos.x - 5.0, y_pos),
+                self.label.draw_at(cx, dvec2(label_x, y_pos),
                     &format!("{:.1}", y_val), TextAnchor::MiddleRight);
             }
 
@@ -6707,6 +10000,20 @@ impl Widget for CandlestickChart {
                     size: dvec2(candle_width, body_height),
                 });
             }
+
+            // Twin series and axes over the candles, then their legend
+            let ctx = TwinDraw {
+                area: self.plot_area,
+                x: AxisRange::linear((x_min, x_max)),
+                y: AxisRange::linear((y_min, y_max)),
+                style: &self.style,
+                cycle: &self.style.prop_cycle,
+                first_color: 0,
+                markers_only: false,
+            };
+            self.twins.draw(cx, &ctx, &mut self.draw_line, &mut self.draw_point, &mut self.label);
+            let points = self.twins.data_points(&ctx);
+            self.legend.draw(cx, &self.plot_area, rect, &points);
         }
 
         DrawStep::done()
@@ -6718,6 +10025,9 @@ impl Widget for CandlestickChart {
 }
 
 impl CandlestickChartRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() { inner.reset(); }
+    }
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
@@ -6736,12 +10046,51 @@ impl CandlestickChartRef {
     pub fn set_candle_width(&self, width: f64) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_candle_width(width); }
     }
+    pub fn set_xtick_strategy(&self, strategy: TickLabelStrategy) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_xtick_strategy(strategy); }
+    }
     pub fn clear(&self) {
         if let Some(mut inner) = self.borrow_mut() { inner.clear(); }
     }
+    pub fn set_twin(&self, axis: TwinAxis) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_twin(axis); }
+    }
+    pub fn add_twinx_series(&self, series: Series) {
+        if let Some(mut inner) = self.borrow_mut() { inner.add_twinx_series(series); }
+    }
+    pub fn add_twiny_series(&self, series: Series) {
+        if let Some(mut inner) = self.borrow_mut() { inner.add_twiny_series(series); }
+    }
+    pub fn clear_twins(&self) {
+        if let Some(mut inner) = self.borrow_mut() { inner.clear_twins(); }
+    }
+    pub fn set_legend(&self, position: LegendPosition) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_legend(position); }
+    }
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_margins(margins); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
+    pub fn set_show_grid(&self, show: bool) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_show_grid(show); }
+    }
+    pub fn set_spine(&self, side: SpineSide, spine: Spine) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_spine(side, spine); }
+    }
+    pub fn set_spines(&self, spines: Spines) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_spines(spines); }
+    }
+    pub fn set_tick_params(&self, params: TickParams) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_tick_params(params); }
+    }
+    pub fn set_grid_style(&self, grid: GridStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_grid_style(grid); }
+    }
 }
 
 // =============================================================================
@@ -6758,10 +10107,10 @@ pub struct RadarSeries {
 }
 
 impl RadarSeries {
-    pub fn new(label: impl Into<String>, values: Vec<f64>) -> Self {
+    pub fn new(label: impl Into<String>, values: impl IntoValues) -> Self {
         Self {
             label: label.into(),
-            values,
+            values: values.into_values().into_owned(),
             color: vec4(0.12, 0.47, 0.71, 1.0),
             fill_alpha: 0.3,
         }
@@ -6786,13 +10135,17 @@ pub struct RadarChart {
     #[live] draw_point: DrawPlotPointGradient,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] axes: Vec<String>,
     #[rust] series: Vec<RadarSeries>,
     #[rust] max_value: f64,
-    #[rust] show_grid: bool,
+    #[live] show_grid: bool,
     #[rust] grid_levels: usize,
     #[rust] use_gradient: bool,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl RadarChart {
@@ -6829,6 +10182,31 @@ impl RadarChart {
         self.use_gradient = false;
     }
 
+    /// Clear the axes and series and put the title, scale and style back to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.axes.clear();
+        self.max_value = 0.0;
+        self.title.clear();
+        self.style_override = None;
+    }
+
+    /// The chart as a "radar" spec
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            categories: self.axes.clone(),
+            series: self.series.iter().map(SeriesSpec::from).collect(),
+            ..ChartSpec::new("radar")
+        }
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
     fn compute_max(&self) -> f64 {
         if self.max_value > 0.0 {
             return self.max_value;
@@ -6843,6 +10221,8 @@ impl RadarChart {
 
 impl Widget for RadarChart {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -6992,6 +10372,9 @@ impl Widget for RadarChart {
 }
 
 impl RadarChartRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() { inner.reset(); }
+    }
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
@@ -7019,6 +10402,9 @@ impl RadarChartRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // =============================================================================
@@ -7049,16 +10435,19 @@ pub struct WaterfallChart {
     #[live] draw_fill: DrawPlotFill,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] entries: Vec<WaterfallEntry>,
     #[rust] positive_color: Vec4,
     #[rust] negative_color: Vec4,
     #[rust] total_color: Vec4,
     #[rust] connector_color: Vec4,
-    #[rust(50.0)] left_margin: f64,
-    #[rust(50.0)] bottom_margin: f64,
-    #[rust(20.0)] right_margin: f64,
-    #[rust(30.0)] top_margin: f64,
+    #[live] margins: Margins,
+    #[rust] x_tick_strategy: TickLabelStrategy,
+    #[rust] x_tick_layout: TickLabelLayout,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl WaterfallChart {
@@ -7066,27 +10455,83 @@ impl WaterfallChart {
         self.title = title.into();
     }
 
-    pub fn set_data(&mut self, entries: Vec<WaterfallEntry>) {
-        self.entries = entries;
+    pub fn set_data(&mut self, entries: Vec<WaterfallEntry>) {
+        self.entries = entries;
+    }
+
+    pub fn add_entry(&mut self, entry: WaterfallEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn set_colors(&mut self, positive: Vec4, negative: Vec4, total: Vec4) {
+        self.positive_color = positive;
+        self.negative_color = negative;
+        self.total_color = total;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Clear the entries and put the title, colours, margins and style back to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.positive_color = Vec4::default();
+        self.negative_color = Vec4::default();
+        self.total_color = Vec4::default();
+        self.connector_color = Vec4::default();
+        self.title.clear();
+        self.margins = Margins::default();
+        self.style_override = None;
+    }
+
+    /// The chart as a "waterfall" spec
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            margins: margins_spec(&self.margins),
+            entries: self.entries.iter().map(WaterfallEntrySpec::from).collect(),
+            ..ChartSpec::new("waterfall")
+        }
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
     }
 
-    pub fn add_entry(&mut self, entry: WaterfallEntry) {
-        self.entries.push(entry);
+    pub fn set_xtick_strategy(&mut self, strategy: TickLabelStrategy) {
+        self.x_tick_strategy = strategy;
     }
 
-    pub fn set_colors(&mut self, positive: Vec4, negative: Vec4, total: Vec4) {
-        self.positive_color = positive;
-        self.negative_color = negative;
-        self.total_color = total;
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
     }
 
-    pub fn clear(&mut self) {
-        self.entries.clear();
+    /// Tight layout: the plot area left once the title and entry labels are measured
+    fn layout_plot_rect(&mut self, cx: &mut Cx2d, rect: Rect) -> Rect {
+        let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), ..Default::default() };
+        let (left, right) = self.margins.columns(cx, &mut self.label, &text, 0.0);
+        let width = rect.size.x - left - right;
+        let bar_spacing = width / self.entries.len().max(1) as f64;
+        let x_ticks = self.entries.iter().enumerate()
+            .map(|(i, entry)| (rect.pos.x + left + (i as f64 + 0.5) * bar_spacing, entry.label.clone()))
+            .collect();
+        self.x_tick_layout = TickLabelLayout::compute(cx, &mut self.label, x_ticks, self.x_tick_strategy);
+        let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &self.x_tick_layout);
+        Rect {
+            pos: dvec2(rect.pos.x + left, rect.pos.y + top),
+            size: dvec2(width, rect.size.y - top - bottom),
+        }
     }
 }
 
 impl Widget for WaterfallChart {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -7105,13 +10550,7 @@ impl Widget for WaterfallChart {
                 self.connector_color = vec4(0.5, 0.5, 0.5, 0.5);
             }
 
-            let plot_rect = Rect {
-                pos: dvec2(rect.pos.x + self.left_margin, rect.pos.y + self.top_margin),
-                size: dvec2(
-                    rect.size.x - self.left_margin - self.right_margin,
-                    rect.size.y - self.top_margin - self.bottom_margin
-                ),
-            };
+            let plot_rect = self.layout_plot_rect(cx, rect);
 
             // Calculate cumulative values and ranges
             let mut cumulative = 0.0;
@@ -7138,14 +10577,14 @@ impl Widget for WaterfallChart {
             min_val -= range * 0.1;
             max_val += range * 0.1;
 
-            // Draw title
-            if !self.title.is_empty() {
-                self.label.draw_at(cx, dvec2(rect.pos.x + rect.size.x / 2.0, rect.pos.y + 5.0),
-                    &self.title, TextAnchor::TopCenter);
-            }
+            // Draw title and entry labels
+            let area = PlotArea::new(plot_rect.pos.x, plot_rect.pos.y, plot_rect.pos.x + plot_rect.size.x, plot_rect.pos.y + plot_rect.size.y);
+            let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), ..Default::default() };
+            text.draw(cx, &mut self.label, &area, &self.x_tick_layout);
+            self.x_tick_layout.draw(cx, &mut self.label, area.bottom);
 
             // Draw axes
-            self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+            self.draw_line.color = self.style.axes_color;
             self.draw_line.draw_line(cx,
                 dvec2(plot_rect.pos.x, plot_rect.pos.y + plot_rect.size.y),
                 dvec2(plot_rect.pos.x + plot_rect.size.x, plot_rect.pos.y + plot_rect.size.y), 1.0);
@@ -7172,7 +10611,7 @@ impl Widget for WaterfallChart {
 
             // Draw bars and connectors
             let mut prev_end_y = None;
-            for (i, ((start, end, is_total, value), entry)) in bar_data.iter().zip(self.entries.iter()).enumerate() {
+            for (i, (start, end, is_total, value)) in bar_data.iter().enumerate() {
                 let x = plot_rect.pos.x + i as f64 * bar_spacing + (bar_spacing - bar_width) / 2.0;
                 let start_y = value_to_y(*start);
                 let end_y = value_to_y(*end);
@@ -7205,11 +10644,6 @@ impl Widget for WaterfallChart {
                     size: dvec2(bar_width, bar_height),
                 });
 
-                // Draw label
-                self.label.draw_at(cx,
-                    dvec2(x + bar_width / 2.0, plot_rect.pos.y + plot_rect.size.y + 5.0),
-                    &entry.label, TextAnchor::TopCenter);
-
                 // Draw value
                 let value_y = if *value >= 0.0 { bar_top - 3.0 } else { bar_top + bar_height + 12.0 };
                 self.label.draw_at(cx, dvec2(x + bar_width / 2.0, value_y),
@@ -7228,6 +10662,9 @@ impl Widget for WaterfallChart {
 }
 
 impl WaterfallChartRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() { inner.reset(); }
+    }
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
@@ -7246,6 +10683,15 @@ impl WaterfallChartRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
+    pub fn set_xtick_strategy(&self, strategy: TickLabelStrategy) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_xtick_strategy(strategy); }
+    }
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_margins(margins); }
+    }
 }
 
 // =============================================================================
@@ -7258,7 +10704,7 @@ pub struct GaugeChart {
     #[live] draw_fill: DrawPlotFill,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] value: f64,
     #[rust] min_value: f64,
     #[rust] max_value: f64,
@@ -7266,6 +10712,10 @@ pub struct GaugeChart {
     #[rust] show_value: bool,
     #[rust] unit: String,
     #[rust] arc_width: f64,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl GaugeChart {
@@ -7298,6 +10748,11 @@ impl GaugeChart {
         self.arc_width = width;
     }
 
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
     fn get_color_for_value(&self, value: f64) -> Vec4 {
         if self.thresholds.is_empty() {
             return vec4(0.12, 0.47, 0.71, 1.0);
@@ -7313,6 +10768,8 @@ impl GaugeChart {
 
 impl Widget for GaugeChart {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -7348,7 +10805,7 @@ impl Widget for GaugeChart {
 
             // Draw background arc
             let num_segments = 60;
-            self.draw_line.color = vec4(0.85, 0.85, 0.85, 1.0);
+            self.draw_line.color = self.style.grid_color;
             for i in 0..num_segments {
                 let t1 = i as f64 / num_segments as f64;
                 let t2 = (i + 1) as f64 / num_segments as f64;
@@ -7387,7 +10844,7 @@ impl Widget for GaugeChart {
             self.draw_line.draw_line(cx, center, needle_end, 3.0);
 
             // Draw center circle
-            self.draw_fill.color = vec4(0.3, 0.3, 0.3, 1.0);
+            self.draw_fill.color = self.style.axes_color;
             self.draw_fill.draw_abs(cx, Rect {
                 pos: dvec2(center.x - 8.0, center.y - 8.0),
                 size: dvec2(16.0, 16.0),
@@ -7441,6 +10898,9 @@ impl GaugeChartRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // =============================================================================
@@ -7472,14 +10932,16 @@ pub struct FunnelChart {
     #[live] draw_fill: DrawPlotFill,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] stages: Vec<FunnelStage>,
-    #[rust] show_percentages: bool,
-    #[rust] show_values: bool,
-    #[rust(30.0)] left_margin: f64,
-    #[rust(20.0)] bottom_margin: f64,
-    #[rust(30.0)] right_margin: f64,
-    #[rust(30.0)] top_margin: f64,
+    #[live] show_percentages: bool,
+    #[live] show_values: bool,
+    #[live] margins: Margins,
+    #[rust] prop_cycle: Option<PropCycle>,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl FunnelChart {
@@ -7506,31 +10968,87 @@ impl FunnelChart {
     pub fn clear(&mut self) {
         self.stages.clear();
     }
+
+    /// Clear the stages and put the title, margins and style back to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.title.clear();
+        self.margins = Margins::default();
+        self.prop_cycle = None;
+        self.style_override = None;
+    }
+
+    /// The chart as a "funnel" spec
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            margins: margins_spec(&self.margins),
+            slices: self.stages.iter().map(SliceSpec::from).collect(),
+            ..ChartSpec::new("funnel")
+        }
+    }
+
+    /// Colours handed out in turn to stages without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    /// Override margins; sides left as `None` are computed from the surrounding text
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
+    }
+
+    /// Value or percentage shown to the right of each stage
+    fn value_labels(&self, max_value: f64) -> Vec<String> {
+        self.stages.iter().map(|stage| {
+            if self.show_percentages {
+                format!("{:.1}%", stage.value / max_value * 100.0)
+            } else {
+                format!("{:.0}", stage.value)
+            }
+        }).collect()
+    }
+
+    /// Tight layout: stage labels on the left, as y tick labels would be, and the values
+    /// on the right
+    fn layout_plot_rect(&mut self, cx: &mut Cx2d, rect: Rect, value_labels: &[String]) -> Rect {
+        let stage_labels: Vec<String> = self.stages.iter().map(|stage| stage.label.clone()).collect();
+        let text = AxesText { title: &self.title, y_ticks: &stage_labels, title_size: Some(self.style.title_font_size), ..Default::default() };
+        let value_width = max_text_width(cx, &mut self.label, value_labels) + TICK_LABEL_PAD;
+        let (left, right) = self.margins.columns(cx, &mut self.label, &text, value_width);
+        let (top, bottom) = self.margins.rows(cx, &mut self.label, &text, &TickLabelLayout::default());
+        Rect {
+            pos: dvec2(rect.pos.x + left, rect.pos.y + top),
+            size: dvec2(rect.size.x - left - right, rect.size.y - top - bottom),
+        }
+    }
 }
 
 impl Widget for FunnelChart {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.stages.is_empty() {
-            let plot_rect = Rect {
-                pos: dvec2(rect.pos.x + self.left_margin, rect.pos.y + self.top_margin),
-                size: dvec2(
-                    rect.size.x - self.left_margin - self.right_margin,
-                    rect.size.y - self.top_margin - self.bottom_margin
-                ),
-            };
-
-            // Draw title
-            if !self.title.is_empty() {
-                self.label.draw_at(cx, dvec2(rect.pos.x + rect.size.x / 2.0, rect.pos.y + 5.0),
-                    &self.title, TextAnchor::TopCenter);
-            }
-
             let max_value = self.stages.iter().map(|s| s.value).fold(0.0f64, f64::max);
             if max_value == 0.0 { return DrawStep::done(); }
 
+            let value_labels = self.value_labels(max_value);
+            let plot_rect = self.layout_plot_rect(cx, rect, &value_labels);
+
+            // Draw title
+            let area = PlotArea::new(plot_rect.pos.x, plot_rect.pos.y, plot_rect.pos.x + plot_rect.size.x, plot_rect.pos.y + plot_rect.size.y);
+            let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), ..Default::default() };
+            text.draw(cx, &mut self.label, &area, &TickLabelLayout::default());
+
             let num_stages = self.stages.len();
             let stage_height = plot_rect.size.y / num_stages as f64;
             let center_x = plot_rect.pos.x + plot_rect.size.x / 2.0;
@@ -7542,7 +11060,7 @@ impl Widget for FunnelChart {
                 let y = plot_rect.pos.y + i as f64 * stage_height;
 
                 // Get color
-                let color = stage.color.unwrap_or_else(|| get_color(i));
+                let color = stage.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(i));
 
                 // Draw trapezoid (approximated as rectangle for simplicity)
                 // For a proper funnel, we'd need next stage's width
@@ -7579,17 +11097,12 @@ impl Widget for FunnelChart {
                 self.draw_line.draw_line(cx, top_right, bottom_right, 1.0);
 
                 // Draw label on left
-                self.label.draw_at(cx, dvec2(plot_rect.pos.x - 5.0, y + stage_height / 2.0),
+                self.label.draw_at(cx, dvec2(plot_rect.pos.x - TICK_LABEL_PAD, y + stage_height / 2.0),
                     &stage.label, TextAnchor::MiddleRight);
 
                 // Draw value/percentage on right
-                let value_text = if self.show_percentages {
-                    format!("{:.1}%", ratio * 100.0)
-                } else {
-                    format!("{:.0}", stage.value)
-                };
-                self.label.draw_at(cx, dvec2(plot_rect.pos.x + plot_rect.size.x + 5.0, y + stage_height / 2.0),
-                    &value_text, TextAnchor::MiddleLeft);
+                self.label.draw_at(cx, dvec2(plot_rect.pos.x + plot_rect.size.x + TICK_LABEL_PAD, y + stage_height / 2.0),
+                    &value_labels[i], TextAnchor::MiddleLeft);
             }
         }
 
@@ -7602,6 +11115,9 @@ impl Widget for FunnelChart {
 }
 
 impl FunnelChartRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() { inner.reset(); }
+    }
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
@@ -7623,6 +11139,15 @@ impl FunnelChartRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_prop_cycle(cycle); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
+    pub fn set_margins(&self, margins: Margins) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_margins(margins); }
+    }
 }
 
 // ============================================================================
@@ -7634,14 +11159,21 @@ pub struct Heatmap {
     #[deref] #[live] view: View,
     #[live] draw_fill: DrawPlotFill,
     #[live] label: PlotLabel,
-    #[rust] title: String,
-    #[rust] data: Vec<Vec<f64>>,
+    #[live] title: String,
+    #[rust] data: Matrix,
     #[rust] x_labels: Vec<String>,
     #[rust] y_labels: Vec<String>,
     #[rust] colormap: Colormap,
-    #[rust] show_values: bool,
+    #[live] show_values: bool,
     #[rust] min_value: Option<f64>,
     #[rust] max_value: Option<f64>,
+    #[live] colorbar: PlotColorbar,
+    #[rust] show_colorbar: bool,
+    #[rust] norm: Option<Box<dyn Norm>>,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Heatmap {
@@ -7649,8 +11181,8 @@ impl Heatmap {
         self.title = title.into();
     }
 
-    pub fn set_data(&mut self, data: Vec<Vec<f64>>) {
-        self.data = data;
+    pub fn set_data(&mut self, data: impl IntoGrid) {
+        self.data = data.into_matrix();
     }
 
     pub fn set_labels(&mut self, x_labels: Vec<String>, y_labels: Vec<String>) {
@@ -7671,17 +11203,47 @@ impl Heatmap {
         self.max_value = Some(max);
     }
 
+    pub fn set_show_colorbar(&mut self, show: bool) {
+        self.show_colorbar = show;
+    }
+
+    pub fn set_colorbar_orientation(&mut self, orientation: ColorbarOrientation) {
+        self.colorbar.set_orientation(orientation);
+    }
+
+    pub fn set_colorbar_label(&mut self, label: impl Into<String>) {
+        self.colorbar.set_label(label);
+    }
+
+    /// Draw arrows on the colorbar for values beyond the range
+    pub fn set_colorbar_extend(&mut self, extend: ColorbarExtend) {
+        self.colorbar.set_extend(extend);
+    }
+
+    /// Colour cells in discrete bands between these boundaries instead of a continuous scale
+    pub fn set_levels(&mut self, levels: Vec<f64>) {
+        self.set_norm(BoundaryNorm::new(levels));
+    }
+
+    /// Map values to colours through any norm instead of a linear scale over the data range
+    pub fn set_norm(&mut self, norm: impl Norm + 'static) {
+        self.norm = Some(Box::new(norm));
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
     fn get_data_range(&self) -> (f64, f64) {
         if let (Some(min), Some(max)) = (self.min_value, self.max_value) {
             return (min, max);
         }
         let mut min = f64::MAX;
         let mut max = f64::MIN;
-        for row in &self.data {
-            for &val in row {
-                if val < min { min = val; }
-                if val > max { max = val; }
-            }
+        for &val in self.data.values() {
+            if val < min { min = val; }
+            if val > max { max = val; }
         }
         if min == f64::MAX { min = 0.0; }
         if max == f64::MIN { max = 1.0; }
@@ -7691,14 +11253,22 @@ impl Heatmap {
 
 impl Widget for Heatmap {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
+        self.colorbar.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.data.is_empty() {
+            let (min_val, max_val) = self.get_data_range();
+            self.colorbar.set_colormap(self.colormap.clone());
+            self.colorbar.set_norm(resolve_norm(&self.norm, min_val, max_val));
+            let (colorbar_right, colorbar_bottom) = if self.show_colorbar { self.colorbar.margins(cx) } else { (0.0, 0.0) };
+
             let padding_left = 60.0;
-            let padding_right = 20.0;
+            let padding_right = 20.0 + colorbar_right;
             let padding_top = 40.0;
-            let padding_bottom = 40.0;
+            let padding_bottom = 40.0 + colorbar_bottom;
 
             let plot_left = rect.pos.x + padding_left;
             let plot_top = rect.pos.y + padding_top;
@@ -7707,23 +11277,22 @@ impl Widget for Heatmap {
             let plot_width = plot_right - plot_left;
             let plot_height = plot_bottom - plot_top;
 
-            let rows = self.data.len();
-            let cols = if rows > 0 { self.data[0].len() } else { 0 };
+            let (rows, cols) = self.data.shape();
 
             if cols > 0 {
                 let cell_width = plot_width / cols as f64;
                 let cell_height = plot_height / rows as f64;
-                let (min_val, max_val) = self.get_data_range();
-                let range = if (max_val - min_val).abs() < 1e-10 { 1.0 } else { max_val - min_val };
 
                 // Draw cells
-                for (row_idx, row) in self.data.iter().enumerate() {
-                    for (col_idx, &val) in row.iter().enumerate() {
+                for row_idx in 0..rows {
+                    for col_idx in 0..cols {
+                        let val = self.data[(row_idx, col_idx)];
+                        // Padding of ragged rows and missing values stay blank
+                        if val.is_nan() { continue; }
                         let x = plot_left + col_idx as f64 * cell_width;
                         let y = plot_top + row_idx as f64 * cell_height;
 
-                        let normalized = (val - min_val) / range;
-                        let color = self.colormap.sample(normalized);
+                        let color = self.colorbar.color(val);
 
                         self.draw_fill.color = color;
                         self.draw_fill.draw_abs(cx, Rect {
@@ -7738,15 +11307,16 @@ impl Widget for Heatmap {
                             } else {
                                 format!("{:.0}", val)
                             };
-                            let brightness = color.x * 0.299 + color.y * 0.587 + color.z * 0.114;
-                            self.label.draw_text.color = if brightness > 0.5 { vec4(0.0, 0.0, 0.0, 1.0) } else { vec4(1.0, 1.0, 1.0, 1.0) };
+                            // Black or white by WCAG contrast against the cell as it appears on the backdrop
+                            let backdrop = self.style.backdrop(self.theme_background);
+                            self.label.draw_text.color = contrasting_text(composite(color, backdrop));
                             self.label.draw_at(cx, dvec2(x + cell_width / 2.0, y + cell_height / 2.0), &text, TextAnchor::Center);
                         }
                     }
                 }
 
                 // Draw X labels
-                self.label.draw_text.color = vec4(0.3, 0.3, 0.3, 1.0);
+                self.label.draw_text.color = self.style.axes_color;
                 for (i, label) in self.x_labels.iter().enumerate() {
                     if i < cols {
                         let x = plot_left + (i as f64 + 0.5) * cell_width;
@@ -7768,6 +11338,11 @@ impl Widget for Heatmap {
                 self.label.draw_text.color = vec4(0.2, 0.2, 0.2, 1.0);
                 self.label.draw_at(cx, dvec2(rect.pos.x + rect.size.x / 2.0, rect.pos.y + 15.0), &self.title, TextAnchor::TopCenter);
             }
+
+            if self.show_colorbar {
+                let area = PlotArea::new(plot_left, plot_top, plot_right, plot_bottom);
+                self.colorbar.draw(cx, &area, rect);
+            }
         }
 
         DrawStep::done()
@@ -7788,7 +11363,7 @@ impl HeatmapRef {
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
-    pub fn set_data(&self, data: Vec<Vec<f64>>) {
+    pub fn set_data(&self, data: impl IntoGrid) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_data(data); }
     }
     pub fn set_labels(&self, x_labels: Vec<String>, y_labels: Vec<String>) {
@@ -7806,6 +11381,27 @@ impl HeatmapRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_show_colorbar(&self, show: bool) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_show_colorbar(show); }
+    }
+    pub fn set_colorbar_orientation(&self, orientation: ColorbarOrientation) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_orientation(orientation); }
+    }
+    pub fn set_colorbar_label(&self, label: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_label(label); }
+    }
+    pub fn set_colorbar_extend(&self, extend: ColorbarExtend) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_extend(extend); }
+    }
+    pub fn set_levels(&self, levels: Vec<f64>) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_levels(levels); }
+    }
+    pub fn set_norm(&self, norm: impl Norm + 'static) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_norm(norm); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // ============================================================================
@@ -7840,9 +11436,14 @@ pub struct Treemap {
     #[live] draw_fill: DrawPlotFill,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] nodes: Vec<TreemapNode>,
     #[rust] show_labels: bool,
+    #[rust] prop_cycle: Option<PropCycle>,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Treemap {
@@ -7854,13 +11455,43 @@ impl Treemap {
         self.nodes = nodes;
     }
 
+    /// Clear the tiles and put the title and style back to their defaults
+    pub fn reset(&mut self) {
+        self.nodes.clear();
+        self.title.clear();
+        self.prop_cycle = None;
+        self.style_override = None;
+    }
+
+    /// The treemap as a "treemap" spec
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            slices: self.nodes.iter().map(SliceSpec::from).collect(),
+            ..ChartSpec::new("treemap")
+        }
+    }
+
     pub fn set_show_labels(&mut self, show: bool) {
         self.show_labels = show;
     }
+
+    /// Colours handed out in turn to nodes without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
 }
 
 impl Widget for Treemap {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -7901,7 +11532,7 @@ impl Widget for Treemap {
                     };
 
                     if node_w > 2.0 && node_h > 2.0 {
-                        let color = node.color.unwrap_or_else(|| get_color(i));
+                        let color = node.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(i));
 
                         // Draw filled rectangle
                         self.draw_fill.color = color;
@@ -7955,6 +11586,9 @@ impl Treemap {
 }
 
 impl TreemapRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() { inner.reset(); }
+    }
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
@@ -7967,6 +11601,12 @@ impl TreemapRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_prop_cycle(cycle); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // ============================================================================
@@ -8031,14 +11671,21 @@ pub struct BubbleChart {
     #[live] draw_line: DrawPlotLine,
     #[live] draw_bubble: DrawPlotPointGradient,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] series: Vec<BubbleSeries>,
-    #[rust] x_label: String,
-    #[rust] y_label: String,
-    #[rust] show_grid: bool,
+    #[live] x_label: String,
+    #[live] y_label: String,
+    #[live] show_grid: bool,
     #[rust] max_bubble_radius: f64,
     #[rust] min_bubble_radius: f64,
     #[rust] use_gradient: bool,
+    #[live] legend: PlotLegend,
+    #[live] legend_position: Option<LegendPosition>,
+    #[live] show_legend: bool,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl BubbleChart {
@@ -8076,6 +11723,11 @@ impl BubbleChart {
         self.use_gradient = false;
     }
 
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
     fn get_data_bounds(&self) -> (f64, f64, f64, f64, f64, f64) {
         let mut x_min = f64::MAX;
         let mut x_max = f64::MIN;
@@ -8112,6 +11764,13 @@ impl BubbleChart {
 
 impl Widget for BubbleChart {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        // Applied once per live update, so later set_legend calls still win
+        if let Some(position) = self.legend_position.take() {
+            self.set_legend(position);
+        }
+        self.style.apply(&mut self.view, &mut self.label);
+        self.legend.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -8120,10 +11779,14 @@ impl Widget for BubbleChart {
             if self.max_bubble_radius == 0.0 { self.max_bubble_radius = 40.0; }
             if self.min_bubble_radius == 0.0 { self.min_bubble_radius = 5.0; }
 
+            let entries = self.legend_entries();
+            self.legend.set_entries(entries);
+            let (legend_right, legend_bottom) = self.legend.outside_margins(cx);
+
             let padding_left = 60.0;
-            let padding_right = 40.0;
+            let padding_right = 40.0 + legend_right;
             let padding_top = 40.0;
-            let padding_bottom = 50.0;
+            let padding_bottom = 50.0 + legend_bottom;
 
             let plot_left = rect.pos.x + padding_left;
             let plot_top = rect.pos.y + padding_top;
@@ -8139,7 +11802,7 @@ impl Widget for BubbleChart {
 
             // Draw grid
             if self.show_grid {
-                self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
+                self.draw_line.color = self.style.grid_color;
                 for i in 0..=5 {
                     let t = i as f64 / 5.0;
                     let x = plot_left + t * plot_width;
@@ -8150,12 +11813,12 @@ impl Widget for BubbleChart {
             }
 
             // Draw axes
-            self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+            self.draw_line.color = self.style.axes_color;
             self.draw_line.draw_line(cx, dvec2(plot_left, plot_bottom), dvec2(plot_right, plot_bottom), 1.5);
             self.draw_line.draw_line(cx, dvec2(plot_left, plot_top), dvec2(plot_left, plot_bottom), 1.5);
 
             // Draw axis tick labels
-            self.label.draw_text.color = vec4(0.3, 0.3, 0.3, 1.0);
+            self.label.draw_text.color = self.style.axes_color;
             for i in 0..=5 {
                 let t = i as f64 / 5.0;
                 let x_val = x_min + t * x_range;
@@ -8225,6 +11888,13 @@ impl Widget for BubbleChart {
             if !self.x_label.is_empty() {
                 self.label.draw_at(cx, dvec2((plot_left + plot_right) / 2.0, rect.pos.y + rect.size.y - 10.0), &self.x_label, TextAnchor::BottomCenter);
             }
+
+            let points: Vec<DVec2> = self.series.iter()
+                .flat_map(|s| s.points.iter())
+                .map(|p| dvec2(plot_left + ((p.x - x_min) / x_range) * plot_width, plot_bottom - ((p.y - y_min) / y_range) * plot_height))
+                .collect();
+            let area = PlotArea::new(plot_left, plot_top, plot_right, plot_bottom);
+            self.legend.draw(cx, &area, rect, &points);
         }
 
         DrawStep::done()
@@ -8236,6 +11906,27 @@ impl Widget for BubbleChart {
 }
 
 impl BubbleChart {
+    /// Show a legend with one entry per series
+    pub fn set_legend(&mut self, position: LegendPosition) {
+        self.show_legend = true;
+        self.legend.set_position(position);
+    }
+
+    pub fn set_legend_columns(&mut self, columns: usize) {
+        self.legend.set_columns(columns);
+    }
+
+    pub fn set_legend_title(&mut self, title: impl Into<String>) {
+        self.legend.set_title(title);
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        if !self.show_legend {
+            return Vec::new();
+        }
+        self.series.iter().map(|series| LegendEntry::marker(series.name.clone(), series.color, MarkerStyle::Circle)).collect()
+    }
+
     pub fn redraw(&mut self, cx: &mut Cx) {
         self.view.redraw(cx);
     }
@@ -8269,6 +11960,18 @@ impl BubbleChartRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_legend(&self, position: LegendPosition) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_legend(position); }
+    }
+    pub fn set_legend_columns(&self, columns: usize) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_legend_columns(columns); }
+    }
+    pub fn set_legend_title(&self, title: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_legend_title(title); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // ============================================================================
@@ -8298,13 +12001,18 @@ pub struct DonutChart {
     #[deref] #[live] view: View,
     #[live] draw_arc: DrawArc,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] slices: Vec<DonutSlice>,
     #[rust] inner_radius_ratio: f64,
     #[rust] center_label: String,
     #[rust] show_labels: bool,
-    #[rust] show_percentages: bool,
+    #[live] show_percentages: bool,
     #[rust] use_gradient: bool,
+    #[rust] prop_cycle: Option<PropCycle>,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl DonutChart {
@@ -8343,10 +12051,42 @@ impl DonutChart {
     pub fn clear(&mut self) {
         self.slices.clear();
     }
+
+    /// Clear the slices and put the title, centre label and style back to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.title.clear();
+        self.center_label.clear();
+        self.use_gradient = false;
+        self.prop_cycle = None;
+        self.style_override = None;
+    }
+
+    /// The chart as a "donut" spec
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            slices: self.slices.iter().map(SliceSpec::from).collect(),
+            ..ChartSpec::new("donut")
+        }
+    }
+
+    /// Colours handed out in turn to slices without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
 }
 
 impl Widget for DonutChart {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -8367,7 +12107,7 @@ impl Widget for DonutChart {
             for (i, slice) in self.slices.iter().enumerate() {
                 let sweep_angle = (slice.value / total) * 2.0 * std::f64::consts::PI;
                 let end_angle = start_angle + sweep_angle;
-                let color = slice.color.unwrap_or_else(|| get_color(i));
+                let color = slice.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(i));
 
                 // Use proper arc shader for clean rendering
                 self.draw_arc.color = color;
@@ -8404,7 +12144,7 @@ impl Widget for DonutChart {
                         slice.label.clone()
                     };
 
-                    self.label.draw_text.color = vec4(0.3, 0.3, 0.3, 1.0);
+                    self.label.draw_text.color = self.style.axes_color;
                     let anchor = if mid_angle.cos() > 0.0 { TextAnchor::MiddleLeft } else { TextAnchor::MiddleRight };
                     self.label.draw_at(cx, label_pos, &label_text, anchor);
                 }
@@ -8439,6 +12179,9 @@ impl DonutChart {
 }
 
 impl DonutChartRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() { inner.reset(); }
+    }
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
@@ -8469,6 +12212,12 @@ impl DonutChartRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_prop_cycle(cycle); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // ============================================================================
@@ -8493,9 +12242,9 @@ impl AreaSeries {
         }
     }
 
-    pub fn with_data(mut self, x: Vec<f64>, y: Vec<f64>) -> Self {
-        self.x = x;
-        self.y = y;
+    pub fn with_data(mut self, x: impl IntoValues, y: impl IntoValues) -> Self {
+        self.x = x.into_values().into_owned();
+        self.y = y.into_values().into_owned();
         self
     }
 
@@ -8511,12 +12260,19 @@ pub struct AreaChart {
     #[live] draw_fill: DrawPlotFill,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] series: Vec<AreaSeries>,
-    #[rust] x_label: String,
-    #[rust] y_label: String,
+    #[live] x_label: String,
+    #[live] y_label: String,
     #[rust] stacked: bool,
-    #[rust] show_grid: bool,
+    #[live] show_grid: bool,
+    #[live] legend: PlotLegend,
+    #[live] legend_position: Option<LegendPosition>,
+    #[live] show_legend: bool,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl AreaChart {
@@ -8548,6 +12304,37 @@ impl AreaChart {
         self.series.clear();
     }
 
+    /// Clear the series and put the title, stacking, legend and style back to their defaults
+    pub fn reset(&mut self) {
+        self.clear();
+        self.stacked = false;
+        self.title.clear();
+        self.x_label.clear();
+        self.y_label.clear();
+        self.legend.reset();
+        self.legend_position = None;
+        self.style_override = None;
+    }
+
+    /// The chart as an "area" spec
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            x_label: self.x_label.clone(),
+            y_label: self.y_label.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            legend: legend_name(self.legend.position),
+            stacked: self.stacked.then_some(true),
+            series: self.series.iter().map(SeriesSpec::from).collect(),
+            ..ChartSpec::new("area")
+        }
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
     fn get_bounds(&self) -> (f64, f64, f64, f64) {
         let mut x_min = f64::MAX;
         let mut x_max = f64::MIN;
@@ -8590,14 +12377,25 @@ impl AreaChart {
 
 impl Widget for AreaChart {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        // Applied once per live update, so later set_legend calls still win
+        if let Some(position) = self.legend_position.take() {
+            self.set_legend(position);
+        }
+        self.style.apply(&mut self.view, &mut self.label);
+        self.legend.set_style(&self.style);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
         if rect.size.x > 0.0 && rect.size.y > 0.0 && !self.series.is_empty() {
+            let entries = self.legend_entries();
+            self.legend.set_entries(entries);
+            let (legend_right, legend_bottom) = self.legend.outside_margins(cx);
+
             let padding_left = 60.0;
-            let padding_right = 20.0;
+            let padding_right = 20.0 + legend_right;
             let padding_top = 40.0;
-            let padding_bottom = 50.0;
+            let padding_bottom = 50.0 + legend_bottom;
 
             let plot_left = rect.pos.x + padding_left;
             let plot_top = rect.pos.y + padding_top;
@@ -8612,7 +12410,7 @@ impl Widget for AreaChart {
 
             // Draw grid
             if self.show_grid {
-                self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
+                self.draw_line.color = self.style.grid_color;
                 for i in 0..=5 {
                     let t = i as f64 / 5.0;
                     let x = plot_left + t * plot_width;
@@ -8623,12 +12421,12 @@ impl Widget for AreaChart {
             }
 
             // Draw axes
-            self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+            self.draw_line.color = self.style.axes_color;
             self.draw_line.draw_line(cx, dvec2(plot_left, plot_bottom), dvec2(plot_right, plot_bottom), 1.5);
             self.draw_line.draw_line(cx, dvec2(plot_left, plot_top), dvec2(plot_left, plot_bottom), 1.5);
 
             // Draw axis labels
-            self.label.draw_text.color = vec4(0.3, 0.3, 0.3, 1.0);
+            self.label.draw_text.color = self.style.axes_color;
             for i in 0..=5 {
                 let t = i as f64 / 5.0;
                 let x_val = x_min + t * x_range;
@@ -8725,6 +12523,9 @@ impl Widget for AreaChart {
                 self.label.draw_text.color = vec4(0.2, 0.2, 0.2, 1.0);
                 self.label.draw_at(cx, dvec2(rect.pos.x + rect.size.x / 2.0, rect.pos.y + 15.0), &self.title, TextAnchor::TopCenter);
             }
+
+            let area = PlotArea::new(plot_left, plot_top, plot_right, plot_bottom);
+            self.legend.draw(cx, &area, rect, &[]);
         }
 
         DrawStep::done()
@@ -8736,12 +12537,36 @@ impl Widget for AreaChart {
 }
 
 impl AreaChart {
+    /// Show a legend with one entry per series
+    pub fn set_legend(&mut self, position: LegendPosition) {
+        self.show_legend = true;
+        self.legend.set_position(position);
+    }
+
+    pub fn set_legend_columns(&mut self, columns: usize) {
+        self.legend.set_columns(columns);
+    }
+
+    pub fn set_legend_title(&mut self, title: impl Into<String>) {
+        self.legend.set_title(title);
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        if !self.show_legend {
+            return Vec::new();
+        }
+        self.series.iter().map(|series| LegendEntry::patch(series.name.clone(), series.color)).collect()
+    }
+
     pub fn redraw(&mut self, cx: &mut Cx) {
         self.view.redraw(cx);
     }
 }
 
 impl AreaChartRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() { inner.reset(); }
+    }
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
@@ -8766,6 +12591,18 @@ impl AreaChartRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_legend(&self, position: LegendPosition) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_legend(position); }
+    }
+    pub fn set_legend_columns(&self, columns: usize) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_legend_columns(columns); }
+    }
+    pub fn set_legend_title(&self, title: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_legend_title(title); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // ============================================================================
@@ -8792,9 +12629,9 @@ impl StepSeries {
         }
     }
 
-    pub fn with_data(mut self, x: Vec<f64>, y: Vec<f64>) -> Self {
-        self.x = x;
-        self.y = y;
+    pub fn with_data(mut self, x: impl IntoValues, y: impl IntoValues) -> Self {
+        self.x = x.into_values().into_owned();
+        self.y = y.into_values().into_owned();
         self
     }
 
@@ -8814,12 +12651,16 @@ pub struct StepPlot {
     #[deref] #[live] view: View,
     #[live] draw_line: DrawPlotLine,
     #[live] label: PlotLabel,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] series: Vec<StepSeries>,
-    #[rust] x_label: String,
-    #[rust] y_label: String,
-    #[rust] show_grid: bool,
+    #[live] x_label: String,
+    #[live] y_label: String,
+    #[live] show_grid: bool,
     #[rust] show_markers: bool,
+    #[live] plot_style: String,
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl StepPlot {
@@ -8851,6 +12692,11 @@ impl StepPlot {
         self.series.clear();
     }
 
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
     fn get_bounds(&self) -> (f64, f64, f64, f64) {
         let mut x_min = f64::MAX;
         let mut x_max = f64::MIN;
@@ -8884,6 +12730,8 @@ impl StepPlot {
 
 impl Widget for StepPlot {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply(&mut self.view, &mut self.label);
         self.view.draw_walk_all(cx, scope, walk);
         let rect = cx.turtle().rect();
 
@@ -8906,7 +12754,7 @@ impl Widget for StepPlot {
 
             // Draw grid
             if self.show_grid {
-                self.draw_line.color = vec4(0.9, 0.9, 0.9, 1.0);
+                self.draw_line.color = self.style.grid_color;
                 for i in 0..=5 {
                     let t = i as f64 / 5.0;
                     let x = plot_left + t * plot_width;
@@ -8917,12 +12765,12 @@ impl Widget for StepPlot {
             }
 
             // Draw axes
-            self.draw_line.color = vec4(0.3, 0.3, 0.3, 1.0);
+            self.draw_line.color = self.style.axes_color;
             self.draw_line.draw_line(cx, dvec2(plot_left, plot_bottom), dvec2(plot_right, plot_bottom), 1.5);
             self.draw_line.draw_line(cx, dvec2(plot_left, plot_top), dvec2(plot_left, plot_bottom), 1.5);
 
             // Draw axis labels
-            self.label.draw_text.color = vec4(0.3, 0.3, 0.3, 1.0);
+            self.label.draw_text.color = self.style.axes_color;
             for i in 0..=5 {
                 let t = i as f64 / 5.0;
                 let x_val = x_min + t * x_range;
@@ -9044,6 +12892,9 @@ impl StepPlotRef {
     pub fn redraw(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() { inner.redraw(cx); }
     }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // =============================================================================
@@ -9091,10 +12942,10 @@ pub struct StackSeries {
 }
 
 impl StackSeries {
-    pub fn new(label: impl Into<String>, values: Vec<f64>) -> Self {
+    pub fn new(label: impl Into<String>, values: impl IntoValues) -> Self {
         Self {
             label: label.into(),
-            values,
+            values: values.into_values().into_owned(),
             color: None,
         }
     }
@@ -9133,11 +12984,17 @@ pub struct Stackplot {
 
     #[rust] series: Vec<StackSeries>,
     #[rust] x_labels: Vec<String>,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] order: StackOrder,
     #[rust] offset: StackOffset,
     #[rust] show_lines: bool,
     #[rust] area: Area,
+    #[rust] prop_cycle: Option<PropCycle>,
+    #[live] plot_style: String,
+    // Background of the host theme, for the automatic light/dark style
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Stackplot {
@@ -9170,6 +13027,16 @@ impl Stackplot {
         self.show_lines = show;
     }
 
+    /// Colours handed out in turn to series without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
     fn compute_stacked(&self) -> Vec<Vec<StackedPoint>> {
         let n_series = self.series.len();
         if n_series == 0 { return vec![]; }
@@ -9287,6 +13154,8 @@ impl Stackplot {
 
 impl Widget for Stackplot {
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply_text(&mut self.label);
         let rect = cx.walk_turtle(walk);
 
         if rect.size.x > 10.0 && rect.size.y > 10.0 {
@@ -9316,21 +13185,9 @@ impl Widget for Stackplot {
             }
             if (y_max - y_min).abs() < 0.001 { y_max = y_min + 1.0; }
 
-            // Color palette
-            let colors = [
-                vec4(0.40, 0.76, 0.65, 0.85),
-                vec4(0.99, 0.55, 0.38, 0.85),
-                vec4(0.55, 0.63, 0.80, 0.85),
-                vec4(0.91, 0.84, 0.42, 0.85),
-                vec4(0.65, 0.85, 0.33, 0.85),
-                vec4(0.90, 0.45, 0.77, 0.85),
-                vec4(0.45, 0.85, 0.90, 0.85),
-                vec4(0.85, 0.65, 0.45, 0.85),
-            ];
-
             // Draw stacked areas
             for (series_idx, series_data) in stacked.iter().enumerate() {
-                let color = self.series[series_idx].color.unwrap_or(colors[series_idx % colors.len()]);
+                let color = self.series[series_idx].color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(series_idx));
                 self.draw_triangle.color = color;
 
                 for i in 0..n_points.saturating_sub(1) {
@@ -9361,9 +13218,8 @@ impl Widget for Stackplot {
             }
 
             // Draw title
-            if !self.title.is_empty() {
-                self.label.draw_at(cx, dvec2(rect.pos.x + rect.size.x / 2.0, rect.pos.y + 15.0), &self.title, TextAnchor::TopCenter);
-            }
+            let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), ..Default::default() };
+            text.draw(cx, &mut self.label, &PlotArea::new(chart_x, chart_y, chart_x + chart_w, chart_y + chart_h), &TickLabelLayout::default());
         }
 
         DrawStep::done()
@@ -9388,6 +13244,12 @@ impl StackplotRef {
     pub fn set_offset(&self, offset: StackOffset) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_offset(offset); }
     }
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_prop_cycle(cycle); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // =============================================================================
@@ -9425,8 +13287,17 @@ pub struct HexbinChart {
     #[rust] hex_radius: f64,
     #[rust] color_low: Vec4,
     #[rust] color_high: Vec4,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] area: Area,
+    #[live] colorbar: PlotColorbar,
+    #[rust] colormap: Option<Colormap>,
+    #[rust] norm: Option<Box<dyn Norm>>,
+    #[rust] show_colorbar: bool,
+    #[live] plot_style: String,
+    // Background of the host theme, for the automatic light/dark style
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl HexbinChart {
@@ -9447,6 +13318,52 @@ impl HexbinChart {
         self.title = title.into();
     }
 
+    /// Colour bins by their point count through a colormap instead of the radial low/high gradient
+    pub fn set_colormap(&mut self, colormap: Colormap) {
+        self.colormap = Some(colormap);
+    }
+
+    /// Show a colorbar of bin counts; bins are then coloured by count
+    pub fn set_show_colorbar(&mut self, show: bool) {
+        self.show_colorbar = show;
+    }
+
+    pub fn set_colorbar_orientation(&mut self, orientation: ColorbarOrientation) {
+        self.colorbar.set_orientation(orientation);
+    }
+
+    pub fn set_colorbar_label(&mut self, label: impl Into<String>) {
+        self.colorbar.set_label(label);
+    }
+
+    /// Group counts into discrete colour bands between these boundaries
+    pub fn set_levels(&mut self, levels: Vec<f64>) {
+        self.set_norm(BoundaryNorm::new(levels));
+    }
+
+    /// Map counts to colours through any norm, e.g. `LogNorm` for heavily skewed bins
+    pub fn set_norm(&mut self, norm: impl Norm + 'static) {
+        self.norm = Some(Box::new(norm));
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
+    /// Low/high colours of the gradient, from the first style colour unless set explicitly
+    fn gradient(&self) -> (Vec4, Vec4) {
+        if self.color_high != Vec4::default() {
+            return (self.color_low, self.color_high);
+        }
+        let base = self.style.cycle(&None).color(0);
+        (lighten(base, 0.85), darken(base, 0.3))
+    }
+
+    fn colors_by_count(&self) -> bool {
+        self.colormap.is_some() || self.norm.is_some() || self.show_colorbar
+    }
+
     fn cube_round(q: f64, r: f64) -> (i32, i32, i32) {
         let s = -q - r;
         let mut rq = q.round();
@@ -9530,11 +13447,12 @@ impl HexbinChart {
 
     fn interpolate_color(&self, t: f64) -> Vec4 {
         // Radial gradient: dark at center (t=0), light at edge (t=1)
+        let (low, high) = self.gradient();
         vec4(
-            self.color_high.x + t as f32 * (self.color_low.x - self.color_high.x),
-            self.color_high.y + t as f32 * (self.color_low.y - self.color_high.y),
-            self.color_high.z + t as f32 * (self.color_low.z - self.color_high.z),
-            self.color_high.w + t as f32 * (self.color_low.w - self.color_high.w),
+            high.x + t as f32 * (low.x - high.x),
+            high.y + t as f32 * (low.y - high.y),
+            high.z + t as f32 * (low.z - high.z),
+            high.w + t as f32 * (low.w - high.w),
         )
     }
 
@@ -9559,39 +13477,55 @@ impl HexbinChart {
 
 impl Widget for HexbinChart {
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply_text(&mut self.label);
+        self.colorbar.set_style(&self.style);
         let rect = cx.walk_turtle(walk);
 
         if rect.size.x > 10.0 && rect.size.y > 10.0 {
+            // Initialize defaults if not set
+            if self.hex_radius <= 0.0 { self.hex_radius = 14.0; }
+
+            // Without a colormap the count scale runs between the low and high colours
+            let (low, high) = self.gradient();
+            let colormap = self.colormap.clone()
+                .unwrap_or_else(|| Colormap::custom(vec![(0.0, low), (1.0, high)]));
+            self.colorbar.set_colormap(colormap);
+            let (colorbar_right, colorbar_bottom) = if self.show_colorbar { self.colorbar.margins(cx) } else { (0.0, 0.0) };
+
             let padding = 30.0;
             let chart_x = rect.pos.x + padding;
             let chart_y = rect.pos.y + padding;
-            let chart_w = rect.size.x - padding * 2.0;
-            let chart_h = rect.size.y - padding * 2.0;
-
-            // Initialize defaults if not set
-            if self.hex_radius <= 0.0 { self.hex_radius = 14.0; }
-            if self.color_high == Vec4::default() {
-                self.color_high = vec4(0.05, 0.15, 0.45, 1.0);
-                self.color_low = vec4(0.92, 0.95, 0.98, 1.0);
-            }
+            let chart_w = rect.size.x - padding * 2.0 - colorbar_right;
+            let chart_h = rect.size.y - padding * 2.0 - colorbar_bottom;
 
             let (bins, max_ring) = self.calculate_bins(chart_x, chart_y, chart_w, chart_h);
+            let max_count = bins.iter().map(|b| b.count).max().unwrap_or(0);
+            self.colorbar.set_norm(resolve_norm(&self.norm, 0.0, max_count.max(1) as f64));
 
             // Sort by ring for proper layering
             let mut sorted_bins: Vec<_> = bins.iter().collect();
             sorted_bins.sort_by_key(|b| b.ring);
 
             for bin in sorted_bins {
-                let t = if max_ring > 0 { bin.ring as f64 / max_ring as f64 } else { 0.0 };
-                let t_eased = t * t * (3.0 - 2.0 * t); // smoothstep
-                let color = self.interpolate_color(t_eased);
+                let color = if self.colors_by_count() {
+                    self.colorbar.color(bin.count as f64)
+                } else {
+                    let t = if max_ring > 0 { bin.ring as f64 / max_ring as f64 } else { 0.0 };
+                    let t_eased = t * t * (3.0 - 2.0 * t); // smoothstep
+                    self.interpolate_color(t_eased)
+                };
                 self.draw_hexagon(cx, bin.center, self.hex_radius * 0.94, color);
             }
 
-            // Draw title
-            if !self.title.is_empty() {
-                self.label.draw_at(cx, dvec2(rect.pos.x + rect.size.x / 2.0, rect.pos.y + 15.0), &self.title, TextAnchor::TopCenter);
+            if self.show_colorbar {
+                let area = PlotArea::new(chart_x, chart_y, chart_x + chart_w, chart_y + chart_h);
+                self.colorbar.draw(cx, &area, rect);
             }
+
+            // Draw title
+            let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), ..Default::default() };
+            text.draw(cx, &mut self.label, &PlotArea::new(chart_x, chart_y, chart_x + chart_w, chart_y + chart_h), &TickLabelLayout::default());
         }
 
         DrawStep::done()
@@ -9613,6 +13547,27 @@ impl HexbinChartRef {
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
+    pub fn set_colormap(&self, colormap: Colormap) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_colormap(colormap); }
+    }
+    pub fn set_show_colorbar(&self, show: bool) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_show_colorbar(show); }
+    }
+    pub fn set_colorbar_orientation(&self, orientation: ColorbarOrientation) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_orientation(orientation); }
+    }
+    pub fn set_colorbar_label(&self, label: impl Into<String>) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_colorbar_label(label); }
+    }
+    pub fn set_levels(&self, levels: Vec<f64>) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_levels(levels); }
+    }
+    pub fn set_norm(&self, norm: impl Norm + 'static) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_norm(norm); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // =============================================================================
@@ -9627,10 +13582,10 @@ pub struct StreamSeries {
 }
 
 impl StreamSeries {
-    pub fn new(name: impl Into<String>, values: Vec<f64>) -> Self {
+    pub fn new(name: impl Into<String>, values: impl IntoValues) -> Self {
         Self {
             name: name.into(),
-            values,
+            values: values.into_values().into_owned(),
             color: None,
         }
     }
@@ -9652,8 +13607,14 @@ pub struct Streamgraph {
 
     #[rust] series: Vec<StreamSeries>,
     #[rust] labels: Vec<String>,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] area: Area,
+    #[rust] prop_cycle: Option<PropCycle>,
+    #[live] plot_style: String,
+    // Background of the host theme, for the automatic light/dark style
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl Streamgraph {
@@ -9669,10 +13630,22 @@ impl Streamgraph {
     pub fn set_title(&mut self, title: impl Into<String>) {
         self.title = title.into();
     }
+
+    /// Colours handed out in turn to series without their own
+    pub fn set_prop_cycle(&mut self, cycle: PropCycle) {
+        self.prop_cycle = Some(cycle);
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
 }
 
 impl Widget for Streamgraph {
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply_text(&mut self.label);
         let rect = cx.walk_turtle(walk);
 
         if rect.size.x > 10.0 && rect.size.y > 10.0 && !self.series.is_empty() {
@@ -9699,19 +13672,10 @@ impl Widget for Streamgraph {
             // Calculate baselines for centering (silhouette offset)
             let baselines: Vec<f64> = totals.iter().map(|&t| (max_total - t) / 2.0).collect();
 
-            let colors = [
-                vec4(0.40, 0.76, 0.65, 0.85),
-                vec4(0.99, 0.55, 0.38, 0.85),
-                vec4(0.55, 0.63, 0.80, 0.85),
-                vec4(0.91, 0.84, 0.42, 0.85),
-                vec4(0.65, 0.85, 0.33, 0.85),
-                vec4(0.90, 0.45, 0.77, 0.85),
-            ];
-
             let mut cumulative = baselines.clone();
 
             for (series_idx, s) in self.series.iter().enumerate() {
-                let color = s.color.unwrap_or(colors[series_idx % colors.len()]);
+                let color = s.color.unwrap_or_else(|| self.style.cycle(&self.prop_cycle).color(series_idx));
 
                 let mut bottom_points: Vec<DVec2> = Vec::new();
                 let mut top_points: Vec<DVec2> = Vec::new();
@@ -9743,9 +13707,8 @@ impl Widget for Streamgraph {
             }
 
             // Draw title
-            if !self.title.is_empty() {
-                self.label.draw_at(cx, dvec2(rect.pos.x + rect.size.x / 2.0, rect.pos.y + 15.0), &self.title, TextAnchor::TopCenter);
-            }
+            let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), ..Default::default() };
+            text.draw(cx, &mut self.label, &PlotArea::new(chart_x, chart_y, chart_x + chart_w, chart_y + chart_h), &TickLabelLayout::default());
         }
 
         DrawStep::done()
@@ -9764,6 +13727,12 @@ impl StreamgraphRef {
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
+    pub fn set_prop_cycle(&self, cycle: PropCycle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_prop_cycle(cycle); }
+    }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
 
 // =============================================================================
@@ -9827,8 +13796,13 @@ pub struct SankeyDiagram {
 
     #[rust] nodes: Vec<SankeyNode>,
     #[rust] links: Vec<SankeyLink>,
-    #[rust] title: String,
+    #[live] title: String,
     #[rust] area: Area,
+    #[live] plot_style: String,
+    // Background of the host theme, for the automatic light/dark style
+    #[live] theme_background: Vec4,
+    #[rust] style: PlotStyle,
+    #[rust] style_override: Option<PlotStyle>,
 }
 
 impl SankeyDiagram {
@@ -9842,6 +13816,30 @@ impl SankeyDiagram {
         self.title = title.into();
     }
 
+    /// Clear the nodes and links and put the title and style back to their defaults
+    pub fn reset(&mut self) {
+        self.nodes.clear();
+        self.links.clear();
+        self.title.clear();
+        self.style_override = None;
+    }
+
+    /// The diagram as a "sankey" spec
+    pub(crate) fn to_spec(&self) -> ChartSpec {
+        ChartSpec {
+            title: self.title.clone(),
+            style: style_name(&self.style_override, &self.plot_style),
+            nodes: self.nodes.iter().map(SankeyNodeSpec::from).collect(),
+            links: self.links.iter().map(SankeyLinkSpec::from).collect(),
+            ..ChartSpec::new("sankey")
+        }
+    }
+
+    /// Style for this widget, overriding its `plot_style` preset and the global style
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style_override = Some(style);
+    }
+
     fn compute_layout(&mut self) {
         if self.nodes.is_empty() { return; }
 
@@ -9917,6 +13915,8 @@ impl SankeyDiagram {
 
 impl Widget for SankeyDiagram {
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.style = PlotStyle::resolve(&self.style_override, &self.plot_style, self.theme_background);
+        self.style.apply_text(&mut self.label);
         let rect = cx.walk_turtle(walk);
 
         if rect.size.x > 10.0 && rect.size.y > 10.0 && !self.nodes.is_empty() {
@@ -10007,9 +14007,8 @@ impl Widget for SankeyDiagram {
             }
 
             // Draw title
-            if !self.title.is_empty() {
-                self.label.draw_at(cx, dvec2(rect.pos.x + rect.size.x / 2.0, rect.pos.y + 15.0), &self.title, TextAnchor::TopCenter);
-            }
+            let text = AxesText { title: &self.title, title_size: Some(self.style.title_font_size), ..Default::default() };
+            text.draw(cx, &mut self.label, &PlotArea::new(chart_x, chart_y, chart_x + chart_width, chart_y + chart_height), &TickLabelLayout::default());
         }
 
         DrawStep::done()
@@ -10019,10 +14018,16 @@ impl Widget for SankeyDiagram {
 }
 
 impl SankeyDiagramRef {
+    pub fn reset(&self) {
+        if let Some(mut inner) = self.borrow_mut() { inner.reset(); }
+    }
     pub fn set_data(&self, nodes: Vec<SankeyNode>, links: Vec<SankeyLink>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_data(nodes, links); }
     }
     pub fn set_title(&self, title: impl Into<String>) {
         if let Some(mut inner) = self.borrow_mut() { inner.set_title(title); }
     }
+    pub fn set_style(&self, style: PlotStyle) {
+        if let Some(mut inner) = self.borrow_mut() { inner.set_style(style); }
+    }
 }
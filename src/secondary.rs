@@ -0,0 +1,163 @@
+// Secondary axes - the host's own x or y axis shown again on the opposite side in other
+// units, through a forward and inverse function pair
+
+use std::rc::Rc;
+use makepad_widgets::*;
+use crate::plot::ScaleType;
+use crate::elements::DrawPlotLine;
+use crate::text::PlotLabel;
+use crate::norm::{linear_ticks, scale_ticks};
+use crate::style::PlotStyle;
+use crate::twin::*;
+
+/// The host's x axis along the top or its y axis on the right, relabelled in other units,
+/// like matplotlib's `secondary_xaxis`/`secondary_yaxis` with `functions=(forward, inverse)`.
+/// Ticks are chosen in the converted units, so °F ticks land on round °F values.
+#[derive(Clone)]
+pub struct SecondaryAxis {
+    /// `Top` converts the x axis, `Right` the y axis
+    pub side: TwinSide,
+    pub label: String,
+    /// Scale used to pick ticks in the converted units
+    pub scale: ScaleType,
+    /// Colour of the spine, ticks and tick labels; the style's colours when unset
+    pub tick_color: Option<Vec4>,
+    forward: Rc<dyn Fn(f64) -> f64>,
+    inverse: Rc<dyn Fn(f64) -> f64>,
+}
+
+impl SecondaryAxis {
+    /// `forward` converts host values into the secondary units, `inverse` converts back
+    pub fn new(
+        side: TwinSide,
+        forward: impl Fn(f64) -> f64 + 'static,
+        inverse: impl Fn(f64) -> f64 + 'static,
+    ) -> Self {
+        Self {
+            side,
+            label: String::new(),
+            scale: ScaleType::Linear,
+            tick_color: None,
+            forward: Rc::new(forward),
+            inverse: Rc::new(inverse),
+        }
+    }
+
+    /// Secondary x axis along the top, e.g. period for a frequency axis:
+    /// `SecondaryAxis::x(|f| 1.0 / f, |p| 1.0 / p)`
+    pub fn x(forward: impl Fn(f64) -> f64 + 'static, inverse: impl Fn(f64) -> f64 + 'static) -> Self {
+        Self::new(TwinSide::Top, forward, inverse)
+    }
+
+    /// Secondary y axis on the right, e.g. °F for a °C axis:
+    /// `SecondaryAxis::y(|c| c * 1.8 + 32.0, |f| (f - 32.0) / 1.8)`
+    pub fn y(forward: impl Fn(f64) -> f64 + 'static, inverse: impl Fn(f64) -> f64 + 'static) -> Self {
+        Self::new(TwinSide::Right, forward, inverse)
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn with_scale(mut self, scale: ScaleType) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_tick_color(mut self, color: Vec4) -> Self {
+        self.tick_color = Some(color);
+        self
+    }
+
+    pub fn forward(&self, value: f64) -> f64 {
+        (self.forward)(value)
+    }
+
+    pub fn inverse(&self, value: f64) -> f64 {
+        (self.inverse)(value)
+    }
+
+    /// The host axis this one converts
+    fn shared(&self, x: AxisRange, y: AxisRange) -> AxisRange {
+        match self.side {
+            TwinSide::Right => y,
+            TwinSide::Top => x,
+        }
+    }
+
+    /// Ticks picked over the converted range and mapped back through `inverse`, as
+    /// positions along the host axis from 0 to 1
+    pub fn ticks(&self, shared: &AxisRange) -> Vec<(f64, String)> {
+        let a = self.forward(shared.min);
+        let b = self.forward(shared.max);
+        if !a.is_finite() || !b.is_finite() {
+            return Vec::new();
+        }
+        // Decreasing conversions such as 1/x swap the ends
+        let (lo, hi) = (a.min(b), a.max(b));
+        let ticks = match self.scale {
+            ScaleType::Linear => linear_ticks(lo, hi),
+            scale => scale_ticks(scale, lo, hi),
+        };
+        ticks.into_iter()
+            .map(|(value, text)| (shared.fraction(self.inverse(value)), text))
+            .filter(|(t, _)| t.is_finite())
+            .collect()
+    }
+}
+
+/// The secondary axes of one chart, at most one per side
+#[derive(Clone, Default)]
+pub struct SecondaryAxes {
+    pub x: Option<SecondaryAxis>,
+    pub y: Option<SecondaryAxis>,
+}
+
+impl SecondaryAxes {
+    /// Attach `axis` on its side, replacing any secondary axis already there
+    pub fn set(&mut self, axis: SecondaryAxis) {
+        match axis.side {
+            TwinSide::Top => self.x = Some(axis),
+            TwinSide::Right => self.y = Some(axis),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.x = None;
+        self.y = None;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SecondaryAxis> {
+        self.y.iter().chain(self.x.iter())
+    }
+
+    /// Extra (right, top) margins for the axes over the host's `x` and `y` ranges
+    pub fn margins(&self, cx: &mut Cx2d, label: &mut PlotLabel, style: &PlotStyle, x: AxisRange, y: AxisRange) -> (f64, f64) {
+        let mut margins = (0.0, 0.0);
+        for axis in self.iter() {
+            let ticks = axis.ticks(&axis.shared(x, y));
+            let margin = SideAxis { side: axis.side, ticks: &ticks, label: &axis.label, color: axis.tick_color, offset: 0.0 }
+                .margin(cx, label, style);
+            match axis.side {
+                TwinSide::Right => margins.0 = margin,
+                TwinSide::Top => margins.1 = margin,
+            }
+        }
+        margins
+    }
+
+    /// Draw the axes on the spines opposite the host's, outside any twin axes
+    /// taking up `twin_margins` (right, top)
+    pub fn draw(&self, cx: &mut Cx2d, ctx: &TwinDraw, twin_margins: (f64, f64), draw_line: &mut DrawPlotLine, label: &mut PlotLabel) {
+        for axis in self.iter() {
+            let ticks = axis.ticks(&axis.shared(ctx.x, ctx.y));
+            let offset = match axis.side {
+                TwinSide::Right => twin_margins.0,
+                TwinSide::Top => twin_margins.1,
+            };
+            SideAxis { side: axis.side, ticks: &ticks, label: &axis.label, color: axis.tick_color, offset }
+                .draw(cx, ctx, draw_line, label);
+        }
+    }
+}
@@ -0,0 +1,944 @@
+// Chart specifications - plain data describing a chart, saved as JSON and loaded into a widget
+//
+// Each chart kind in CHART_KINDS has a `load_spec` and a `to_spec` on its widget ref.
+// Loading resets the widget first, so whatever the spec leaves out is back at its default.
+// Exporting keeps only what a spec has a field for: twin and secondary axes, fills,
+// reference lines, spans, insets, norms and custom colormaps are left out.
+// Widgets without a spec kind, which cannot be saved or loaded this way: BubbleChart,
+// BoxPlot, Stem, Step, Violin, Polar, Contour, Quiver, Hexbin and Stackplot
+
+use makepad_widgets::*;
+use makepad_widgets::makepad_micro_serde::*;
+use crate::plot::*;
+use crate::elements::{LineStyle, MarkerStyle};
+use crate::layout::Margins;
+use crate::legend::LegendPosition;
+use crate::colormap::Colormap;
+use crate::norm::{norm_from_name, Norm};
+use crate::color::{parse_color, to_hex};
+use crate::style::PlotStyle;
+
+/// Chart kinds a spec can describe, by the name used in `ChartSpec::kind`
+pub const CHART_KINDS: [&str; 14] = [
+    "line", "scatter", "area", "bar", "pie", "histogram", "heatmap", "candlestick",
+    "sankey", "waterfall", "funnel", "treemap", "donut", "radar",
+];
+
+/// Everything needed to rebuild a chart: its kind, data, styling, limits and annotations.
+/// Colours are written as strings `parse_color` understands, enums by their matplotlib names.
+/// Only the sections used by `kind` are read when loading.
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct ChartSpec {
+    /// One of `CHART_KINDS`
+    pub kind: String,
+    #[nserde(default)]
+    pub title: String,
+    #[nserde(default)]
+    pub x_label: String,
+    #[nserde(default)]
+    pub y_label: String,
+    /// Style preset name, see `PlotStyle::PRESETS`
+    pub style: Option<String>,
+    pub x_scale: Option<String>,
+    pub y_scale: Option<String>,
+    /// [min, max]
+    pub xlim: Option<Vec<f64>>,
+    pub ylim: Option<Vec<f64>>,
+    /// Legend `loc` name, e.g. "upper left"
+    pub legend: Option<String>,
+    pub margins: Option<MarginsSpec>,
+    pub colormap: Option<String>,
+    /// Heatmap norm: "linear", "log", "symlog" or "centered", fitted to the data range
+    pub norm: Option<String>,
+    /// Single colour for bar and histogram charts
+    pub color: Option<String>,
+    /// Stack bar groups or area series
+    pub stacked: Option<bool>,
+    /// Horizontal bars
+    pub horizontal: Option<bool>,
+    /// Histogram bin count; unset uses Sturges' rule
+    pub bins: Option<usize>,
+    /// Bar categories, line and scatter plot x categories, heatmap column labels or radar axes
+    #[nserde(default)]
+    pub categories: Vec<String>,
+    /// Heatmap row labels
+    #[nserde(default)]
+    pub y_categories: Vec<String>,
+    /// Bar heights or histogram samples
+    #[nserde(default)]
+    pub values: Vec<f64>,
+    /// Heatmap rows
+    #[nserde(default)]
+    pub matrix: Vec<Vec<f64>>,
+    #[nserde(default)]
+    pub series: Vec<SeriesSpec>,
+    #[nserde(default)]
+    pub groups: Vec<BarGroupSpec>,
+    /// Pie, donut and funnel slices or treemap tiles
+    #[nserde(default)]
+    pub slices: Vec<SliceSpec>,
+    #[nserde(default)]
+    pub candles: Vec<CandleSpec>,
+    #[nserde(default)]
+    pub nodes: Vec<SankeyNodeSpec>,
+    #[nserde(default)]
+    pub links: Vec<SankeyLinkSpec>,
+    #[nserde(default)]
+    pub entries: Vec<WaterfallEntrySpec>,
+    #[nserde(default)]
+    pub annotations: Vec<AnnotationSpec>,
+}
+
+/// Mirrors `Margins`; unset sides keep the tight layout
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct MarginsSpec {
+    pub left: Option<f64>,
+    pub top: Option<f64>,
+    pub right: Option<f64>,
+    pub bottom: Option<f64>,
+}
+
+/// Mirrors `Series` for line and scatter charts, and `RadarSeries` (values in `y`)
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct SeriesSpec {
+    #[nserde(default)]
+    pub label: String,
+    #[nserde(default)]
+    pub x: Vec<f64>,
+    #[nserde(default)]
+    pub y: Vec<f64>,
+    pub color: Option<String>,
+    pub line_style: Option<String>,
+    pub marker: Option<String>,
+    pub line_width: Option<f64>,
+    pub marker_size: Option<f64>,
+    pub yerr_minus: Option<Vec<f64>>,
+    pub yerr_plus: Option<Vec<f64>>,
+    pub x_categories: Option<Vec<String>>,
+    pub color_values: Option<Vec<f64>>,
+    pub sizes: Option<Vec<f64>>,
+    pub visible: Option<bool>,
+}
+
+/// Mirrors `BarGroup`
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct BarGroupSpec {
+    pub label: String,
+    pub values: Vec<f64>,
+    pub color: Option<String>,
+}
+
+/// Mirrors `PieSlice`, `DonutSlice`, `FunnelStage` and `TreemapNode`
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct SliceSpec {
+    pub label: String,
+    pub value: f64,
+    pub color: Option<String>,
+}
+
+/// Mirrors `Candle`
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct CandleSpec {
+    pub timestamp: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: Option<f64>,
+}
+
+/// Mirrors `SankeyNode`; nodes without a colour take one from the default cycle
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct SankeyNodeSpec {
+    pub name: String,
+    pub layer: usize,
+    pub value: f64,
+    pub color: Option<String>,
+}
+
+/// Mirrors `SankeyLink`, with nodes referred to by index
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct SankeyLinkSpec {
+    pub source: usize,
+    pub target: usize,
+    pub value: f64,
+}
+
+/// Mirrors `WaterfallEntry`
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct WaterfallEntrySpec {
+    pub label: String,
+    pub value: f64,
+    #[nserde(default)]
+    pub is_total: bool,
+}
+
+/// Text annotation in data coordinates, mirroring the common `TextAnnotation` fields
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct AnnotationSpec {
+    pub text: String,
+    pub x: f64,
+    pub y: f64,
+    pub color: Option<String>,
+    pub font_size: Option<f64>,
+    pub rotation: Option<f64>,
+    #[nserde(default)]
+    pub math: bool,
+}
+
+/// Why a spec could not be read or loaded
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpecError {
+    Json(String),
+    UnknownKind(String),
+    /// Valid input using a feature the importer does not handle
+    Unsupported(String),
+    /// The widget passed to `ChartSpec::load` is not of the spec's chart kind
+    WrongWidget(String),
+    /// A value the spec field does not accept, e.g. an unknown colour or scale name
+    Invalid(String),
+}
+
+impl std::fmt::Display for SpecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SpecError::Json(msg) => write!(f, "invalid chart spec: {}", msg),
+            SpecError::UnknownKind(kind) => write!(f, "unknown chart kind \"{}\"", kind),
+            SpecError::Unsupported(what) => write!(f, "unsupported: {}", what),
+            SpecError::WrongWidget(kind) => write!(f, "widget is not a {} chart", kind),
+            SpecError::Invalid(what) => write!(f, "invalid {}", what),
+        }
+    }
+}
+
+impl ChartSpec {
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self { kind: kind.into(), ..Default::default() }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SpecError> {
+        Self::deserialize_json(json).map_err(|err| SpecError::Json(format!("{:?}", err)))
+    }
+
+    pub fn to_json(&self) -> String {
+        self.serialize_json()
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_labels(mut self, x_label: impl Into<String>, y_label: impl Into<String>) -> Self {
+        self.x_label = x_label.into();
+        self.y_label = y_label.into();
+        self
+    }
+
+    pub fn with_series(mut self, series: &Series) -> Self {
+        self.series.push(SeriesSpec::from(series));
+        self
+    }
+
+    /// Load the spec into `widget`, which must be of the matching chart type.
+    /// Fails with `SpecError::WrongWidget` for any other widget, and with
+    /// `SpecError::Invalid` for an unknown colour, scale, style or other name, or for
+    /// limits that are not `[min, max]`; the widget is left unchanged on failure.
+    pub fn load(&self, widget: &WidgetRef) -> Result<(), SpecError> {
+        match self.kind.as_str() {
+            "line" => widget.as_line_plot().load_spec(self),
+            "scatter" => widget.as_scatter_plot().load_spec(self),
+            "area" => widget.as_area_chart().load_spec(self),
+            "bar" => widget.as_bar_plot().load_spec(self),
+            "pie" => widget.as_pie_chart().load_spec(self),
+            "histogram" => widget.as_histogram_chart().load_spec(self),
+            "heatmap" => widget.as_heatmap_chart().load_spec(self),
+            "candlestick" => widget.as_candlestick_chart().load_spec(self),
+            "sankey" => widget.as_sankey_diagram().load_spec(self),
+            "waterfall" => widget.as_waterfall_chart().load_spec(self),
+            "funnel" => widget.as_funnel_chart().load_spec(self),
+            "treemap" => widget.as_treemap().load_spec(self),
+            "donut" => widget.as_donut_chart().load_spec(self),
+            "radar" => widget.as_radar_chart().load_spec(self),
+            kind => Err(SpecError::UnknownKind(kind.to_string())),
+        }
+    }
+
+    fn style(&self) -> Result<Option<PlotStyle>, SpecError> {
+        named(&self.style, "style", PlotStyle::preset)
+    }
+
+    fn legend(&self) -> Result<Option<LegendPosition>, SpecError> {
+        named(&self.legend, "legend location", LegendPosition::from_name)
+    }
+
+    fn margins(&self) -> Option<Margins> {
+        self.margins.as_ref().map(|m| Margins { left: m.left, top: m.top, right: m.right, bottom: m.bottom })
+    }
+
+    fn colormap(&self) -> Result<Option<Colormap>, SpecError> {
+        named(&self.colormap, "colormap", Colormap::from_name)
+    }
+
+    /// The `norm` over the matrix's finite values, positive ones only for "log"
+    pub(crate) fn norm(&self) -> Result<Option<Box<dyn Norm>>, SpecError> {
+        let log = self.norm.as_deref() == Some("log");
+        let (vmin, vmax) = self.matrix.iter().flatten()
+            .filter(|v| v.is_finite() && (!log || **v > 0.0))
+            .fold((f64::MAX, f64::MIN), |(lo, hi), v| (lo.min(*v), hi.max(*v)));
+        let (vmin, vmax) = if vmin > vmax { (0.0, 1.0) } else { (vmin, vmax) };
+        named(&self.norm, "norm", |name| norm_from_name(name, vmin, vmax))
+    }
+}
+
+/// Look up an optional name, failing on one `parse` does not know
+fn named<T>(name: &Option<String>, what: &str, parse: impl Fn(&str) -> Option<T>) -> Result<Option<T>, SpecError> {
+    name.as_deref()
+        .map(|n| parse(n).ok_or_else(|| SpecError::Invalid(format!("{} \"{}\"", what, n))))
+        .transpose()
+}
+
+fn color(text: &Option<String>) -> Result<Option<Vec4>, SpecError> {
+    named(text, "colour", parse_color)
+}
+
+// Writers for the widgets' `to_spec`, leaving settings at their defaults unset
+
+/// The preset a widget's style comes from, if it has a name
+pub(crate) fn style_name(style: &Option<PlotStyle>, preset: &str) -> Option<String> {
+    match style {
+        Some(style) => style.preset_name().map(String::from),
+        None => PlotStyle::preset(preset).map(|_| preset.to_string()),
+    }
+}
+
+pub(crate) fn scale_name(scale: ScaleType) -> Option<String> {
+    (scale != ScaleType::default()).then(|| scale.name().to_string())
+}
+
+pub(crate) fn legend_name(position: LegendPosition) -> Option<String> {
+    (position != LegendPosition::default()).then(|| position.name().to_string())
+}
+
+/// Named colormaps only; custom and derived ones have no name to load them by
+pub(crate) fn colormap_name(colormap: &Colormap) -> Option<String> {
+    let name = colormap.name();
+    (*colormap != Colormap::default() && Colormap::from_name(name).as_ref() == Some(colormap)).then(|| name.to_string())
+}
+
+pub(crate) fn limits_spec(lim: Option<DVec2>) -> Option<Vec<f64>> {
+    lim.map(|lim| vec![lim.x, lim.y])
+}
+
+pub(crate) fn margins_spec(margins: &Margins) -> Option<MarginsSpec> {
+    (*margins != Margins::default()).then(|| MarginsSpec { left: margins.left, top: margins.top, right: margins.right, bottom: margins.bottom })
+}
+
+fn color_spec(color: Vec4) -> Option<String> {
+    (color != Vec4::default()).then(|| to_hex(color))
+}
+
+fn scale(name: &Option<String>) -> Result<Option<ScaleType>, SpecError> {
+    named(name, "scale", ScaleType::from_name)
+}
+
+fn limits(lim: &Option<Vec<f64>>) -> Result<Option<(f64, f64)>, SpecError> {
+    match lim.as_deref() {
+        None => Ok(None),
+        Some([min, max]) if min.is_finite() && max.is_finite() && min != max => Ok(Some((*min, *max))),
+        Some(values) => Err(SpecError::Invalid(format!("limits {:?}, expected [min, max]", values))),
+    }
+}
+
+/// Convert every item, stopping at the first invalid one
+fn convert<S, T>(items: &[S], f: impl Fn(&S) -> Result<T, SpecError>) -> Result<Vec<T>, SpecError> {
+    items.iter().map(f).collect()
+}
+
+impl From<&Series> for SeriesSpec {
+    fn from(series: &Series) -> Self {
+        Self {
+            label: series.label.clone(),
+            x: series.x.clone(),
+            y: series.y.clone(),
+            color: series.color.map(to_hex),
+            line_style: Some(series.line_style.name().to_string()),
+            marker: Some(series.marker_style.name().to_string()),
+            line_width: series.line_width,
+            marker_size: series.marker_size,
+            yerr_minus: series.yerr_minus.clone(),
+            yerr_plus: series.yerr_plus.clone(),
+            x_categories: series.x_categories.clone(),
+            color_values: series.color_values.clone(),
+            sizes: series.sizes.clone(),
+            visible: Some(series.visible),
+        }
+    }
+}
+
+impl SeriesSpec {
+    pub fn to_series(&self) -> Result<Series, SpecError> {
+        let mut series = Series::new(self.label.clone()).with_data(self.x.clone(), self.y.clone());
+        series.color = color(&self.color)?;
+        if let Some(style) = named(&self.line_style, "line style", LineStyle::from_name)? {
+            series.line_style = style;
+        }
+        if let Some(marker) = named(&self.marker, "marker", MarkerStyle::from_name)? {
+            series.marker_style = marker;
+        }
+        series.line_width = self.line_width;
+        series.marker_size = self.marker_size;
+        series.yerr_minus = self.yerr_minus.clone();
+        series.yerr_plus = self.yerr_plus.clone();
+        series.x_categories = self.x_categories.clone();
+        series.color_values = self.color_values.clone();
+        series.sizes = self.sizes.clone();
+        series.visible = self.visible.unwrap_or(true);
+        Ok(series)
+    }
+
+    fn to_area_series(&self) -> Result<AreaSeries, SpecError> {
+        let series = AreaSeries::new(self.label.clone()).with_data(self.x.clone(), self.y.clone());
+        Ok(match color(&self.color)? {
+            Some(c) => series.with_color(c),
+            None => series,
+        })
+    }
+
+    fn to_radar_series(&self) -> Result<RadarSeries, SpecError> {
+        let series = RadarSeries::new(self.label.clone(), self.y.clone());
+        Ok(match color(&self.color)? {
+            Some(c) => series.with_color(c),
+            None => series,
+        })
+    }
+}
+
+impl From<&BarGroup> for BarGroupSpec {
+    fn from(group: &BarGroup) -> Self {
+        Self { label: group.label.clone(), values: group.values.clone(), color: group.color.map(to_hex) }
+    }
+}
+
+impl From<&PieSlice> for SliceSpec {
+    fn from(slice: &PieSlice) -> Self {
+        Self { label: slice.label.clone(), value: slice.value, color: slice.color.map(to_hex) }
+    }
+}
+
+impl From<&Candle> for CandleSpec {
+    fn from(candle: &Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume,
+        }
+    }
+}
+
+impl From<&SankeyNode> for SankeyNodeSpec {
+    fn from(node: &SankeyNode) -> Self {
+        Self { name: node.name.clone(), layer: node.layer, value: node.value, color: Some(to_hex(node.color)) }
+    }
+}
+
+impl From<&SankeyLink> for SankeyLinkSpec {
+    fn from(link: &SankeyLink) -> Self {
+        Self { source: link.source, target: link.target, value: link.value }
+    }
+}
+
+impl From<&WaterfallEntry> for WaterfallEntrySpec {
+    fn from(entry: &WaterfallEntry) -> Self {
+        Self { label: entry.label.clone(), value: entry.value, is_total: entry.is_total }
+    }
+}
+
+impl From<&AreaSeries> for SeriesSpec {
+    fn from(series: &AreaSeries) -> Self {
+        Self { label: series.name.clone(), x: series.x.clone(), y: series.y.clone(), color: color_spec(series.color), ..Default::default() }
+    }
+}
+
+impl From<&RadarSeries> for SeriesSpec {
+    fn from(series: &RadarSeries) -> Self {
+        Self { label: series.label.clone(), y: series.values.clone(), color: color_spec(series.color), ..Default::default() }
+    }
+}
+
+impl From<&DonutSlice> for SliceSpec {
+    fn from(slice: &DonutSlice) -> Self {
+        Self { label: slice.label.clone(), value: slice.value, color: slice.color.map(to_hex) }
+    }
+}
+
+impl From<&FunnelStage> for SliceSpec {
+    fn from(stage: &FunnelStage) -> Self {
+        Self { label: stage.label.clone(), value: stage.value, color: stage.color.map(to_hex) }
+    }
+}
+
+impl From<&TreemapNode> for SliceSpec {
+    fn from(node: &TreemapNode) -> Self {
+        Self { label: node.label.clone(), value: node.value, color: node.color.map(to_hex) }
+    }
+}
+
+/// Only annotations placed in data coordinates; the spec has no other coordinate systems
+impl From<&TextAnnotation> for AnnotationSpec {
+    fn from(annotation: &TextAnnotation) -> Self {
+        Self {
+            text: annotation.text.clone(),
+            x: annotation.x,
+            y: annotation.y,
+            color: Some(to_hex(annotation.color)),
+            font_size: Some(annotation.font_size),
+            rotation: (annotation.rotation != 0.0).then_some(annotation.rotation),
+            math: annotation.is_math,
+        }
+    }
+}
+
+impl AnnotationSpec {
+    pub fn to_annotation(&self) -> Result<TextAnnotation, SpecError> {
+        let mut annotation = TextAnnotation::new(self.text.clone(), self.x, self.y);
+        if let Some(c) = color(&self.color)? {
+            annotation = annotation.with_color(c);
+        }
+        if let Some(size) = self.font_size {
+            annotation = annotation.with_font_size(size);
+        }
+        if let Some(degrees) = self.rotation {
+            annotation = annotation.with_rotation(degrees);
+        }
+        Ok(if self.math { annotation.math() } else { annotation })
+    }
+}
+
+// Loaders read and check the whole spec before touching the widget, so an invalid spec
+// leaves it as it was. A valid one resets the widget before loading.
+
+impl LinePlotRef {
+    /// Replace the plot's contents and settings with those of a "line" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let x_scale = scale(&spec.x_scale)?;
+        let y_scale = scale(&spec.y_scale)?;
+        let legend = spec.legend()?;
+        let xlim = limits(&spec.xlim)?;
+        let ylim = limits(&spec.ylim)?;
+        let series = convert(&spec.series, SeriesSpec::to_series)?;
+        let annotations = convert(&spec.annotations, AnnotationSpec::to_annotation)?;
+
+        self.reset();
+        if !spec.categories.is_empty() {
+            self.set_x_categories(spec.categories.clone());
+        }
+        self.set_title(spec.title.clone());
+        self.set_xlabel(spec.x_label.clone());
+        self.set_ylabel(spec.y_label.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(scale) = x_scale { self.set_x_scale(scale); }
+        if let Some(scale) = y_scale { self.set_y_scale(scale); }
+        if let Some(position) = legend { self.set_legend(position); }
+        if let Some(margins) = spec.margins() { self.set_margins(margins); }
+        for series in series {
+            self.add_series(series);
+        }
+        for annotation in annotations {
+            self.add_annotation(annotation);
+        }
+        if let Some((min, max)) = xlim { self.set_xlim(min, max); }
+        if let Some((min, max)) = ylim { self.set_ylim(min, max); }
+        Ok(())
+    }
+
+    /// The chart as a "line" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl ScatterPlotRef {
+    /// Replace the plot's contents and settings with those of a "scatter" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let legend = spec.legend()?;
+        let colormap = spec.colormap()?;
+        let series = convert(&spec.series, SeriesSpec::to_series)?;
+
+        self.reset();
+        if !spec.categories.is_empty() {
+            self.set_x_categories(spec.categories.clone());
+        }
+        self.set_title(spec.title.clone());
+        self.set_xlabel(spec.x_label.clone());
+        self.set_ylabel(spec.y_label.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(position) = legend { self.set_legend(position); }
+        if let Some(margins) = spec.margins() { self.set_margins(margins); }
+        if let Some(colormap) = colormap { self.set_colormap(colormap); }
+        for series in series {
+            self.add_series(series);
+        }
+        Ok(())
+    }
+
+    /// The chart as a "scatter" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl AreaChartRef {
+    /// Replace the chart's series and settings with those of an "area" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let legend = spec.legend()?;
+        let series = convert(&spec.series, SeriesSpec::to_area_series)?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        self.set_x_label(spec.x_label.clone());
+        self.set_y_label(spec.y_label.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(position) = legend { self.set_legend(position); }
+        if let Some(stacked) = spec.stacked { self.set_stacked(stacked); }
+        for series in series {
+            self.add_series(series);
+        }
+        Ok(())
+    }
+
+    /// The chart as an "area" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl BarPlotRef {
+    /// Replace the chart's bars and settings with those of a "bar" spec: `categories` with
+    /// either `values` or grouped `groups`
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let legend = spec.legend()?;
+        let bar_color = color(&spec.color)?;
+        let groups = convert(&spec.groups, |g| {
+            let group = BarGroup::new(g.label.clone(), g.values.clone());
+            Ok(match color(&g.color)? {
+                Some(c) => group.with_color(c),
+                None => group,
+            })
+        })?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(position) = legend { self.set_legend(position); }
+        if let Some(margins) = spec.margins() { self.set_margins(margins); }
+        if let Some(c) = bar_color { self.set_color(c); }
+        if let Some(stacked) = spec.stacked { self.set_stacked(stacked); }
+        if let Some(horizontal) = spec.horizontal { self.set_horizontal(horizontal); }
+        if groups.is_empty() {
+            self.set_data(spec.categories.clone(), spec.values.clone());
+        } else {
+            self.set_groups(spec.categories.clone(), groups);
+        }
+        Ok(())
+    }
+
+    /// The chart as a "bar" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl PieChartRef {
+    /// Replace the chart's slices and settings with those of a "pie" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let legend = spec.legend()?;
+        let slices = convert(&spec.slices, |s| {
+            let slice = PieSlice::new(s.label.clone(), s.value);
+            Ok(match color(&s.color)? {
+                Some(c) => slice.with_color(c),
+                None => slice,
+            })
+        })?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(position) = legend { self.set_legend(position); }
+        self.set_slices(slices);
+        Ok(())
+    }
+
+    /// The chart as a "pie" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl HistogramChartRef {
+    /// Replace the chart's samples and settings with those of a "histogram" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let bar_color = color(&spec.color)?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        self.set_xlabel(spec.x_label.clone());
+        self.set_ylabel(spec.y_label.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(margins) = spec.margins() { self.set_margins(margins); }
+        if let Some(c) = bar_color { self.set_color(c); }
+        if let Some(bins) = spec.bins { self.set_num_bins(bins); }
+        self.set_values(spec.values.clone());
+        Ok(())
+    }
+
+    /// The chart as a "histogram" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl HeatmapChartRef {
+    /// Replace the chart's grid and settings with those of a "heatmap" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let colormap = spec.colormap()?;
+        let norm = spec.norm()?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(margins) = spec.margins() { self.set_margins(margins); }
+        if let Some(colormap) = colormap { self.set_colormap(colormap); }
+        if let Some(norm) = norm { self.set_norm(norm); }
+        self.set_data(&spec.matrix);
+        self.set_x_labels(spec.categories.clone());
+        self.set_y_labels(spec.y_categories.clone());
+        Ok(())
+    }
+
+    /// The chart as a "heatmap" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl CandlestickChartRef {
+    /// Replace the chart's candles and settings with those of a "candlestick" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(margins) = spec.margins() { self.set_margins(margins); }
+        self.set_data(spec.candles.iter().map(|c| {
+            let candle = Candle::new(c.timestamp, c.open, c.high, c.low, c.close);
+            match c.volume {
+                Some(volume) => candle.with_volume(volume),
+                None => candle,
+            }
+        }).collect());
+        Ok(())
+    }
+
+    /// The chart as a "candlestick" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl SankeyDiagramRef {
+    /// Replace the diagram's nodes and links with those of a "sankey" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let nodes = spec.nodes.iter().enumerate().map(|(i, n)| {
+            let default_color = || style.as_ref().map(|s| s.cycle(&None).color(i)).unwrap_or_else(|| get_color(i));
+            let node_color = color(&n.color)?.unwrap_or_else(default_color);
+            Ok(SankeyNode::new(n.name.clone(), n.layer, n.value, node_color))
+        }).collect::<Result<Vec<_>, SpecError>>()?;
+        let links = spec.links.iter().map(|l| SankeyLink::new(l.source, l.target, l.value)).collect();
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        self.set_data(nodes, links);
+        Ok(())
+    }
+
+    /// The chart as a "sankey" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl WaterfallChartRef {
+    /// Replace the chart's entries and settings with those of a "waterfall" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(margins) = spec.margins() { self.set_margins(margins); }
+        self.set_data(spec.entries.iter().map(|e| {
+            if e.is_total { WaterfallEntry::total(e.label.clone(), e.value) } else { WaterfallEntry::new(e.label.clone(), e.value) }
+        }).collect());
+        Ok(())
+    }
+
+    /// The chart as a "waterfall" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl FunnelChartRef {
+    /// Replace the chart's stages and settings with those of a "funnel" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let stages = convert(&spec.slices, |s| {
+            let stage = FunnelStage::new(s.label.clone(), s.value);
+            Ok(match color(&s.color)? {
+                Some(c) => stage.with_color(c),
+                None => stage,
+            })
+        })?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        if let Some(margins) = spec.margins() { self.set_margins(margins); }
+        self.set_data(stages);
+        Ok(())
+    }
+
+    /// The chart as a "funnel" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl TreemapRef {
+    /// Replace the treemap's tiles and settings with those of a "treemap" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let nodes = convert(&spec.slices, |s| {
+            let node = TreemapNode::new(s.label.clone(), s.value);
+            Ok(match color(&s.color)? {
+                Some(c) => node.with_color(c),
+                None => node,
+            })
+        })?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        self.set_data(nodes);
+        Ok(())
+    }
+
+    /// The chart as a "treemap" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl DonutChartRef {
+    /// Replace the chart's slices and settings with those of a "donut" spec
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let slices = convert(&spec.slices, |s| {
+            let slice = DonutSlice::new(s.label.clone(), s.value);
+            Ok(match color(&s.color)? {
+                Some(c) => slice.with_color(c),
+                None => slice,
+            })
+        })?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        self.set_data(slices);
+        Ok(())
+    }
+
+    /// The chart as a "donut" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
+
+impl RadarChartRef {
+    /// Replace the chart's axes and series with those of a "radar" spec: `categories` name
+    /// the axes and each series holds one value per axis in `y`
+    pub fn load_spec(&self, spec: &ChartSpec) -> Result<(), SpecError> {
+        if self.borrow().is_none() {
+            return Err(SpecError::WrongWidget(spec.kind.clone()));
+        }
+        let style = spec.style()?;
+        let series = convert(&spec.series, SeriesSpec::to_radar_series)?;
+
+        self.reset();
+        self.set_title(spec.title.clone());
+        if let Some(style) = style { self.set_style(style); }
+        self.set_axes(spec.categories.clone());
+        for series in series {
+            self.add_series(series);
+        }
+        Ok(())
+    }
+
+    /// The chart as a "radar" spec, for `load_spec` to rebuild it; None without a widget
+    pub fn to_spec(&self) -> Option<ChartSpec> {
+        self.borrow().map(|inner| inner.to_spec())
+    }
+}
@@ -0,0 +1,442 @@
+// Spines, tick marks and grid lines - which sides of the plot area get an axis line,
+// where it sits, and how ticks and grid lines are drawn along it
+
+use makepad_widgets::*;
+use crate::plot::{PlotArea, ScaleType};
+use crate::elements::{DrawPlotLine, LineStyle};
+use crate::style::PlotStyle;
+use crate::twin::AxisRange;
+
+/// Minor ticks between two major ticks on linear axes, plus one
+const MINOR_DIVISIONS: usize = 5;
+
+/// One side of the plot area
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpineSide {
+    Left,
+    Bottom,
+    Right,
+    Top,
+}
+
+impl SpineSide {
+    fn is_vertical(self) -> bool {
+        matches!(self, SpineSide::Left | SpineSide::Right)
+    }
+
+    /// Unit vector pointing away from the plot area
+    fn outward(self) -> DVec2 {
+        match self {
+            SpineSide::Left => dvec2(-1.0, 0.0),
+            SpineSide::Bottom => dvec2(0.0, 1.0),
+            SpineSide::Right => dvec2(1.0, 0.0),
+            SpineSide::Top => dvec2(0.0, -1.0),
+        }
+    }
+}
+
+/// Where a spine crosses the other axis, like matplotlib's `Spine.set_position`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SpinePosition {
+    /// Along its edge of the plot area
+    #[default]
+    Edge,
+    /// Through zero of the other axis, as in "zero"
+    Zero,
+    /// Through the middle of the plot area, as in "center"
+    Center,
+    /// Through this value of the other axis, as in ("data", value)
+    Data(f64),
+}
+
+/// An axis line on one side of the plot area
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spine {
+    pub visible: bool,
+    pub position: SpinePosition,
+    /// The style's axes colour and width when unset
+    pub color: Option<Vec4>,
+    pub width: Option<f64>,
+}
+
+impl Default for Spine {
+    fn default() -> Self {
+        Self { visible: true, position: SpinePosition::Edge, color: None, width: None }
+    }
+}
+
+impl Spine {
+    pub fn hidden() -> Self {
+        Self { visible: false, ..Default::default() }
+    }
+
+    pub fn with_position(mut self, position: SpinePosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with_color(mut self, color: Vec4) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_width(mut self, width: f64) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Pixel x of a left or right spine, or pixel y of a bottom or top one, kept inside
+    /// the plot area
+    pub fn offset(&self, side: SpineSide, area: &PlotArea, x: AxisRange, y: AxisRange) -> f64 {
+        let edge = match side {
+            SpineSide::Left => area.left,
+            SpineSide::Bottom => area.bottom,
+            SpineSide::Right => area.right,
+            SpineSide::Top => area.top,
+        };
+        let at = |value: f64| {
+            if side.is_vertical() {
+                area.left + x.fraction(value) * area.width()
+            } else {
+                area.bottom - y.fraction(value) * area.height()
+            }
+        };
+        let offset = match self.position {
+            SpinePosition::Edge => return edge,
+            SpinePosition::Zero => at(0.0),
+            SpinePosition::Data(value) => at(value),
+            SpinePosition::Center if side.is_vertical() => (area.left + area.right) / 2.0,
+            SpinePosition::Center => (area.top + area.bottom) / 2.0,
+        };
+        if !offset.is_finite() {
+            return edge;
+        }
+        if side.is_vertical() {
+            offset.clamp(area.left, area.right)
+        } else {
+            offset.clamp(area.top, area.bottom)
+        }
+    }
+}
+
+/// The four spines of a chart. By default only the left and bottom ones are drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spines {
+    pub left: Spine,
+    pub bottom: Spine,
+    pub right: Spine,
+    pub top: Spine,
+}
+
+impl Default for Spines {
+    fn default() -> Self {
+        Self { left: Spine::default(), bottom: Spine::default(), right: Spine::hidden(), top: Spine::hidden() }
+    }
+}
+
+impl Spines {
+    /// A closed box around the plot area, matplotlib's classic look
+    pub fn boxed() -> Self {
+        Self { left: Spine::default(), bottom: Spine::default(), right: Spine::default(), top: Spine::default() }
+    }
+
+    pub fn get(&self, side: SpineSide) -> &Spine {
+        match side {
+            SpineSide::Left => &self.left,
+            SpineSide::Bottom => &self.bottom,
+            SpineSide::Right => &self.right,
+            SpineSide::Top => &self.top,
+        }
+    }
+
+    pub fn get_mut(&mut self, side: SpineSide) -> &mut Spine {
+        match side {
+            SpineSide::Left => &mut self.left,
+            SpineSide::Bottom => &mut self.bottom,
+            SpineSide::Right => &mut self.right,
+            SpineSide::Top => &mut self.top,
+        }
+    }
+
+    /// Draw the visible spines and the tick marks along them
+    pub fn draw(&self, cx: &mut Cx2d, frame: &AxesFrame, ticks: &TickParams, draw_line: &mut DrawPlotLine) {
+        let area = &frame.area;
+        let style = frame.style;
+        for side in [SpineSide::Left, SpineSide::Bottom, SpineSide::Right, SpineSide::Top] {
+            let spine = self.get(side);
+            let offset = spine.offset(side, area, frame.x, frame.y);
+            let (start, end) = if side.is_vertical() {
+                (dvec2(offset, area.bottom), dvec2(offset, area.top))
+            } else {
+                (dvec2(area.left, offset), dvec2(area.right, offset))
+            };
+            if spine.visible {
+                draw_line.color = spine.color.unwrap_or(style.axes_color);
+                draw_line.draw_line(cx, start, end, spine.width.unwrap_or(style.axes_width));
+            }
+
+            if !ticks.on(side) {
+                continue;
+            }
+            draw_line.color = ticks.color.or(spine.color).unwrap_or(style.axes_color);
+            let (major, minor) = if side.is_vertical() {
+                (frame.y_ticks, frame.y_minor)
+            } else {
+                (frame.x_ticks, frame.x_minor)
+            };
+            let major_length = ticks.major_length.unwrap_or(style.tick_length);
+            let major_width = ticks.major_width.unwrap_or(style.axes_width);
+            let minor_width = ticks.minor_width.unwrap_or(major_width * 0.75);
+            let mut marks: Vec<(f64, f64, f64)> = major.iter().map(|p| (*p, major_length, major_width)).collect();
+            if ticks.minor {
+                marks.extend(minor.iter().map(|p| (*p, ticks.minor_length, minor_width)));
+            }
+            for (p, length, width) in marks {
+                if length <= 0.0 {
+                    continue;
+                }
+                let at = if side.is_vertical() { dvec2(offset, p) } else { dvec2(p, offset) };
+                let (a, b) = ticks.direction.span(length);
+                let out = side.outward();
+                draw_line.draw_line(cx, at + out * a, at + out * b, width);
+            }
+        }
+    }
+}
+
+/// Which way tick marks point from their spine
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TickDirection {
+    /// Into the plot area
+    In,
+    /// Away from the plot area
+    #[default]
+    Out,
+    /// Across the spine
+    InOut,
+}
+
+impl TickDirection {
+    /// Start and end of a tick of `length`, measured outwards from the spine
+    fn span(self, length: f64) -> (f64, f64) {
+        match self {
+            TickDirection::In => (-length, 0.0),
+            TickDirection::Out => (0.0, length),
+            TickDirection::InOut => (-length / 2.0, length / 2.0),
+        }
+    }
+}
+
+/// How tick marks are drawn, like matplotlib's `tick_params`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TickParams {
+    pub direction: TickDirection,
+    /// The style's tick length when unset
+    pub major_length: Option<f64>,
+    /// The style's axes width when unset
+    pub major_width: Option<f64>,
+    pub minor_length: f64,
+    /// Three quarters of the major width when unset
+    pub minor_width: Option<f64>,
+    /// Draw minor ticks between the major ones
+    pub minor: bool,
+    /// The spine's colour when unset
+    pub color: Option<Vec4>,
+    /// Sides with tick marks. Ticks on a hidden spine still sit where it would be.
+    pub left: bool,
+    pub bottom: bool,
+    pub right: bool,
+    pub top: bool,
+}
+
+impl Default for TickParams {
+    fn default() -> Self {
+        Self {
+            direction: TickDirection::Out,
+            major_length: None,
+            major_width: None,
+            minor_length: 2.0,
+            minor_width: None,
+            minor: false,
+            color: None,
+            left: true,
+            bottom: true,
+            right: false,
+            top: false,
+        }
+    }
+}
+
+impl TickParams {
+    pub fn with_direction(mut self, direction: TickDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_major(mut self, length: f64, width: f64) -> Self {
+        self.major_length = Some(length);
+        self.major_width = Some(width);
+        self
+    }
+
+    /// Show minor ticks of the given size
+    pub fn with_minor(mut self, length: f64, width: f64) -> Self {
+        self.minor = true;
+        self.minor_length = length;
+        self.minor_width = Some(width);
+        self
+    }
+
+    /// Ticks on the top and right sides as well
+    pub fn mirrored(mut self) -> Self {
+        self.top = true;
+        self.right = true;
+        self
+    }
+
+    pub fn on(&self, side: SpineSide) -> bool {
+        match side {
+            SpineSide::Left => self.left,
+            SpineSide::Bottom => self.bottom,
+            SpineSide::Right => self.right,
+            SpineSide::Top => self.top,
+        }
+    }
+
+    /// How far the tick marks on `side` reach out past the spine, room the tick labels
+    /// have to keep clear of
+    pub fn outward_extent(&self, side: SpineSide, style: &PlotStyle) -> f64 {
+        if !self.on(side) {
+            return 0.0;
+        }
+        let major = self.direction.span(self.major_length.unwrap_or(style.tick_length).max(0.0)).1;
+        let minor = if self.minor { self.direction.span(self.minor_length.max(0.0)).1 } else { 0.0 };
+        major.max(minor)
+    }
+}
+
+/// Look of one set of grid lines; unset fields come from the style
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridLines {
+    pub visible: bool,
+    pub color: Option<Vec4>,
+    pub width: Option<f64>,
+    pub line_style: Option<LineStyle>,
+}
+
+impl GridLines {
+    pub fn shown() -> Self {
+        Self { visible: true, color: None, width: None, line_style: None }
+    }
+
+    pub fn hidden() -> Self {
+        Self { visible: false, ..Self::shown() }
+    }
+
+    pub fn with_color(mut self, color: Vec4) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_width(mut self, width: f64) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_line_style(mut self, line_style: LineStyle) -> Self {
+        self.line_style = Some(line_style);
+        self
+    }
+}
+
+/// Major and minor grid lines, like matplotlib's `grid(which="major"/"minor")`.
+/// Only major lines are drawn by default.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridStyle {
+    pub major: GridLines,
+    pub minor: GridLines,
+}
+
+impl Default for GridStyle {
+    fn default() -> Self {
+        Self { major: GridLines::shown(), minor: GridLines::hidden() }
+    }
+}
+
+impl GridStyle {
+    pub fn draw(&self, cx: &mut Cx2d, frame: &AxesFrame, draw_line: &mut DrawPlotLine) {
+        let style = frame.style;
+        // Minor lines first so the major ones sit on top; thinner and dotted unless set
+        let sets = [
+            (self.minor, frame.x_minor, frame.y_minor, style.grid_width * 0.5, LineStyle::Dotted),
+            (self.major, frame.x_ticks, frame.y_ticks, style.grid_width, style.grid_style),
+        ];
+        let area = &frame.area;
+        for (lines, xs, ys, width, line_style) in sets {
+            if !lines.visible {
+                continue;
+            }
+            draw_line.color = lines.color.unwrap_or(style.grid_color);
+            let width = lines.width.unwrap_or(width);
+            let line_style = lines.line_style.unwrap_or(line_style);
+            for y in ys {
+                draw_line.draw_line_styled(cx, dvec2(area.left, *y), dvec2(area.right, *y), width, line_style, 0.0);
+            }
+            for x in xs {
+                draw_line.draw_line_styled(cx, dvec2(*x, area.bottom), dvec2(*x, area.top), width, line_style, 0.0);
+            }
+        }
+    }
+}
+
+/// What the spines and grid are drawn against: the plot area, the host's axes for
+/// placing spines at data values, and tick positions in pixels
+pub struct AxesFrame<'a> {
+    pub area: PlotArea,
+    pub x: AxisRange,
+    pub y: AxisRange,
+    pub x_ticks: &'a [f64],
+    pub y_ticks: &'a [f64],
+    pub x_minor: &'a [f64],
+    pub y_minor: &'a [f64],
+    pub style: &'a PlotStyle,
+}
+
+/// Minor tick values within `range` for an axis with the given major ticks: 2-9 times
+/// each power of ten on log axes, otherwise even subdivisions of the major step
+pub fn minor_ticks(scale: ScaleType, majors: &[f64], range: (f64, f64)) -> Vec<f64> {
+    let (lo, hi) = (range.0.min(range.1), range.0.max(range.1));
+    let inside = |v: &f64| *v >= lo && *v <= hi;
+    match scale {
+        ScaleType::Log => {
+            if lo <= 0.0 {
+                return Vec::new();
+            }
+            let first = lo.log10().floor() as i32;
+            let last = hi.log10().ceil() as i32;
+            (first..=last)
+                .flat_map(|exp| (2..=9).map(move |m| m as f64 * 10.0_f64.powi(exp)))
+                .filter(inside)
+                .collect()
+        }
+        // Symlog majors are not evenly spaced
+        ScaleType::SymLog => Vec::new(),
+        ScaleType::Linear | ScaleType::Time => {
+            if majors.len() < 2 {
+                return Vec::new();
+            }
+            let step = (majors[1] - majors[0]) / MINOR_DIVISIONS as f64;
+            if step <= 0.0 || !step.is_finite() {
+                return Vec::new();
+            }
+            // Start one major step early so the range before the first major is covered
+            let start = majors[0] - step * MINOR_DIVISIONS as f64;
+            (1..)
+                .map(|i| (i, start + i as f64 * step))
+                .take_while(|(_, v)| *v <= hi)
+                .filter(|(i, v)| i % MINOR_DIVISIONS != 0 && inside(v))
+                .map(|(_, v)| v)
+                .collect()
+        }
+    }
+}
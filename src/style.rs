@@ -0,0 +1,229 @@
+// Plot styles - rcParams-like theming shared by the plot widgets, with matplotlib's common presets
+
+use makepad_widgets::*;
+use std::sync::Mutex;
+use crate::elements::LineStyle;
+use crate::color::{hex_color, is_dark, PropCycle};
+use crate::text::PlotLabel;
+
+/// Colours, line weights and font sizes the widgets draw their axes, grid and text with.
+/// A widget uses, in order: the style set with `set_style`, the preset named by its
+/// `plot_style` live property, the global style, then the light or dark style matching
+/// the theme background it is placed on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotStyle {
+    /// Fill behind the whole widget; fully transparent leaves the parent's background showing
+    pub background: Vec4,
+    pub axes_color: Vec4,
+    pub text_color: Vec4,
+    pub grid_color: Vec4,
+    pub grid_style: LineStyle,
+    pub grid_width: f64,
+    pub axes_width: f64,
+    /// Length of the tick marks outside the axes; 0 draws none
+    pub tick_length: f64,
+    pub font_size: f64,
+    pub title_font_size: f64,
+    pub line_width: f64,
+    pub marker_size: f64,
+    pub prop_cycle: PropCycle,
+    pub legend_background: Vec4,
+    pub legend_border: Vec4,
+}
+
+/// Style used by widgets without one of their own
+static GLOBAL_STYLE: Mutex<Option<PlotStyle>> = Mutex::new(None);
+
+impl Default for PlotStyle {
+    fn default() -> Self {
+        Self {
+            background: vec4(0.0, 0.0, 0.0, 0.0),
+            axes_color: vec4(0.3, 0.3, 0.3, 1.0),
+            text_color: vec4(0.3, 0.3, 0.3, 1.0),
+            grid_color: vec4(0.9, 0.9, 0.9, 1.0),
+            grid_style: LineStyle::Solid,
+            grid_width: 0.5,
+            axes_width: 1.0,
+            tick_length: 0.0,
+            font_size: 10.0,
+            title_font_size: 10.0,
+            line_width: 2.0,
+            marker_size: 4.0,
+            prop_cycle: PropCycle::default(),
+            legend_background: vec4(0.97, 0.97, 0.97, 0.9),
+            legend_border: vec4(0.8, 0.8, 0.8, 1.0),
+        }
+    }
+}
+
+fn cycle(colors: &[u32]) -> PropCycle {
+    PropCycle::new(colors.iter().map(|hex| hex_color(*hex)).collect())
+}
+
+impl PlotStyle {
+    /// Names accepted by `preset`
+    pub const PRESETS: [&'static str; 7] = ["default", "light", "dark", "dark_background", "ggplot", "seaborn-whitegrid", "fivethirtyeight"];
+
+    /// Built-in style by its matplotlib name
+    pub fn preset(name: &str) -> Option<PlotStyle> {
+        match name {
+            "default" | "light" => Some(Self::default()),
+            "dark" => Some(Self::dark()),
+            "dark_background" => Some(Self::dark_background()),
+            "ggplot" => Some(Self::ggplot()),
+            "seaborn-whitegrid" | "seaborn_whitegrid" => Some(Self::seaborn_whitegrid()),
+            "fivethirtyeight" => Some(Self::fivethirtyeight()),
+            _ => None,
+        }
+    }
+
+    /// Name of the first preset equal to this style, if any
+    pub fn preset_name(&self) -> Option<&'static str> {
+        Self::PRESETS.into_iter().find(|name| Self::preset(name).as_ref() == Some(self))
+    }
+
+    /// Default style for dark host themes; draws on the app's own background
+    pub fn dark() -> Self {
+        Self {
+            axes_color: vec4(0.7, 0.7, 0.7, 1.0),
+            text_color: vec4(0.85, 0.85, 0.85, 1.0),
+            grid_color: vec4(0.25, 0.25, 0.25, 1.0),
+            legend_background: vec4(0.12, 0.12, 0.12, 0.9),
+            legend_border: vec4(0.35, 0.35, 0.35, 1.0),
+            ..Self::default()
+        }
+    }
+
+    /// Light or dark default to suit the background the widget sits on
+    pub fn auto(background: Vec4) -> Self {
+        if background.w > 0.0 && is_dark(background) { Self::dark() } else { Self::default() }
+    }
+
+    pub fn dark_background() -> Self {
+        Self {
+            background: vec4(0.0, 0.0, 0.0, 1.0),
+            axes_color: vec4(1.0, 1.0, 1.0, 1.0),
+            text_color: vec4(1.0, 1.0, 1.0, 1.0),
+            grid_color: vec4(0.3, 0.3, 0.3, 1.0),
+            legend_background: vec4(0.0, 0.0, 0.0, 0.8),
+            legend_border: vec4(0.5, 0.5, 0.5, 1.0),
+            prop_cycle: cycle(&[
+                0x8dd3c7, 0xfeffb3, 0xbfbbd9, 0xfa8174, 0x81b1d2, 0xfdb462, 0xb3de69, 0xbc82bd, 0xccebc4, 0xffed6f,
+            ]),
+            ..Self::default()
+        }
+    }
+
+    pub fn ggplot() -> Self {
+        Self {
+            background: hex_color(0xe5e5e5),
+            axes_color: hex_color(0xffffff),
+            text_color: hex_color(0x555555),
+            grid_color: hex_color(0xffffff),
+            grid_width: 1.0,
+            tick_length: 3.5,
+            title_font_size: 12.0,
+            prop_cycle: cycle(&[0xe24a33, 0x348abd, 0x988ed5, 0x777777, 0xfbc15e, 0x8eba42, 0xffb5b8]),
+            ..Self::default()
+        }
+    }
+
+    pub fn seaborn_whitegrid() -> Self {
+        Self {
+            background: hex_color(0xffffff),
+            axes_color: hex_color(0xcccccc),
+            text_color: hex_color(0x262626),
+            grid_color: hex_color(0xcccccc),
+            grid_width: 1.0,
+            title_font_size: 12.0,
+            line_width: 1.75,
+            marker_size: 3.5,
+            prop_cycle: cycle(&[
+                0x4c72b0, 0xdd8452, 0x55a868, 0xc44e52, 0x8172b3, 0x937860, 0xda8bc3, 0x8c8c8c, 0xccb974, 0x64b5cd,
+            ]),
+            ..Self::default()
+        }
+    }
+
+    pub fn fivethirtyeight() -> Self {
+        Self {
+            background: hex_color(0xf0f0f0),
+            axes_color: hex_color(0xf0f0f0),
+            text_color: hex_color(0x3c3c3c),
+            grid_color: hex_color(0xcbcbcb),
+            grid_width: 1.0,
+            font_size: 12.0,
+            title_font_size: 14.0,
+            line_width: 3.0,
+            marker_size: 5.0,
+            prop_cycle: cycle(&[0x008fd5, 0xfc4f30, 0xe5ae38, 0x6d904f, 0x8b8b8b, 0x810f7c]),
+            ..Self::default()
+        }
+    }
+
+    /// Make `style` the default for every widget without a style of its own.
+    /// Widgets pick it up on their next redraw.
+    pub fn set_global(style: PlotStyle) {
+        if let Ok(mut global) = GLOBAL_STYLE.lock() {
+            *global = Some(style);
+        }
+    }
+
+    /// Make a preset the global style, like `plt.style.use`; false if the name is unknown
+    pub fn use_preset(name: &str) -> bool {
+        match Self::preset(name) {
+            Some(style) => {
+                Self::set_global(style);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn global() -> PlotStyle {
+        Self::global_override().unwrap_or_default()
+    }
+
+    /// Go back to picking light or dark from each widget's theme background
+    pub fn clear_global() {
+        if let Ok(mut global) = GLOBAL_STYLE.lock() {
+            *global = None;
+        }
+    }
+
+    fn global_override() -> Option<PlotStyle> {
+        GLOBAL_STYLE.lock().ok().and_then(|global| global.clone())
+    }
+
+    /// The style a widget draws with: its own, else its named preset, else the global one,
+    /// else light or dark to match `theme_background`
+    pub fn resolve(style: &Option<PlotStyle>, preset: &str, theme_background: Vec4) -> PlotStyle {
+        style.clone()
+            .or_else(|| Self::preset(preset))
+            .or_else(Self::global_override)
+            .unwrap_or_else(|| Self::auto(theme_background))
+    }
+
+    /// What the plot is drawn on: the style's background, or the theme's when that is transparent
+    pub fn backdrop(&self, theme_background: Vec4) -> Vec4 {
+        if self.background.w > 0.0 { self.background } else { theme_background }
+    }
+
+    /// Set up the widget background and text before drawing
+    pub fn apply(&self, view: &mut View, label: &mut PlotLabel) {
+        view.show_bg = self.background.w > 0.0;
+        view.draw_bg.color = self.background;
+        self.apply_text(label);
+    }
+
+    /// Set up the text of widgets drawn straight into their turtle, without a View
+    pub fn apply_text(&self, label: &mut PlotLabel) {
+        label.set_color(self.text_color);
+        label.set_font_size(self.font_size);
+    }
+
+    /// The widget's own colour cycle if it has one, else the style's
+    pub fn cycle<'a>(&'a self, custom: &'a Option<PropCycle>) -> &'a PropCycle {
+        custom.as_ref().unwrap_or(&self.prop_cycle)
+    }
+}
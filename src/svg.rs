@@ -0,0 +1,576 @@
+// SVG export - draws chart specs without a window, for saving figures headlessly
+//
+// This is a simplified renderer of its own, not a trace of what the widgets draw. With no
+// font system at hand it cannot measure text, so it does not share the widgets' layout:
+// - margins are the fixed MARGIN_* sizes rather than the tight layout, so long tick
+//   labels or titles can be clipped and rotated labels are not accounted for
+// - ticks come from `ScaleType::generate_ticks`; tick strategies, spines and tick
+//   parameters set on a widget are not part of the spec and are not applied
+// - the legend is sized from character counts and always sits at the top right
+// - only line, scatter, area, bar, histogram, pie, donut and heatmap charts are drawn
+
+use std::fmt::Write;
+use makepad_widgets::*;
+use crate::spec::*;
+use crate::figure::Figure;
+use crate::plot::ScaleType;
+use crate::style::PlotStyle;
+use crate::colormap::Colormap;
+use crate::norm::{resolve_norm, Norm};
+use crate::color::{parse_color, to_hex};
+use crate::elements::{LineStyle, MarkerStyle};
+use crate::text::{math_symbol, script_argument, split_math, TextSegment};
+
+/// Space around the plot area of each chart, for tick labels, axis labels and the title.
+/// Fixed, as text cannot be measured here
+const MARGIN_LEFT: f64 = 60.0;
+const MARGIN_TOP: f64 = 36.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_BOTTOM: f64 = 48.0;
+
+/// Gap between subplots
+const SPACING: f64 = 10.0;
+
+/// Ticks aimed for along each axis
+const TICK_COUNT: usize = 5;
+
+/// Render every subplot of a figure into one SVG document
+pub fn figure_svg(figure: &Figure) -> String {
+    let mut svg = Svg::new(figure.width, figure.height);
+    let cols = figure.cols.max(1);
+    let rows = figure.rows.max(1);
+    let cell_width = (figure.width - SPACING * (cols - 1) as f64) / cols as f64;
+    let cell_height = (figure.height - SPACING * (rows - 1) as f64) / rows as f64;
+    for (index, spec) in figure.specs() {
+        let (row, col) = ((index - 1) / cols, (index - 1) % cols);
+        let cell = Frame {
+            left: col as f64 * (cell_width + SPACING),
+            top: row as f64 * (cell_height + SPACING),
+            width: cell_width,
+            height: cell_height,
+        };
+        draw_chart(&mut svg, &spec, cell);
+    }
+    svg.finish()
+}
+
+/// Render a single chart spec as an SVG document
+pub fn chart_svg(spec: &ChartSpec, width: f64, height: f64) -> String {
+    let mut svg = Svg::new(width, height);
+    draw_chart(&mut svg, spec, Frame { left: 0.0, top: 0.0, width, height });
+    svg.finish()
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Frame {
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Frame {
+    fn right(&self) -> f64 {
+        self.left + self.width
+    }
+
+    fn bottom(&self) -> f64 {
+        self.top + self.height
+    }
+
+    fn center_x(&self) -> f64 {
+        self.left + self.width / 2.0
+    }
+
+    /// Plot area inside the chart margins
+    fn inset(&self) -> Frame {
+        Frame {
+            left: self.left + MARGIN_LEFT,
+            top: self.top + MARGIN_TOP,
+            width: (self.width - MARGIN_LEFT - MARGIN_RIGHT).max(1.0),
+            height: (self.height - MARGIN_TOP - MARGIN_BOTTOM).max(1.0),
+        }
+    }
+}
+
+/// Data range of one axis and its scale
+#[derive(Clone, Copy, Debug)]
+struct Axis {
+    min: f64,
+    max: f64,
+    scale: ScaleType,
+}
+
+impl Axis {
+    /// Range over `values` with 5% padding, or `limits` when given
+    fn fit(values: impl Iterator<Item = f64>, limits: &Option<Vec<f64>>, scale: ScaleType) -> Axis {
+        if let Some([min, max]) = limits.as_deref() {
+            return Axis { min: *min, max: *max, scale };
+        }
+        let (min, max) = values
+            .filter(|v| v.is_finite() && (scale != ScaleType::Log || *v > 0.0))
+            .fold((f64::MAX, f64::MIN), |(min, max), v| (min.min(v), max.max(v)));
+        if min > max {
+            return Axis { min: 0.0, max: 1.0, scale };
+        }
+        if scale == ScaleType::Log {
+            return Axis { min: min / 1.5, max: max * 1.5, scale };
+        }
+        let pad = if max > min { (max - min) * 0.05 } else { 0.5 };
+        Axis { min: min - pad, max: max + pad, scale }
+    }
+
+    /// Position in [0, 1] along the axis
+    fn fraction(&self, value: f64) -> f64 {
+        let (lo, hi) = (self.scale.transform(self.min), self.scale.transform(self.max));
+        if hi == lo { 0.5 } else { (self.scale.transform(value) - lo) / (hi - lo) }
+    }
+
+    fn ticks(&self) -> Vec<f64> {
+        self.scale.generate_ticks(self.min, self.max, TICK_COUNT)
+    }
+}
+
+fn scale(name: &Option<String>) -> ScaleType {
+    name.as_deref().and_then(ScaleType::from_name).unwrap_or_default()
+}
+
+fn dash_array(style: LineStyle) -> &'static str {
+    match style {
+        LineStyle::Solid => "",
+        LineStyle::Dashed => " stroke-dasharray=\"6,4\"",
+        LineStyle::Dotted => " stroke-dasharray=\"2,3\"",
+        LineStyle::DashDot => " stroke-dasharray=\"6,3,2,3\"",
+    }
+}
+
+/// SVG colour attribute pair for `color`, e.g. `fill="#1f77b4" fill-opacity="1"`
+fn paint(attr: &str, color: Vec4) -> String {
+    let hex = to_hex(vec4(color.x, color.y, color.z, 1.0));
+    format!("{}=\"{}\" {}-opacity=\"{}\"", attr, hex, attr, color.w.clamp(0.0, 1.0))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Text content with `$...$` runs turned into SVG markup, as tick labels like `$10^{3}$` need
+fn text_markup(text: &str) -> String {
+    split_math(text).iter().map(|segment| match segment {
+        TextSegment::Plain(plain) => escape(plain),
+        TextSegment::Math(latex) => math_markup(latex),
+    }).collect()
+}
+
+/// A small LaTeX subset: `^` and `_` scripts become shifted tspans, Greek letters and
+/// common symbols their Unicode characters; other commands are written out by name
+fn math_markup(latex: &str) -> String {
+    let mut out = String::new();
+    let mut chars = latex.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '^' | '_' => {
+                let shift = if c == '^' { "super" } else { "sub" };
+                let script = math_markup(&script_argument(&mut chars));
+                let _ = write!(out, "<tspan baseline-shift=\"{}\" font-size=\"70%\">{}</tspan>", shift, script);
+            }
+            '\\' => {
+                let mut name = String::new();
+                while let Some(&next) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                    name.push(next);
+                    chars.next();
+                }
+                if name.is_empty() {
+                    // Escaped characters such as `\$` or `\{`, and spacing such as `\,`
+                    match chars.next() {
+                        Some(',' | ';' | ' ' | '!') | None => {}
+                        Some(escaped) => out.push_str(&escape(&escaped.to_string())),
+                    }
+                } else {
+                    match math_symbol(&name) {
+                        Some(symbol) => out.push_str(symbol),
+                        None => out.push_str(&name),
+                    }
+                }
+            }
+            '{' | '}' => {}
+            _ => out.push_str(&escape(&c.to_string())),
+        }
+    }
+    out
+}
+
+struct Svg {
+    out: String,
+}
+
+impl Svg {
+    fn new(width: f64, height: f64) -> Self {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\">",
+            w = width,
+            h = height
+        );
+        Self { out }
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push_str("</svg>\n");
+        self.out
+    }
+
+    fn rect(&mut self, frame: Frame, fill: Vec4) {
+        let _ = writeln!(
+            self.out,
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" {}/>",
+            frame.left, frame.top, frame.width.max(0.0), frame.height.max(0.0), paint("fill", fill)
+        );
+    }
+
+    fn line(&mut self, p1: (f64, f64), p2: (f64, f64), color: Vec4, width: f64, style: LineStyle) {
+        let _ = writeln!(
+            self.out,
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" {} stroke-width=\"{}\"{}/>",
+            p1.0, p1.1, p2.0, p2.1, paint("stroke", color), width, dash_array(style)
+        );
+    }
+
+    fn polyline(&mut self, points: &[(f64, f64)], color: Vec4, width: f64, style: LineStyle) {
+        if points.len() < 2 {
+            return;
+        }
+        let coords: Vec<String> = points.iter().map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect();
+        let _ = writeln!(
+            self.out,
+            "<polyline points=\"{}\" fill=\"none\" {} stroke-width=\"{}\" stroke-linejoin=\"round\"{}/>",
+            coords.join(" "), paint("stroke", color), width, dash_array(style)
+        );
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], fill: Vec4) {
+        let coords: Vec<String> = points.iter().map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect();
+        let _ = writeln!(self.out, "<polygon points=\"{}\" {}/>", coords.join(" "), paint("fill", fill));
+    }
+
+    fn circle(&mut self, center: (f64, f64), radius: f64, fill: Vec4) {
+        let _ = writeln!(
+            self.out,
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" {}/>",
+            center.0, center.1, radius, paint("fill", fill)
+        );
+    }
+
+    fn path(&mut self, d: &str, fill: Vec4) {
+        let _ = writeln!(self.out, "<path d=\"{}\" {}/>", d, paint("fill", fill));
+    }
+
+    /// Text with `anchor` "start", "middle" or "end", rotated by `degrees` around its position
+    fn text(&mut self, pos: (f64, f64), text: &str, size: f64, color: Vec4, anchor: &str, degrees: f64) {
+        if text.is_empty() {
+            return;
+        }
+        let rotate = if degrees != 0.0 {
+            format!(" transform=\"rotate({} {:.2} {:.2})\"", degrees, pos.0, pos.1)
+        } else {
+            String::new()
+        };
+        let _ = writeln!(
+            self.out,
+            "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"{}\" text-anchor=\"{}\" {}{}>{}</text>",
+            pos.0, pos.1, size, anchor, paint("fill", color), rotate, text_markup(text)
+        );
+    }
+}
+
+fn draw_chart(svg: &mut Svg, spec: &ChartSpec, cell: Frame) {
+    let style = spec.style.as_deref().and_then(PlotStyle::preset).unwrap_or_else(PlotStyle::global);
+    if style.background.w > 0.0 {
+        svg.rect(cell, style.background);
+    }
+    svg.text((cell.center_x(), cell.top + MARGIN_TOP * 0.6), &spec.title, style.title_font_size, style.text_color, "middle", 0.0);
+
+    let area = cell.inset();
+    match spec.kind.as_str() {
+        "line" | "scatter" | "area" => draw_xy(svg, spec, &style, area),
+        "bar" | "histogram" => draw_bars(svg, spec, &style, area),
+        "pie" | "donut" => draw_pie(svg, spec, &style, area),
+        "heatmap" => draw_heatmap(svg, spec, &style, area),
+        kind => {
+            let message = format!("{} charts are not exported to SVG", kind);
+            svg.text((cell.center_x(), area.top + area.height / 2.0), &message, style.font_size, style.text_color, "middle", 0.0);
+        }
+    }
+}
+
+/// Grid lines, tick labels, the two axes and their labels
+fn draw_axes(svg: &mut Svg, spec: &ChartSpec, style: &PlotStyle, area: Frame, x: Option<&Axis>, y: &Axis) {
+    for tick in y.ticks() {
+        let py = area.bottom() - y.fraction(tick) * area.height;
+        svg.line((area.left, py), (area.right(), py), style.grid_color, style.grid_width, style.grid_style);
+        svg.text((area.left - 6.0, py + style.font_size * 0.35), &y.scale.format_tick(tick), style.font_size, style.text_color, "end", 0.0);
+    }
+    if let Some(x) = x {
+        for tick in x.ticks() {
+            let px = area.left + x.fraction(tick) * area.width;
+            svg.line((px, area.top), (px, area.bottom()), style.grid_color, style.grid_width, style.grid_style);
+            svg.text((px, area.bottom() + style.font_size + 4.0), &x.scale.format_tick(tick), style.font_size, style.text_color, "middle", 0.0);
+        }
+    }
+    svg.line((area.left, area.bottom()), (area.right(), area.bottom()), style.axes_color, style.axes_width, LineStyle::Solid);
+    svg.line((area.left, area.top), (area.left, area.bottom()), style.axes_color, style.axes_width, LineStyle::Solid);
+    svg.text((area.center_x(), area.bottom() + MARGIN_BOTTOM - 8.0), &spec.x_label, style.font_size, style.text_color, "middle", 0.0);
+    let y_label_pos = (area.left - MARGIN_LEFT + style.font_size, area.top + area.height / 2.0);
+    svg.text(y_label_pos, &spec.y_label, style.font_size, style.text_color, "middle", -90.0);
+}
+
+/// Labelled colour swatches in the top right corner of the plot area
+fn draw_legend(svg: &mut Svg, style: &PlotStyle, area: Frame, entries: &[(String, Vec4)]) {
+    let entries: Vec<&(String, Vec4)> = entries.iter().filter(|(label, _)| !label.is_empty()).collect();
+    if entries.is_empty() {
+        return;
+    }
+    let row = style.font_size + 6.0;
+    let width = entries.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0) as f64 * style.font_size * 0.6 + 30.0;
+    let frame = Frame { left: area.right() - width - 8.0, top: area.top + 8.0, width, height: row * entries.len() as f64 + 6.0 };
+    svg.rect(frame, style.legend_background);
+    for (i, (label, color)) in entries.iter().enumerate() {
+        let y = frame.top + 3.0 + row * (i as f64 + 0.5);
+        svg.rect(Frame { left: frame.left + 6.0, top: y - 4.0, width: 12.0, height: 8.0 }, *color);
+        svg.text((frame.left + 24.0, y + style.font_size * 0.35), label, style.font_size, style.text_color, "start", 0.0);
+    }
+}
+
+fn draw_annotations(svg: &mut Svg, spec: &ChartSpec, style: &PlotStyle, area: Frame, x: &Axis, y: &Axis) {
+    for annotation in &spec.annotations {
+        let pos = (area.left + x.fraction(annotation.x) * area.width, area.bottom() - y.fraction(annotation.y) * area.height);
+        let color = annotation.color.as_deref().and_then(parse_color).unwrap_or(style.text_color);
+        let size = annotation.font_size.unwrap_or(style.font_size);
+        svg.text(pos, &annotation.text, size, color, "start", -annotation.rotation.unwrap_or(0.0));
+    }
+}
+
+/// Line, scatter and area charts
+fn draw_xy(svg: &mut Svg, spec: &ChartSpec, style: &PlotStyle, area: Frame) {
+    let stacked = spec.kind == "area" && spec.stacked == Some(true);
+    // Stacked areas sit on the running total of the series below them
+    let mut baselines: Vec<Vec<f64>> = Vec::new();
+    let mut totals: Vec<f64> = Vec::new();
+    let mut ys: Vec<Vec<f64>> = Vec::new();
+    for series in &spec.series {
+        if stacked {
+            totals.resize(totals.len().max(series.y.len()), 0.0);
+            baselines.push(totals[..series.y.len()].to_vec());
+            for (total, y) in totals.iter_mut().zip(&series.y) {
+                *total += y;
+            }
+            ys.push(totals[..series.y.len()].to_vec());
+        } else {
+            baselines.push(vec![0.0; series.y.len()]);
+            ys.push(series.y.clone());
+        }
+    }
+
+    let categories = spec.series.iter().find_map(|s| s.x_categories.clone()).unwrap_or_else(|| spec.categories.clone());
+    let x = if categories.is_empty() {
+        Axis::fit(spec.series.iter().flat_map(|s| s.x.iter().copied()), &spec.xlim, scale(&spec.x_scale))
+    } else {
+        Axis { min: -0.5, max: categories.len() as f64 - 0.5, scale: ScaleType::Linear }
+    };
+    let with_zero = if spec.kind == "area" { Some(0.0) } else { None };
+    let y = Axis::fit(ys.iter().flatten().copied().chain(with_zero), &spec.ylim, scale(&spec.y_scale));
+
+    draw_axes(svg, spec, style, area, categories.is_empty().then_some(&x), &y);
+    for (i, name) in categories.iter().enumerate() {
+        let px = area.left + x.fraction(i as f64) * area.width;
+        svg.text((px, area.bottom() + style.font_size + 4.0), name, style.font_size, style.text_color, "middle", 0.0);
+    }
+
+    let to_pixel = |vx: f64, vy: f64| (area.left + x.fraction(vx) * area.width, area.bottom() - y.fraction(vy) * area.height);
+    let mut legend = Vec::new();
+    for (i, series) in spec.series.iter().enumerate() {
+        if series.visible == Some(false) {
+            continue;
+        }
+        let color = series.color.as_deref().and_then(parse_color).unwrap_or_else(|| style.prop_cycle.color(i));
+        legend.push((series.label.clone(), color));
+        // Category series are placed by name
+        let xs: Vec<f64> = match &series.x_categories {
+            Some(names) if !categories.is_empty() => names.iter().map(|n| categories.iter().position(|c| c == n).unwrap_or(0) as f64).collect(),
+            _ => series.x.clone(),
+        };
+        let points: Vec<(f64, f64)> = xs.iter().zip(&ys[i]).filter(|(vx, vy)| vx.is_finite() && vy.is_finite()).map(|(vx, vy)| to_pixel(*vx, *vy)).collect();
+
+        if spec.kind == "area" {
+            let mut outline = points.clone();
+            let base: Vec<(f64, f64)> = xs.iter().zip(&baselines[i]).map(|(vx, vy)| to_pixel(*vx, *vy)).collect();
+            outline.extend(base.into_iter().rev());
+            svg.polygon(&outline, vec4(color.x, color.y, color.z, color.w * 0.5));
+        }
+        if spec.kind != "scatter" {
+            let line_style = series.line_style.as_deref().and_then(LineStyle::from_name).unwrap_or_default();
+            svg.polyline(&points, color, series.line_width.unwrap_or(style.line_width), line_style);
+        }
+        let marker = series.marker.as_deref().and_then(MarkerStyle::from_name).unwrap_or(MarkerStyle::None);
+        if spec.kind == "scatter" || marker != MarkerStyle::None {
+            let radius = series.marker_size.unwrap_or(style.marker_size);
+            for (j, point) in points.iter().enumerate() {
+                let r = series.sizes.as_ref().and_then(|sizes| sizes.get(j)).copied().unwrap_or(radius);
+                svg.circle(*point, r, color);
+            }
+        }
+    }
+    draw_annotations(svg, spec, style, area, &x, &y);
+    draw_legend(svg, style, area, &legend);
+}
+
+/// Bar charts, grouped or stacked, and histograms
+fn draw_bars(svg: &mut Svg, spec: &ChartSpec, style: &PlotStyle, area: Frame) {
+    let single = spec.color.as_deref().and_then(parse_color).unwrap_or_else(|| style.prop_cycle.color(0));
+    // (left, right, bottom, top, colour) in data space
+    let mut bars: Vec<(f64, f64, f64, f64, Vec4)> = Vec::new();
+    let mut legend = Vec::new();
+
+    if spec.kind == "histogram" {
+        let values: Vec<f64> = spec.values.iter().copied().filter(|v| v.is_finite()).collect();
+        if !values.is_empty() {
+            let min = values.iter().copied().fold(f64::MAX, f64::min);
+            let max = values.iter().copied().fold(f64::MIN, f64::max);
+            let bins = spec.bins.unwrap_or_else(|| (1.0 + 3.322 * (values.len() as f64).log10()).ceil() as usize).max(1);
+            let width = if max > min { (max - min) / bins as f64 } else { 1.0 };
+            let mut counts = vec![0usize; bins];
+            for v in &values {
+                counts[(((v - min) / width).floor() as usize).min(bins - 1)] += 1;
+            }
+            for (i, count) in counts.iter().enumerate() {
+                let left = min + i as f64 * width;
+                bars.push((left, left + width, 0.0, *count as f64, single));
+            }
+        }
+    } else if spec.groups.is_empty() {
+        for (i, value) in spec.values.iter().enumerate() {
+            bars.push((i as f64 - 0.4, i as f64 + 0.4, 0.0, *value, single));
+        }
+    } else {
+        let stacked = spec.stacked == Some(true);
+        let groups = spec.groups.len() as f64;
+        let mut tops = vec![0.0; spec.categories.len()];
+        for (g, group) in spec.groups.iter().enumerate() {
+            let color = group.color.as_deref().and_then(parse_color).unwrap_or_else(|| style.prop_cycle.color(g));
+            legend.push((group.label.clone(), color));
+            for (i, value) in group.values.iter().enumerate().take(tops.len()) {
+                if stacked {
+                    bars.push((i as f64 - 0.4, i as f64 + 0.4, tops[i], tops[i] + value, color));
+                    tops[i] += value;
+                } else {
+                    let left = i as f64 - 0.4 + 0.8 * g as f64 / groups;
+                    bars.push((left, left + 0.8 / groups, 0.0, *value, color));
+                }
+            }
+        }
+    }
+
+    let categorical = spec.kind == "bar";
+    let x = if categorical {
+        let count = spec.categories.len().max(spec.values.len());
+        Axis { min: -0.5, max: count as f64 - 0.5, scale: ScaleType::Linear }
+    } else {
+        Axis::fit(bars.iter().flat_map(|b| [b.0, b.1]), &spec.xlim, ScaleType::Linear)
+    };
+    let y = Axis::fit(bars.iter().flat_map(|b| [b.2, b.3]).chain(Some(0.0)), &spec.ylim, scale(&spec.y_scale));
+
+    draw_axes(svg, spec, style, area, (!categorical).then_some(&x), &y);
+    if categorical {
+        for (i, name) in spec.categories.iter().enumerate() {
+            let px = area.left + x.fraction(i as f64) * area.width;
+            svg.text((px, area.bottom() + style.font_size + 4.0), name, style.font_size, style.text_color, "middle", 0.0);
+        }
+    }
+    for (left, right, bottom, top, color) in bars {
+        let (x0, x1) = (area.left + x.fraction(left) * area.width, area.left + x.fraction(right) * area.width);
+        let (y0, y1) = (area.bottom() - y.fraction(bottom) * area.height, area.bottom() - y.fraction(top) * area.height);
+        svg.rect(Frame { left: x0.min(x1), top: y0.min(y1), width: (x1 - x0).abs(), height: (y1 - y0).abs() }, color);
+    }
+    draw_legend(svg, style, area, &legend);
+}
+
+/// Pie and donut charts with percentage labels
+fn draw_pie(svg: &mut Svg, spec: &ChartSpec, style: &PlotStyle, area: Frame) {
+    let total: f64 = spec.slices.iter().map(|s| s.value.max(0.0)).sum();
+    if total <= 0.0 {
+        return;
+    }
+    let center = (area.center_x(), area.top + area.height / 2.0);
+    let radius = area.width.min(area.height) / 2.0 - 10.0;
+    let inner = if spec.kind == "donut" { radius * 0.5 } else { 0.0 };
+    let point = |angle: f64, r: f64| (center.0 + r * angle.sin(), center.1 - r * angle.cos());
+
+    let mut angle = 0.0;
+    let mut legend = Vec::new();
+    for (i, slice) in spec.slices.iter().enumerate() {
+        let sweep = slice.value.max(0.0) / total * std::f64::consts::TAU;
+        let color = slice.color.as_deref().and_then(parse_color).unwrap_or_else(|| style.prop_cycle.color(i));
+        legend.push((slice.label.clone(), color));
+        // A full circle needs two arcs
+        let steps = if sweep > std::f64::consts::PI { 2 } else { 1 };
+        let mut d = String::new();
+        let start = point(angle, radius);
+        let _ = write!(d, "M{:.2},{:.2}", start.0, start.1);
+        for step in 1..=steps {
+            let p = point(angle + sweep * step as f64 / steps as f64, radius);
+            let _ = write!(d, " A{:.2},{:.2} 0 0 1 {:.2},{:.2}", radius, radius, p.0, p.1);
+        }
+        if inner > 0.0 {
+            let p = point(angle + sweep, inner);
+            let _ = write!(d, " L{:.2},{:.2}", p.0, p.1);
+            for step in (0..steps).rev() {
+                let p = point(angle + sweep * step as f64 / steps as f64, inner);
+                let _ = write!(d, " A{:.2},{:.2} 0 0 0 {:.2},{:.2}", inner, inner, p.0, p.1);
+            }
+        } else {
+            let _ = write!(d, " L{:.2},{:.2}", center.0, center.1);
+        }
+        d.push_str(" Z");
+        svg.path(&d, color);
+
+        let label_pos = point(angle + sweep / 2.0, (radius + inner) / 2.0 + if inner > 0.0 { 0.0 } else { radius * 0.15 });
+        let percent = format!("{:.1}%", slice.value.max(0.0) / total * 100.0);
+        svg.text((label_pos.0, label_pos.1 + style.font_size * 0.35), &percent, style.font_size, style.text_color, "middle", 0.0);
+        angle += sweep;
+    }
+    draw_legend(svg, style, area, &legend);
+}
+
+/// Heatmap cells coloured through the spec's colormap and norm, linear over the data
+/// range by default
+fn draw_heatmap(svg: &mut Svg, spec: &ChartSpec, style: &PlotStyle, area: Frame) {
+    let rows = spec.matrix.len();
+    let cols = spec.matrix.iter().map(Vec::len).max().unwrap_or(0);
+    if rows == 0 || cols == 0 {
+        return;
+    }
+    let colormap = spec.colormap.as_deref().and_then(Colormap::from_name).unwrap_or(Colormap::Viridis);
+    let (min, max) = spec.matrix.iter().flatten().filter(|v| v.is_finite()).fold((f64::MAX, f64::MIN), |(min, max), v| (min.min(*v), max.max(*v)));
+    let norm = resolve_norm(&spec.norm().ok().flatten(), min, max);
+    let cell_width = area.width / cols as f64;
+    let cell_height = area.height / rows as f64;
+    for (r, row) in spec.matrix.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            if !value.is_finite() {
+                continue;
+            }
+            let t = norm.normalize(*value);
+            let frame = Frame { left: area.left + c as f64 * cell_width, top: area.top + r as f64 * cell_height, width: cell_width, height: cell_height };
+            svg.rect(frame, colormap.sample(t));
+        }
+    }
+    for (c, name) in spec.categories.iter().enumerate().take(cols) {
+        let px = area.left + (c as f64 + 0.5) * cell_width;
+        svg.text((px, area.bottom() + style.font_size + 4.0), name, style.font_size, style.text_color, "middle", 0.0);
+    }
+    for (r, name) in spec.y_categories.iter().enumerate().take(rows) {
+        let py = area.top + (r as f64 + 0.5) * cell_height + style.font_size * 0.35;
+        svg.text((area.left - 6.0, py), name, style.font_size, style.text_color, "end", 0.0);
+    }
+}
@@ -1,10 +1,31 @@
 // Text rendering for plot labels
 
 use makepad_widgets::*;
+use math_widget::math::Math;
+
+/// Height of a line of inline math relative to the font size
+const MATH_LINE_SCALE: f64 = 1.4;
 
 live_design! {
     use link::theme::*;
     use link::shaders::*;
+    use math_widget::math::Math;
+
+    // Text shader that can rotate glyphs around a pivot point (used for rotated tick labels)
+    pub DrawPlotText = {{DrawPlotText}} {
+        fn vertex(self) -> vec4 {
+            let p = mix(self.rect_pos, self.rect_pos + self.rect_size, self.geom_pos);
+            self.t = mix(self.t_min, self.t_max, self.geom_pos.xy);
+
+            // Rotate around the pivot (counter-clockwise on screen, y points down)
+            let d = p - self.pivot;
+            let c = cos(self.rotation);
+            let s = sin(self.rotation);
+            let r = vec2(d.x * c + d.y * s, d.y * c - d.x * s) + self.pivot;
+
+            return self.camera_projection * (self.camera_view * (self.view_transform * vec4(r.x, r.y, self.char_depth + self.draw_zbias, 1.)));
+        }
+    }
 
     pub PlotLabel = {{PlotLabel}} {
         draw_text: {
@@ -13,6 +34,25 @@ live_design! {
             }
             color: #666666
         }
+        draw_text_bold: {
+            text_style: <THEME_FONT_BOLD> {
+                font_size: 10.0
+            }
+        }
+        draw_text_italic: {
+            text_style: <THEME_FONT_ITALIC> {
+                font_size: 10.0
+            }
+        }
+        draw_text_bold_italic: {
+            text_style: <THEME_FONT_BOLD_ITALIC> {
+                font_size: 10.0
+            }
+        }
+        math: <Math> {
+            color: #666666,
+            font_size: 10.0
+        }
     }
 }
 
@@ -30,33 +70,510 @@ pub enum TextAnchor {
     BottomRight,
 }
 
+impl TextAnchor {
+    /// Offset from the anchor point to the top-left corner of a text box of the given size
+    pub fn offset(&self, size: DVec2) -> DVec2 {
+        match self {
+            TextAnchor::TopLeft => dvec2(0.0, 0.0),
+            TextAnchor::TopCenter => dvec2(-size.x / 2.0, 0.0),
+            TextAnchor::TopRight => dvec2(-size.x, 0.0),
+            TextAnchor::MiddleLeft => dvec2(0.0, -size.y / 2.0),
+            TextAnchor::Center => dvec2(-size.x / 2.0, -size.y / 2.0),
+            TextAnchor::MiddleRight => dvec2(-size.x, -size.y / 2.0),
+            TextAnchor::BottomLeft => dvec2(0.0, -size.y),
+            TextAnchor::BottomCenter => dvec2(-size.x / 2.0, -size.y),
+            TextAnchor::BottomRight => dvec2(-size.x, -size.y),
+        }
+    }
+}
+
+/// Font weight, as matplotlib's `fontweight`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TextWeight {
+    #[default]
+    Normal,
+    Bold,
+}
+
+/// Font slant, as matplotlib's `fontstyle`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TextSlant {
+    #[default]
+    Normal,
+    Italic,
+}
+
+/// Horizontal alignment of the lines inside a multi-line text block
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LineAlign {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+impl LineAlign {
+    /// Offset of a line of width `line_width` inside a block of width `block_width`
+    pub fn offset(&self, line_width: f64, block_width: f64) -> f64 {
+        match self {
+            LineAlign::Left => 0.0,
+            LineAlign::Center => (block_width - line_width) / 2.0,
+            LineAlign::Right => block_width - line_width,
+        }
+    }
+}
+
+/// Background box behind a text block, like matplotlib's `bbox=dict(...)`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextBox {
+    pub fill_color: Vec4,
+    pub border_color: Vec4,
+    pub border_width: f64,
+    /// Corner radius in pixels, 0 for square corners
+    pub radius: f64,
+    /// Space between the text and the box edge in pixels
+    pub padding: f64,
+    /// Opacity multiplied into both the fill and the border
+    pub alpha: f64,
+}
+
+impl Default for TextBox {
+    fn default() -> Self {
+        Self {
+            fill_color: vec4(1.0, 1.0, 1.0, 1.0),
+            border_color: vec4(0.3, 0.3, 0.3, 1.0),
+            border_width: 1.0,
+            radius: 4.0,
+            padding: 4.0,
+            alpha: 1.0,
+        }
+    }
+}
+
+impl TextBox {
+    /// Rounded box with the given fill and the default border
+    pub fn round(fill_color: Vec4) -> Self {
+        Self {
+            fill_color,
+            ..Default::default()
+        }
+    }
+
+    /// Square box with the given fill and the default border
+    pub fn square(fill_color: Vec4) -> Self {
+        Self {
+            fill_color,
+            radius: 0.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_border(mut self, color: Vec4, width: f64) -> Self {
+        self.border_color = color;
+        self.border_width = width;
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f64) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: f64) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Fill and border colors with `alpha` applied
+    pub fn colors(&self) -> (Vec4, Vec4) {
+        let alpha = self.alpha.clamp(0.0, 1.0) as f32;
+        let fill = vec4(self.fill_color.x, self.fill_color.y, self.fill_color.z, self.fill_color.w * alpha);
+        let border = vec4(self.border_color.x, self.border_color.y, self.border_color.z, self.border_color.w * alpha);
+        (fill, border)
+    }
+}
+
+/// A run of plain text or of LaTeX inside a line of mixed text
+#[derive(Clone, Debug, PartialEq)]
+pub enum TextSegment {
+    Plain(String),
+    Math(String),
+}
+
+/// Split text like `"Peak at $\alpha=0.3$"` into plain and math segments.
+/// `\$` is a literal dollar sign; an unmatched `$` is kept as plain text.
+pub fn split_math(text: &str) -> Vec<TextSegment> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut in_math = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'$') => {
+                chars.next();
+                if in_math {
+                    current.push_str("\\$");
+                } else {
+                    current.push('$');
+                }
+            }
+            '$' => {
+                if !current.is_empty() {
+                    let run = std::mem::take(&mut current);
+                    segments.push(if in_math { TextSegment::Math(run) } else { TextSegment::Plain(run) });
+                }
+                in_math = !in_math;
+            }
+            _ => current.push(c),
+        }
+    }
+
+    if in_math {
+        // Unterminated math: treat the opening dollar as literal text
+        let run = format!("${}", current);
+        match segments.last_mut() {
+            Some(TextSegment::Plain(last)) => last.push_str(&run),
+            _ => segments.push(TextSegment::Plain(run)),
+        }
+    } else if !current.is_empty() {
+        segments.push(TextSegment::Plain(current));
+    }
+    segments
+}
+
+/// True if the text has at least one `$...$` run
+pub fn contains_math(text: &str) -> bool {
+    text.contains('$') && split_math(text).iter().any(|s| matches!(s, TextSegment::Math(_)))
+}
+
+/// Text without math runs as it is displayed, with `\$` escapes turned into dollar signs
+pub fn unescape_dollars(text: &str) -> std::borrow::Cow<'_, str> {
+    if text.contains("\\$") {
+        std::borrow::Cow::Owned(text.replace("\\$", "$"))
+    } else {
+        std::borrow::Cow::Borrowed(text)
+    }
+}
+
+/// Text with its `$...$` runs spelled out in Unicode, for where LaTeX cannot be drawn,
+/// such as rotated labels. Greek letters and common symbols become their characters and
+/// scripts of digits and signs become super- or subscript characters; other scripts keep
+/// their `^` or `_`.
+pub fn math_as_plain(text: &str) -> String {
+    split_math(text).into_iter().map(|segment| match segment {
+        TextSegment::Plain(plain) => plain,
+        TextSegment::Math(latex) => latex_as_plain(&latex),
+    }).collect()
+}
+
+fn latex_as_plain(latex: &str) -> String {
+    let mut out = String::new();
+    let mut chars = latex.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '^' | '_' => {
+                let script = latex_as_plain(&script_argument(&mut chars));
+                match script_chars(&script, c == '^') {
+                    Some(shifted) => out.push_str(&shifted),
+                    None if script.chars().count() == 1 => {
+                        out.push(c);
+                        out.push_str(&script);
+                    }
+                    None => {
+                        out.push(c);
+                        out.push('(');
+                        out.push_str(&script);
+                        out.push(')');
+                    }
+                }
+            }
+            '\\' => {
+                let mut name = String::new();
+                while let Some(&next) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                    name.push(next);
+                    chars.next();
+                }
+                if name.is_empty() {
+                    match chars.next() {
+                        Some(' ' | ',' | ';') => out.push(' '),
+                        Some('!') | None => {}
+                        Some(escaped) => out.push(escaped),
+                    }
+                } else {
+                    match math_symbol(&name) {
+                        Some(symbol) => out.push_str(symbol),
+                        None => out.push_str(&name),
+                    }
+                }
+            }
+            '{' | '}' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// `script` in Unicode super- or subscript characters, when it only has digits and signs
+fn script_chars(script: &str, superscript: bool) -> Option<String> {
+    const PLAIN: &str = "0123456789+-=()";
+    const SUPER: [char; 15] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹', '⁺', '⁻', '⁼', '⁽', '⁾'];
+    const SUB: [char; 15] = ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉', '₊', '₋', '₌', '₍', '₎'];
+    let table = if superscript { SUPER } else { SUB };
+    if script.is_empty() {
+        return None;
+    }
+    script.chars().map(|c| PLAIN.find(c).map(|i| table[i])).collect()
+}
+
+/// The argument of a `^` or `_`: a braced group, a command or a single character
+pub(crate) fn script_argument(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut arg = String::new();
+    match chars.next() {
+        Some('{') => {
+            let mut depth = 1;
+            for c in chars.by_ref() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+                arg.push(c);
+            }
+        }
+        Some('\\') => {
+            arg.push('\\');
+            while let Some(&next) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                arg.push(next);
+                chars.next();
+            }
+        }
+        Some(c) => arg.push(c),
+        None => {}
+    }
+    arg
+}
+
+/// Unicode character for a LaTeX command such as `alpha` or `times`
+pub(crate) fn math_symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α", "beta" => "β", "gamma" => "γ", "delta" => "δ", "epsilon" => "ε",
+        "zeta" => "ζ", "eta" => "η", "theta" => "θ", "kappa" => "κ", "lambda" => "λ",
+        "mu" => "μ", "nu" => "ν", "xi" => "ξ", "pi" => "π", "rho" => "ρ", "sigma" => "σ",
+        "tau" => "τ", "phi" => "φ", "chi" => "χ", "psi" => "ψ", "omega" => "ω",
+        "Gamma" => "Γ", "Delta" => "Δ", "Theta" => "Θ", "Lambda" => "Λ", "Pi" => "Π",
+        "Sigma" => "Σ", "Phi" => "Φ", "Psi" => "Ψ", "Omega" => "Ω",
+        "times" => "×", "cdot" => "·", "pm" => "±", "infty" => "∞", "leq" => "≤", "geq" => "≥",
+        "neq" => "≠", "approx" => "≈", "circ" => "°", "degree" => "°", "sqrt" => "√",
+        "rightarrow" => "→", "leftarrow" => "←", "partial" => "∂", "sum" => "∑", "int" => "∫",
+        _ => return None,
+    })
+}
+
+/// Rough width of rendered LaTeX, for laying out math next to plain text.
+/// Commands such as `\alpha` count as one glyph, grouping and script markers as none.
+pub fn estimate_math_width(latex: &str, font_size: f64) -> f64 {
+    let mut glyphs = 0usize;
+    let mut chars = latex.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                while chars.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                    chars.next();
+                }
+                glyphs += 1;
+            }
+            '{' | '}' | '^' | '_' | ' ' => {}
+            _ => glyphs += 1,
+        }
+    }
+    glyphs as f64 * font_size * 0.6
+}
+
+/// Text angle in degrees (counter-clockwise) for a pixel-space direction,
+/// flipped by 180° where needed so the text never reads upside down
+pub fn upright_angle(direction: DVec2) -> f64 {
+    let angle = (-direction.y).atan2(direction.x).to_degrees();
+    if angle > 90.0 {
+        angle - 180.0
+    } else if angle < -90.0 {
+        angle + 180.0
+    } else {
+        angle
+    }
+}
+
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawPlotText {
+    #[deref] pub draw_super: DrawText,
+    #[live] pub rotation: f32,   // Radians, counter-clockwise
+    #[live] pub pivot: Vec2,     // Rotation centre in absolute coordinates
+}
+
+/// Text drawn by every chart: titles, axis and tick labels, legends and annotations.
+/// Text between a pair of `$` signs is rendered as LaTeX, so a label such as
+/// "Revenue ($M) vs cost ($K)" must escape its dollar signs as `\$`.
 #[derive(Live, LiveHook, LiveRegister)]
 pub struct PlotLabel {
-    #[live] pub draw_text: DrawText,
+    #[live] pub draw_text: DrawPlotText,
+    #[live] pub draw_text_bold: DrawPlotText,
+    #[live] pub draw_text_italic: DrawPlotText,
+    #[live] pub draw_text_bold_italic: DrawPlotText,
+    #[live] pub math: Math,     // Renders `$...$` runs, follows the color and size of draw_text
+    #[rust] pub rotation: f64,   // Degrees, counter-clockwise, applied by draw_at
+    #[rust] pub weight: TextWeight,
+    #[rust] pub slant: TextSlant,
 }
 
 impl PlotLabel {
+    /// Shader for the current weight and slant.
+    /// `draw_text` holds the color and size, the other faces follow it.
+    fn face(&mut self) -> &mut DrawPlotText {
+        let color = self.draw_text.color;
+        let font_scale = self.draw_text.font_scale;
+        let face = match (self.weight, self.slant) {
+            (TextWeight::Normal, TextSlant::Normal) => return &mut self.draw_text,
+            (TextWeight::Bold, TextSlant::Normal) => &mut self.draw_text_bold,
+            (TextWeight::Normal, TextSlant::Italic) => &mut self.draw_text_italic,
+            (TextWeight::Bold, TextSlant::Italic) => &mut self.draw_text_bold_italic,
+        };
+        face.color = color;
+        face.font_scale = font_scale;
+        face
+    }
+
+    /// Font size in points, as passed to set_font_size
+    pub fn font_size(&self) -> f64 {
+        self.draw_text.font_scale as f64 * 10.0
+    }
+
+    /// Measure the size of a text string as it would be drawn by draw_at.
+    /// `$...$` runs are measured as math.
+    pub fn measure(&mut self, cx: &mut Cx2d, text: &str) -> DVec2 {
+        if !contains_math(text) {
+            return self.measure_plain(cx, &unescape_dollars(text));
+        }
+        let mut size = dvec2(0.0, 0.0);
+        for segment in split_math(text) {
+            let s = self.measure_segment(cx, &segment);
+            size.x += s.x;
+            size.y = size.y.max(s.y);
+        }
+        size
+    }
+
+    fn measure_segment(&mut self, cx: &mut Cx2d, segment: &TextSegment) -> DVec2 {
+        match segment {
+            TextSegment::Plain(text) => self.measure_plain(cx, text),
+            TextSegment::Math(latex) => {
+                let font_size = self.font_size();
+                dvec2(estimate_math_width(latex, font_size), font_size * MATH_LINE_SCALE)
+            }
+        }
+    }
+
+    fn measure_plain(&mut self, cx: &mut Cx2d, text: &str) -> DVec2 {
+        let face = self.face();
+        let layout = face.layout(cx, 0.0, 0.0, None, false, Align::default(), text);
+        dvec2(
+            layout.size_in_lpxs.width as f64 * face.font_scale as f64,
+            layout.size_in_lpxs.height as f64 * face.font_scale as f64,
+        )
+    }
+
+    /// Size of the axis-aligned box around the text when rotated by `degrees`
+    pub fn measure_rotated(&mut self, cx: &mut Cx2d, text: &str, degrees: f64) -> DVec2 {
+        let size = if degrees != 0.0 && contains_math(text) {
+            self.measure_plain(cx, &math_as_plain(text))
+        } else {
+            self.measure(cx, text)
+        };
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        dvec2(
+            size.x * cos.abs() + size.y * sin.abs(),
+            size.x * sin.abs() + size.y * cos.abs(),
+        )
+    }
+
+    /// Draw text at the anchor point, rotated by the current `rotation`
     pub fn draw_at(&mut self, cx: &mut Cx2d, pos: DVec2, text: &str, anchor: TextAnchor) {
+        let rotation = self.rotation;
+        self.draw_rotated(cx, pos, text, anchor, rotation);
+    }
+
+    /// Draw text rotated by `degrees` (counter-clockwise) around the anchor point.
+    /// The math renderer cannot rotate, so rotated text with `$...$` runs is drawn as
+    /// plain text with the math spelled out in Unicode (see `math_as_plain`).
+    pub fn draw_rotated(&mut self, cx: &mut Cx2d, pos: DVec2, text: &str, anchor: TextAnchor, degrees: f64) {
+        if !contains_math(text) {
+            self.draw_plain(cx, pos, &unescape_dollars(text), anchor, degrees);
+            return;
+        }
+        if degrees != 0.0 {
+            self.draw_plain(cx, pos, &math_as_plain(text), anchor, degrees);
+            return;
+        }
+
+        let size = self.measure(cx, text);
+        let offset = anchor.offset(size);
+        let mut x = offset.x;
+        for segment in split_math(text) {
+            let s = self.measure_segment(cx, &segment);
+            let top_left = pos + dvec2(x, offset.y + (size.y - s.y) / 2.0);
+            match segment {
+                TextSegment::Plain(text) => self.draw_plain(cx, top_left, &text, TextAnchor::TopLeft, 0.0),
+                TextSegment::Math(latex) => self.draw_math(cx, top_left, &latex),
+            }
+            x += s.x;
+        }
+    }
+
+    fn draw_plain(&mut self, cx: &mut Cx2d, pos: DVec2, text: &str, anchor: TextAnchor, degrees: f64) {
         // Layout text to get dimensions
-        let layout = self.draw_text.layout(cx, 0.0, 0.0, None, false, Align::default(), text);
-        let text_width = layout.size_in_lpxs.width as f64 * self.draw_text.font_scale as f64;
-        let text_height = layout.size_in_lpxs.height as f64 * self.draw_text.font_scale as f64;
+        let size = self.measure_plain(cx, text);
+        let face = self.face();
+        face.rotation = degrees.to_radians() as f32;
+        face.pivot = vec2(pos.x as f32, pos.y as f32);
+        face.draw_abs(cx, pos + anchor.offset(size), text);
+    }
 
-        // Calculate offset based on anchor
-        let offset = match anchor {
-            TextAnchor::TopLeft => dvec2(0.0, 0.0),
-            TextAnchor::TopCenter => dvec2(-text_width / 2.0, 0.0),
-            TextAnchor::TopRight => dvec2(-text_width, 0.0),
-            TextAnchor::MiddleLeft => dvec2(0.0, -text_height / 2.0),
-            TextAnchor::Center => dvec2(-text_width / 2.0, -text_height / 2.0),
-            TextAnchor::MiddleRight => dvec2(-text_width, -text_height / 2.0),
-            TextAnchor::BottomLeft => dvec2(0.0, -text_height),
-            TextAnchor::BottomCenter => dvec2(-text_width / 2.0, -text_height),
-            TextAnchor::BottomRight => dvec2(-text_width, -text_height),
+    fn draw_math(&mut self, cx: &mut Cx2d, top_left: DVec2, latex: &str) {
+        let color = self.draw_text.color;
+        let font_size = self.font_size();
+        self.math.apply_over(cx, live! {
+            color: (color),
+            font_size: (font_size)
+        });
+        self.math.set_text(cx, latex);
+        let walk = Walk {
+            abs_pos: Some(top_left),
+            ..Walk::default()
         };
+        let _ = self.math.draw_walk(cx, &mut Scope::empty(), walk);
+    }
+
+    /// Rotation in degrees (counter-clockwise) used by draw_at
+    pub fn set_rotation(&mut self, degrees: f64) {
+        self.rotation = degrees;
+    }
+
+    pub fn set_weight(&mut self, weight: TextWeight) {
+        self.weight = weight;
+    }
 
-        let draw_pos = pos + offset;
-        self.draw_text.draw_abs(cx, draw_pos, text);
+    pub fn set_slant(&mut self, slant: TextSlant) {
+        self.slant = slant;
     }
 
     pub fn set_color(&mut self, color: Vec4) {
@@ -0,0 +1,481 @@
+// Twin axes - a second y axis on the right (twinx) or x axis along the top (twiny),
+// sharing the plot area and the other axis of a Cartesian chart
+
+use makepad_widgets::*;
+use crate::plot::{PlotArea, ScaleType, Series};
+use crate::elements::*;
+use crate::text::*;
+use crate::layout::{TICK_LABEL_PAD, max_text_width};
+use crate::legend::*;
+use crate::color::PropCycle;
+use crate::style::PlotStyle;
+
+/// Gap between the twin tick labels and the twin axis label
+const AXIS_LABEL_PAD: f64 = 6.0;
+
+/// Where the twin axis sits, and so which axis it shares with the host chart
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TwinSide {
+    /// `twinx`: own y axis on the right, x axis shared
+    #[default]
+    Right,
+    /// `twiny`: own x axis along the top, y axis shared
+    Top,
+}
+
+/// Visible range and scale of one axis
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AxisRange {
+    pub min: f64,
+    pub max: f64,
+    pub scale: ScaleType,
+}
+
+impl AxisRange {
+    pub fn new(range: (f64, f64), scale: ScaleType) -> Self {
+        Self { min: range.0, max: range.1, scale }
+    }
+
+    pub fn linear(range: (f64, f64)) -> Self {
+        Self::new(range, ScaleType::Linear)
+    }
+
+    /// Position of `value` along the axis, 0 at `min` and 1 at `max`
+    pub fn fraction(&self, value: f64) -> f64 {
+        let lo = self.scale.transform(self.min);
+        let hi = self.scale.transform(self.max);
+        if hi == lo {
+            return 0.5;
+        }
+        (self.scale.transform(value) - lo) / (hi - lo)
+    }
+}
+
+/// What a host chart passes to its twin axes when drawing
+#[derive(Clone, Copy)]
+pub struct TwinDraw<'a> {
+    pub area: PlotArea,
+    /// The host's axes; a twin shares x when on the right and y when on top
+    pub x: AxisRange,
+    pub y: AxisRange,
+    pub style: &'a PlotStyle,
+    pub cycle: &'a PropCycle,
+    /// Colour cycle index of the first twin series, so they follow the host's own series
+    pub first_color: usize,
+    /// Draw the twin series as unconnected markers, as scatter plots do
+    pub markers_only: bool,
+}
+
+/// A second axis with its own series, scale, limits, label and tick colour, like the
+/// axes returned by matplotlib's `twinx`/`twiny`
+#[derive(Clone, Debug, Default)]
+pub struct TwinAxis {
+    pub side: TwinSide,
+    pub series: Vec<Series>,
+    pub label: String,
+    pub scale: ScaleType,
+    /// Fixed limits, kept when the data is rescaled
+    pub limits: Option<(f64, f64)>,
+    /// Colour of the spine, ticks and tick labels; the style's colours when unset
+    pub tick_color: Option<Vec4>,
+    range: (f64, f64),
+    initial_range: (f64, f64),
+}
+
+impl TwinAxis {
+    pub fn new(side: TwinSide) -> Self {
+        Self { side, range: (0.0, 1.0), ..Default::default() }
+    }
+
+    /// Second y axis on the right, sharing x
+    pub fn twinx() -> Self {
+        Self::new(TwinSide::Right)
+    }
+
+    /// Second x axis along the top, sharing y
+    pub fn twiny() -> Self {
+        Self::new(TwinSide::Top)
+    }
+
+    pub fn with_series(mut self, series: Series) -> Self {
+        self.add_series(series);
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn with_scale(mut self, scale: ScaleType) -> Self {
+        self.set_scale(scale);
+        self
+    }
+
+    pub fn with_limits(mut self, min: f64, max: f64) -> Self {
+        self.set_limits(min, max);
+        self
+    }
+
+    pub fn with_tick_color(mut self, color: Vec4) -> Self {
+        self.tick_color = Some(color);
+        self
+    }
+
+    pub fn range(&self) -> (f64, f64) {
+        self.range
+    }
+
+    pub fn axis(&self) -> AxisRange {
+        AxisRange::new(self.range, self.scale)
+    }
+
+    pub fn add_series(&mut self, series: Series) {
+        self.series.push(series);
+        self.auto_range();
+    }
+
+    pub fn clear(&mut self) {
+        self.series.clear();
+        self.auto_range();
+    }
+
+    pub fn set_limits(&mut self, min: f64, max: f64) {
+        self.limits = Some((min, max));
+        self.range = (min, max);
+    }
+
+    pub fn set_scale(&mut self, scale: ScaleType) {
+        self.scale = scale;
+        self.auto_range();
+    }
+
+    /// Values along the twin's own dimension: y for `Right`, x for `Top`
+    fn own_values(series: &Series, side: TwinSide) -> &[f64] {
+        match side {
+            TwinSide::Right => &series.y,
+            TwinSide::Top => &series.x,
+        }
+    }
+
+    /// Fit the range to the visible series, with the same padding as the host charts
+    pub fn auto_range(&mut self) {
+        let (min, max) = self.series.iter()
+            .filter(|s| s.visible)
+            .flat_map(|s| Self::own_values(s, self.side).iter().copied())
+            .filter(|v| v.is_finite() && (self.scale != ScaleType::Log || *v > 0.0))
+            .fold((f64::MAX, f64::MIN), |(min, max), v| (min.min(v), max.max(v)));
+
+        if min <= max {
+            self.range = match self.scale {
+                ScaleType::Log => (min / 1.5, max * 1.5),
+                _ if max > min => {
+                    let pad = (max - min) * 0.05;
+                    (min - pad, max + pad)
+                }
+                _ => (min - 0.5, max + 0.5),
+            };
+        }
+        // Fixed limits win over the data range
+        if let Some(limits) = self.limits {
+            self.range = limits;
+        }
+    }
+
+    pub fn ticks(&self) -> Vec<f64> {
+        self.scale.generate_ticks(self.range.0, self.range.1, 5)
+    }
+
+    pub fn tick_labels(&self) -> Vec<String> {
+        self.ticks().iter().map(|v| self.scale.format_tick(*v)).collect()
+    }
+
+    /// Remember the range at the start of a pan
+    pub fn begin_pan(&mut self) {
+        self.initial_range = self.range;
+    }
+
+    /// Pan by a drag of `delta` pixels since `begin_pan`, moving with the host's shared axis
+    pub fn pan(&mut self, delta: DVec2, area: &PlotArea) {
+        let size = self.initial_range.1 - self.initial_range.0;
+        let shift = match self.side {
+            TwinSide::Right => delta.y * size / area.height(),
+            TwinSide::Top => -delta.x * size / area.width(),
+        };
+        self.range = (self.initial_range.0 + shift, self.initial_range.1 + shift);
+    }
+
+    /// Zoom by `factor` around `rel`, the cursor position as a fraction of the plot area
+    /// (x from the left, y from the bottom)
+    pub fn zoom(&mut self, rel: DVec2, factor: f64) {
+        let rel = match self.side {
+            TwinSide::Right => rel.y,
+            TwinSide::Top => rel.x,
+        };
+        let anchor = self.range.0 + rel * (self.range.1 - self.range.0);
+        let size = (self.range.1 - self.range.0) * factor;
+        self.range = (anchor - rel * size, anchor + (1.0 - rel) * size);
+    }
+
+    /// Ticks as positions along the axis, from 0 at the bottom or left to 1
+    fn side_ticks(&self) -> Vec<(f64, String)> {
+        let own = self.axis();
+        self.ticks().into_iter().map(|v| (own.fraction(v), self.scale.format_tick(v))).collect()
+    }
+
+    /// Room the twin needs outside the plot area: the right margin for `Right`,
+    /// the top margin for `Top`
+    pub fn margin(&self, cx: &mut Cx2d, label: &mut PlotLabel, style: &PlotStyle) -> f64 {
+        let ticks = self.side_ticks();
+        SideAxis { side: self.side, ticks: &ticks, label: &self.label, color: self.tick_color, offset: 0.0 }
+            .margin(cx, label, style)
+    }
+
+    /// Pixel position of a point of a twin series
+    pub fn data_to_pixel(&self, ctx: &TwinDraw, x: f64, y: f64) -> DVec2 {
+        let own = self.axis();
+        let (fx, fy) = match self.side {
+            TwinSide::Right => (ctx.x.fraction(x), own.fraction(y)),
+            TwinSide::Top => (own.fraction(x), ctx.y.fraction(y)),
+        };
+        dvec2(ctx.area.left + fx * ctx.area.width(), ctx.area.bottom - fy * ctx.area.height())
+    }
+
+    /// Scatter points without a marker style fall back to circles
+    fn marker(ctx: &TwinDraw, series: &Series) -> MarkerStyle {
+        if ctx.markers_only && series.marker_style == MarkerStyle::None {
+            MarkerStyle::Circle
+        } else {
+            series.marker_style
+        }
+    }
+
+    fn series_color(&self, ctx: &TwinDraw, index: usize) -> Vec4 {
+        self.series[index].color.unwrap_or_else(|| ctx.cycle.color(ctx.first_color + index))
+    }
+
+    pub fn draw_series(&self, cx: &mut Cx2d, ctx: &TwinDraw, draw_line: &mut DrawPlotLine, draw_point: &mut DrawPlotPoint) {
+        for (index, series) in self.series.iter().enumerate() {
+            if !series.visible {
+                continue;
+            }
+            let color = self.series_color(ctx, index);
+            draw_line.color = color;
+            draw_point.color = color;
+
+            let points: Vec<DVec2> = series.x.iter().zip(&series.y)
+                .filter(|(x, y)| x.is_finite() && y.is_finite())
+                .map(|(x, y)| self.data_to_pixel(ctx, *x, *y))
+                .collect();
+            if !ctx.markers_only {
+                let line_width = series.line_width.unwrap_or(ctx.style.line_width);
+                let mut dash_offset = 0.0;
+                for pair in points.windows(2) {
+                    draw_line.draw_line_styled(cx, pair[0], pair[1], line_width, series.line_style, dash_offset);
+                    dash_offset += (pair[1] - pair[0]).length();
+                }
+            }
+
+            let marker_size = series.marker_size.unwrap_or(ctx.style.marker_size);
+            let marker = Self::marker(ctx, series);
+            for p in &points {
+                draw_point.draw_marker(cx, *p, marker_size, marker);
+            }
+        }
+    }
+
+    /// Spine, ticks, tick labels and axis label on the twin's side of the plot area
+    pub fn draw_axis(&self, cx: &mut Cx2d, ctx: &TwinDraw, draw_line: &mut DrawPlotLine, label: &mut PlotLabel) {
+        let ticks = self.side_ticks();
+        SideAxis { side: self.side, ticks: &ticks, label: &self.label, color: self.tick_color, offset: 0.0 }
+            .draw(cx, ctx, draw_line, label);
+    }
+
+    /// Legend rows for the twin series, added after the host's own
+    pub fn legend_entries(&self, ctx: &TwinDraw) -> Vec<LegendEntry> {
+        self.series.iter().enumerate().map(|(index, series)| {
+            let color = self.series_color(ctx, index);
+            if ctx.markers_only {
+                return LegendEntry::marker(series.label.clone(), color, Self::marker(ctx, series)).with_visible(series.visible);
+            }
+            let symbol = LegendSymbol::Line {
+                style: series.line_style,
+                width: series.line_width.unwrap_or(ctx.style.line_width),
+                marker: series.marker_style,
+            };
+            LegendEntry::new(series.label.clone(), color, symbol).with_visible(series.visible)
+        }).collect()
+    }
+
+    /// Pixel positions of the twin data, for placing the legend clear of it
+    pub fn data_points(&self, ctx: &TwinDraw) -> Vec<DVec2> {
+        self.series.iter()
+            .filter(|s| s.visible)
+            .flat_map(|s| s.x.iter().zip(&s.y).map(|(x, y)| self.data_to_pixel(ctx, *x, *y)))
+            .collect()
+    }
+}
+
+/// An axis drawn on one side of the plot area, outside the host's own axes
+pub(crate) struct SideAxis<'a> {
+    pub side: TwinSide,
+    /// Tick positions along the axis, from 0 at the bottom or left to 1, with their labels
+    pub ticks: &'a [(f64, String)],
+    pub label: &'a str,
+    /// Colour of the spine, ticks and tick labels; the style's colours when unset
+    pub color: Option<Vec4>,
+    /// Distance of the spine outside the plot area, to stack several axes on one side
+    pub offset: f64,
+}
+
+impl SideAxis<'_> {
+    /// Room the axis needs beyond its offset
+    pub fn margin(&self, cx: &mut Cx2d, label: &mut PlotLabel, style: &PlotStyle) -> f64 {
+        let tick = style.tick_length.max(0.0);
+        let label_height = if self.label.is_empty() {
+            0.0
+        } else {
+            label.measure(cx, self.label).y + AXIS_LABEL_PAD
+        };
+        let ticks = match self.side {
+            TwinSide::Right => {
+                let texts: Vec<String> = self.ticks.iter().map(|(_, text)| text.clone()).collect();
+                max_text_width(cx, label, &texts)
+            }
+            TwinSide::Top => label.measure(cx, "0").y,
+        };
+        tick + TICK_LABEL_PAD + ticks + label_height
+    }
+
+    pub fn draw(&self, cx: &mut Cx2d, ctx: &TwinDraw, draw_line: &mut DrawPlotLine, label: &mut PlotLabel) {
+        let area = &ctx.area;
+        let style = ctx.style;
+        let tick = style.tick_length.max(0.0);
+        draw_line.color = self.color.unwrap_or(style.axes_color);
+        label.set_color(self.color.unwrap_or(style.text_color));
+
+        let visible = self.ticks.iter().filter(|(t, _)| (-1e-9..=1.0 + 1e-9).contains(t));
+        match self.side {
+            TwinSide::Right => {
+                let x0 = area.right + self.offset;
+                draw_line.draw_line(cx, dvec2(x0, area.top), dvec2(x0, area.bottom), style.axes_width);
+                for (t, text) in visible {
+                    let y = area.bottom - t * area.height();
+                    draw_line.draw_line(cx, dvec2(x0, y), dvec2(x0 + tick, y), style.axes_width);
+                    label.draw_at(cx, dvec2(x0 + tick + TICK_LABEL_PAD, y), text, TextAnchor::MiddleLeft);
+                }
+                if !self.label.is_empty() {
+                    let texts: Vec<String> = self.ticks.iter().map(|(_, text)| text.clone()).collect();
+                    let x = x0 + tick + TICK_LABEL_PAD + max_text_width(cx, label, &texts) + AXIS_LABEL_PAD;
+                    // Reads bottom-to-top like the left label, with its top towards the axis
+                    label.draw_rotated(cx, dvec2(x, (area.top + area.bottom) / 2.0), self.label, TextAnchor::TopCenter, 90.0);
+                }
+            }
+            TwinSide::Top => {
+                let y0 = area.top - self.offset;
+                draw_line.draw_line(cx, dvec2(area.left, y0), dvec2(area.right, y0), style.axes_width);
+                for (t, text) in visible {
+                    let x = area.left + t * area.width();
+                    draw_line.draw_line(cx, dvec2(x, y0 - tick), dvec2(x, y0), style.axes_width);
+                    label.draw_at(cx, dvec2(x, y0 - tick - TICK_LABEL_PAD), text, TextAnchor::BottomCenter);
+                }
+                if !self.label.is_empty() {
+                    let line_height = label.measure(cx, "0").y;
+                    let y = y0 - tick - TICK_LABEL_PAD - line_height - AXIS_LABEL_PAD;
+                    label.draw_at(cx, dvec2((area.left + area.right) / 2.0, y), self.label, TextAnchor::BottomCenter);
+                }
+            }
+        }
+        label.set_color(style.text_color);
+    }
+}
+
+/// The twin axes of one chart, at most one per side
+#[derive(Clone, Debug, Default)]
+pub struct TwinAxes {
+    pub right: Option<TwinAxis>,
+    pub top: Option<TwinAxis>,
+}
+
+impl TwinAxes {
+    /// The twin on `side`, created empty if the chart has none yet
+    pub fn get_or_insert(&mut self, side: TwinSide) -> &mut TwinAxis {
+        match side {
+            TwinSide::Right => self.right.get_or_insert_with(TwinAxis::twinx),
+            TwinSide::Top => self.top.get_or_insert_with(TwinAxis::twiny),
+        }
+    }
+
+    /// Attach `axis` on its side, replacing any twin already there
+    pub fn set(&mut self, mut axis: TwinAxis) {
+        axis.auto_range();
+        match axis.side {
+            TwinSide::Right => self.right = Some(axis),
+            TwinSide::Top => self.top = Some(axis),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.right = None;
+        self.top = None;
+    }
+
+    pub fn get_mut(&mut self, side: TwinSide) -> Option<&mut TwinAxis> {
+        match side {
+            TwinSide::Right => self.right.as_mut(),
+            TwinSide::Top => self.top.as_mut(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TwinAxis> {
+        self.right.iter().chain(self.top.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut TwinAxis> {
+        self.right.iter_mut().chain(self.top.iter_mut())
+    }
+
+    /// Extra (right, top) margins for the twin tick labels and axis labels
+    pub fn margins(&self, cx: &mut Cx2d, label: &mut PlotLabel, style: &PlotStyle) -> (f64, f64) {
+        let right = self.right.as_ref().map_or(0.0, |twin| twin.margin(cx, label, style));
+        let top = self.top.as_ref().map_or(0.0, |twin| twin.margin(cx, label, style));
+        (right, top)
+    }
+
+    pub fn begin_pan(&mut self) {
+        self.iter_mut().for_each(TwinAxis::begin_pan);
+    }
+
+    pub fn pan(&mut self, delta: DVec2, area: &PlotArea) {
+        self.iter_mut().for_each(|twin| twin.pan(delta, area));
+    }
+
+    pub fn zoom(&mut self, rel: DVec2, factor: f64) {
+        self.iter_mut().for_each(|twin| twin.zoom(rel, factor));
+    }
+
+    /// Context for each twin, with the top twin's colours following the right twin's
+    fn contexts<'a>(&'a self, ctx: &TwinDraw<'a>) -> impl Iterator<Item = (&'a TwinAxis, TwinDraw<'a>)> {
+        let ctx = *ctx;
+        let right_count = self.right.as_ref().map_or(0, |twin| twin.series.len());
+        let top_ctx = TwinDraw { first_color: ctx.first_color + right_count, ..ctx };
+        self.right.iter().map(move |twin| (twin, ctx))
+            .chain(self.top.iter().map(move |twin| (twin, top_ctx)))
+    }
+
+    /// Draw the twin series and axes over the host's plot area
+    pub fn draw(&self, cx: &mut Cx2d, ctx: &TwinDraw, draw_line: &mut DrawPlotLine, draw_point: &mut DrawPlotPoint, label: &mut PlotLabel) {
+        for (twin, ctx) in self.contexts(ctx) {
+            twin.draw_series(cx, &ctx, draw_line, draw_point);
+            twin.draw_axis(cx, &ctx, draw_line, label);
+        }
+    }
+
+    pub fn legend_entries(&self, ctx: &TwinDraw) -> Vec<LegendEntry> {
+        self.contexts(ctx).flat_map(|(twin, ctx)| twin.legend_entries(&ctx)).collect()
+    }
+
+    pub fn data_points(&self, ctx: &TwinDraw) -> Vec<DVec2> {
+        self.contexts(ctx).flat_map(|(twin, ctx)| twin.data_points(&ctx)).collect()
+    }
+}
@@ -0,0 +1,824 @@
+// Vega-Lite import - turns the common subset of Vega-Lite specs into chart specs
+
+use crate::spec::*;
+
+/// Marker area range, in square pixels, that a `size` field is mapped onto (Vega-Lite's default)
+const SIZE_RANGE: (f64, f64) = (9.0, 361.0);
+
+/// Deepest nesting of arrays and objects the JSON parser accepts, so hostile input
+/// fails instead of overflowing the stack
+const MAX_DEPTH: usize = 128;
+
+impl ChartSpec {
+    /// Convert a Vega-Lite spec to a chart spec, to be loaded with `ChartSpec::load` into a
+    /// widget of the kind it names.
+    ///
+    /// Handles single-view specs with inline `data.values`, the marks line, bar, point
+    /// (also circle and square), area, rect and arc, and the encodings x, y, color, size and
+    /// theta. Marks become "line", "bar", "scatter", "area", "heatmap" and "pie" charts.
+    /// Rows with the same `color` field value form one series, bar group or slice.
+    /// Bars and arcs sum their values per category, which also covers the "sum" and
+    /// "count" aggregates; other aggregates, and any aggregate on other marks, fail with
+    /// `SpecError::Unsupported`.
+    pub fn from_vega_lite(json: &str) -> Result<ChartSpec, SpecError> {
+        let root = Json::parse(json).map_err(SpecError::Json)?;
+        VegaLite::new(&root)?.to_spec()
+    }
+}
+
+/// Data type of an encoded field
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FieldType {
+    Quantitative,
+    Temporal,
+    Ordinal,
+    Nominal,
+}
+
+impl FieldType {
+    fn is_discrete(&self) -> bool {
+        matches!(self, FieldType::Ordinal | FieldType::Nominal)
+    }
+}
+
+/// One encoding channel: a data field or a constant value
+struct Channel<'a> {
+    def: &'a Json,
+    field: Option<&'a str>,
+    field_type: FieldType,
+    aggregate: Option<&'a str>,
+}
+
+impl<'a> Channel<'a> {
+    fn new(def: &'a Json) -> Self {
+        let field = def.get("field").and_then(Json::as_str);
+        let field_type = match def.get("type").and_then(Json::as_str) {
+            Some("quantitative") => FieldType::Quantitative,
+            Some("temporal") => FieldType::Temporal,
+            Some("ordinal") => FieldType::Ordinal,
+            Some("nominal") => FieldType::Nominal,
+            // Vega-Lite requires a type for fields; treat a missing one as quantitative
+            _ => FieldType::Quantitative,
+        };
+        let aggregate = def.get("aggregate").and_then(Json::as_str);
+        Self { def, field, field_type, aggregate }
+    }
+
+    fn title(&self) -> String {
+        match (self.def.get("title").and_then(Json::as_str), self.field) {
+            (Some(title), _) => title.to_string(),
+            (None, None) if self.aggregate == Some("count") => "Count of Records".to_string(),
+            (None, field) => field.unwrap_or_default().to_string(),
+        }
+    }
+
+    fn value<'r>(&self, row: &'r Json) -> Option<&'r Json> {
+        self.field.and_then(|field| row.get(field))
+    }
+
+    /// Numeric value of the field in `row`; temporal values in seconds. Under a "count"
+    /// aggregate every row counts one.
+    fn number(&self, row: &Json) -> f64 {
+        if self.aggregate == Some("count") {
+            return 1.0;
+        }
+        match (self.value(row), self.field_type) {
+            (Some(Json::String(text)), FieldType::Temporal) => parse_date(text).unwrap_or(f64::NAN),
+            // Vega-Lite timestamps are in milliseconds
+            (Some(Json::Number(ms)), FieldType::Temporal) => ms / 1000.0,
+            (Some(value), _) => value.as_f64().unwrap_or(f64::NAN),
+            (None, _) => f64::NAN,
+        }
+    }
+
+    fn category(&self, row: &Json) -> String {
+        self.value(row).map(Json::text).unwrap_or_default()
+    }
+
+    /// `scale.type` name, or "time" for temporal fields
+    fn scale(&self) -> Option<String> {
+        if self.field_type == FieldType::Temporal {
+            return Some("time".to_string());
+        }
+        self.def.get("scale").and_then(|s| s.get("type")).and_then(Json::as_str).map(str::to_string)
+    }
+
+    /// Distinct values of the field in Vega-Lite's order: ascending unless `sort` is null
+    /// or an explicit list
+    fn categories(&self, rows: &[Json]) -> Vec<String> {
+        let mut categories: Vec<String> = Vec::new();
+        for row in rows {
+            let category = self.category(row);
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+        match self.def.get("sort") {
+            Some(Json::Null) => {}
+            Some(Json::Array(order)) => {
+                let order: Vec<String> = order.iter().map(Json::text).collect();
+                categories.sort_by_key(|c| order.iter().position(|o| o == c).unwrap_or(order.len()));
+            }
+            Some(Json::String(dir)) if dir == "descending" => sort_categories(&mut categories, true),
+            _ => sort_categories(&mut categories, false),
+        }
+        categories
+    }
+
+    /// Constant colour from `value`
+    fn constant_color(&self) -> Option<String> {
+        self.def.get("value").and_then(Json::as_str).map(str::to_string)
+    }
+}
+
+/// Sort numerically when every category is a number, else alphabetically
+fn sort_categories(categories: &mut [String], descending: bool) {
+    if categories.iter().all(|c| c.parse::<f64>().is_ok()) {
+        categories.sort_by(|a, b| {
+            let (a, b) = (a.parse::<f64>().unwrap_or(0.0), b.parse::<f64>().unwrap_or(0.0));
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+    } else {
+        categories.sort();
+    }
+    if descending {
+        categories.reverse();
+    }
+}
+
+struct VegaLite<'a> {
+    mark: String,
+    title: String,
+    rows: &'a [Json],
+    x: Option<Channel<'a>>,
+    y: Option<Channel<'a>>,
+    color: Option<Channel<'a>>,
+    size: Option<Channel<'a>>,
+    theta: Option<Channel<'a>>,
+}
+
+impl<'a> VegaLite<'a> {
+    fn new(root: &'a Json) -> Result<Self, SpecError> {
+        if root.get("layer").is_some() || root.get("hconcat").is_some() || root.get("vconcat").is_some() || root.get("facet").is_some() {
+            return Err(SpecError::Unsupported("composite views".to_string()));
+        }
+        let mark = match root.get("mark") {
+            Some(Json::String(mark)) => mark.clone(),
+            Some(mark) => mark.get("type").and_then(Json::as_str).unwrap_or_default().to_string(),
+            None => return Err(SpecError::Unsupported("spec without a mark".to_string())),
+        };
+        let title = match root.get("title") {
+            Some(Json::String(title)) => title.clone(),
+            Some(title) => title.get("text").map(Json::text).unwrap_or_default(),
+            None => String::new(),
+        };
+        let rows = match root.get("data").and_then(|d| d.get("values")) {
+            Some(Json::Array(rows)) => rows.as_slice(),
+            _ => return Err(SpecError::Unsupported("data other than inline values".to_string())),
+        };
+        let encoding = root.get("encoding");
+        let channel = |name: &str| encoding.and_then(|e| e.get(name)).map(Channel::new);
+        Ok(Self {
+            mark,
+            title,
+            rows,
+            x: channel("x"),
+            y: channel("y"),
+            color: channel("color"),
+            size: channel("size"),
+            theta: channel("theta"),
+        })
+    }
+
+    fn to_spec(&self) -> Result<ChartSpec, SpecError> {
+        match self.mark.as_str() {
+            "line" => self.xy_spec("line"),
+            "area" => self.xy_spec("area"),
+            "point" | "circle" | "square" => self.xy_spec("scatter"),
+            "bar" => self.bar_spec(),
+            "rect" => self.heatmap_spec(),
+            "arc" => self.pie_spec(),
+            mark => Err(SpecError::Unsupported(format!("mark \"{}\"", mark))),
+        }
+    }
+
+    /// Fail on an aggregate outside `allowed` in any channel
+    fn check_aggregates(&self, allowed: &[&str]) -> Result<(), SpecError> {
+        let channels = [&self.x, &self.y, &self.color, &self.size, &self.theta];
+        match channels.into_iter().flatten().find_map(|c| c.aggregate.filter(|a| !allowed.contains(a))) {
+            Some(aggregate) => Err(SpecError::Unsupported(format!("\"{}\" aggregate on a {} mark", aggregate, self.mark))),
+            None => Ok(()),
+        }
+    }
+
+    fn channel<'c>(&self, channel: &'c Option<Channel<'a>>, name: &str) -> Result<&'c Channel<'a>, SpecError> {
+        channel.as_ref().ok_or_else(|| SpecError::Unsupported(format!("{} mark without a {} encoding", self.mark, name)))
+    }
+
+    /// Field of the color channel, when it splits the rows into groups
+    fn group_field(&self) -> Option<&Channel<'a>> {
+        // A quantitative colour on points colours each point through the colormap instead
+        self.color.as_ref().filter(|c| c.field.is_some() && (c.field_type.is_discrete() || !self.is_point_mark()))
+    }
+
+    fn is_point_mark(&self) -> bool {
+        matches!(self.mark.as_str(), "point" | "circle" | "square")
+    }
+
+    fn base_spec(&self, kind: &str) -> ChartSpec {
+        let mut spec = ChartSpec::new(kind).with_title(self.title.clone());
+        if let Some(x) = &self.x {
+            spec.x_label = x.title();
+        }
+        if let Some(y) = &self.y {
+            spec.y_label = y.title();
+        }
+        spec
+    }
+
+    /// Rows split by the colour field, in colour category order; one group without it
+    fn groups(&self) -> Vec<(String, Vec<&'a Json>)> {
+        match self.group_field() {
+            Some(color) => color
+                .categories(self.rows)
+                .into_iter()
+                .map(|name| {
+                    let rows = self.rows.iter().filter(|row| color.category(row) == name).collect();
+                    (name, rows)
+                })
+                .collect(),
+            None => vec![(String::new(), self.rows.iter().collect())],
+        }
+    }
+
+    /// Line, area and point marks: one series per colour group
+    fn xy_spec(&self, kind: &str) -> Result<ChartSpec, SpecError> {
+        self.check_aggregates(&[])?;
+        let x = self.channel(&self.x, "x")?;
+        let y = self.channel(&self.y, "y")?;
+        let mut spec = self.base_spec(kind);
+        spec.x_scale = x.scale();
+        spec.y_scale = y.scale();
+        let constant_color = self.color.as_ref().and_then(Channel::constant_color);
+        let size_range = self.size_range();
+
+        for (label, mut rows) in self.groups() {
+            // Lines and areas are drawn in x order, like Vega-Lite does by default
+            if kind != "scatter" && !x.field_type.is_discrete() {
+                rows.sort_by(|a, b| x.number(a).partial_cmp(&x.number(b)).unwrap_or(std::cmp::Ordering::Equal));
+            }
+            let mut series = SeriesSpec {
+                label,
+                y: rows.iter().map(|row| y.number(row)).collect(),
+                color: constant_color.clone(),
+                ..Default::default()
+            };
+            if x.field_type.is_discrete() {
+                let categories: Vec<String> = rows.iter().map(|row| x.category(row)).collect();
+                series.x = (0..categories.len()).map(|i| i as f64).collect();
+                series.x_categories = Some(categories);
+            } else {
+                series.x = rows.iter().map(|row| x.number(row)).collect();
+            }
+            if kind == "scatter" {
+                if let Some(color) = self.color.as_ref().filter(|c| c.field.is_some() && !c.field_type.is_discrete()) {
+                    series.color_values = Some(rows.iter().map(|row| color.number(row)).collect());
+                }
+                series.sizes = self.sizes(&rows, size_range);
+            }
+            spec.series.push(series);
+        }
+
+        // A discrete x axis needs the full category order on the plot itself, sorted
+        // like Vega-Lite's domain and like the bar mapping
+        if kind != "area" && x.field_type.is_discrete() {
+            spec.categories = x.categories(self.rows);
+        }
+        if kind == "area" {
+            // Vega-Lite stacks areas split by colour
+            spec.stacked = Some(self.group_field().is_some());
+        }
+        Ok(spec)
+    }
+
+    /// Data range of the size field
+    fn size_range(&self) -> Option<(f64, f64)> {
+        let size = self.size.as_ref().filter(|s| s.field.is_some())?;
+        let values = self.rows.iter().map(|row| size.number(row)).filter(|v| v.is_finite());
+        let (min, max) = values.fold((f64::MAX, f64::MIN), |(min, max), v| (min.min(v), max.max(v)));
+        (min <= max).then_some((min, max))
+    }
+
+    /// Marker radii from the size channel, which gives marker areas like Vega-Lite
+    fn sizes(&self, rows: &[&Json], range: Option<(f64, f64)>) -> Option<Vec<f64>> {
+        let size = self.size.as_ref()?;
+        let area_to_radius = |area: f64| (area.max(0.0) / std::f64::consts::PI).sqrt();
+        match range {
+            Some((min, max)) => Some(rows.iter().map(|row| {
+                let t = if max > min { (size.number(row) - min) / (max - min) } else { 0.5 };
+                area_to_radius(SIZE_RANGE.0 + t.clamp(0.0, 1.0) * (SIZE_RANGE.1 - SIZE_RANGE.0))
+            }).collect()),
+            None => {
+                let area = size.def.get("value").and_then(Json::as_f64)?;
+                Some(vec![area_to_radius(area); rows.len()])
+            }
+        }
+    }
+
+    /// Bars: the discrete axis gives the categories, the other one the summed heights;
+    /// a colour field makes stacked groups
+    fn bar_spec(&self) -> Result<ChartSpec, SpecError> {
+        self.check_aggregates(&["sum", "count"])?;
+        let x = self.channel(&self.x, "x")?;
+        let y = self.channel(&self.y, "y")?;
+        let (category, value, horizontal) = match (x.field_type.is_discrete(), y.field_type.is_discrete()) {
+            (true, false) => (x, y, false),
+            (false, true) => (y, x, true),
+            _ => return Err(SpecError::Unsupported("bar mark without one discrete and one quantitative axis".to_string())),
+        };
+        let mut spec = self.base_spec("bar");
+        spec.horizontal = Some(horizontal);
+        spec.categories = category.categories(self.rows);
+        spec.color = self.color.as_ref().and_then(Channel::constant_color);
+
+        let sums = |rows: &[&Json]| -> Vec<f64> {
+            spec.categories.iter().map(|name| {
+                rows.iter().filter(|row| category.category(row) == *name).map(|row| value.number(row)).filter(|v| v.is_finite()).sum()
+            }).collect()
+        };
+        let groups = self.groups();
+        if self.group_field().is_some() {
+            let groups: Vec<BarGroupSpec> = groups.iter().map(|(label, rows)| BarGroupSpec {
+                label: label.clone(),
+                values: sums(rows),
+                color: None,
+            }).collect();
+            spec.groups = groups;
+            spec.stacked = Some(true);
+        } else {
+            spec.values = sums(&groups[0].1);
+        }
+        Ok(spec)
+    }
+
+    /// Rect marks: x and y categories with the colour field as the cell value
+    fn heatmap_spec(&self) -> Result<ChartSpec, SpecError> {
+        self.check_aggregates(&[])?;
+        let x = self.channel(&self.x, "x")?;
+        let y = self.channel(&self.y, "y")?;
+        let color = self.channel(&self.color, "color")?;
+        let mut spec = self.base_spec("heatmap");
+        spec.categories = x.categories(self.rows);
+        spec.y_categories = y.categories(self.rows);
+        spec.matrix = vec![vec![f64::NAN; spec.categories.len()]; spec.y_categories.len()];
+        for row in self.rows {
+            let col = spec.categories.iter().position(|c| *c == x.category(row));
+            let line = spec.y_categories.iter().position(|c| *c == y.category(row));
+            if let (Some(col), Some(line)) = (col, line) {
+                spec.matrix[line][col] = color.number(row);
+            }
+        }
+        spec.colormap = color.def.get("scale").and_then(|s| s.get("scheme")).and_then(Json::as_str).map(str::to_string);
+        Ok(spec)
+    }
+
+    /// Arc marks: one slice per colour category, sized by the summed theta field
+    fn pie_spec(&self) -> Result<ChartSpec, SpecError> {
+        self.check_aggregates(&["sum", "count"])?;
+        let theta = self.channel(&self.theta, "theta")?;
+        let mut spec = self.base_spec("pie");
+        spec.slices = self.groups().into_iter().map(|(label, rows)| SliceSpec {
+            label,
+            value: rows.iter().map(|row| theta.number(row)).filter(|v| v.is_finite()).sum(),
+            color: None,
+        }).collect();
+        Ok(spec)
+    }
+}
+
+/// Seconds since the Unix epoch for "YYYY-MM-DD" with an optional "THH:MM[:SS]" time, in UTC
+fn parse_date(text: &str) -> Option<f64> {
+    let (date, time) = match text.find(['T', ' ']) {
+        Some(split) => (&text[..split], text[split + 1..].trim_end_matches('Z')),
+        None => (text, ""),
+    };
+    let mut date_parts = date.split('-').map(|p| p.parse::<i64>().ok());
+    let year = date_parts.next()??;
+    let month = date_parts.next().unwrap_or(Some(1))?;
+    let day = date_parts.next().unwrap_or(Some(1))?;
+    let mut seconds = 0.0;
+    for (part, unit) in time.split(':').zip([3600.0, 60.0, 1.0]) {
+        if !part.is_empty() {
+            seconds += part.parse::<f64>().ok()? * unit;
+        }
+    }
+    Some(days_from_civil(year, month, day) as f64 * 86400.0 + seconds)
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Parsed JSON document; Vega-Lite data rows can have any fields, so they are read untyped
+#[derive(Clone, Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = JsonParser { bytes: text.as_bytes(), pos: 0, depth: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.bytes.len() {
+            return Err(format!("trailing characters at {}", parser.pos));
+        }
+        Ok(value)
+    }
+
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => Some(*n),
+            Json::String(text) => text.trim().parse().ok(),
+            Json::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+    }
+
+    /// Value as a category label
+    fn text(&self) -> String {
+        match self {
+            Json::Null => "null".to_string(),
+            Json::Bool(b) => b.to_string(),
+            Json::Number(n) => n.to_string(),
+            Json::String(text) => text.clone(),
+            Json::Array(_) | Json::Object(_) => String::new(),
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// Arrays and objects currently open
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected '{}' at {}", byte as char, self.pos))
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        if self.bytes[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(format!("unexpected token at {}", self.pos))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        match self.peek() {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(_) => self.number(),
+            None => Err("unexpected end of input".to_string()),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(format!("nesting deeper than {} at {}", MAX_DEPTH, self.pos));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect(b'{')?;
+        let mut entries = Vec::new();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':')?;
+            entries.push((key, self.value()?));
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(entries));
+                }
+                _ => return Err(format!("expected ',' or '}}' at {}", self.pos)),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(format!("expected ',' or ']' at {}", self.pos)),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        if self.bytes.get(self.pos) != Some(&b'"') {
+            return Err(format!("expected string at {}", self.pos));
+        }
+        self.pos += 1;
+        let mut out = Vec::new();
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            self.pos += 1;
+            match byte {
+                b'"' => return String::from_utf8(out).map_err(|e| e.to_string()),
+                b'\\' => {
+                    let escape = *self.bytes.get(self.pos).ok_or("unterminated string")?;
+                    self.pos += 1;
+                    let c = match escape {
+                        b'n' => '\n',
+                        b't' => '\t',
+                        b'r' => '\r',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'u' => {
+                            let hex = self.bytes.get(self.pos..self.pos + 4).ok_or("bad unicode escape")?;
+                            self.pos += 4;
+                            let code = u32::from_str_radix(std::str::from_utf8(hex).map_err(|e| e.to_string())?, 16).map_err(|e| e.to_string())?;
+                            // Surrogate pairs are not combined
+                            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        other => other as char,
+                    };
+                    let mut buf = [0; 4];
+                    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                _ => out.push(byte),
+            }
+        }
+        Err("unterminated string".to_string())
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+        while self.pos < self.bytes.len() && matches!(self.bytes[self.pos], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .map(Json::Number)
+            .ok_or_else(|| format!("invalid number at {}", start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind_of(mark: &str, encoding: &str) -> Result<String, SpecError> {
+        let json = format!(
+            r#"{{"mark": {}, "data": {{"values": [{{"a": "x", "b": 1, "c": "p"}}, {{"a": "y", "b": 2, "c": "q"}}]}}, "encoding": {}}}"#,
+            mark, encoding
+        );
+        ChartSpec::from_vega_lite(&json).map(|spec| spec.kind)
+    }
+
+    #[test]
+    fn parses_literals_and_numbers() {
+        assert_eq!(Json::parse("null"), Ok(Json::Null));
+        assert_eq!(Json::parse(" true "), Ok(Json::Bool(true)));
+        assert_eq!(Json::parse("false"), Ok(Json::Bool(false)));
+        assert_eq!(Json::parse("42"), Ok(Json::Number(42.0)));
+        assert_eq!(Json::parse("-0.5"), Ok(Json::Number(-0.5)));
+        assert_eq!(Json::parse("1.5e3"), Ok(Json::Number(1500.0)));
+        assert_eq!(Json::parse("2E-2"), Ok(Json::Number(0.02)));
+    }
+
+    #[test]
+    fn parses_string_escapes() {
+        assert_eq!(Json::parse(r#""a\"b\\c\/d""#), Ok(Json::String("a\"b\\c/d".to_string())));
+        assert_eq!(Json::parse(r#""\n\t\r\b\f""#), Ok(Json::String("\n\t\r\u{8}\u{c}".to_string())));
+        assert_eq!(Json::parse(r#""caf\u00e9 \u20ac""#), Ok(Json::String("café €".to_string())));
+        assert_eq!(Json::parse("\"naïve\""), Ok(Json::String("naïve".to_string())));
+    }
+
+    #[test]
+    fn parses_nested_values() {
+        let json = Json::parse(r#"{"a": [1, {"b": [true, null]}, []], "c": {}, "a2": "x"}"#).unwrap();
+        assert_eq!(json.get("a"), Some(&Json::Array(vec![
+            Json::Number(1.0),
+            Json::Object(vec![("b".to_string(), Json::Array(vec![Json::Bool(true), Json::Null]))]),
+            Json::Array(Vec::new()),
+        ])));
+        assert_eq!(json.get("c"), Some(&Json::Object(Vec::new())));
+        assert_eq!(json.get("a2").and_then(Json::as_str), Some("x"));
+        assert_eq!(json.get("missing"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        for text in ["", "{", "[1, 2", "[1,]", "{\"a\" 1}", "{\"a\": 1,}", "{a: 1}", "tru", "\"open", "\"bad \\u12\"", "-", "1 2", "[] x"] {
+            assert!(Json::parse(text).is_err(), "{:?} should not parse", text);
+        }
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(Json::parse(&nested(MAX_DEPTH)).is_ok());
+        assert!(Json::parse(&nested(MAX_DEPTH + 1)).is_err());
+        assert!(Json::parse(&"[{\"a\":".repeat(100_000)).is_err());
+        assert!(matches!(ChartSpec::from_vega_lite(&"[".repeat(1_000_000)), Err(SpecError::Json(_))));
+    }
+
+    #[test]
+    fn reads_values_as_numbers_and_text() {
+        assert_eq!(Json::String(" 3.5 ".to_string()).as_f64(), Some(3.5));
+        assert_eq!(Json::Bool(true).as_f64(), Some(1.0));
+        assert_eq!(Json::Null.as_f64(), None);
+        assert_eq!(Json::Number(2.0).text(), "2");
+        assert_eq!(Json::Null.text(), "null");
+    }
+
+    #[test]
+    fn parses_dates_in_utc() {
+        assert_eq!(parse_date("1970-01-01"), Some(0.0));
+        assert_eq!(parse_date("1969-12-31"), Some(-86400.0));
+        assert_eq!(parse_date("2000-03-01"), Some(951868800.0));
+        assert_eq!(parse_date("2024-02-29T12:30"), Some(1709209800.0));
+        assert_eq!(parse_date("2021-01-01 00:00:00Z"), Some(1609459200.0));
+        assert_eq!(parse_date("2020"), Some(1577836800.0));
+        assert_eq!(parse_date("June 1"), None);
+        assert_eq!(parse_date("2020-xx-01"), None);
+        assert_eq!(parse_date("2020-01-01T1a:00"), None);
+    }
+
+    #[test]
+    fn counts_days_across_leap_years_and_eras() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 2, 29), 11016);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(2100, 3, 1) - days_from_civil(2100, 2, 28), 1);
+        assert_eq!(days_from_civil(1600, 1, 1), -135140);
+    }
+
+    #[test]
+    fn maps_marks_to_chart_kinds() {
+        let xy = r#"{"x": {"field": "b", "type": "quantitative"}, "y": {"field": "b", "type": "quantitative"}}"#;
+        assert_eq!(kind_of(r#""line""#, xy), Ok("line".to_string()));
+        assert_eq!(kind_of(r#""area""#, xy), Ok("area".to_string()));
+        assert_eq!(kind_of(r#""point""#, xy), Ok("scatter".to_string()));
+        assert_eq!(kind_of(r#""circle""#, xy), Ok("scatter".to_string()));
+        assert_eq!(kind_of(r#"{"type": "square"}"#, xy), Ok("scatter".to_string()));
+        let bar = r#"{"x": {"field": "a", "type": "nominal"}, "y": {"field": "b", "type": "quantitative"}}"#;
+        assert_eq!(kind_of(r#""bar""#, bar), Ok("bar".to_string()));
+        let rect = r#"{"x": {"field": "a", "type": "nominal"}, "y": {"field": "c", "type": "nominal"}, "color": {"field": "b", "type": "quantitative"}}"#;
+        assert_eq!(kind_of(r#""rect""#, rect), Ok("heatmap".to_string()));
+        let arc = r#"{"theta": {"field": "b", "type": "quantitative"}, "color": {"field": "a", "type": "nominal"}}"#;
+        assert_eq!(kind_of(r#""arc""#, arc), Ok("pie".to_string()));
+    }
+
+    #[test]
+    fn rejects_unsupported_specs() {
+        let xy = r#"{"x": {"field": "b", "type": "quantitative"}, "y": {"field": "b", "type": "quantitative"}}"#;
+        assert!(matches!(kind_of(r#""geoshape""#, xy), Err(SpecError::Unsupported(_))));
+        assert!(matches!(kind_of(r#""bar""#, xy), Err(SpecError::Unsupported(_))));
+        assert!(matches!(kind_of(r#""line""#, r#"{"x": {"field": "b"}}"#), Err(SpecError::Unsupported(_))));
+        assert!(matches!(ChartSpec::from_vega_lite(r#"{"layer": [], "mark": "line"}"#), Err(SpecError::Unsupported(_))));
+        assert!(matches!(ChartSpec::from_vega_lite(r#"{"mark": "line", "data": {"url": "a.csv"}}"#), Err(SpecError::Unsupported(_))));
+        assert!(matches!(ChartSpec::from_vega_lite("{\"mark\": "), Err(SpecError::Json(_))));
+    }
+
+    #[test]
+    fn groups_rows_by_colour_field() {
+        let spec = ChartSpec::from_vega_lite(r#"{
+            "mark": "bar",
+            "data": {"values": [
+                {"k": "b", "v": 1, "g": "q"}, {"k": "a", "v": 2, "g": "p"},
+                {"k": "a", "v": 3, "g": "q"}, {"k": "a", "v": 4, "g": "q"}
+            ]},
+            "encoding": {
+                "x": {"field": "k", "type": "nominal"},
+                "y": {"field": "v", "type": "quantitative"},
+                "color": {"field": "g", "type": "nominal"}
+            }
+        }"#).unwrap();
+        assert_eq!(spec.categories, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(spec.stacked, Some(true));
+        assert_eq!(spec.groups.iter().map(|g| g.label.as_str()).collect::<Vec<_>>(), vec!["p", "q"]);
+        assert_eq!(spec.groups[0].values, vec![2.0, 0.0]);
+        assert_eq!(spec.groups[1].values, vec![7.0, 1.0]);
+    }
+
+    #[test]
+    fn sorts_lines_by_x_and_reads_temporal_fields() {
+        let spec = ChartSpec::from_vega_lite(r#"{
+            "mark": "line",
+            "data": {"values": [{"t": "1970-01-03", "v": 3}, {"t": "1970-01-02", "v": 2}, {"t": 0, "v": 1}]},
+            "encoding": {"x": {"field": "t", "type": "temporal"}, "y": {"field": "v", "type": "quantitative"}}
+        }"#).unwrap();
+        assert_eq!(spec.x_scale.as_deref(), Some("time"));
+        assert_eq!(spec.series[0].x, vec![0.0, 86400.0, 172800.0]);
+        assert_eq!(spec.series[0].y, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn sorts_discrete_point_categories() {
+        let spec = ChartSpec::from_vega_lite(r#"{
+            "mark": "point",
+            "data": {"values": [{"k": "b", "v": 1}, {"k": "c", "v": 2}, {"k": "a", "v": 3}]},
+            "encoding": {"x": {"field": "k", "type": "nominal"}, "y": {"field": "v", "type": "quantitative"}}
+        }"#).unwrap();
+        assert_eq!(spec.categories, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert_eq!(spec.series[0].x_categories, Some(vec!["b".to_string(), "c".to_string(), "a".to_string()]));
+    }
+
+    #[test]
+    fn counts_rows_for_count_aggregates() {
+        let bar = ChartSpec::from_vega_lite(r#"{
+            "mark": "bar",
+            "data": {"values": [{"k": "a"}, {"k": "b"}, {"k": "a"}]},
+            "encoding": {"x": {"field": "k", "type": "nominal"}, "y": {"aggregate": "count", "type": "quantitative"}}
+        }"#).unwrap();
+        assert_eq!(bar.values, vec![2.0, 1.0]);
+        assert_eq!(bar.y_label, "Count of Records");
+
+        let mean = ChartSpec::from_vega_lite(r#"{
+            "mark": "bar",
+            "data": {"values": [{"k": "a", "v": 1}]},
+            "encoding": {"x": {"field": "k", "type": "nominal"}, "y": {"field": "v", "aggregate": "mean", "type": "quantitative"}}
+        }"#);
+        assert!(matches!(mean, Err(SpecError::Unsupported(_))));
+
+        let line = ChartSpec::from_vega_lite(r#"{
+            "mark": "line",
+            "data": {"values": [{"k": 1}]},
+            "encoding": {"x": {"field": "k", "type": "quantitative"}, "y": {"aggregate": "count", "type": "quantitative"}}
+        }"#);
+        assert!(matches!(line, Err(SpecError::Unsupported(_))));
+    }
+}